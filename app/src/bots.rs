@@ -0,0 +1,335 @@
+use std::{
+    collections::HashSet,
+    net::SocketAddr,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use ambient_ecs::query;
+use ambient_input::{actions::action_map, MouseButton, PlayerRawInput, VirtualKeyCode};
+use ambient_network::{
+    client::GameRpcArgs,
+    headless::{HeadlessClient, HeadlessClientStats},
+    transport::Transport,
+};
+use ambient_rpc::RpcRegistry;
+use ambient_std::to_byte_unit;
+use anyhow::Context;
+use glam::{vec2, Vec2};
+use parking_lot::Mutex;
+use rand::{rngs::StdRng, seq::IteratorRandom, Rng, SeedableRng};
+use serde::Deserialize;
+
+use crate::{
+    cli::BotsCli,
+    shared::{self, player::player_input_datagram},
+};
+
+/// Connects the bots to the server, reports their stats every `report_interval` and a summary when they are done.
+/// Fails if any bot failed to connect or lost its connection
+pub async fn run(server_addr: SocketAddr, args: &BotsCli) -> anyhow::Result<()> {
+    let script = args.script.as_deref().map(BotScript::load).transpose()?.map(Arc::new);
+    let rpc_registry = Arc::new(shared::create_rpc_registry());
+    let input_interval = Duration::from_secs_f32(1. / args.input_rate.max(0.1));
+    let transport = args.transport;
+
+    log::info!("Connecting {} bots to {server_addr} over {}", args.count, args.transport);
+    let bots = (0..args.count)
+        .map(|index| {
+            let bot = Arc::new(Mutex::new(BotStatus::default()));
+            let policy: Box<dyn BotPolicy> = match &script {
+                Some(script) => Box::new(ScriptPolicy { script: script.clone(), step: 0, time: 0. }),
+                None => Box::new(RandomPolicy::new(index as u64)),
+            };
+            let task = tokio::spawn({
+                let bot = bot.clone();
+                let rpc_registry = rpc_registry.clone();
+                async move {
+                    let result = run_bot(index, server_addr, transport, &rpc_registry, policy, input_interval, &bot).await;
+                    if let Err(err) = result {
+                        log::error!("Bot {index} failed: {err:?}");
+                        bot.lock().error = Some(format!("{err:#}"));
+                    }
+                }
+            });
+            (bot, task)
+        })
+        .collect::<Vec<_>>();
+
+    let start = Instant::now();
+    let duration = args.duration.map(Duration::from_secs_f32);
+    let deadline = async move {
+        match duration {
+            Some(duration) => tokio::time::sleep(duration).await,
+            None => std::future::pending().await,
+        }
+    };
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(deadline, ctrl_c);
+    let mut report_timer = tokio::time::interval(Duration::from_secs_f32(args.report_interval.max(0.1)));
+    report_timer.tick().await;
+    let mut prev_report = (Instant::now(), vec![HeadlessClientStats::default(); bots.len()]);
+    loop {
+        tokio::select! {
+            _ = report_timer.tick() => {
+                let stats = bots.iter().map(|(bot, _)| bot.lock().stats.clone()).collect::<Vec<_>>();
+                report(&bots, &stats, &prev_report.1, prev_report.0.elapsed());
+                prev_report = (Instant::now(), stats);
+            }
+            _ = &mut deadline => break,
+            _ = &mut ctrl_c => break,
+        }
+        if bots.iter().all(|(_, task)| task.is_finished()) {
+            break;
+        }
+    }
+
+    for (_, task) in &bots {
+        task.abort();
+    }
+    let stats = bots.iter().map(|(bot, _)| bot.lock().stats.clone()).collect::<Vec<_>>();
+    log::info!("Summary after {:.1}s:", start.elapsed().as_secs_f32());
+    report(&bots, &stats, &vec![HeadlessClientStats::default(); bots.len()], start.elapsed());
+
+    let failed = bots.iter().filter(|(bot, _)| bot.lock().error.is_some()).count();
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} bots failed", bots.len());
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct BotStatus {
+    stats: HeadlessClientStats,
+    error: Option<String>,
+}
+
+fn report(
+    bots: &[(Arc<Mutex<BotStatus>>, tokio::task::JoinHandle<()>)],
+    stats: &[HeadlessClientStats],
+    prev: &[HeadlessClientStats],
+    elapsed: Duration,
+) {
+    let (mut total_sent, mut total_received) = (0., 0.);
+    for (index, ((bot, _), (stats, prev))) in bots.iter().zip(stats.iter().zip(prev)).enumerate() {
+        let (sent, received) = stats.bandwidth_since(prev, elapsed);
+        total_sent += sent;
+        total_received += received;
+        match &bot.lock().error {
+            Some(err) => log::info!("Bot {index}: failed: {err}"),
+            None => log::info!("Bot {index}: {}/s out, {}/s in, {stats}", to_byte_unit(sent as u64), to_byte_unit(received as u64)),
+        }
+    }
+    log::info!("All bots: {}/s out, {}/s in", to_byte_unit(total_sent as u64), to_byte_unit(total_received as u64));
+}
+
+async fn run_bot(
+    index: usize,
+    server_addr: SocketAddr,
+    transport: Transport,
+    rpc_registry: &RpcRegistry<GameRpcArgs>,
+    mut policy: Box<dyn BotPolicy>,
+    input_interval: Duration,
+    bot: &Mutex<BotStatus>,
+) -> anyhow::Result<()> {
+    let mut client = HeadlessClient::connect(server_addr, transport, format!("bot_{index}"), rpc_registry).await?;
+    log::debug!("Bot {index} connected");
+
+    let mut input_timer = tokio::time::interval(input_interval);
+    let mut input = PlayerRawInput::default();
+    let mut prev_input = PlayerRawInput::default();
+    let mut last_input = Instant::now();
+    loop {
+        tokio::select! {
+            result = client.update() => result?,
+            _ = input_timer.tick() => {
+                policy.update(&mut input, last_input.elapsed().as_secs_f32());
+                last_input = Instant::now();
+
+                let map = query(action_map()).iter(&client.world, None).map(|(_, map)| map.clone()).next().unwrap_or_default();
+                let actions = map.evaluate(&input, &prev_input);
+                prev_input = input.clone();
+                client.send_datagram(player_input_datagram(&input, &actions).into())?;
+
+                bot.lock().stats = client.stats();
+            }
+        }
+    }
+}
+
+/// Decides what a bot does
+trait BotPolicy: Send {
+    /// Changes `input` for `dt` seconds having passed
+    fn update(&mut self, input: &mut PlayerRawInput, dt: f32);
+}
+
+/// Walks, jumps, looks around and clicks at random. Each bot has its own seed, so that runs can be compared
+struct RandomPolicy {
+    rng: StdRng,
+    next_change: f32,
+    mouse_velocity: Vec2,
+}
+impl RandomPolicy {
+    const KEYS: [VirtualKeyCode; 5] = [VirtualKeyCode::W, VirtualKeyCode::A, VirtualKeyCode::S, VirtualKeyCode::D, VirtualKeyCode::Space];
+
+    fn new(seed: u64) -> Self {
+        Self { rng: StdRng::seed_from_u64(seed), next_change: 0., mouse_velocity: Vec2::ZERO }
+    }
+}
+impl BotPolicy for RandomPolicy {
+    fn update(&mut self, input: &mut PlayerRawInput, dt: f32) {
+        self.next_change -= dt;
+        if self.next_change <= 0. {
+            self.next_change = self.rng.gen_range(0.5..2.);
+            let key_count = self.rng.gen_range(0..=2);
+            input.keys = Self::KEYS.into_iter().choose_multiple(&mut self.rng, key_count).into_iter().collect();
+            input.mouse_buttons = if self.rng.gen_bool(0.2) { HashSet::from([MouseButton::Left]) } else { HashSet::new() };
+            self.mouse_velocity = vec2(self.rng.gen_range(-200.0..200.0), self.rng.gen_range(-50.0..50.0));
+        }
+        input.mouse_position += self.mouse_velocity * dt;
+        input.cursor_position = input.mouse_position;
+    }
+}
+
+/// Plays the steps of a [BotScript] in a loop
+struct ScriptPolicy {
+    script: Arc<BotScript>,
+    step: usize,
+    time: f32,
+}
+impl BotPolicy for ScriptPolicy {
+    fn update(&mut self, input: &mut PlayerRawInput, dt: f32) {
+        self.time += dt;
+        while self.time >= self.script.steps[self.step].duration {
+            self.time -= self.script.steps[self.step].duration;
+            self.step = (self.step + 1) % self.script.steps.len();
+        }
+        let step = &self.script.steps[self.step];
+        input.keys = step.keys.iter().copied().collect();
+        input.mouse_buttons = step.mouse_buttons.iter().copied().collect();
+        input.mouse_position += step.mouse_velocity * dt;
+        input.cursor_position = input.mouse_position;
+        input.mouse_wheel += step.mouse_wheel_velocity * dt;
+    }
+}
+
+/// The input a bot plays, e.g.
+///
+/// ```toml
+/// [[step]]
+/// duration = 2.0
+/// keys = ["W"]
+/// mouse_velocity = [100.0, 0.0]
+///
+/// [[step]]
+/// duration = 0.5
+/// keys = ["Space"]
+/// mouse_buttons = ["Left"]
+/// ```
+#[derive(Debug, Deserialize)]
+struct BotScript {
+    #[serde(rename = "step")]
+    steps: Vec<BotStep>,
+}
+impl BotScript {
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        let toml = std::fs::read_to_string(path).with_context(|| format!("Failed to read the bot script {path:?}"))?;
+        Self::parse(&toml).with_context(|| format!("Failed to parse the bot script {path:?}"))
+    }
+    fn parse(toml: &str) -> anyhow::Result<Self> {
+        let script: Self = toml::from_str(toml)?;
+        anyhow::ensure!(!script.steps.is_empty(), "The script has no steps");
+        anyhow::ensure!(script.steps.iter().all(|step| step.duration > 0.), "The steps of the script must have a positive duration");
+        Ok(script)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BotStep {
+    /// In seconds
+    duration: f32,
+    /// The keys held down during the step
+    #[serde(default)]
+    keys: Vec<VirtualKeyCode>,
+    /// The mouse buttons held down during the step
+    #[serde(default)]
+    mouse_buttons: Vec<MouseButton>,
+    /// How fast the mouse moves, in pixels per second
+    #[serde(default)]
+    mouse_velocity: Vec2,
+    /// How fast the mouse wheel scrolls, in pixels per second
+    #[serde(default)]
+    mouse_wheel_velocity: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = r#"
+        [[step]]
+        duration = 1.0
+        keys = ["W"]
+        mouse_velocity = [100.0, 0.0]
+
+        [[step]]
+        duration = 0.5
+        keys = ["Space"]
+        mouse_buttons = ["Left"]
+    "#;
+
+    #[test]
+    fn parse_script() {
+        let script = BotScript::parse(SCRIPT).unwrap();
+        assert_eq!(script.steps.len(), 2);
+        assert_eq!(script.steps[0].keys, vec![VirtualKeyCode::W]);
+        assert_eq!(script.steps[0].mouse_velocity, vec2(100., 0.));
+        assert!(script.steps[0].mouse_buttons.is_empty());
+        assert_eq!(script.steps[1].mouse_buttons, vec![MouseButton::Left]);
+
+        assert!(BotScript::parse("step = []").is_err());
+        assert!(BotScript::parse("[[step]]\nduration = 0.0").is_err());
+        assert!(BotScript::parse("[[step]]\nkeys = [\"W\"]").is_err());
+    }
+
+    #[test]
+    fn script_policy_advances_steps() {
+        let mut policy = ScriptPolicy { script: Arc::new(BotScript::parse(SCRIPT).unwrap()), step: 0, time: 0. };
+        let mut input = PlayerRawInput::default();
+
+        policy.update(&mut input, 0.5);
+        assert_eq!(policy.step, 0);
+        assert_eq!(input.keys, HashSet::from([VirtualKeyCode::W]));
+        assert_eq!(input.mouse_position, vec2(50., 0.));
+
+        policy.update(&mut input, 0.6);
+        assert_eq!(policy.step, 1);
+        assert_eq!(input.keys, HashSet::from([VirtualKeyCode::Space]));
+        assert_eq!(input.mouse_buttons, HashSet::from([MouseButton::Left]));
+
+        // Loops around, skipping the steps which were entirely passed
+        policy.update(&mut input, 2.5);
+        assert_eq!(policy.step, 0);
+        assert!((policy.time - 0.6).abs() < 1e-4, "{}", policy.time);
+        assert!(input.mouse_buttons.is_empty());
+    }
+
+    #[test]
+    fn random_policy_is_seeded() {
+        let run = |seed| {
+            let mut policy = RandomPolicy::new(seed);
+            let mut input = PlayerRawInput::default();
+            (0..20)
+                .map(|_| {
+                    policy.update(&mut input, 0.3);
+                    assert!(input.keys.len() <= 2 && input.keys.iter().all(|key| RandomPolicy::KEYS.contains(key)));
+                    assert!(policy.next_change <= 2.);
+                    (input.keys.clone(), input.mouse_position)
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run(1), run(1));
+        assert_ne!(run(1), run(2));
+    }
+}
@@ -0,0 +1,43 @@
+use std::path::Path;
+
+use ambient_ecs::{is_serialized_world, migrate_serialized_world, DeserWorldWithWarnings};
+use anyhow::Context;
+
+/// Directories that only contain generated content, and are thus not migrated
+const SKIPPED_DIRS: &[&str] = &["build", "target"];
+
+pub(crate) fn migrate_project(project_path: &Path) -> anyhow::Result<()> {
+    let mut migrated = 0;
+    let walker = walkdir::WalkDir::new(project_path).into_iter().filter_entry(|entry| {
+        let name = entry.file_name().to_str().unwrap_or_default();
+        !(entry.depth() > 0 && entry.file_type().is_dir() && (name.starts_with('.') || SKIPPED_DIRS.contains(&name)))
+    });
+    for entry in walker {
+        let entry = entry?;
+        let path = entry.path();
+        if !entry.file_type().is_file() || path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let content = std::fs::read(path).with_context(|| format!("Failed to read {path:?}"))?;
+        let Ok(mut world) = serde_json::from_slice::<serde_json::Value>(&content) else { continue };
+        if !is_serialized_world(&world) {
+            continue;
+        }
+
+        let report = migrate_serialized_world(&mut world).with_context(|| format!("Failed to migrate {path:?}"))?;
+        if !report.migrated() {
+            continue;
+        }
+        let content = serde_json::to_vec_pretty(&world)?;
+        let DeserWorldWithWarnings { warnings, .. } =
+            serde_json::from_slice(&content).with_context(|| format!("Migrated world {path:?} could not be deserialized"))?;
+        report.warnings.log_warnings();
+        warnings.log_warnings();
+
+        std::fs::write(path, content).with_context(|| format!("Failed to write {path:?}"))?;
+        log::info!("Migrated {path:?} from format version {} to {}", report.from_version, report.to_version);
+        migrated += 1;
+    }
+    log::info!("Migrated {migrated} worlds in {project_path:?}");
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use std::path::PathBuf;
 
+use ambient_network::{permissions::Role, transport::Transport};
 use clap::{Args, Parser};
 
+pub mod migrate;
 pub mod new_project;
 
 #[derive(Parser, Clone)]
@@ -28,6 +30,9 @@ pub enum Cli {
     Build {
         #[command(flatten)]
         project_args: ProjectCli,
+        /// Also bundle the built assets and the WASM module into a single content pack, at `build/content.pack`
+        #[arg(long)]
+        pack: bool,
     },
     /// Builds and runs the project in server-only mode
     Serve {
@@ -43,12 +48,28 @@ pub enum Cli {
         /// Relative to the project path
         asset_path: PathBuf,
     },
+    /// Upgrades all serialized worlds and prefabs in the project to the current format version, in place
+    Migrate {
+        #[command(flatten)]
+        project_args: ProjectCli,
+    },
     /// Join a multiplayer session
     Join {
         #[command(flatten)]
         run_args: RunCli,
         /// The server to connect to; defaults to localhost
         host: Option<String>,
+        /// Read the content of the server from this content pack (a path or a url), instead of downloading its assets
+        /// one by one. The server serves its own pack at `/content.pack`
+        #[arg(long)]
+        pack: Option<String>,
+    },
+    /// Connects simulated players to a server without graphics, and reports how the server and the network hold up
+    Bots {
+        #[command(flatten)]
+        bots_args: BotsCli,
+        /// The server to connect to; defaults to localhost
+        host: Option<String>,
     },
     /// Updates all WASM APIs with the core primitive components (not for users)
     #[cfg(not(feature = "production"))]
@@ -72,6 +93,33 @@ pub struct RunCli {
     /// The user ID to join this server with
     #[clap(short, long)]
     pub user_id: Option<String>,
+
+    /// How to connect to the server: quic, or websocket like the web client
+    #[arg(long, default_value = "quic")]
+    pub transport: Transport,
+}
+#[derive(Args, Clone)]
+pub struct BotsCli {
+    /// The number of bots to connect
+    #[arg(short, long, default_value_t = 1)]
+    pub count: usize,
+    /// A TOML file with the `[[step]]`s of input that the bots play in a loop; the bots move randomly if not set
+    #[arg(short, long)]
+    pub script: Option<PathBuf>,
+    /// Disconnect the bots and exit after this many seconds; the bots run until Ctrl+C if not set
+    ///
+    /// Exits with an exit code of 1 if any bot failed to connect or lost its connection
+    #[arg(long)]
+    pub duration: Option<f32>,
+    /// How many times per second the bots send their input
+    #[arg(long, default_value_t = 30.)]
+    pub input_rate: f32,
+    /// How often to report the stats of the bots, in seconds
+    #[arg(long, default_value_t = 5.)]
+    pub report_interval: f32,
+    /// How the bots connect to the server: quic, or websocket like the web client
+    #[arg(long, default_value = "quic")]
+    pub transport: Transport,
 }
 #[derive(Args, Clone)]
 pub struct ProjectCli {
@@ -85,6 +133,33 @@ pub struct HostCli {
     /// Defaults to localhost
     #[arg(long)]
     pub public_host: Option<String>,
+    /// The role of players connecting to the instance: player, editor or admin
+    ///
+    /// Players can only play the game, editors can also edit the world and admins can also assign roles to other players
+    #[arg(long, default_value_t = Role::default())]
+    pub default_role: Role,
+    /// The user id of a player which is given the admin role. Can be passed multiple times
+    ///
+    /// User ids are not authenticated, so anyone connecting with this user id gets the admin role; don't rely on roles to
+    /// protect a server open to the public
+    #[arg(long = "admin")]
+    pub admins: Vec<String>,
+    /// Save the world to this file, and restore it from the file when the instance starts
+    ///
+    /// Only the components with the `Store` attribute are saved
+    #[arg(long)]
+    pub save: Option<PathBuf>,
+    /// How often to save the world when `--save` is set, in seconds; 0 only saves when the instance shuts down or a module asks for it
+    #[arg(long, default_value_t = 300.)]
+    pub autosave_interval: f32,
+    /// Serve the content from this content pack (made with `ambient build --pack`) instead of the build directory
+    ///
+    /// The project isn't built then; only its `ambient.toml` is used
+    #[arg(long)]
+    pub pack: Option<PathBuf>,
+    /// Also accept clients over WebSockets, like the web client, on the TCP port with the same number as the QUIC port
+    #[arg(long)]
+    pub websocket: bool,
 }
 
 impl Cli {
@@ -96,7 +171,9 @@ impl Cli {
             Cli::Build { .. } => None,
             Cli::Serve { .. } => None,
             Cli::View { .. } => None,
+            Cli::Migrate { .. } => None,
             Cli::Join { run_args, .. } => Some(run_args),
+            Cli::Bots { .. } => None,
             #[cfg(not(feature = "production"))]
             Cli::UpdateInterfaceComponents => None,
         }
@@ -109,7 +186,9 @@ impl Cli {
             Cli::Build { project_args, .. } => Some(project_args),
             Cli::Serve { project_args, .. } => Some(project_args),
             Cli::View { project_args, .. } => Some(project_args),
+            Cli::Migrate { project_args } => Some(project_args),
             Cli::Join { .. } => None,
+            Cli::Bots { .. } => None,
             #[cfg(not(feature = "production"))]
             Cli::UpdateInterfaceComponents => None,
         }
@@ -122,7 +201,9 @@ impl Cli {
             Cli::Build { .. } => None,
             Cli::Serve { host_args, .. } => Some(host_args),
             Cli::View { .. } => None,
+            Cli::Migrate { .. } => None,
             Cli::Join { .. } => None,
+            Cli::Bots { .. } => None,
             #[cfg(not(feature = "production"))]
             Cli::UpdateInterfaceComponents => None,
         }
@@ -2,50 +2,91 @@ use std::{net::SocketAddr, path::PathBuf, process::exit, sync::Arc, time::Durati
 
 use ambient_app::{window_title, AppBuilder};
 use ambient_cameras::UICamera;
-use ambient_core::{camera::active_camera, runtime};
+use ambient_core::{asset_cache, camera::active_camera, runtime};
 use ambient_debugger::Debugger;
 use ambient_ecs::{Entity, SystemGroup, World};
 use ambient_element::{element_component, Element, ElementComponentExt, Hooks};
 use ambient_network::{
     client::{GameClient, GameClientNetworkStats, GameClientRenderTarget, GameClientServerStats, GameClientView, UseOnce},
     events::ServerEventRegistry,
+    transport::Transport,
+};
+use ambient_std::{
+    asset_cache::{AssetCache, SyncAssetKeyExt},
+    asset_url::ServerBaseUrlKey,
+    cb,
+    content_pack::{mount_content_pack, ContentPack},
+    friendly_id, sha256_digest,
 };
-use ambient_std::{asset_cache::AssetCache, cb, friendly_id};
 use ambient_ui::{use_window_physical_resolution, Dock, FocusRoot, StylesExt, Text, WindowSized};
 
 use crate::{cli::RunCli, shared};
 use ambient_renderer::RenderTarget;
 use glam::uvec2;
 
-/// Construct an app and enter the main client view
-pub async fn run(assets: AssetCache, server_addr: SocketAddr, run: &RunCli, project_path: Option<PathBuf>) {
+/// Construct an app and enter the main client view. The `content_pack` is mounted at the content url of the server once connected
+pub async fn run(
+    assets: AssetCache,
+    server_addr: SocketAddr,
+    run: &RunCli,
+    project_path: Option<PathBuf>,
+    project_id: Option<String>,
+    content_pack: Option<Arc<ContentPack>>,
+) {
     let user_id = run.user_id.clone().unwrap_or_else(|| format!("user_{}", friendly_id()));
+    let action_bindings_path = action_bindings_path(project_id.as_deref(), &user_id);
     let headless = if run.headless { Some(uvec2(400, 400)) } else { None };
 
     let is_debug = std::env::var("AMBIENT_DEBUGGER").is_ok() || run.debugger;
+    let transport = run.transport;
 
     AppBuilder::simple()
         .ui_renderer(true)
         .with_asset_cache(assets)
         .headless(headless)
         .run(move |app, _runtime| {
-            MainApp { server_addr, user_id, show_debug: is_debug, screenshot_test: run.screenshot_test, project_path }
-                .el()
-                .spawn_interactive(&mut app.world);
+            MainApp {
+                server_addr,
+                transport,
+                user_id,
+                action_bindings_path,
+                show_debug: is_debug,
+                screenshot_test: run.screenshot_test,
+                project_path,
+                content_pack,
+            }
+            .el()
+            .spawn_interactive(&mut app.world);
         })
         .await;
 }
 
+/// Rebound actions are kept per user and project, in the config dir of the OS user. Projects which are joined
+/// rather than run locally share the bindings in `default`. The user id can be anything passed on the command line,
+/// so the file is named after its hash rather than the id itself
+fn action_bindings_path(project_id: Option<&str>, user_id: &str) -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_default()
+        .join("ambient")
+        .join("bindings")
+        .join(project_id.unwrap_or("default"))
+        .join(format!("{}.toml", sha256_digest(user_id)))
+}
+
 #[element_component]
 fn MainApp(
     hooks: &mut Hooks,
     server_addr: SocketAddr,
+    transport: Transport,
     project_path: Option<PathBuf>,
     user_id: String,
+    action_bindings_path: PathBuf,
     show_debug: bool,
     screenshot_test: Option<f32>,
+    content_pack: Option<Arc<ContentPack>>,
 ) -> Element {
     let resolution = use_window_physical_resolution(hooks);
+    let assets = hooks.world.resource(asset_cache()).clone();
 
     let update_network_stats = hooks.provide_context(GameClientNetworkStats::default);
     let update_server_stats = hooks.provide_context(GameClientServerStats::default);
@@ -55,19 +96,30 @@ fn MainApp(
     FocusRoot::el([
         UICamera.el().set(active_camera(), 0.),
         shared::player::PlayerRawInputHandler.el(),
+        shared::player::PlayerActionBindings { path: action_bindings_path }.el(),
         shared::player::PlayerDataUpload.el(),
         WindowSized::el([GameClientView {
             server_addr,
+            transport,
             user_id,
             resolution,
             on_disconnect: cb(move || {}),
             init_world: cb(UseOnce::new(Box::new(move |world, render_target| {
-                world.add_resource(ambient_network::events::event_registry(), Arc::new(ServerEventRegistry::new()));
+                let event_registry = ServerEventRegistry::new();
+                ambient_network::messages::register_client_message_handler(&event_registry);
+                ambient_network::debug_draw::register_client_debug_draw_handler(&event_registry);
+                world.add_resource(ambient_network::events::event_registry(), Arc::new(event_registry));
                 if let Some(seconds) = screenshot_test {
                     run_screenshot_test(world, render_target, project_path, seconds);
                 }
             }))),
-            on_loaded: cb(move |_game_state, _game_client| Ok(Box::new(|| {}))),
+            on_loaded: cb(move |_game_state, _game_client| {
+                // The client knows the content url of the server once it has connected
+                if let Some(pack) = &content_pack {
+                    mount_content_pack(&assets, ServerBaseUrlKey.get(&assets), pack.clone());
+                }
+                Ok(Box::new(|| {}))
+            }),
             error_view: cb(move |error| Dock(vec![Text::el("Error").header_style(), Text::el(error)]).el()),
             on_network_stats: cb(move |stats| update_network_stats(stats)),
             on_server_stats: cb(move |stats| update_server_stats(stats)),
@@ -141,6 +193,7 @@ fn systems() -> SystemGroup {
             Box::new(ambient_sky::systems()),
             Box::new(ambient_water::systems()),
             Box::new(ambient_physics::client_systems()),
+            Box::new(ambient_network::debug_draw::client_systems()),
         ],
     )
 }
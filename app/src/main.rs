@@ -1,9 +1,15 @@
+use std::{net::SocketAddr, sync::Arc};
+
 use ambient_std::{
     asset_cache::{AssetCache, SyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    content_pack::ContentPack,
     download_asset::AssetsCacheOnDisk,
+    to_byte_unit,
 };
 use clap::Parser;
 
+mod bots;
 mod cli;
 mod client;
 mod server;
@@ -78,6 +84,18 @@ fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
+    // If migrate: upgrade the serialized worlds in the project, immediately exit
+    if let Cli::Migrate { .. } = &cli {
+        cli::migrate::migrate_project(&project_path)?;
+        return Ok(());
+    }
+
+    // If bots: connect them to the server, and exit when they are done
+    if let Cli::Bots { bots_args, host } = &cli {
+        let server_addr = server_addr(host.as_deref())?;
+        return runtime.block_on(bots::run(server_addr, bots_args));
+    }
+
     // If UIC: write components to disk, immediately exit
     #[cfg(not(feature = "production"))]
     if let Cli::UpdateInterfaceComponents = cli {
@@ -106,38 +124,57 @@ fn main() -> anyhow::Result<()> {
         })
         .transpose()?;
 
-    if let Some(manifest) = manifest.as_ref() {
+    // A server with a content pack serves the content of the pack, so the project isn't built
+    let host_pack = cli
+        .host()
+        .and_then(|host| host.pack.clone())
+        .map(|path| anyhow::Ok((path.clone(), Arc::new(ContentPack::open(path)?))))
+        .transpose()?;
+
+    if let Some(manifest) = manifest.as_ref().filter(|_| host_pack.is_none()) {
         let project_name = manifest.project.name.as_deref().unwrap_or("project");
         log::info!("Building {}", project_name);
         runtime.block_on(ambient_build::build(PhysicsKey.get(&assets), &assets, project_path.clone(), manifest));
         log::info!("Done building {}", project_name);
     }
 
-    // If this is just a build, exit now
-    if matches!(&cli, Cli::Build { .. }) {
+    // If this is just a build, pack it if asked to and exit now
+    if let Cli::Build { pack, .. } = &cli {
+        if *pack {
+            let (path, stats) = ambient_build::pack(&project_path, manifest.as_ref().expect("no manifest"))?;
+            log::info!(
+                "Packed {} files into {path:?}: {} ({} uncompressed)",
+                stats.entries,
+                to_byte_unit(stats.compressed_size),
+                to_byte_unit(stats.size)
+            );
+        }
         return Ok(());
     }
 
     // Otherwise, either connect to a server or host one
-    let server_addr = if let Cli::Join { host, .. } = &cli {
-        if let Some(mut host) = host.clone() {
-            if !host.contains(':') {
-                host = format!("{host}:{QUIC_INTERFACE_PORT}");
-            }
-            host.parse().with_context(|| format!("Invalid address for host {host}"))?
-        } else {
-            format!("127.0.0.1:{QUIC_INTERFACE_PORT}").parse()?
-        }
+    let (server_addr, join_pack) = if let Cli::Join { host, pack, .. } = &cli {
+        // The pack is mounted at the content url of the server once the client has connected to it
+        let pack = pack
+            .as_ref()
+            .map(|pack| {
+                let bytes =
+                    runtime.block_on(AbsAssetUrl::parse(pack)?.download_bytes(&assets)).context("Failed to load the content pack")?;
+                anyhow::Ok(Arc::new(ContentPack::from_bytes(bytes)?))
+            })
+            .transpose()?;
+        (server_addr(host.as_deref())?, pack)
     } else {
-        let port = server::start(&runtime, assets.clone(), cli.clone(), project_path, manifest.as_ref().expect("no manifest"));
-        format!("127.0.0.1:{port}").parse()?
+        let port = server::start(&runtime, assets.clone(), cli.clone(), project_path, manifest.as_ref().expect("no manifest"), host_pack);
+        (format!("127.0.0.1:{port}").parse()?, None)
     };
 
     // Time to join!
     let handle = runtime.handle().clone();
     if let Some(run) = cli.run() {
         // If we have run parameters, start a client and join a server
-        runtime.block_on(client::run(assets, server_addr, run, cli.project().and_then(|p| p.path.clone())));
+        let project_id = manifest.as_ref().map(|manifest| manifest.project.id.as_ref().to_string());
+        runtime.block_on(client::run(assets, server_addr, run, cli.project().and_then(|p| p.path.clone()), project_id, join_pack));
     } else {
         // Otherwise, wait for the Ctrl+C signal
         handle.block_on(async move {
@@ -149,3 +186,13 @@ fn main() -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+/// The address of the server at `host`, which defaults to localhost and to the default port
+fn server_addr(host: Option<&str>) -> anyhow::Result<SocketAddr> {
+    Ok(if let Some(host) = host {
+        let host = if host.contains(':') { host.to_string() } else { format!("{host}:{QUIC_INTERFACE_PORT}") };
+        host.parse().with_context(|| format!("Invalid address for host {host}"))?
+    } else {
+        format!("127.0.0.1:{QUIC_INTERFACE_PORT}").parse()?
+    })
+}
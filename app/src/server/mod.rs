@@ -3,30 +3,43 @@ use std::{
     net::SocketAddr,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use ambient_core::{app_start_time, asset_cache, dtime, no_sync, project_name, time};
 use ambient_ecs::{
-    world_events, ComponentDesc, ComponentRegistry, Entity, Networked, SystemGroup, World, WorldEventsSystem, WorldStreamCompEvent,
+    query, world_events, ComponentDesc, ComponentRegistry, DynSystem, Entity, FnSystem, Networked, SystemGroup, World, WorldEventsSystem,
+    WorldStreamCompEvent,
 };
+use ambient_input::actions::{action_map, ActionMap};
+use ambient_localization::{localization_url, LOCALIZATION_DIR, LOCALIZATION_INDEX};
 use ambient_network::{
     bi_stream_handlers, datagram_handlers,
+    persistence::{persistence_on_shutdown_systems, persistence_systems, world_persistence, WorldPersistence},
+    rpc_table,
     server::{ForkingEvent, GameServer, ShutdownEvent},
+    synced_resources,
+    transport::Transport,
 };
-use ambient_prefab::PrefabFromUrl;
+use ambient_prefab::PrefabFromCrateUrl;
 use ambient_std::{
     asset_cache::{AssetCache, AsyncAssetKeyExt, SyncAssetKeyExt},
     asset_url::{AbsAssetUrl, ServerBaseUrlKey},
+    content_pack::{mount_content_pack, ContentPack},
+};
+use ambient_sys::{
+    task::RuntimeHandle,
+    time::{Instant, SystemTime},
 };
-use ambient_sys::{task::RuntimeHandle, time::SystemTime};
 use anyhow::Context;
 use axum::{
+    extract::Path as UrlPath,
     http::{Method, StatusCode},
-    response::IntoResponse,
+    response::{IntoResponse, Response},
     routing::{get, get_service},
-    Router,
+    Extension, Router,
 };
-use tower_http::{cors::CorsLayer, services::ServeDir};
+use tower_http::{cors::CorsLayer, services::{ServeDir, ServeFile}};
 
 use crate::{cli::Cli, shared};
 
@@ -38,14 +51,21 @@ pub fn start(
     cli: Cli,
     project_path: PathBuf,
     manifest: &ambient_project::Manifest,
+    pack: Option<(PathBuf, Arc<ContentPack>)>,
 ) -> u16 {
     log::info!("Creating server");
-    let server = runtime.block_on(async move {
-        GameServer::new_with_port_in_range(QUIC_INTERFACE_PORT..(QUIC_INTERFACE_PORT + 10), false)
+    // The client of `ambient run` may connect over a WebSocket too
+    let websocket = cli.host().map_or(false, |host| host.websocket) || cli.run().map_or(false, |run| run.transport == Transport::WebSocket);
+    let mut server = runtime.block_on(async move {
+        GameServer::new_with_port_in_range(QUIC_INTERFACE_PORT..(QUIC_INTERFACE_PORT + 10), false, websocket)
             .await
             .context("failed to create game server with port in range")
             .unwrap()
     });
+    if let Some(host) = cli.host() {
+        server.default_role = host.default_role;
+        server.admins = host.admins.clone();
+    }
     let port = server.port;
 
     wasm::init_all_components();
@@ -55,9 +75,15 @@ pub fn start(
         .or_else(|| local_ip_address::local_ip().ok().map(|x| x.to_string()))
         .unwrap_or("localhost".to_string());
     log::info!("Created server, running at {public_host}:{port}");
-    ServerBaseUrlKey.insert(&assets, AbsAssetUrl::parse(format!("http://{public_host}:{HTTP_INTERFACE_PORT}/content/")).unwrap());
+    let base_url = AbsAssetUrl::parse(format!("http://{public_host}:{HTTP_INTERFACE_PORT}/content/")).unwrap();
+    ServerBaseUrlKey.insert(&assets, base_url.clone());
+    // The server (and the client of `ambient run`) read the content from the pack too, instead of requesting it
+    if let Some((_, pack)) = &pack {
+        mount_content_pack(&assets, base_url, pack.clone());
+    }
 
-    start_http_interface(runtime, &project_path);
+    start_http_interface(runtime, &project_path, pack.clone());
+    let pack = pack.map(|(_, pack)| pack);
 
     ComponentRegistry::get_mut().add_external(manifest.all_defined_components(false).unwrap());
 
@@ -72,52 +98,129 @@ pub fn start(
         let name = manifest.project.name.clone().unwrap_or_else(|| "Ambient".into());
         server_world.add_components(server_world.resource_entity(), Entity::new().with(project_name(), name)).unwrap();
 
-        wasm::initialize(&mut server_world, project_path.clone(), &manifest).await.unwrap();
+        // The actions of the project are synchronized to the clients, which evaluate them for their players
+        let actions = ActionMap::from_table(manifest.actions.clone()).unwrap_or_else(|err| {
+            log::warn!("Failed to parse the actions of the project: {err}");
+            ActionMap::default()
+        });
+        let mut synced = Entity::new().with(action_map(), actions).with_default(synced_resources());
+        // The clients load the string tables built by the localization pipeline from there
+        let localization_index = format!("assets/{LOCALIZATION_DIR}/{LOCALIZATION_INDEX}");
+        let localization = ServerBaseUrlKey.get(&assets).push(format!("assets/{LOCALIZATION_DIR}/")).unwrap().to_string();
+        let localization = match &pack {
+            // The content of a pack doesn't change
+            Some(pack) => {
+                if pack.contains(&localization_index) {
+                    synced.set(localization_url(), localization);
+                }
+                None
+            }
+            // The project may be built (again) while the server runs
+            None => Some((project_path.join("build").join(&localization_index), localization)),
+        };
+        synced.spawn(&mut server_world);
+
+        wasm::initialize(&mut server_world, project_path.clone(), &manifest, pack).await.unwrap();
+
+        // Restore the world from its last save, and keep saving it. The modules are loaded from the project instead, and
+        // spawn what they spawn on load again
+        if let Some((path, autosave_interval)) = cli.host().and_then(|host| Some((host.save.clone()?, host.autosave_interval))) {
+            let autosave_interval = (autosave_interval > 0.).then(|| Duration::from_secs_f32(autosave_interval));
+            let persistence = WorldPersistence::new(path, autosave_interval)
+                .exclude(ambient_wasm::shared::module())
+                .exclude(ambient_wasm::shared::spawned_on_module_load());
+            match persistence.load(&mut server_world) {
+                Ok(restored) => {
+                    match restored {
+                        Some(count) => log::info!("Restored {count} entities from {:?}", persistence.path),
+                        None => log::info!("There is no save at {:?} yet, it will be created", persistence.path),
+                    }
+                    server_world.add_resource(world_persistence(), persistence);
+                }
+                // Saving would overwrite the save which failed to load
+                Err(err) => log::error!("{err:?}\nThe world will not be saved"),
+            }
+        }
 
         if let Cli::View { asset_path, .. } = cli.clone() {
-            let asset_path = AbsAssetUrl::from_file_path(project_path.join("build").join(asset_path).join("prefabs/main.json"));
+            let asset_path = AbsAssetUrl::from_directory_path(project_path.join("build").join(asset_path));
             log::info!("Spawning asset from {:?}", asset_path);
-            let obj = PrefabFromUrl(asset_path.into()).get(&assets).await.unwrap();
+            let obj = PrefabFromCrateUrl(asset_path).get(&assets).await.unwrap();
             obj.spawn_into_world(&mut server_world, None);
         }
         log::info!("Starting server");
         server
-            .run(server_world, Arc::new(systems), Arc::new(on_forking_systems), Arc::new(on_shutdown_systems), Arc::new(is_sync_component))
+            .run(
+                server_world,
+                Arc::new(move |world: &mut World| systems(world, localization.clone())),
+                Arc::new(on_forking_systems),
+                Arc::new(on_shutdown_systems),
+                Arc::new(is_sync_component),
+            )
             .await;
     });
     port
 }
 
-fn systems(_world: &mut World) -> SystemGroup {
-    SystemGroup::new(
-        "server",
-        vec![
-            ambient_physics::run_simulation_system(),
-            // Can happen *during* the physics step
-            Box::new(ambient_core::async_ecs::async_ecs_systems()),
-            Box::new(ambient_prefab::systems()),
-            // Happens after the physics step
-            ambient_physics::fetch_simulation_system(),
-            Box::new(ambient_physics::physx::sync_ecs_physics()),
-            Box::new(ambient_core::transform::TransformSystem::new()),
-            ambient_core::remove_at_time_system(),
-            Box::new(WorldEventsSystem),
-            Box::new(ambient_text::systems(false)),
-            Box::new(ambient_layout::layout_systems()),
-            Box::new(ambient_input::picking::frame_systems()),
-            Box::new(ambient_physics::server_systems()),
-            Box::new(ambient_core::camera::camera_systems()),
-            Box::new(shared::player::server_systems()),
-            Box::new(wasm::systems()),
-            Box::new(shared::player::server_systems_final()),
-        ],
-    )
+fn systems(_world: &mut World, localization: Option<(PathBuf, String)>) -> SystemGroup {
+    let mut systems: Vec<DynSystem> = vec![
+        ambient_physics::run_simulation_system(),
+        // Can happen *during* the physics step
+        Box::new(ambient_core::async_ecs::async_ecs_systems()),
+        Box::new(ambient_prefab::systems()),
+        // Happens after the physics step
+        ambient_physics::fetch_simulation_system(),
+        Box::new(ambient_physics::physx::sync_ecs_physics()),
+        Box::new(ambient_core::transform::TransformSystem::new()),
+        ambient_core::remove_at_time_system(),
+        ambient_core::relationship::relationships_system(),
+        Box::new(WorldEventsSystem),
+        Box::new(ambient_text::systems(false)),
+        Box::new(ambient_layout::layout_systems()),
+        Box::new(ambient_input::picking::frame_systems()),
+        Box::new(ambient_physics::server_systems()),
+        Box::new(ambient_core::camera::camera_systems()),
+        Box::new(shared::player::server_systems()),
+        Box::new(wasm::systems()),
+        Box::new(ambient_network::debug_draw::server_systems()),
+        Box::new(shared::player::server_systems_final()),
+        Box::new(persistence_systems()),
+    ];
+    if let Some((index, url)) = localization {
+        systems.push(localization_url_system(index, url));
+    }
+    SystemGroup::new("server", systems)
+}
+
+/// Sets the localization URL on the synced resources once the localization pipeline has written its `index`
+fn localization_url_system(index: PathBuf, url: String) -> DynSystem {
+    let mut next_check = Instant::now();
+    Box::new(FnSystem::new(move |world, _| {
+        if Instant::now() < next_check {
+            return;
+        }
+        next_check = Instant::now() + Duration::from_secs(1);
+        let Some(id) = query(()).incl(synced_resources()).excl(localization_url()).iter(world, None).map(|(id, _)| id).next() else {
+            return;
+        };
+        if index.exists() {
+            log::info!("Found the string tables of the project at {url}");
+            world.add_component(id, localization_url(), url.clone()).unwrap();
+        }
+    }))
 }
 fn on_forking_systems() -> SystemGroup<ForkingEvent> {
     SystemGroup::new("on_forking_systems", vec![Box::new(ambient_physics::on_forking_systems()), Box::new(wasm::on_forking_systems())])
 }
 fn on_shutdown_systems() -> SystemGroup<ShutdownEvent> {
-    SystemGroup::new("on_shutdown_systems", vec![Box::new(ambient_physics::on_shutdown_systems()), Box::new(wasm::on_shutdown_systems())])
+    SystemGroup::new(
+        "on_shutdown_systems",
+        vec![
+            Box::new(persistence_on_shutdown_systems()),
+            Box::new(ambient_physics::on_shutdown_systems()),
+            Box::new(wasm::on_shutdown_systems()),
+        ],
+    )
 }
 
 fn is_sync_component(component: ComponentDesc, _: WorldStreamCompEvent) -> bool {
@@ -138,11 +241,14 @@ fn create_resources(assets: AssetCache) -> Entity {
     server_resources.set(dtime(), 1. / 60.);
 
     let mut handlers = HashMap::new();
-    ambient_network::register_rpc_bi_stream_handler(&mut handlers, shared::create_rpc_registry());
+    let rpc_registry = shared::create_rpc_registry();
+    server_resources.set(rpc_table(), rpc_registry.table().clone());
+    ambient_network::register_rpc_bi_stream_handler(&mut handlers, rpc_registry);
     server_resources.set(bi_stream_handlers(), handlers);
 
     let mut handlers = HashMap::new();
     shared::player::register_datagram_handler(&mut handlers);
+    ambient_network::messages::register_server_message_datagram_handler(&mut handlers);
     server_resources.set(datagram_handlers(), handlers);
 
     server_resources
@@ -151,10 +257,17 @@ fn create_resources(assets: AssetCache) -> Entity {
 pub const HTTP_INTERFACE_PORT: u16 = 8999;
 pub const QUIC_INTERFACE_PORT: u16 = 9000;
 
-fn start_http_interface(runtime: &tokio::runtime::Runtime, project_path: &Path) {
-    let router = Router::new()
-        .route("/ping", get(|| async move { "ok" }))
-        .nest_service("/content", get_service(ServeDir::new(project_path.join("build"))).handle_error(handle_error))
+fn start_http_interface(runtime: &tokio::runtime::Runtime, project_path: &Path, pack: Option<(PathBuf, Arc<ContentPack>)>) {
+    let router = Router::new().route("/ping", get(|| async move { "ok" }));
+    let router = match pack {
+        // The whole pack is also served, for clients to download it at once
+        Some((path, pack)) => router
+            .route("/content/*path", get(serve_content_pack_entry))
+            .route_service("/content.pack", get_service(ServeFile::new(path)).handle_error(handle_error))
+            .layer(Extension(pack)),
+        None => router.nest_service("/content", get_service(ServeDir::new(project_path.join("build"))).handle_error(handle_error)),
+    };
+    let router = router
         .layer(CorsLayer::new().allow_origin(tower_http::cors::Any).allow_methods(vec![Method::GET]).allow_headers(tower_http::cors::Any));
 
     runtime.spawn(async move {
@@ -163,6 +276,17 @@ fn start_http_interface(runtime: &tokio::runtime::Runtime, project_path: &Path)
     });
 }
 
+async fn serve_content_pack_entry(Extension(pack): Extension<Arc<ContentPack>>, UrlPath(path): UrlPath<String>) -> Response {
+    match tokio::task::spawn_blocking(move || pack.read(&path)).await.unwrap() {
+        Ok(Some(data)) => data.into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(err) => {
+            log::error!("Failed to serve from the content pack: {err:?}");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong...").into_response()
+        }
+    }
+}
+
 async fn handle_error(_err: std::io::Error) -> impl IntoResponse {
     (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong...")
 }
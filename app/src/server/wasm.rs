@@ -2,8 +2,12 @@ use std::{path::PathBuf, sync::Arc};
 
 use ambient_ecs::{components, EntityId, Resource, SystemGroup, World};
 use ambient_network::server::{ForkingEvent, ShutdownEvent};
+use ambient_std::content_pack::ContentPack;
 use ambient_wasm::{
-    server::bindings::{Bindings as ElementsBindings, WasmServerContext},
+    server::{
+        bindings::{Bindings as ElementsBindings, WasmServerContext},
+        storage::{storage, Storage, StorageQuota},
+    },
     shared::{
         get_module_name, host_guest_state::BaseHostGuestState, module_bytecode, spawn_module, MessageType, ModuleBytecode, ModuleState,
     },
@@ -24,6 +28,7 @@ components!("wasm::server", {
 
 pub fn init_all_components() {
     init_components();
+    ambient_wasm::server::storage::init_components();
 }
 
 pub fn systems() -> SystemGroup {
@@ -38,7 +43,12 @@ pub fn on_shutdown_systems() -> SystemGroup<ShutdownEvent> {
     ambient_wasm::server::on_shutdown_systems(module_state())
 }
 
-pub async fn initialize(world: &mut World, project_path: PathBuf, manifest: &ambient_project::Manifest) -> anyhow::Result<()> {
+pub async fn initialize(
+    world: &mut World,
+    project_path: PathBuf,
+    manifest: &ambient_project::Manifest,
+    pack: Option<Arc<ContentPack>>,
+) -> anyhow::Result<()> {
     let messenger = Arc::new(|world: &World, id: EntityId, type_: MessageType, message: &str| {
         let name = get_module_name(world, id);
         let (prefix, level) = match type_ {
@@ -59,10 +69,22 @@ pub async fn initialize(world: &mut World, project_path: PathBuf, manifest: &amb
     )
     .await?;
 
-    let main_wasm_path = project_path.join("build").join(format!("{}.wasm", manifest.project.id));
-    if main_wasm_path.exists() {
-        let bytecode = std::fs::read(main_wasm_path)?;
+    let storage_path = project_path.join("data").join("storage").join(manifest.project.id.to_string());
+    world.add_resource(storage(), Arc::new(Storage::open(storage_path, StorageQuota::default())?));
 
+    let main_wasm_name = format!("{}.wasm", manifest.project.id);
+    let bytecode = match pack {
+        Some(pack) => pack.read(&main_wasm_name)?,
+        None => {
+            let main_wasm_path = project_path.join("build").join(main_wasm_name);
+            if main_wasm_path.exists() {
+                Some(std::fs::read(main_wasm_path)?)
+            } else {
+                None
+            }
+        }
+    };
+    if let Some(bytecode) = bytecode {
         let id = spawn_module(world, &manifest.project.id, manifest.project.description.clone().unwrap_or_default(), true)?;
         world.add_component(id, module_bytecode(), ModuleBytecode(bytecode))?;
     }
@@ -1,4 +1,4 @@
-use std::{io::Write, sync::Arc};
+use std::{io::Write, path::PathBuf, sync::Arc};
 
 use ambient_core::{
     player::{get_player_by_user_id, player},
@@ -8,7 +8,9 @@ use ambient_core::{
 use ambient_ecs::{query, query_mut, Entity, SystemGroup, WorldDiff};
 use ambient_element::{element_component, Element, Hooks};
 use ambient_input::{
+    actions::{action_bindings, action_map, player_actions, player_prev_actions, ActionBindings, PlayerActions},
     event_focus_change, event_keyboard_input, event_mouse_input, event_mouse_motion, event_mouse_wheel, event_mouse_wheel_pixels,
+    gamepad::gamepads,
     mouse_button, mouse_button_from_u32, player_prev_raw_input, player_raw_input, ElementState, PlayerRawInput,
 };
 use ambient_network::{client::game_client, log_network_result, rpc::rpc_world_diff, DatagramHandlers};
@@ -18,16 +20,27 @@ use byteorder::{BigEndian, WriteBytesExt};
 
 const PLAYER_INPUT_DATAGRAM_ID: u32 = 5;
 
+/// Encodes the input of the local player, to be sent to the server as a datagram
+pub fn player_input_datagram(input: &PlayerRawInput, actions: &PlayerActions) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.write_u32::<BigEndian>(PLAYER_INPUT_DATAGRAM_ID).unwrap();
+    data.write_all(&bincode::serialize(&(input, actions)).unwrap()).unwrap();
+    data
+}
+
 pub fn register_datagram_handler(handlers: &mut DatagramHandlers) {
     handlers.insert(
         PLAYER_INPUT_DATAGRAM_ID,
         Arc::new(|state, _assets, user_id, data| {
-            let input: PlayerRawInput = unwrap_log_err!(bincode::deserialize(&data));
+            let (input, actions): (PlayerRawInput, PlayerActions) = unwrap_log_err!(bincode::deserialize(&data));
             let mut state = state.lock();
             if let Some(world) = state.get_player_world_mut(user_id) {
                 if let Some(player_id) = get_player_by_user_id(world, user_id) {
                     world.add_component(player_id, cursor_position(), input.cursor_position).unwrap();
                     world.set(player_id, player_raw_input(), input).ok();
+                    // The actions are evaluated by the client, so they're only trusted as far as ActionMap::validate goes
+                    let map = query(action_map()).iter(world, None).map(|(_, map)| map.clone()).next().unwrap_or_default();
+                    world.set(player_id, player_actions(), map.validate(actions)).ok();
                 }
             }
         }),
@@ -40,7 +53,16 @@ pub fn server_systems() -> SystemGroup {
         vec![query(player()).spawned().to_system(|q, world, qs, _| {
             let player_ids = q.collect_ids(world, qs);
             for player_id in player_ids {
-                world.add_components(player_id, Entity::new().with_default(player_raw_input()).with_default(player_prev_raw_input())).ok();
+                world
+                    .add_components(
+                        player_id,
+                        Entity::new()
+                            .with_default(player_raw_input())
+                            .with_default(player_prev_raw_input())
+                            .with_default(player_actions())
+                            .with_default(player_prev_actions()),
+                    )
+                    .ok();
             }
         })],
     )
@@ -49,11 +71,18 @@ pub fn server_systems() -> SystemGroup {
 pub fn server_systems_final() -> SystemGroup {
     SystemGroup::new(
         "player/server_systems_final",
-        vec![query_mut(player_prev_raw_input(), player_raw_input()).to_system(|q, world, qs, _| {
-            for (_, prev, input) in q.iter(world, qs) {
-                *prev = input.clone();
-            }
-        })],
+        vec![
+            query_mut(player_prev_raw_input(), player_raw_input()).to_system(|q, world, qs, _| {
+                for (_, prev, input) in q.iter(world, qs) {
+                    *prev = input.clone();
+                }
+            }),
+            query_mut(player_prev_actions(), player_actions()).to_system(|q, world, qs, _| {
+                for (_, prev, actions) in q.iter(world, qs) {
+                    *prev = actions.clone();
+                }
+            }),
+        ],
     )
 }
 
@@ -89,6 +118,7 @@ pub fn PlayerRawInputHandler(hooks: &mut Hooks) -> Element {
     const PIXELS_PER_LINE: f32 = 5.0;
 
     let input = hooks.use_ref_with(|_| PlayerRawInput::default());
+    let prev_input = hooks.use_ref_with(|_| PlayerRawInput::default());
     let (has_focus, set_has_focus) = hooks.use_state(false);
 
     hooks.use_world_event({
@@ -134,17 +164,28 @@ pub fn PlayerRawInputHandler(hooks: &mut Hooks) -> Element {
             let runtime = world.resource(runtime()).clone();
             let input = input.clone();
             let cursor_position = *world.resource(cursor_position());
+            input.lock().gamepads = world.resource_opt(gamepads()).cloned().unwrap_or_default();
 
-            runtime.spawn(async move {
-                let mut data = Vec::new();
-                data.write_u32::<BigEndian>(PLAYER_INPUT_DATAGRAM_ID).unwrap();
+            // The action map comes from the server, but the user can rebind its actions
+            let map = query(action_map()).iter(&gc.game_state.lock().world, None).map(|(_, map)| map.clone()).next().unwrap_or_default();
+            let map = match world.resource_opt(action_bindings()) {
+                Some(bindings) => map.with_bindings(bindings),
+                None => map,
+            };
+            let actions = {
+                let input = input.lock();
+                let mut prev_input = prev_input.lock();
+                let actions = map.evaluate(&input, &prev_input);
+                *prev_input = input.clone();
+                actions
+            };
 
-                let msg = {
+            runtime.spawn(async move {
+                let data = {
                     let mut input = input.lock();
                     input.cursor_position = cursor_position;
-                    bincode::serialize(&*input).unwrap()
+                    player_input_datagram(&input, &actions)
                 };
-                data.write_all(&msg).unwrap();
                 gc.connection.send_datagram(data.into()).ok();
             });
         }
@@ -152,3 +193,36 @@ pub fn PlayerRawInputHandler(hooks: &mut Hooks) -> Element {
 
     Element::new()
 }
+
+/// Loads the action bindings of the user from `path`, and saves them there whenever they change
+#[element_component]
+pub fn PlayerActionBindings(hooks: &mut Hooks, path: PathBuf) -> Element {
+    let saved = hooks.use_ref_with(|world| {
+        let bindings = match std::fs::read_to_string(&path) {
+            Ok(toml) => ActionBindings::parse(&toml).unwrap_or_else(|err| {
+                log::warn!("Failed to parse the action bindings in {path:?}: {err}");
+                ActionBindings::default()
+            }),
+            Err(_) => ActionBindings::default(),
+        };
+        world.add_resource(action_bindings(), bindings.clone());
+        bindings
+    });
+    hooks.use_frame(move |world| {
+        let bindings = match world.resource_opt(action_bindings()) {
+            Some(bindings) => bindings,
+            None => return,
+        };
+        let mut saved = saved.lock();
+        if *bindings == *saved {
+            return;
+        }
+        *saved = bindings.clone();
+        let toml = unwrap_log_err!(bindings.to_toml());
+        if let Some(dir) = path.parent() {
+            unwrap_log_err!(std::fs::create_dir_all(dir));
+        }
+        unwrap_log_err!(std::fs::write(&path, toml));
+    });
+    Element::new()
+}
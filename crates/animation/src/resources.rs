@@ -2,7 +2,7 @@ use std::{collections::HashMap, f32::consts::PI, ops::Neg};
 
 use ambient_core::transform::{euler_rotation, rotation, scale, translation};
 use ambient_ecs::{Component, ComponentDesc, EntityId};
-use ambient_std::{download_asset::BincodeFromUrl, math::mix};
+use ambient_std::{asset_cache::AssetSize, download_asset::BincodeFromUrl, math::mix};
 use glam::{EulerRot, Quat, Vec3};
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
@@ -184,6 +184,19 @@ pub struct AnimationClip {
     pub start: f32,
     pub end: f32,
 }
+impl AssetSize for AnimationClip {
+    fn size_bytes(&self) -> Option<u64> {
+        let tracks = self.tracks.iter().map(|track| {
+            let outputs = match &track.outputs {
+                AnimationOutputs::Vec3 { data, .. } => std::mem::size_of_val(&data[..]),
+                AnimationOutputs::Quat { data, .. } => std::mem::size_of_val(&data[..]),
+                AnimationOutputs::Vec3Field { data, .. } => std::mem::size_of_val(&data[..]),
+            };
+            std::mem::size_of_val(&track.inputs[..]) + outputs
+        });
+        Some(tracks.sum::<usize>() as u64)
+    }
+}
 impl AnimationClip {
     pub fn from_tracks(tracks: Vec<AnimationTrack>) -> Self {
         let end = tracks.iter().map(|x| ordered_float::OrderedFloat::from(x.duration())).max().unwrap().into();
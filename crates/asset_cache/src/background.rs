@@ -29,6 +29,10 @@ where
         AssetLoadDropPolicy::KeepLoading
     }
 
+    fn track_memory(&self) -> bool {
+        false
+    }
+
     async fn load(self, assets: AssetCache) -> T {
         // Short happy path
         // This is needed as JoinHandle does not complete immediately, even if the spawned future
@@ -1,4 +1,5 @@
 mod background;
+mod memory;
 
 use std::{
     any::Any,
@@ -17,9 +18,11 @@ use ambient_sys::{
 use async_trait::async_trait;
 use background::BackgroundKey;
 use futures::{
-    future::{pending, BoxFuture, Shared, WeakShared},
+    future::{BoxFuture, Shared, WeakShared},
     Future, FutureExt,
 };
+use memory::AssetsMemory;
+pub use memory::{AssetCacheStats, AssetTypeStats};
 use parking_lot::Mutex;
 use pin_project::{pin_project, pinned_drop};
 use serde::{Deserialize, Serialize};
@@ -92,6 +95,10 @@ impl std::fmt::Debug for ContentState {
     }
 }
 
+/// The references which keep an asset alive, shared by the cache and the keepalive task so that the cache can release
+/// them right away
+type KeepaliveRef = Arc<Mutex<Option<Box<dyn Any + Send>>>>;
+
 struct AsyncAssetLoc {
     #[allow(dead_code)]
     key: AssetKey,
@@ -100,9 +107,22 @@ struct AsyncAssetLoc {
     keepalive_guard: Weak<KeepaliveGuard>,
     content: ContentState,
     keepalive_task: Option<ChildTask<()>>,
+    keepalive_ref: Option<KeepaliveRef>,
 }
 
 impl AsyncAssetLoc {
+    /// Replaces the references which keep the asset alive, and releases the previous ones
+    fn keep_alive(&mut self, keepalive_ref: KeepaliveRef) {
+        if let Some(previous) = self.keepalive_ref.replace(keepalive_ref) {
+            previous.lock().take();
+        }
+    }
+    fn release(&mut self) {
+        self.keepalive_task = None;
+        if let Some(keepalive_ref) = self.keepalive_ref.take() {
+            keepalive_ref.lock().take();
+        }
+    }
     /// Checks if the resource has been dropped since this method was called last time
     fn state(&mut self) -> AsyncAssetState {
         match &mut self.content {
@@ -162,6 +182,7 @@ pub struct AssetCache {
     async_cache: Arc<Mutex<HashMap<AssetKey, AsyncAssetLoc>>>,
     sync: Arc<Mutex<HashMap<AssetKey, SyncAssetLoc>>>,
     pub timeline: Arc<Mutex<AssetsTimeline>>,
+    memory: Arc<Mutex<AssetsMemory>>,
     runtime: RuntimeHandle,
     max_keepalive: Option<Duration>,
     /// stack is used for nested asset loading, to visualize for the timeline who loaded what
@@ -178,6 +199,7 @@ impl AssetCache {
             async_cache: Arc::new(Mutex::new(HashMap::new())),
             sync: Arc::new(Mutex::new(HashMap::new())),
             timeline: Arc::new(Mutex::new(AssetsTimeline::new())),
+            memory: Arc::new(Mutex::new(AssetsMemory::default())),
             runtime: runtime.clone(),
             max_keepalive,
             stack: Vec::new(),
@@ -243,13 +265,31 @@ impl AssetCache {
         for (key, asset) in &mut *async_ {
             let state = asset.state();
             match state {
-                AsyncAssetState::Died => self.timeline.lock().dropped(key),
+                AsyncAssetState::Died => {
+                    self.timeline.lock().dropped(key);
+                    self.memory.lock().dropped(key);
+                }
                 AsyncAssetState::Aborted => self.timeline.lock().aborted(key),
                 _ => {}
             }
         }
     }
 
+    /// Sets the memory budget of the assets of type `T` (e.g. `Result<Arc<Texture>, AssetError>`), in bytes. When the
+    /// loaded assets of this type are larger than that, the cache releases the least recently used ones, unless they
+    /// are kept alive forever. Assets still used elsewhere stay loaded until they're dropped
+    pub fn set_memory_budget<T: 'static>(&self, budget: Option<u64>) {
+        let mut cache = self.async_cache.lock();
+        let mut memory = self.memory.lock();
+        memory.set_budget::<T>(budget);
+        release_assets(&mut cache, memory.over_budget::<T>(None));
+    }
+
+    /// The number of loaded assets, their sizes and the cache hits and misses, by asset type
+    pub fn memory_stats(&self) -> AssetCacheStats {
+        self.memory.lock().stats()
+    }
+
     /// Returns a snapshot of the current state of the asset
     pub(crate) fn content_state<T: 'static + Clone + Asset + Send + Sync, K: AsyncAssetKeyExt<T>>(&self, key: &K) -> Option<ContentState> {
         let key = AssetKey::new(key.key());
@@ -277,9 +317,13 @@ impl AssetCache {
         let timeline = self.timeline.clone();
 
         let asset_key = AssetKey::new(key.key());
+        let track_memory = key.track_memory();
 
         let load = || {
             tracing::debug!("Loading asset: {asset_key:?}");
+            if track_memory {
+                self.memory.lock().miss::<T>();
+            }
 
             // No future loading the value was found.
             //
@@ -291,6 +335,7 @@ impl AssetCache {
 
             let fut = (Box::pin(AssetLoadFuture {
                 cache: self.async_cache.clone(),
+                memory: self.memory.clone(),
                 key: key.clone(),
                 completed: false,
                 timeline: timeline.clone(),
@@ -319,6 +364,9 @@ impl AssetCache {
                 match &mut loc.content {
                     ContentState::Loading { fut } => {
                         if let Some(fut) = fut.upgrade() {
+                            if track_memory {
+                                self.memory.lock().hit::<T>();
+                            }
                             fut
                         } else {
                             // Start the loading, and update the content state yet again with the
@@ -334,9 +382,13 @@ impl AssetCache {
 
                         let content = value.as_any().downcast_ref::<<T as Asset>::WeakType>().unwrap();
                         if let Some(content) = T::from_weak(content) {
+                            if track_memory {
+                                self.memory.lock().hit::<T>();
+                            }
                             return Ok((asset_key, content));
                         }
 
+                        self.memory.lock().dropped(&asset_key);
                         let (fut, c, k) = load();
                         loc.content = c;
                         loc.keepalive_task = k;
@@ -357,7 +409,7 @@ impl AssetCache {
                 let (fut, content, keepalive_task) = load();
                 let key = slot.key().clone();
 
-                slot.insert(AsyncAssetLoc { key, content, keepalive_task, keepalive_guard: Weak::new() });
+                slot.insert(AsyncAssetLoc { key, content, keepalive_task, keepalive_guard: Weak::new(), keepalive_ref: None });
 
                 fut
             }
@@ -372,6 +424,7 @@ impl AssetCache {
         T: 'static + Asset + Clone + Sync + Send,
     {
         let keepalive = key.keepalive();
+        let track_memory = key.track_memory();
 
         let (asset_key, value) = match self.get_asset_future(key) {
             Ok(value) => value,
@@ -388,7 +441,6 @@ impl AssetCache {
 
         // Start or replace the keepalive task
 
-        let keepalive_ref = value.clone();
         // Use a drop impl since cancelling a task causes the task to not reach the end, and
         // therefore not registering that the keepalive ended.
         //
@@ -399,30 +451,40 @@ impl AssetCache {
             loc.keepalive_guard.upgrade().unwrap_or_else(|| Arc::new(KeepaliveGuard::begin(asset_key.clone(), self.timeline.clone())));
         loc.keepalive_guard = Arc::downgrade(&guard);
 
+        let released = if track_memory {
+            let mut memory = self.memory.lock();
+            memory.used::<T>(&asset_key, matches!(keepalive, AssetKeepalive::Forever));
+            memory.over_budget::<T>(Some(&asset_key))
+        } else {
+            Vec::new()
+        };
+
+        let keepalive_ref: KeepaliveRef = Arc::new(Mutex::new(Some(Box::new((value.clone(), guard)))));
         match keepalive {
             AssetKeepalive::Timeout(mut dur) => {
                 if let Some(max_keepalive) = self.max_keepalive {
                     dur = dur.min(max_keepalive);
                 }
 
-                let task = self.runtime.spawn(async move {
-                    time::sleep(dur).await;
-                    tracing::debug!("Keepalive timed out for {asset_key:?}");
-                    drop((keepalive_ref, guard));
+                let task = self.runtime.spawn({
+                    let keepalive_ref = keepalive_ref.clone();
+                    async move {
+                        time::sleep(dur).await;
+                        tracing::debug!("Keepalive timed out for {asset_key:?}");
+                        keepalive_ref.lock().take();
+                    }
                 });
 
                 loc.keepalive_task = Some(task.into());
+                loc.keep_alive(keepalive_ref);
             }
             AssetKeepalive::Forever => {
-                let task = self.runtime.spawn(async move {
-                    pending::<()>().await;
-                    drop((keepalive_ref, guard));
-                });
-
-                loc.keepalive_task = Some(task.into());
+                loc.keepalive_task = None;
+                loc.keep_alive(keepalive_ref);
             }
             _ => (),
         }
+        release_assets(&mut cache, released);
 
         value
     }
@@ -432,6 +494,16 @@ impl AssetCache {
     }
 }
 
+/// Drops the references the cache keeps to the assets of `keys`, which unloads them unless they're used elsewhere
+fn release_assets(cache: &mut HashMap<AssetKey, AsyncAssetLoc>, keys: Vec<AssetKey>) {
+    for key in keys {
+        tracing::debug!("Releasing asset over the memory budget: {key:?}");
+        if let Some(loc) = cache.get_mut(&key) {
+            loc.release();
+        }
+    }
+}
+
 impl std::fmt::Debug for AssetCache {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AssetCache").finish_non_exhaustive()
@@ -495,12 +567,19 @@ pub trait AsyncAssetKey<T: Asset + Clone + Sync + Send + 'static>: Sync + Send +
         AssetLoadDropPolicy::StopLoading
     }
 
+    /// The size of `asset` in memory, which counts towards the memory budget of its type. Keys of assets which implement
+    /// [AssetSize] can return `asset.size_bytes()`
     fn cpu_size(&self, _asset: &T) -> Option<u64> {
         None
     }
     fn gpu_size(&self, _asset: &T) -> Option<u64> {
         None
     }
+    /// If the assets of this key count in the memory stats and budgets of the cache. Keys which share the asset of
+    /// another key, like [AsyncAssetKey::in_background], opt out so that it isn't counted twice
+    fn track_memory(&self) -> bool {
+        true
+    }
 }
 #[async_trait]
 pub trait AsyncAssetKeyExt<T: Asset + Clone + Sync + Send + 'static>: AsyncAssetKey<T> {
@@ -612,6 +691,37 @@ impl<T: Asset + Sync + Send> Asset for Vec<T> {
     }
 }
 
+/// The number of bytes an asset holds in memory. It's implemented through `Arc`s, `Result`s and `Option`s, so that the
+/// keys of these assets can report it with [AsyncAssetKey::cpu_size]
+pub trait AssetSize {
+    fn size_bytes(&self) -> Option<u64>;
+}
+impl<T: AssetSize + ?Sized> AssetSize for Arc<T> {
+    fn size_bytes(&self) -> Option<u64> {
+        (**self).size_bytes()
+    }
+}
+impl<T: AssetSize, E> AssetSize for Result<T, E> {
+    fn size_bytes(&self) -> Option<u64> {
+        self.as_ref().ok().and_then(|val| val.size_bytes())
+    }
+}
+impl<T: AssetSize> AssetSize for Option<T> {
+    fn size_bytes(&self) -> Option<u64> {
+        self.as_ref().and_then(|val| val.size_bytes())
+    }
+}
+impl AssetSize for [u8] {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.len() as u64)
+    }
+}
+impl AssetSize for Vec<u8> {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.len() as u64)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetLifetime {
     pub start_load: chrono::DateTime<chrono::Utc>,
@@ -723,6 +833,7 @@ impl AssetsTimeline {
 struct AssetLoadFuture<F, K> {
     // Where to store the result
     cache: Arc<Mutex<HashMap<AssetKey, AsyncAssetLoc>>>,
+    memory: Arc<Mutex<AssetsMemory>>,
     asset_key: AssetKey,
     timeline: Arc<Mutex<AssetsTimeline>>,
     #[pin]
@@ -750,6 +861,10 @@ where
             let cpu_size = p.key.cpu_size(&res);
             let gpu_size = p.key.gpu_size(&res);
             p.timeline.lock().end_load(p.asset_key, cpu_size, gpu_size);
+            if p.key.track_memory() {
+                let bytes = cpu_size.into_iter().chain(gpu_size).reduce(|a, b| a + b);
+                p.memory.lock().loaded::<T>(p.asset_key.clone(), bytes);
+            }
 
             let weak_res = Arc::new(T::to_weak(&res)) as Arc<dyn AssetHolder>;
 
@@ -0,0 +1,165 @@
+use std::{
+    any::{type_name, TypeId},
+    collections::HashMap,
+};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::AssetKey;
+
+/// The assets of one type in an [AssetCache](crate::AssetCache)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AssetTypeStats {
+    pub type_name: String,
+    /// The number of loaded assets which are still alive
+    pub count: usize,
+    /// The total size of these assets in CPU and GPU memory, for the ones which report their size
+    pub bytes: u64,
+    pub budget: Option<u64>,
+    /// Requests for an asset which was loaded or being loaded
+    pub hits: u64,
+    /// Requests which had to load the asset
+    pub misses: u64,
+    /// The number of assets the cache released to stay within the budget
+    pub evictions: u64,
+}
+impl AssetTypeStats {
+    pub fn hit_rate(&self) -> f32 {
+        let requests = self.hits + self.misses;
+        if requests == 0 {
+            0.
+        } else {
+            self.hits as f32 / requests as f32
+        }
+    }
+}
+
+/// The memory used by an [AssetCache](crate::AssetCache), by asset type
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AssetCacheStats {
+    /// Sorted by size, the largest first
+    pub types: Vec<AssetTypeStats>,
+}
+impl AssetCacheStats {
+    pub fn total_count(&self) -> usize {
+        self.types.iter().map(|ty| ty.count).sum()
+    }
+    pub fn total_bytes(&self) -> u64 {
+        self.types.iter().map(|ty| ty.bytes).sum()
+    }
+}
+
+#[derive(Debug)]
+struct EntryMemory {
+    bytes: u64,
+    last_used: u64,
+    /// Kept alive forever, so it's never released
+    pinned: bool,
+    /// Released by the cache, but may still be used elsewhere
+    released: bool,
+}
+
+#[derive(Debug)]
+struct TypeMemory {
+    type_name: &'static str,
+    budget: Option<u64>,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+    entries: HashMap<AssetKey, EntryMemory>,
+}
+
+/// Tracks the sizes of the loaded assets and their last use, to release the least recently used ones when a type is over
+/// its budget
+#[derive(Debug, Default)]
+pub(crate) struct AssetsMemory {
+    types: HashMap<TypeId, TypeMemory>,
+    clock: u64,
+}
+impl AssetsMemory {
+    fn type_mut<T: 'static>(&mut self) -> &mut TypeMemory {
+        self.types.entry(TypeId::of::<T>()).or_insert_with(|| TypeMemory {
+            type_name: type_name::<T>(),
+            budget: None,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+            entries: HashMap::new(),
+        })
+    }
+    pub(crate) fn hit<T: 'static>(&mut self) {
+        self.type_mut::<T>().hits += 1;
+    }
+    pub(crate) fn miss<T: 'static>(&mut self) {
+        self.type_mut::<T>().misses += 1;
+    }
+    pub(crate) fn loaded<T: 'static>(&mut self, key: AssetKey, bytes: Option<u64>) {
+        self.clock += 1;
+        let last_used = self.clock;
+        self.type_mut::<T>()
+            .entries
+            .insert(key, EntryMemory { bytes: bytes.unwrap_or_default(), last_used, pinned: false, released: false });
+    }
+    /// The cache holds the asset of `key` again
+    pub(crate) fn used<T: 'static>(&mut self, key: &AssetKey, pinned: bool) {
+        self.clock += 1;
+        let clock = self.clock;
+        if let Some(entry) = self.type_mut::<T>().entries.get_mut(key) {
+            entry.last_used = clock;
+            entry.pinned = pinned;
+            entry.released = false;
+        }
+    }
+    pub(crate) fn dropped(&mut self, key: &AssetKey) {
+        for ty in self.types.values_mut() {
+            ty.entries.remove(key);
+        }
+    }
+    pub(crate) fn set_budget<T: 'static>(&mut self, budget: Option<u64>) {
+        self.type_mut::<T>().budget = budget;
+    }
+    /// Picks the least recently used assets of `T` to release until the ones the cache holds fit in the budget. `keep`
+    /// is never picked
+    pub(crate) fn over_budget<T: 'static>(&mut self, keep: Option<&AssetKey>) -> Vec<AssetKey> {
+        let Some(ty) = self.types.get_mut(&TypeId::of::<T>()) else { return Vec::new() };
+        let Some(budget) = ty.budget else { return Vec::new() };
+        let mut held: u64 = ty.entries.values().filter(|entry| !entry.released).map(|entry| entry.bytes).sum();
+        let candidates = ty
+            .entries
+            .iter()
+            .filter(|(key, entry)| !entry.pinned && !entry.released && entry.bytes > 0 && Some(*key) != keep)
+            .sorted_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone())
+            .collect_vec();
+        let mut released = Vec::new();
+        for key in candidates {
+            if held <= budget {
+                break;
+            }
+            let entry = ty.entries.get_mut(&key).unwrap();
+            entry.released = true;
+            held -= entry.bytes;
+            ty.evictions += 1;
+            released.push(key);
+        }
+        released
+    }
+    pub(crate) fn stats(&self) -> AssetCacheStats {
+        let types = self
+            .types
+            .values()
+            .map(|ty| AssetTypeStats {
+                type_name: ty.type_name.to_string(),
+                count: ty.entries.len(),
+                bytes: ty.entries.values().map(|entry| entry.bytes).sum(),
+                budget: ty.budget,
+                hits: ty.hits,
+                misses: ty.misses,
+                evictions: ty.evictions,
+            })
+            .sorted_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.type_name.cmp(&b.type_name)))
+            .collect();
+        AssetCacheStats { types }
+    }
+}
@@ -0,0 +1,97 @@
+use std::{sync::Arc, time::Duration};
+
+use ambient_asset_cache::{AssetCache, AssetKeepalive, AssetSize, AsyncAssetKey, AsyncAssetKeyExt};
+use async_trait::async_trait;
+use pretty_assertions::assert_eq;
+use tokio::runtime;
+
+#[derive(PartialEq, Eq, Debug)]
+struct TestAsset {
+    name: String,
+}
+
+#[derive(Debug, Clone)]
+struct TestAssetKey {
+    name: String,
+    size: u64,
+}
+impl TestAssetKey {
+    fn new(name: &str, size: u64) -> Self {
+        Self { name: name.into(), size }
+    }
+}
+
+#[async_trait]
+impl AsyncAssetKey<Arc<TestAsset>> for TestAssetKey {
+    async fn load(self, _: AssetCache) -> Arc<TestAsset> {
+        Arc::new(TestAsset { name: self.name })
+    }
+
+    fn keepalive(&self) -> AssetKeepalive {
+        AssetKeepalive::Timeout(Duration::from_secs(60))
+    }
+
+    fn cpu_size(&self, _asset: &Arc<TestAsset>) -> Option<u64> {
+        Some(self.size)
+    }
+}
+
+#[tokio::test]
+async fn stats() {
+    let assets = AssetCache::new(runtime::Handle::current());
+
+    let _a = TestAssetKey::new("a", 100).get(&assets).await;
+    let _b = TestAssetKey::new("b", 50).get(&assets).await;
+    let _a = TestAssetKey::new("a", 100).get(&assets).await;
+
+    let stats = assets.memory_stats();
+    assert_eq!(stats.total_count(), 2);
+    assert_eq!(stats.total_bytes(), 150);
+    let ty = &stats.types[0];
+    assert_eq!((ty.hits, ty.misses, ty.evictions), (1, 2, 0));
+    assert_eq!(ty.budget, None);
+}
+
+#[tokio::test]
+async fn evict_least_recently_used() {
+    let assets = AssetCache::new(runtime::Handle::current());
+    assets.set_memory_budget::<Arc<TestAsset>>(Some(250));
+
+    TestAssetKey::new("a", 100).get(&assets).await;
+    TestAssetKey::new("b", 100).get(&assets).await;
+    // Using `a` again makes `b` the least recently used
+    TestAssetKey::new("a", 100).get(&assets).await;
+    TestAssetKey::new("c", 100).get(&assets).await;
+
+    assert_eq!(TestAssetKey::new("a", 100).is_loaded(&assets).as_deref(), Some(&TestAsset { name: "a".into() }));
+    assert_eq!(TestAssetKey::new("b", 100).is_loaded(&assets), None);
+    assert_eq!(TestAssetKey::new("c", 100).is_loaded(&assets).as_deref(), Some(&TestAsset { name: "c".into() }));
+    assert_eq!(assets.memory_stats().types[0].evictions, 1);
+
+    // Lowering the budget releases the assets right away
+    assets.set_memory_budget::<Arc<TestAsset>>(Some(100));
+    assert_eq!(TestAssetKey::new("a", 100).is_loaded(&assets), None);
+    assert_eq!(TestAssetKey::new("c", 100).is_loaded(&assets).as_deref(), Some(&TestAsset { name: "c".into() }));
+}
+
+#[tokio::test]
+async fn keep_used_assets() {
+    let assets = AssetCache::new(runtime::Handle::current());
+    assets.set_memory_budget::<Arc<TestAsset>>(Some(100));
+
+    let a = TestAssetKey::new("a", 100).get(&assets).await;
+    TestAssetKey::new("b", 100).get(&assets).await;
+
+    // `a` was released by the cache, but is still alive
+    assert_eq!(Arc::strong_count(&a), 1);
+    assert_eq!(TestAssetKey::new("a", 100).is_loaded(&assets), Some(a));
+}
+
+#[test]
+fn asset_size() {
+    let bytes = Arc::new(vec![0u8; 10]);
+    assert_eq!(bytes.size_bytes(), Some(10));
+    assert_eq!(Ok::<_, ()>(bytes.clone()).size_bytes(), Some(10));
+    assert_eq!(Err::<Arc<Vec<u8>>, _>(()).size_bytes(), None);
+    assert_eq!(Some(Arc::<[u8]>::from(&bytes[..4])).size_bytes(), Some(4));
+}
@@ -4,7 +4,7 @@ use ambient_core::{asset_cache, transform::translation};
 use ambient_element::{Element, ElementComponent, ElementComponentExt, Hooks};
 use ambient_renderer::color;
 use ambient_std::{
-    asset_cache::{AssetKey, AssetLifetime, AssetTimeline, AssetsTimeline},
+    asset_cache::{AssetCacheStats, AssetKey, AssetLifetime, AssetTimeline, AssetTypeStats, AssetsTimeline},
     color::Color,
     pretty_duration, to_byte_unit,
 };
@@ -357,6 +357,62 @@ impl ElementComponent for AssetLifetimeViz {
     }
 }
 
+/// The memory used by each asset type of an asset cache, with their budgets and how often the cache had them loaded
+#[derive(Debug, Clone)]
+pub struct AssetMemoryStats {
+    pub stats: AssetCacheStats,
+}
+impl ElementComponent for AssetMemoryStats {
+    fn render(self: Box<Self>, _hooks: &mut Hooks) -> Element {
+        let Self { stats } = *self;
+        let row = |name: Element, cells: Vec<Element>| {
+            Dock(
+                [name.set(width(), 200.).set(fit_horizontal(), Fit::None).set(docking(), Docking::Left)]
+                    .into_iter()
+                    .chain(
+                        cells
+                            .into_iter()
+                            .map(|cell| cell.set(width(), 100.).set(docking(), Docking::Left).set(margin(), Borders::left(STREET))),
+                    )
+                    .collect_vec(),
+            )
+            .el()
+            .set(height(), 20.)
+            .set(fit_horizontal(), Fit::Parent)
+        };
+        let header = row(
+            Text::el(format!("Assets ({})", stats.total_count())),
+            vec![Text::el(to_byte_unit(stats.total_bytes())), Text::el("Budget"), Text::el("Hit rate"), Text::el("Evictions")],
+        );
+        let types = stats.types.into_iter().map(|ty| {
+            let AssetTypeStats { type_name, count, bytes, budget, hits, misses, evictions } = ty.clone();
+            let name = if type_name.len() > 30 { &type_name[type_name.len() - 30..] } else { &type_name };
+            let over_budget = budget.map_or(false, |budget| bytes > budget);
+            row(
+                Tooltip { inner: Text::el(format!("{name} ({count})")), tooltip: Text::el(type_name.clone()) }.el(),
+                vec![
+                    Text::el(to_byte_unit(bytes)).set(color(), if over_budget { vec4(1., 0.5, 0.5, 1.) } else { Vec4::ONE }),
+                    Text::el(budget.map(to_byte_unit).unwrap_or_else(|| "-".to_string())),
+                    Text::el(format!("{:.0}% of {}", ty.hit_rate() * 100., hits + misses)),
+                    Text::el(evictions.to_string()),
+                ],
+            )
+        });
+        FlowColumn::el([header].into_iter().chain(types).collect_vec()).set(fit_horizontal(), Fit::Parent)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalAssetMemoryStats;
+impl ElementComponent for LocalAssetMemoryStats {
+    fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
+        let (stats, set_stats) = hooks.use_state(AssetCacheStats::default());
+        let assets = hooks.world.resource(asset_cache()).clone();
+        use_interval(hooks, 1., move || set_stats(assets.memory_stats()));
+        AssetMemoryStats { stats }.el()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LocalAssetTimelineVisualizer;
 impl ElementComponent for LocalAssetTimelineVisualizer {
@@ -367,6 +423,6 @@ impl ElementComponent for LocalAssetTimelineVisualizer {
             let timeline = assets.timeline.lock().clone();
             set_timeline(timeline);
         });
-        AssetTimelineVisualizer { timeline }.el()
+        FlowColumn::el([LocalAssetMemoryStats.el(), AssetTimelineVisualizer { timeline }.el()]).set(fit_horizontal(), Fit::Parent)
     }
 }
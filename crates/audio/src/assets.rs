@@ -43,6 +43,13 @@ impl AsyncAssetKey<Result<Arc<Track>, Arc<Error>>> for AudioFromUrl {
 
         Ok(Arc::new(Track::from_format(bytes, format)?))
     }
+
+    fn cpu_size(&self, asset: &Result<Arc<Track>, Arc<Error>>) -> Option<u64> {
+        asset
+            .as_ref()
+            .ok()
+            .map(|track| track.size_in_bytes() as u64)
+    }
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -67,6 +74,13 @@ impl AsyncAssetKey<Result<Arc<VorbisTrack>, Arc<Error>>> for VorbisFromUrl {
 
         Ok(Arc::new(VorbisTrack::new(bytes)?))
     }
+
+    fn cpu_size(&self, asset: &Result<Arc<VorbisTrack>, Arc<Error>>) -> Option<u64> {
+        asset
+            .as_ref()
+            .ok()
+            .map(|track| track.size_in_bytes() as u64)
+    }
 }
 
 impl GetAssetType for VorbisTrack {
@@ -37,6 +37,13 @@ impl Track {
         }
     }
 
+    pub fn size_in_bytes(&self) -> usize {
+        match self {
+            Track::Vorbis(v) => v.size_in_bytes(),
+            Track::Wav(v) => v.size_in_bytes(),
+        }
+    }
+
     pub fn decode(&self) -> TrackDecodeStream {
         match self {
             Track::Vorbis(v) => TrackDecodeStream::Vorbis(Box::new(v.decode())),
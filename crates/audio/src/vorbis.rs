@@ -91,6 +91,11 @@ impl VorbisTrack {
         })
     }
 
+    /// The size of the encoded audio, which is kept in memory
+    pub fn size_in_bytes(&self) -> usize {
+        self.bytes.len()
+    }
+
     pub fn decode(&self) -> VorbisDecodeStream {
         let streamer = OggStreamReader::new(Cursor::new(self.bytes.clone())).unwrap();
         let _channels: ChannelCount = streamer.ident_hdr.audio_channels as _;
@@ -56,6 +56,11 @@ impl WavTrack {
         Ok(Self { bytes, decoded_len })
     }
 
+    /// The size of the encoded audio, which is kept in memory
+    pub fn size_in_bytes(&self) -> usize {
+        self.bytes.len()
+    }
+
     pub fn decode(&self) -> WavDecodeStream {
         let streamer = WavReader::new(Cursor::new(self.bytes.clone())).unwrap();
 
@@ -6,7 +6,10 @@ use std::{
 use ambient_asset_cache::{AssetCache, SyncAssetKeyExt};
 use ambient_physics::physx::{Physics, PhysicsKey};
 use ambient_project::Manifest as ProjectManifest;
-use ambient_std::asset_url::AbsAssetUrl;
+use ambient_std::{
+    asset_url::AbsAssetUrl,
+    content_pack::{content_pack_path, ContentPackStats, ContentPackWriter},
+};
 use futures::FutureExt;
 use itertools::Itertools;
 use pipelines::{FileCollection, ProcessCtx, ProcessCtxKey};
@@ -38,6 +41,26 @@ pub async fn build(physics: Physics, _assets: &AssetCache, path: PathBuf, manife
     build_scripts(&path, manifest, &build_path).await.unwrap();
 }
 
+/// Bundles the built assets and the WASM module of the project at `path` into a content pack, which is written to
+/// [content_pack_path]. Build the project first
+pub fn pack(path: &Path, manifest: &ProjectManifest) -> anyhow::Result<(PathBuf, ContentPackStats)> {
+    let build_path = path.join("build");
+    let mut writer = ContentPackWriter::new();
+    let assets_path = build_path.join("assets");
+    if assets_path.exists() {
+        writer.add_dir(&build_path, &assets_path)?;
+    }
+    let wasm_name = format!("{}.wasm", manifest.project.id);
+    let wasm_path = build_path.join(&wasm_name);
+    if wasm_path.exists() {
+        writer.add(wasm_name, &std::fs::read(wasm_path)?)?;
+    }
+
+    let pack_path = content_pack_path(path);
+    let stats = writer.write(&pack_path)?;
+    Ok((pack_path, stats))
+}
+
 async fn build_assets(physics: Physics, assets_path: &Path, build_path: &Path) {
     let files = WalkDir::new(assets_path)
         .into_iter()
@@ -41,9 +41,13 @@ impl PipelineCtx {
         }
     }
 
-    pub async fn write_model_crate(&self, model_crate: &ModelCrate, path: &RelativePath) -> TypedAssetUrl<ModelCrateAssetType> {
-        join_all(model_crate.to_items().iter().map(|item| self.write_file(path.join(&item.path), (*item.data).clone()))).await;
-        self.out_root().push(path).unwrap().as_directory().into()
+    pub async fn write_model_crate(
+        &self,
+        model_crate: &ModelCrate,
+        path: &RelativePath,
+    ) -> anyhow::Result<TypedAssetUrl<ModelCrateAssetType>> {
+        join_all(model_crate.to_items()?.iter().map(|item| self.write_file(path.join(&item.path), (*item.data).clone()))).await;
+        Ok(self.out_root().push(path)?.as_directory().into())
     }
     pub async fn write_file(&self, path: impl AsRef<str>, content: Vec<u8>) -> AbsAssetUrl {
         (self.process_ctx.write_file)(self.root_path.join(path.as_ref()).to_string(), content).await
@@ -66,16 +70,12 @@ impl PipelineCtx {
         (self.process_ctx.on_error)(err).await;
         Vec::new()
     }
-    pub async fn process_files<F: Future<Output = anyhow::Result<Vec<OutAsset>>> + Send>(
-        &self,
-        filter: impl Fn(&AbsAssetUrl) -> bool,
-        process_file: impl Fn(PipelineCtx, AbsAssetUrl) -> F + Sync + Send + 'static,
-    ) -> Vec<OutAsset> {
+    /// The files of the pipeline which match its `sources` and `filter`
+    pub fn source_files(&self, filter: impl Fn(&AbsAssetUrl) -> bool) -> Vec<AbsAssetUrl> {
         let sources_filter =
             self.pipeline.sources.iter().map(|p| glob::Pattern::new(p)).collect::<Result<Vec<_>, glob::PatternError>>().unwrap();
         let opt_filter = self.process_ctx.input_file_filter.as_ref().and_then(|x| glob::Pattern::new(x).ok());
-        let files = self
-            .files
+        self.files
             .0
             .iter()
             .filter(move |file| {
@@ -97,7 +97,14 @@ impl PipelineCtx {
             })
             .filter(|f| filter(f))
             .cloned()
-            .collect_vec();
+            .collect_vec()
+    }
+    pub async fn process_files<F: Future<Output = anyhow::Result<Vec<OutAsset>>> + Send>(
+        &self,
+        filter: impl Fn(&AbsAssetUrl) -> bool,
+        process_file: impl Fn(PipelineCtx, AbsAssetUrl) -> F + Sync + Send + 'static,
+    ) -> Vec<OutAsset> {
+        let files = self.source_files(filter);
         let n_files = files.len();
         let process_file = Arc::new(process_file);
         let semaphore = PipelineFileSemaphore.get(&self.process_ctx.assets);
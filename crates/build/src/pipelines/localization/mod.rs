@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+
+use ambient_localization::{LocalizationIndex, StringTable, LOCALIZATION_DIR, LOCALIZATION_INDEX};
+use ambient_std::asset_url::AssetType;
+use anyhow::Context;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use tracing::{info_span, Instrument};
+
+use super::{
+    context::PipelineCtx,
+    out_asset::{asset_id_from_url, OutAsset, OutAssetContent, OutAssetPreview},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalizationPipeline {
+    /// The locale which has all the strings, e.g. `en`.
+    /// The other locales fall back to it at runtime, and the build reports the strings they are missing.
+    #[serde(default = "default_locale")]
+    pub default_locale: String,
+}
+fn default_locale() -> String {
+    "en".to_string()
+}
+
+pub async fn pipeline(ctx: &PipelineCtx, config: LocalizationPipeline) -> Vec<OutAsset> {
+    ctx.process_single(move |ctx| async move {
+        let files = ctx.source_files(|file| file.extension_is("json") && !file.path().as_str().ends_with("pipeline.json"));
+        let mut tables = BTreeMap::new();
+        for file in files {
+            let locale = file.path().file_stem().with_context(|| format!("Invalid string table file name {file}"))?.to_string();
+            let table: StringTable =
+                file.download_json(ctx.assets()).await.with_context(|| format!("Failed to parse the string table {file}"))?;
+            table.validate().map_err(anyhow::Error::msg).with_context(|| format!("In string table {file}"))?;
+            if let Some((other, _)) = tables.insert(locale.clone(), (file.clone(), table)) {
+                anyhow::bail!("Both {other} and {file} are string tables for the locale {locale}");
+            }
+        }
+        let default_locale = &config.default_locale;
+        let (_, default) = tables
+            .get(default_locale)
+            .with_context(|| format!("There is no string table for the default locale {default_locale} ({default_locale}.json)"))?;
+        for (locale, (_, table)) in &tables {
+            if locale != default_locale {
+                for difference in differences(locale, table, default_locale, default) {
+                    log::warn!("{difference}");
+                }
+            }
+        }
+
+        let mut assets = Vec::new();
+        for (locale, (file, table)) in &tables {
+            let url = (ctx.process_ctx.write_file)(format!("{LOCALIZATION_DIR}/{locale}.json"), serde_json::to_vec(table).unwrap()).await;
+            assets.push(OutAsset {
+                id: asset_id_from_url(file),
+                type_: AssetType::StringTable,
+                hidden: false,
+                name: locale.clone(),
+                tags: Vec::new(),
+                categories: Default::default(),
+                preview: OutAssetPreview::None,
+                content: OutAssetContent::Content(url),
+                source: Some(file.clone()),
+            });
+        }
+        let index = LocalizationIndex { default_locale: default_locale.clone(), locales: tables.keys().cloned().collect() };
+        (ctx.process_ctx.write_file)(format!("{LOCALIZATION_DIR}/{LOCALIZATION_INDEX}"), serde_json::to_vec(&index).unwrap()).await;
+        Ok(assets)
+    })
+    .instrument(info_span!("localization_pipeline"))
+    .await
+}
+
+/// The strings missing from a translation, or which don't match the default locale
+fn differences(locale: &str, table: &StringTable, default_locale: &str, default: &StringTable) -> Vec<String> {
+    let mut differences = Vec::new();
+    let missing = table.missing_keys(default);
+    if !missing.is_empty() {
+        differences.push(format!(
+            "Locale {locale} is missing {} strings of {default_locale}: {}",
+            missing.len(),
+            missing.iter().join(", ")
+        ));
+    }
+    let unknown = default.missing_keys(table);
+    if !unknown.is_empty() {
+        differences.push(format!(
+            "Locale {locale} has {} strings which aren't in {default_locale}: {}",
+            unknown.len(),
+            unknown.iter().join(", ")
+        ));
+    }
+    for (key, message) in &table.0 {
+        let Some(reference) = default.get(key) else { continue };
+        let (arguments, expected) = (message.arguments().unwrap_or_default(), reference.arguments().unwrap_or_default());
+        if let Some(argument) = arguments.difference(&expected).next() {
+            differences.push(format!(
+                "The string {key:?} of locale {locale} uses the argument {{{argument}}}, which {default_locale} doesn't have"
+            ));
+        }
+    }
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_differences() {
+        let en: StringTable = serde_json::from_str(
+            r#"{ "greeting": "Hello, {name}!", "farewell": "Bye", "items": { "one": "{count} item", "other": "{count} items" } }"#,
+        )
+        .unwrap();
+        let fr: StringTable = serde_json::from_str(
+            r#"{ "greeting": "Bonjour, {nom} !", "items": { "one": "{count} objet", "other": "{count} objets" }, "extra": "En plus" }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            differences("fr", &fr, "en", &en),
+            vec![
+                "Locale fr is missing 1 strings of en: farewell".to_string(),
+                "Locale fr has 1 strings which aren't in en: extra".to_string(),
+                "The string \"greeting\" of locale fr uses the argument {nom}, which en doesn't have".to_string(),
+            ]
+        );
+        assert!(differences("en-GB", &en, "en", &en).is_empty());
+    }
+}
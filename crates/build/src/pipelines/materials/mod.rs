@@ -91,7 +91,13 @@ pub async fn pipeline(ctx: &PipelineCtx, config: MaterialsPipeline) -> Vec<OutAs
                         .with(collider(), ambient_physics::collider::ColliderDef::Box { size: Vec3::ONE, center: Vec3::ZERO })
                         .with(collider_type(), ambient_physics::collider::ColliderType::Picking),
                 );
-                let model_url = ctx.write_model_crate(&model_crate, &model_path).await;
+                let model_url = match ctx.write_model_crate(&model_crate, &model_path).await {
+                    Ok(url) => url,
+                    Err(err) => {
+                        (ctx.process_ctx.on_error)(err).await;
+                        continue;
+                    }
+                };
                 res.push(OutAsset {
                     id: asset_id_from_url(&out_model_url),
                     type_: AssetType::Prefab,
@@ -35,7 +35,7 @@ pub async fn pipeline(ctx: &PipelineCtx, _config: MaterialsPipeline) -> Vec<OutA
                 quixel_json["tags"].as_array().unwrap().iter().map(|x| x.as_str().unwrap().to_string().to_case(Case::Title)).collect_vec();
             let pack_name = quixel_json["semanticTags"]["name"].as_str().unwrap().to_string();
 
-            let model_crate_url = ctx.write_model_crate(&asset_crate, &ctx.in_root().relative_path(file.path())).await;
+            let model_crate_url = ctx.write_model_crate(&asset_crate, &ctx.in_root().relative_path(file.path())).await?;
 
             res.push(OutAsset {
                 id: asset_id_from_url(&file),
@@ -9,10 +9,11 @@ use image::ImageFormat;
 use out_asset::{OutAsset, OutAssetContent, OutAssetPreview};
 use serde::{Deserialize, Serialize};
 
-use self::{materials::MaterialsPipeline, models::ModelsPipeline};
+use self::{localization::LocalizationPipeline, materials::MaterialsPipeline, models::ModelsPipeline};
 
 pub mod audio;
 pub mod context;
+pub mod localization;
 pub mod materials;
 pub mod models;
 pub mod out_asset;
@@ -29,6 +30,9 @@ pub enum PipelineConfig {
     /// The audio asset pipeline.
     /// Will import supported audio file formats and produce Ogg Vorbis files to be used by the runtime.
     Audio,
+    /// The localization asset pipeline.
+    /// Will check the string tables of each locale (e.g. `en.json`, `fr.json`) and produce them for the runtime, which uses them for localized text.
+    Localization(LocalizationPipeline),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +58,7 @@ impl Pipeline {
             PipelineConfig::Models(config) => models::pipeline(&ctx, config.clone()).await,
             PipelineConfig::Materials(config) => materials::pipeline(&ctx, config.clone()).await,
             PipelineConfig::Audio => audio::pipeline(&ctx).await,
+            PipelineConfig::Localization(config) => localization::pipeline(&ctx, config.clone()).await,
         };
         for asset in &mut assets {
             asset.tags.extend(self.tags.clone());
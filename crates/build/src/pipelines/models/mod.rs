@@ -2,9 +2,12 @@ use std::{path::PathBuf, sync::Arc};
 
 use ambient_core::hierarchy::children;
 use ambient_ecs::Entity;
-use ambient_model_import::{model_crate::ModelCrate, MaterialFilter, ModelTextureSize, ModelTransform, TextureResolver};
+use ambient_model_import::{
+    gltf::GltfImportOptions, model_crate::ModelCrate, LodGenerationOptions, MaterialFilter, ModelTextureSize, ModelTransform,
+    TextureResolver,
+};
 use ambient_physics::collider::{collider_type, ColliderType};
-use ambient_std::asset_url::AssetType;
+use ambient_std::asset_url::{AssetType, ModelCrateAssetType, PrefabAssetType, TypedAssetUrl};
 use futures::FutureExt;
 use relative_path::RelativePath;
 use serde::{Deserialize, Serialize};
@@ -59,6 +62,9 @@ pub struct ModelsPipeline {
     /// This will support more file formats, but is less well-integrated. Off by default.
     #[serde(default)]
     force_assimp: bool,
+    /// Controls whether node extras, lights and cameras are imported from glTF files.
+    #[serde(default)]
+    gltf: GltfImportOptions,
     #[serde(default)]
     /// The physics collider to use for this mesh.
     collider: Collider,
@@ -67,6 +73,12 @@ pub struct ModelsPipeline {
     collider_type: ColliderType,
     /// Whether or not this mesh should have its texture sizes capped.
     cap_texture_sizes: Option<ModelTextureSize>,
+    /// Reorder the triangles of the meshes to make better use of the gpu's vertex cache and to reduce overdraw. Off by default.
+    #[serde(default)]
+    optimize_meshes: bool,
+    /// If specified, lods will be generated for the meshes by simplifying them, and used based on the size of the model on screen.
+    #[serde(default)]
+    generate_lods: Option<LodGenerationOptions>,
     /// Treats all assets in the pipeline as variations, and outputs a single asset which is a collection of all assets.
     /// Most useful for grass and other entities whose individual identity is not important.
     #[serde(default)]
@@ -74,6 +86,10 @@ pub struct ModelsPipeline {
     /// Output prefabs that can be spawned. On by default.
     #[serde(default = "true_value")]
     output_prefabs: bool,
+    /// Output the prefabs in a compact binary format instead of json, as `prefabs/main.world`.
+    /// This is considerably faster to load for large prefabs, but is not human-readable. Off by default.
+    #[serde(default)]
+    binary_prefabs: bool,
     /// Output the animations that belonged to this model.
     #[serde(default = "true_value")]
     output_animations: bool,
@@ -112,6 +128,12 @@ impl ModelsPipeline {
         if let Some(max_size) = self.cap_texture_sizes {
             model_crate.cap_texture_sizes(max_size.size());
         }
+        if self.optimize_meshes {
+            model_crate.optimize_meshes();
+        }
+        if let Some(options) = &self.generate_lods {
+            model_crate.generate_lods(options);
+        }
         model_crate.finalize_model();
         match self.collider {
             Collider::None => {}
@@ -124,8 +146,19 @@ impl ModelsPipeline {
         let world = model_crate.prefab_world_mut();
         let obj = world.resource(children())[0];
         world.add_components(obj, self.prefab_components.clone()).unwrap();
+        if self.binary_prefabs {
+            model_crate.use_binary_prefabs();
+        }
         Ok(())
     }
+    /// The url of the main prefab of a model crate written by this pipeline
+    pub fn prefab_url(&self, model_crate_url: &TypedAssetUrl<ModelCrateAssetType>) -> TypedAssetUrl<PrefabAssetType> {
+        if self.binary_prefabs {
+            model_crate_url.binary_prefab()
+        } else {
+            model_crate_url.prefab()
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,7 +59,7 @@ pub async fn pipeline(ctx: &PipelineCtx, config: ModelsPipeline) -> Vec<OutAsset
                     let out_model_path = ctx.in_root().relative_path(file.path()).join(i.to_string());
                     config.apply(&ctx, &mut asset_crate, &out_model_path).await?;
 
-                    let model_crate_url = ctx.write_model_crate(&asset_crate, &out_model_path).await;
+                    let model_crate_url = ctx.write_model_crate(&asset_crate, &out_model_path).await?;
 
                     res.push(OutAsset {
                         id: id.clone(),
@@ -70,7 +70,7 @@ pub async fn pipeline(ctx: &PipelineCtx, config: ModelsPipeline) -> Vec<OutAsset
                         categories: Default::default(),
 
                         preview: OutAssetPreview::FromModel { url: model_crate_url.model().abs().unwrap() },
-                        content: OutAssetContent::Content(model_crate_url.prefab().abs().unwrap()),
+                        content: OutAssetContent::Content(config.prefab_url(&model_crate_url).abs().unwrap()),
                         source: Some({
                             let mut f = file.clone();
                             f.0.set_fragment(Some(&i.to_string()));
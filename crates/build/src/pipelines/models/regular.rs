@@ -22,7 +22,7 @@ pub async fn pipeline(ctx: &PipelineCtx, config: ModelsPipeline) -> Vec<OutAsset
 
                 let mut model_crate = ModelCrate::new();
                 model_crate
-                    .import(&ctx.process_ctx.assets, &file, true, config.force_assimp, create_texture_resolver(&ctx))
+                    .import(&ctx.process_ctx.assets, &file, true, config.force_assimp, create_texture_resolver(&ctx), &config.gltf)
                     .await
                     .with_context(|| format!("Failed to import model {file}"))?;
                 model_crate.model_mut().set_name(file.path().file_name().unwrap());
@@ -31,7 +31,7 @@ pub async fn pipeline(ctx: &PipelineCtx, config: ModelsPipeline) -> Vec<OutAsset
                 let out_model_path = ctx.in_root().relative_path(file.path());
                 config.apply(&ctx, &mut model_crate, &out_model_path).await?;
 
-                let model_crate_url = ctx.write_model_crate(&model_crate, &out_model_path).await;
+                let model_crate_url = ctx.write_model_crate(&model_crate, &out_model_path).await?;
 
                 if config.output_prefabs {
                     res.push(OutAsset {
@@ -43,7 +43,7 @@ pub async fn pipeline(ctx: &PipelineCtx, config: ModelsPipeline) -> Vec<OutAsset
                         tags: Default::default(),
                         categories: Default::default(),
                         preview: OutAssetPreview::FromModel { url: model_crate_url.model().abs().unwrap() },
-                        content: OutAssetContent::Content(model_crate_url.prefab().abs().unwrap()),
+                        content: OutAssetContent::Content(config.prefab_url(&model_crate_url).abs().unwrap()),
                         source: Some(file.clone()),
                     });
                 }
@@ -106,7 +106,7 @@ pub async fn pipeline(ctx: &PipelineCtx, use_prefabs: bool, config: ModelsPipeli
                     .await?;
                     config.apply(&ctx, &mut asset_crate, &out_model_path).await?;
 
-                    let model_crate_url = ctx.write_model_crate(&asset_crate, &out_model_path).await;
+                    let model_crate_url = ctx.write_model_crate(&asset_crate, &out_model_path).await?;
                     res.push(OutAsset {
                         id: asset_id_from_url(&file),
                         type_: AssetType::Prefab,
@@ -115,7 +115,7 @@ pub async fn pipeline(ctx: &PipelineCtx, use_prefabs: bool, config: ModelsPipeli
                         tags: Default::default(),
                         categories: Default::default(),
                         preview: OutAssetPreview::FromModel { url: model_crate_url.model().abs().unwrap() },
-                        content: OutAssetContent::Content(model_crate_url.prefab().abs().unwrap()),
+                        content: OutAssetContent::Content(config.prefab_url(&model_crate_url).abs().unwrap()),
                         source: Some(file.clone()),
                     });
                     Ok(res)
@@ -157,7 +157,7 @@ pub async fn pipeline(ctx: &PipelineCtx, use_prefabs: bool, config: ModelsPipeli
 
                     config.apply(&ctx, &mut asset_crate, &out_path).await?;
 
-                    let model_crate_url = ctx.write_model_crate(&asset_crate, &out_path).await;
+                    let model_crate_url = ctx.write_model_crate(&asset_crate, &out_path).await?;
                     res.push(OutAsset {
                         id: asset_id_from_url(&file),
                         type_: AssetType::Prefab,
@@ -166,7 +166,7 @@ pub async fn pipeline(ctx: &PipelineCtx, use_prefabs: bool, config: ModelsPipeli
                         tags: Default::default(),
                         categories: Default::default(),
                         preview: OutAssetPreview::FromModel { url: model_crate_url.model().abs().unwrap() },
-                        content: OutAssetContent::Content(model_crate_url.prefab().abs().unwrap()),
+                        content: OutAssetContent::Content(config.prefab_url(&model_crate_url).abs().unwrap()),
                         source: Some(file.clone()),
                     });
                     Ok(res)
@@ -442,7 +442,7 @@ impl MeshModels {
     async fn get(&mut self, ctx: &PipelineCtx, mesh_url: &AbsAssetUrl) -> anyhow::Result<Arc<ModelCrate>> {
         if !self.models.contains_key(mesh_url) {
             let mut tmp_model = ModelCrate::new();
-            tmp_model.import(ctx.assets(), mesh_url, false, self.force_assimp, create_texture_resolver(ctx)).await?;
+            tmp_model.import(ctx.assets(), mesh_url, false, self.force_assimp, create_texture_resolver(ctx), &Default::default()).await?;
             tmp_model.update_transforms();
             // dump_world_hierarchy_to_tmp_file(tmp_model.model_world());
             self.models.insert(mesh_url.clone(), Arc::new(tmp_model));
@@ -18,7 +18,9 @@ pub mod bounding;
 pub mod camera;
 pub mod gpu_ecs;
 pub mod hierarchy;
+pub mod network;
 pub mod player;
+pub mod relationship;
 pub mod transform;
 pub mod window;
 
@@ -104,9 +106,11 @@ components!("app", {
 
 pub fn init_all_components() {
     init_components();
+    network::init_components();
     player::init_components();
     window::init_components();
     hierarchy::init_components();
+    relationship::init_components();
     async_ecs::init_components();
     gpu_ecs::init_components();
     camera::init_components();
@@ -0,0 +1,11 @@
+use ambient_ecs::{components, Debuggable, Description, Name, Networked};
+
+components!("network", {
+    /// Works like `world.resource_entity` for server worlds, except it's synchronized to clients. State is not persisted to disk.
+    @[
+        Debuggable, Networked,
+        Name["Synced resources"],
+        Description["If attached, this entity contains global resources that are synchronized to clients, but not persisted."]
+    ]
+    synced_resources: (),
+});
@@ -0,0 +1,104 @@
+//! Relationships are [EntityId] components which point at another entity (the target), and which are cleaned up
+//! automatically when the target is despawned. A component becomes a relationship by giving it the [Relationship]
+//! attribute, which also decides what happens on cleanup.
+
+use std::collections::HashSet;
+
+use ambient_ecs::{
+    components, query, with_component_registry, AttributeConstructor, AttributeStore, Component, ComponentAttribute, Debuggable,
+    Description, DynSystem, EntityId, Name, Networked, Query, Store, World,
+};
+use itertools::Itertools;
+
+/// What happens to the entities related to a target when the target is despawned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnTargetDespawn {
+    /// Remove the relationship component from the related entities
+    Remove,
+    /// Despawn the related entities as well, which may in turn clean up the entities related to them
+    Despawn,
+}
+
+/// Marks an [EntityId] component as a relationship
+#[derive(Debug, Clone, Copy)]
+pub struct Relationship(pub OnTargetDespawn);
+impl ComponentAttribute for Relationship {}
+impl AttributeConstructor<EntityId, OnTargetDespawn> for Relationship {
+    fn construct(store: &mut AttributeStore, value: OnTargetDespawn) {
+        store.set(Self(value))
+    }
+}
+
+components!("ecs", {
+    @[
+        Debuggable, Networked, Store, Relationship[OnTargetDespawn::Despawn],
+        Name["Attached to"],
+        Description["The entity this entity is attached to. This entity is despawned when the entity it's attached to is despawned."]
+    ]
+    attached_to: EntityId,
+    @[
+        Debuggable, Networked, Store, Relationship[OnTargetDespawn::Remove],
+        Name["Owned by"],
+        Description["The entity that owns this entity. This component is removed when the owner is despawned."]
+    ]
+    owned_by: EntityId,
+});
+
+/// All components with the [Relationship] attribute
+pub fn relationship_components() -> Vec<(Component<EntityId>, OnTargetDespawn)> {
+    with_component_registry(|r| r.all().filter_map(|desc| Some((Component::new(desc), desc.attribute::<Relationship>()?.0))).collect_vec())
+}
+
+/// Returns the entities whose `relationship` points at `target`
+pub fn related_to(world: &World, relationship: Component<EntityId>, target: EntityId) -> Vec<EntityId> {
+    query(relationship).iter(world, None).filter(|(_, t)| **t == target).map(|(id, _)| id).collect()
+}
+
+/// Returns the entities which point at `target` with any relationship
+pub fn all_related_to(world: &World, target: EntityId) -> Vec<(EntityId, Component<EntityId>)> {
+    relationship_components()
+        .into_iter()
+        .flat_map(|(relationship, _)| related_to(world, relationship, target).into_iter().map(move |id| (id, relationship)))
+        .collect()
+}
+
+/// Returns true if `id` points at `target` with `relationship`
+pub fn is_related(world: &World, id: EntityId, relationship: Component<EntityId>, target: EntityId) -> bool {
+    world.get(id, relationship).map(|t| t == target).unwrap_or(false)
+}
+
+/// Applies the cleanup policies of all relationships pointing at `despawned`, which must no longer exist
+pub fn cleanup_relationships(world: &mut World, despawned: HashSet<EntityId>) {
+    let relationships = relationship_components();
+    let mut targets = despawned;
+    while !targets.is_empty() {
+        let mut next = HashSet::new();
+        for &(relationship, on_target_despawn) in &relationships {
+            let related =
+                query(relationship).iter(world, None).filter(|(_, target)| targets.contains(target)).map(|(id, _)| id).collect_vec();
+            for id in related {
+                match on_target_despawn {
+                    OnTargetDespawn::Remove => {
+                        world.remove_component(id, relationship).ok();
+                    }
+                    OnTargetDespawn::Despawn => {
+                        if world.despawn(id).is_some() {
+                            next.insert(id);
+                        }
+                    }
+                }
+            }
+        }
+        targets = next;
+    }
+}
+
+/// Cleans up the relationships of entities despawned since the last frame
+pub fn relationships_system() -> DynSystem {
+    Query::all().despawned().to_system(|q, world, qs, _| {
+        let despawned = q.iter(world, Some(qs)).map(|acc| acc.id()).collect::<HashSet<_>>();
+        if !despawned.is_empty() {
+            cleanup_relationships(world, despawned);
+        }
+    })
+}
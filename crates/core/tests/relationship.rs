@@ -0,0 +1,37 @@
+use ambient_core::relationship::{attached_to, owned_by, related_to, relationships_system};
+use ambient_ecs::{Entity, FrameEvent, System, World};
+
+#[test]
+fn relationships_are_cleaned_up_on_target_despawn() {
+    ambient_core::init_all_components();
+    let mut world = World::new("relationship");
+    let mut system = relationships_system();
+    system.run(&mut world, &FrameEvent);
+
+    let owner = Entity::new().spawn(&mut world);
+    let item = Entity::new().with(owned_by(), owner).spawn(&mut world);
+    let attachment = Entity::new().with(attached_to(), item).spawn(&mut world);
+    let nested = Entity::new().with(attached_to(), attachment).spawn(&mut world);
+    let on_owner = Entity::new().with(attached_to(), owner).spawn(&mut world);
+    assert_eq!(related_to(&world, attached_to(), item), vec![attachment]);
+
+    world.despawn(item);
+    system.run(&mut world, &FrameEvent);
+    // Attachments are despawned recursively
+    assert!(!world.exists(attachment));
+    assert!(!world.exists(nested));
+    assert!(world.exists(on_owner));
+
+    world.despawn(owner);
+    system.run(&mut world, &FrameEvent);
+    // This one is attached to the owner, so it goes with it
+    assert!(!world.exists(on_owner));
+
+    let owner = Entity::new().spawn(&mut world);
+    let item = Entity::new().with(owned_by(), owner).spawn(&mut world);
+    world.despawn(owner);
+    system.run(&mut world, &FrameEvent);
+    // Ownership is removed, but the item stays
+    assert!(world.exists(item));
+    assert!(!world.has_component(item, owned_by()));
+}
@@ -1,5 +1,6 @@
 use std::{num::NonZeroU32, sync::Arc};
 
+use ambient_asset_timeline::LocalAssetMemoryStats;
 use ambient_core::{
     asset_cache,
     bounding::world_bounding_sphere,
@@ -13,7 +14,12 @@ use ambient_ecs::{query, World};
 use ambient_ecs_editor::ECSEditor;
 use ambient_element::{element_component, Element, ElementComponentExt, Hooks};
 use ambient_gizmos::{gizmos, GizmoPrimitive};
-use ambient_network::client::{GameClient, GameRpcArgs};
+use ambient_network::{
+    client::{GameClient, GameRpcArgs},
+    debug_draw::{debug_draw_categories, debug_draw_known_categories},
+    hooks::{use_remote_player_component, use_remote_synced_resource},
+    permissions::Role,
+};
 use ambient_renderer::{RenderTarget, Renderer};
 use ambient_rpc::RpcRegistry;
 use ambient_std::{asset_cache::SyncAssetKeyExt, cb, color::Color, download_asset::AssetsCacheDir, line_hash, Cb};
@@ -35,13 +41,14 @@ pub async fn rpc_dump_world_hierarchy(args: GameRpcArgs, _: ()) -> Option<String
 }
 
 pub fn register_rpcs(reg: &mut RpcRegistry<GameRpcArgs>) {
-    reg.register(rpc_dump_world_hierarchy);
+    reg.register_with("debugger::dump_world_hierarchy", 1, Role::Editor, rpc_dump_world_hierarchy);
 }
 
 #[element_component]
 pub fn Debugger(hooks: &mut Hooks, get_state: GetDebuggerState) -> Element {
     let (show_shadows, set_show_shadows) = hooks.use_state(false);
     let (show_ecs, set_show_ecs) = hooks.use_state(false);
+    let (show_asset_memory, set_show_asset_memory) = hooks.use_state(false);
     let (game_client, _) = hooks.consume_context::<GameClient>().unwrap();
     FlowColumn::el([
         FlowRow(vec![
@@ -165,11 +172,17 @@ pub fn Debugger(hooks: &mut Hooks, get_state: GetDebuggerState) -> Element {
             .hotkey(VirtualKeyCode::F8)
             .style(ButtonStyle::Flat)
             .el(),
+            Button::new("Show Asset Memory", move |_| set_show_asset_memory(!show_asset_memory))
+                .toggled(show_asset_memory)
+                .style(ButtonStyle::Flat)
+                .el(),
             ShaderDebug { get_state: get_state.clone() }.el(),
+            DebugDrawCategories.el(),
         ])
         .el()
         .set(space_between_items(), 5.),
         if show_shadows { ShadowMapsViz { get_state: get_state.clone() }.el() } else { Element::new() },
+        if show_asset_memory { LocalAssetMemoryStats.el() } else { Element::new() },
         if show_ecs {
             ECSEditor { get_world: cb(move |res| get_state(&mut move |_, _, world| res(world))), on_change: cb(|_, _| {}) }
                 .el()
@@ -275,3 +288,41 @@ fn ShaderDebug(hooks: &mut Hooks, get_state: GetDebuggerState) -> Element {
     }
     .el()
 }
+
+/// Toggles which categories of debug drawings the server sends to this client
+#[element_component]
+fn DebugDrawCategories(hooks: &mut Hooks) -> Element {
+    let (show, set_show) = hooks.use_state(false);
+    let (known, _) = use_remote_synced_resource(hooks, debug_draw_known_categories());
+    let (enabled, set_enabled) = use_remote_player_component(hooks, debug_draw_categories());
+
+    Dropdown {
+        content: Button::new("Debug Drawing", move |_| set_show(!show)).toggled(show).el(),
+        dropdown: FlowColumn::el(
+            known
+                .unwrap_or_default()
+                .into_iter()
+                .map(|category| {
+                    let is_enabled = enabled.contains(&category);
+                    Button::new(category.clone(), {
+                        let enabled = enabled.clone();
+                        let set_enabled = set_enabled.clone();
+                        move |_| {
+                            let mut enabled = enabled.clone();
+                            if is_enabled {
+                                enabled.retain(|c| c != &category);
+                            } else {
+                                enabled.push(category.clone());
+                            }
+                            set_enabled(enabled)
+                        }
+                    })
+                    .toggled(is_enabled)
+                    .el()
+                })
+                .collect::<Vec<_>>(),
+        ),
+        show,
+    }
+    .el()
+}
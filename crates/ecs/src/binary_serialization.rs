@@ -0,0 +1,224 @@
+//! A compact binary alternative to the json world format.
+//!
+//! The content is a table of all component paths used, followed by one block per archetype with
+//! the ids of its entities and one column per component with the bincode encoded values. Only
+//! [Serializable] components are stored. The values are stored as a self describing encoding of
+//! their json representation, so that a binary world can be turned back into a json world. This
+//! way it goes through the same migrations as json worlds, even if the layout of a component changed.
+
+use std::collections::HashMap;
+
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use serde_json::{Map, Number, Value};
+
+use crate::{
+    dont_store, migrate_world_value, world_format_version, ComponentDesc, ComponentEntry, DeserEntityDataWithWarnings,
+    DeserWorldWithWarnings, Entity, EntityId, Serializable, World, WORLD_FORMAT_VERSION_KEY,
+};
+
+/// All binary worlds start with these bytes, which is how they are told apart from json worlds
+pub const BINARY_WORLD_MAGIC: &[u8; 4] = b"AMBW";
+
+pub fn is_binary_world(content: &[u8]) -> bool {
+    content.starts_with(BINARY_WORLD_MAGIC)
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryWorld {
+    format_version: u32,
+    paths: Vec<String>,
+    archetypes: Vec<BinaryArchetype>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryArchetype {
+    entities: Vec<u128>,
+    /// Indices into the path table, one for each column
+    components: Vec<u32>,
+    columns: Vec<ByteBuf>,
+}
+
+/// A json value which bincode can decode without knowing the type it was serialized from
+#[derive(Serialize, Deserialize)]
+enum BinaryValue {
+    Null,
+    Bool(bool),
+    U64(u64),
+    I64(i64),
+    /// Floats which fit in an f32 without losing precision, which is most of them
+    F32(f32),
+    F64(f64),
+    String(String),
+    Array(Vec<BinaryValue>),
+    Object(Vec<(String, BinaryValue)>),
+}
+impl From<Value> for BinaryValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(value) => Self::Bool(value),
+            Value::Number(value) => {
+                if let Some(value) = value.as_u64() {
+                    Self::U64(value)
+                } else if let Some(value) = value.as_i64() {
+                    Self::I64(value)
+                } else {
+                    let value = value.as_f64().unwrap_or_default();
+                    if value as f32 as f64 == value {
+                        Self::F32(value as f32)
+                    } else {
+                        Self::F64(value)
+                    }
+                }
+            }
+            Value::String(value) => Self::String(value),
+            Value::Array(values) => Self::Array(values.into_iter().map(Self::from).collect()),
+            Value::Object(values) => Self::Object(values.into_iter().map(|(key, value)| (key, value.into())).collect()),
+        }
+    }
+}
+impl From<BinaryValue> for Value {
+    fn from(value: BinaryValue) -> Self {
+        match value {
+            BinaryValue::Null => Value::Null,
+            BinaryValue::Bool(value) => Value::Bool(value),
+            BinaryValue::U64(value) => Value::from(value),
+            BinaryValue::I64(value) => Value::from(value),
+            BinaryValue::F32(value) => Number::from_f64(value as f64).map(Value::Number).unwrap_or_default(),
+            BinaryValue::F64(value) => Number::from_f64(value).map(Value::Number).unwrap_or_default(),
+            BinaryValue::String(value) => Value::String(value),
+            BinaryValue::Array(values) => Value::Array(values.into_iter().map(Value::from).collect()),
+            BinaryValue::Object(values) => Value::Object(values.into_iter().map(|(key, value)| (key, value.into())).collect()),
+        }
+    }
+}
+
+fn options() -> impl Options {
+    bincode::DefaultOptions::new()
+}
+
+#[derive(Default)]
+struct BinaryWorldWriter {
+    paths: Vec<String>,
+    path_indices: HashMap<ComponentDesc, u32>,
+    archetypes: Vec<BinaryArchetype>,
+}
+impl BinaryWorldWriter {
+    fn path_index(&mut self, desc: ComponentDesc) -> u32 {
+        let paths = &mut self.paths;
+        *self.path_indices.entry(desc).or_insert_with(|| {
+            paths.push(desc.path());
+            (paths.len() - 1) as u32
+        })
+    }
+    /// `value` returns the value of a component for the nth entity
+    fn write_archetype(
+        &mut self,
+        entities: Vec<EntityId>,
+        components: impl IntoIterator<Item = ComponentDesc>,
+        value: impl Fn(ComponentDesc, usize) -> ComponentEntry,
+    ) -> anyhow::Result<()> {
+        let mut arch = BinaryArchetype { entities: entities.iter().map(|id| id.0).collect(), components: Vec::new(), columns: Vec::new() };
+        for desc in components {
+            let Some(ser) = desc.attribute::<Serializable>() else { continue };
+            let mut column = Vec::new();
+            for i in 0..entities.len() {
+                let entry = value(desc, i);
+                let value = serde_json::to_value(ser.serialize(&entry))
+                    .map_err(|err| anyhow::anyhow!("Failed to serialize component {}: {err}", desc.path()))?;
+                options().serialize_into(&mut column, &BinaryValue::from(value))?;
+            }
+            arch.components.push(self.path_index(desc));
+            arch.columns.push(ByteBuf::from(column));
+        }
+        self.archetypes.push(arch);
+        Ok(())
+    }
+    fn finish(self) -> anyhow::Result<Vec<u8>> {
+        let world = BinaryWorld { format_version: world_format_version(), paths: self.paths, archetypes: self.archetypes };
+        let mut res = BINARY_WORLD_MAGIC.to_vec();
+        options().serialize_into(&mut res, &world)?;
+        Ok(res)
+    }
+}
+
+/// Converts a binary world to the json representation of the same world, with the format version it was written with.
+/// This is what [DeserWorldWithWarnings::from_slice] migrates and deserializes binary worlds from
+pub fn binary_world_to_json(content: &[u8]) -> anyhow::Result<Value> {
+    let content = content.strip_prefix(BINARY_WORLD_MAGIC).ok_or_else(|| anyhow::anyhow!("Not a binary world"))?;
+    let world: BinaryWorld = options().deserialize(content)?;
+
+    let mut res = Map::new();
+    res.insert(WORLD_FORMAT_VERSION_KEY.to_string(), Value::from(world.format_version));
+    for arch in world.archetypes {
+        let mut entities = vec![Map::new(); arch.entities.len()];
+        for (path_index, column) in arch.components.into_iter().zip(arch.columns) {
+            let path = world.paths.get(path_index as usize).ok_or_else(|| anyhow::anyhow!("Invalid component path index: {path_index}"))?;
+            let mut deserializer = bincode::Deserializer::from_slice(&column, options());
+            for entity in &mut entities {
+                let value =
+                    BinaryValue::deserialize(&mut deserializer).map_err(|err| anyhow::anyhow!("Failed to read component {path}: {err}"))?;
+                entity.insert(path.clone(), value.into());
+            }
+        }
+        for (id, entity) in arch.entities.into_iter().zip(entities) {
+            res.insert(EntityId(id).to_string(), Value::Object(entity));
+        }
+    }
+    Ok(Value::Object(res))
+}
+
+impl World {
+    /// Serializes this world to the compact binary format. Like the json format, this only includes
+    /// [Serializable] components and skips entities with [dont_store]
+    pub fn to_binary(&self) -> anyhow::Result<Vec<u8>> {
+        let mut writer = BinaryWorldWriter::default();
+        for arch in &self.archetypes {
+            if arch.entity_count() == 0 || arch.active_components.contains(dont_store().desc()) {
+                continue;
+            }
+            writer.write_archetype(arch.entity_indices_to_ids.clone(), arch.components.iter().map(|c| c.component), |desc, i| {
+                arch.get_component_buffer_untyped(desc).unwrap().clone_value_boxed(i)
+            })?;
+        }
+        writer.finish()
+    }
+    /// Deserializes a world produced by [World::to_binary]
+    pub fn from_binary_slice(content: &[u8]) -> anyhow::Result<Self> {
+        let DeserWorldWithWarnings { world, warnings } = DeserWorldWithWarnings::from_binary_slice(content)?;
+        warnings.log_warnings();
+        Ok(world)
+    }
+}
+
+impl DeserWorldWithWarnings {
+    /// Deserializes a world produced by [World::to_binary], upgrading it with the registered migrations
+    pub fn from_binary_slice(content: &[u8]) -> anyhow::Result<Self> {
+        Self::from_value(binary_world_to_json(content)?)
+    }
+}
+
+impl Entity {
+    /// Serializes the [Serializable] components of this entity to the compact binary format
+    pub fn to_binary(&self) -> anyhow::Result<Vec<u8>> {
+        let mut writer = BinaryWorldWriter::default();
+        writer.write_archetype(vec![EntityId::null()], self.components(), |desc, _| self.get_entry(desc).unwrap().clone())?;
+        writer.finish()
+    }
+    /// Deserializes an entity produced by [Entity::to_binary]
+    pub fn from_binary_slice(content: &[u8]) -> anyhow::Result<Self> {
+        let mut world = binary_world_to_json(content)?;
+        let mut warnings = migrate_world_value(&mut world)?;
+        let entity = match world {
+            Value::Object(entities) => entities.into_iter().find(|(key, _)| key != WORLD_FORMAT_VERSION_KEY).map(|(_, entity)| entity),
+            _ => None,
+        };
+        let Some(entity) = entity else { return Ok(Entity::new()) };
+        let entity: DeserEntityDataWithWarnings = serde_json::from_value(entity)?;
+        warnings.warnings.extend(entity.warnings.warnings);
+        warnings.log_warnings();
+        Ok(entity.entity)
+    }
+}
@@ -26,6 +26,7 @@ use thiserror::Error;
 
 mod archetype;
 mod attributes;
+mod binary_serialization;
 pub mod component;
 mod component_entry;
 mod component_registry;
@@ -36,12 +37,16 @@ mod entity;
 mod events;
 mod index;
 mod location;
+mod migration;
+mod observers;
 mod primitive_component;
 mod query;
+mod schedule;
 mod serialization;
 mod stream;
 pub use archetype::*;
 pub use attributes::*;
+pub use binary_serialization::*;
 pub use component::{Component, ComponentDesc, ComponentValue, ComponentValueBase};
 pub use component_entry::*;
 pub use component_registry::*;
@@ -51,8 +56,11 @@ pub use entity::*;
 pub use events::*;
 pub use index::*;
 pub use location::*;
+pub use migration::*;
+pub use observers::*;
 pub use primitive_component::*;
 pub use query::*;
+pub use schedule::*;
 pub use serialization::*;
 pub use stream::*;
 
@@ -115,6 +123,7 @@ pub struct World {
     /// Used for reset_events. Prevents change events in queries when you use reset_events
     ignore_query_inits: bool,
     query_ticker: CloneableAtomicU64,
+    observers: Observers,
 }
 impl World {
     pub fn new(name: &'static str) -> Self {
@@ -133,6 +142,7 @@ impl World {
             shape_change_events: None,
             ignore_query_inits: false,
             query_ticker: CloneableAtomicU64::new(0),
+            observers: Observers::default(),
         };
         if resources {
             world.spawn_with_id(EntityId::resources(), Entity::new());
@@ -159,8 +169,9 @@ impl World {
         Self::from_slice(&content)
     }
 
+    /// Deserializes a json or binary world. Json worlds have any registered [WorldMigration]s run on them first
     pub fn from_slice(content: &[u8]) -> anyhow::Result<Self> {
-        let DeserWorldWithWarnings { world, warnings } = serde_json::from_slice(content)?;
+        let DeserWorldWithWarnings { world, warnings } = DeserWorldWithWarnings::from_slice(content)?;
         warnings.log_warnings();
         Ok(world)
     }
@@ -182,8 +193,10 @@ impl World {
     pub fn spawn_with_id(&mut self, entity_id: EntityId, entity_data: Entity) -> bool {
         if let std::collections::hash_map::Entry::Vacant(e) = self.locs.entry(entity_id) {
             e.insert(EntityLocation::empty());
+            let events = self.observers.entity_events(&[entity_id], ObserverTrigger::Add, &entity_data);
             let version = self.inc_version();
             self.batch_spawn_with_ids_internal(EntityMoveData::from_entity_data(entity_data, version), vec![entity_id]);
+            self.notify_observers(events);
             true
         } else {
             false
@@ -193,8 +206,10 @@ impl World {
         if let Some(events) = &mut self.shape_change_events {
             events.add_events(ids.iter().map(|id| WorldChange::Spawn(Some(*id), entity_data.clone())));
         }
+        let events = self.observers.entity_events(&ids, ObserverTrigger::Add, &entity_data);
         let version = self.inc_version();
         self.batch_spawn_with_ids_internal(EntityMoveData::from_entity_data(entity_data, version), ids.clone());
+        self.notify_observers(events);
     }
     fn batch_spawn_with_ids_internal(&mut self, entity_data: EntityMoveData, ids: Vec<EntityId>) {
         let arch_id = self.archetypes.iter().position(|x| x.active_components == entity_data.active_components);
@@ -225,7 +240,10 @@ impl World {
                 self.locs.get_mut(&last_entity_in_arch).unwrap().index = loc.index;
                 self.loc_changed.add_event(last_entity_in_arch);
             }
-            Some(arch.moveout(loc.index, entity_id, version).into())
+            let entity: Entity = arch.moveout(loc.index, entity_id, version).into();
+            let events = self.observers.entity_events(&[entity_id], ObserverTrigger::Remove, &entity);
+            self.notify_observers(events);
+            Some(entity)
         } else {
             None
         }
@@ -254,26 +272,42 @@ impl World {
         value: T,
     ) -> Result<T, ECSError> {
         let p = self.get_mut(entity_id, component)?;
-        Ok(std::mem::replace(p, value))
+        let old = std::mem::replace(p, value);
+        if self.observers.observes(component.desc(), ObserverTrigger::Change) {
+            let new = self.get_ref(entity_id, component)?.clone();
+            self.notify_observers(vec![ObserverEvent {
+                id: entity_id,
+                trigger: ObserverTrigger::Change,
+                component: component.desc(),
+                old: Some(ComponentEntry::new(component, old.clone())),
+                new: Some(ComponentEntry::new(component, new)),
+            }]);
+        }
+        Ok(old)
     }
 
     pub fn set_entry(&mut self, entity_id: EntityId, entry: ComponentEntry) -> Result<ComponentEntry, ECSError> {
-        if let Some(loc) = self.locs.get(&entity_id) {
+        if let Some(loc) = self.locs.get(&entity_id).copied() {
+            let events = self.observe_set(entity_id, once(&entry));
             let version = self.inc_version();
             let arch = self.archetypes.get_mut(loc.archetype).expect("Archetype doesn't exist");
-            arch.replace_with_entry(entity_id, loc.index, entry, version)
+            let old = arch.replace_with_entry(entity_id, loc.index, entry, version)?;
+            self.notify_observers(events);
+            Ok(old)
         } else {
             Err(ECSError::NoSuchEntity { entity_id })
         }
     }
 
     pub fn set_components(&mut self, entity_id: EntityId, data: Entity) -> Result<(), ECSError> {
-        if let Some(loc) = self.locs.get(&entity_id) {
+        if let Some(loc) = self.locs.get(&entity_id).copied() {
+            let events = self.observe_set(entity_id, data.iter());
             let version = self.inc_version();
             let arch = self.archetypes.get_mut(loc.archetype).expect("Archetype doesn't exist");
             for entry in data {
                 arch.replace_with_entry(entity_id, loc.index, entry, version)?;
             }
+            self.notify_observers(events);
             Ok(())
         } else {
             Err(ECSError::NoSuchEntity { entity_id })
@@ -420,7 +454,10 @@ impl World {
         if let Some(events) = &mut self.shape_change_events {
             events.add_event(WorldChange::AddComponents(entity_id, data.clone()));
         }
-        self.map_entity(entity_id, |ed| ed.append(data))
+        let events = self.observe_add(entity_id, &data);
+        self.map_entity(entity_id, |ed| ed.append(data))?;
+        self.notify_observers(events);
+        Ok(())
     }
     // will also replace the existing component of the same type if it exists
     pub fn add_component<T: ComponentValue>(&mut self, entity_id: EntityId, component: Component<T>, value: T) -> Result<(), ECSError> {
@@ -440,7 +477,10 @@ impl World {
         if let Some(events) = &mut self.shape_change_events {
             events.add_event(WorldChange::RemoveComponents(entity_id, components.clone()));
         }
-        self.map_entity(entity_id, |entity| entity.remove_components(components))
+        let events = self.observe_remove(entity_id, &components);
+        self.map_entity(entity_id, |entity| entity.remove_components(components))?;
+        self.notify_observers(events);
+        Ok(())
     }
     pub fn resource_entity(&self) -> EntityId {
         EntityId::resources()
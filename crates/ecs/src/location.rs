@@ -38,7 +38,8 @@ impl EntityId {
     }
     pub fn from_base64(value: &str) -> Result<Self, data_encoding::DecodeError> {
         let bytes = BASE64URL_NOPAD.decode(value.as_bytes())?;
-        Ok(Self(u128::from_le_bytes(bytes.try_into().unwrap())))
+        let bytes = bytes.try_into().map_err(|_| data_encoding::DecodeError { position: 0, kind: data_encoding::DecodeKind::Length })?;
+        Ok(Self(u128::from_le_bytes(bytes)))
     }
 }
 impl std::fmt::Display for EntityId {
@@ -113,6 +114,8 @@ fn test_entity_id_serialization() {
         let id = EntityId::new();
         assert_eq!(id, serde_json::from_str(&serde_json::to_string(&id).unwrap()).unwrap());
     }
+    assert!(EntityId::from_base64("").is_err());
+    assert!(EntityId::from_base64("AAAA").is_err());
 }
 
 /// This just pipes a u64 value through
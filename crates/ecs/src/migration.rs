@@ -0,0 +1,210 @@
+use std::fmt;
+
+use data_encoding::BASE64URL_NOPAD;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde_json::{Map, Value};
+
+use crate::{ECSDeserializationWarnings, EntityId};
+
+/// The key used to stamp the format version into a serialized world.
+pub const WORLD_FORMAT_VERSION_KEY: &str = "$format_version";
+/// The version worlds are stamped with when no migrations are registered. Migrations must use a version above it.
+pub const BASE_WORLD_FORMAT_VERSION: u32 = 1;
+/// The version of worlds saved before versions were stamped, i.e. without [WORLD_FORMAT_VERSION_KEY].
+pub const UNVERSIONED_WORLD_FORMAT_VERSION: u32 = 0;
+
+/// A single serialized entity; component path to component value.
+pub type SerializedEntity = Map<String, Value>;
+
+type MigrationFn = Box<dyn Fn(&mut SerializedEntity) -> anyhow::Result<()> + Sync + Send>;
+
+/// A transformation applied to every entity of a serialized world whose format version
+/// is older than `version`.
+pub struct WorldMigration {
+    pub version: u32,
+    pub description: String,
+    apply: MigrationFn,
+}
+impl WorldMigration {
+    /// Run an arbitrary transformation on each entity
+    pub fn map_entity(
+        version: u32,
+        description: impl Into<String>,
+        apply: impl Fn(&mut SerializedEntity) -> anyhow::Result<()> + Sync + Send + 'static,
+    ) -> Self {
+        Self { version, description: description.into(), apply: Box::new(apply) }
+    }
+    /// Move the component at path `from` to the path `to`
+    pub fn rename(version: u32, from: impl Into<String>, to: impl Into<String>) -> Self {
+        let (from, to) = (from.into(), to.into());
+        Self::map_entity(version, format!("rename {from} to {to}"), move |entity| {
+            if let Some(value) = entity.remove(&from) {
+                entity.insert(to.clone(), value);
+            }
+            Ok(())
+        })
+    }
+    /// Convert the value of the component at `path`. If the conversion fails, the component is dropped
+    pub fn convert(
+        version: u32,
+        path: impl Into<String>,
+        convert: impl Fn(Value) -> anyhow::Result<Value> + Sync + Send + 'static,
+    ) -> Self {
+        let path = path.into();
+        Self::map_entity(version, format!("convert {path}"), move |entity| {
+            if let Some(value) = entity.remove(&path) {
+                entity.insert(path.clone(), convert(value)?);
+            }
+            Ok(())
+        })
+    }
+    /// Replace the component at `from` with the components returned by `split`
+    pub fn split(
+        version: u32,
+        from: impl Into<String>,
+        split: impl Fn(Value) -> anyhow::Result<Vec<(String, Value)>> + Sync + Send + 'static,
+    ) -> Self {
+        let from = from.into();
+        Self::map_entity(version, format!("split {from}"), move |entity| {
+            if let Some(value) = entity.remove(&from) {
+                entity.extend(split(value)?);
+            }
+            Ok(())
+        })
+    }
+    /// Replace the components at `from` with a single component at `to`. `merge` receives the values in the
+    /// same order as `from`, and is only called for entities that have at least one of them
+    pub fn merge(
+        version: u32,
+        from: Vec<String>,
+        to: impl Into<String>,
+        merge: impl Fn(Vec<Option<Value>>) -> anyhow::Result<Value> + Sync + Send + 'static,
+    ) -> Self {
+        let to = to.into();
+        Self::map_entity(version, format!("merge {} into {to}", from.join(", ")), move |entity| {
+            if from.iter().any(|path| entity.contains_key(path)) {
+                let values = from.iter().map(|path| entity.remove(path)).collect();
+                entity.insert(to.clone(), merge(values)?);
+            }
+            Ok(())
+        })
+    }
+}
+impl fmt::Debug for WorldMigration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorldMigration").field("version", &self.version).field("description", &self.description).finish()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WorldMigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub warnings: ECSDeserializationWarnings,
+}
+impl WorldMigrationReport {
+    pub fn migrated(&self) -> bool {
+        self.from_version != self.to_version
+    }
+}
+
+/// An ordered set of migrations. Use [register_world_migration] to add migrations to the global registry
+/// used when loading worlds.
+#[derive(Debug, Default)]
+pub struct WorldMigrations {
+    migrations: Vec<WorldMigration>,
+}
+impl WorldMigrations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn register(&mut self, migration: WorldMigration) {
+        assert!(
+            migration.version > BASE_WORLD_FORMAT_VERSION,
+            "World migration `{}` must have a version above {BASE_WORLD_FORMAT_VERSION}",
+            migration.description
+        );
+        // Keep insertion order for migrations with the same version
+        let index = self.migrations.partition_point(|m| m.version <= migration.version);
+        self.migrations.insert(index, migration);
+    }
+    /// The version that worlds serialized now are stamped with
+    pub fn current_version(&self) -> u32 {
+        self.migrations.last().map(|m| m.version).unwrap_or(BASE_WORLD_FORMAT_VERSION).max(BASE_WORLD_FORMAT_VERSION)
+    }
+    /// True if a world stamped with `from_version` has to go through [WorldMigrations::migrate] before it can be loaded
+    pub fn requires_migration(&self, from_version: u32) -> bool {
+        from_version > self.current_version() || self.migrations.iter().any(|m| m.version > from_version)
+    }
+    /// Upgrades a serialized world (as produced by serializing a [crate::World] to json) to the current version
+    /// in place, and stamps it with the current version
+    pub fn migrate(&self, world: &mut Value) -> anyhow::Result<WorldMigrationReport> {
+        let entities = world.as_object_mut().ok_or_else(|| anyhow::anyhow!("Serialized world is not an object"))?;
+        let from_version = match entities.remove(WORLD_FORMAT_VERSION_KEY) {
+            Some(version) => {
+                let version = version.as_u64().ok_or_else(|| anyhow::anyhow!("Invalid world format version: {version}"))?;
+                u32::try_from(version)?
+            }
+            None => UNVERSIONED_WORLD_FORMAT_VERSION,
+        };
+        let to_version = self.current_version();
+        if from_version > to_version {
+            anyhow::bail!("World has format version {from_version}, but the newest supported version is {to_version}");
+        }
+
+        let mut report = WorldMigrationReport { from_version, to_version, warnings: Default::default() };
+        for migration in self.migrations.iter().filter(|m| m.version > from_version) {
+            for (id, entity) in entities.iter_mut() {
+                let Some(entity) = entity.as_object_mut() else { continue };
+                if let Err(err) = (migration.apply)(entity) {
+                    let id = parse_entity_id(id).unwrap_or_else(EntityId::null);
+                    report.warnings.push((id, migration.description.clone(), format!("{err:?}")));
+                }
+            }
+        }
+        entities.insert(WORLD_FORMAT_VERSION_KEY.to_string(), Value::from(to_version));
+        Ok(report)
+    }
+}
+
+static WORLD_MIGRATIONS: Lazy<RwLock<WorldMigrations>> = Lazy::new(Default::default);
+
+/// Register a migration which will be applied when loading worlds saved with an older format version.
+/// Should be called during initialization, together with `init_components`
+pub fn register_world_migration(migration: WorldMigration) {
+    WORLD_MIGRATIONS.write().register(migration);
+}
+pub fn world_format_version() -> u32 {
+    WORLD_MIGRATIONS.read().current_version()
+}
+/// See [WorldMigrations::requires_migration]
+pub fn world_requires_migration(from_version: u32) -> bool {
+    WORLD_MIGRATIONS.read().requires_migration(from_version)
+}
+/// Run all registered migrations on a serialized world
+pub fn migrate_serialized_world(world: &mut Value) -> anyhow::Result<WorldMigrationReport> {
+    WORLD_MIGRATIONS.read().migrate(world)
+}
+
+/// Returns true if the json value looks like a serialized world; an object with entity ids as keys and objects as values
+pub fn is_serialized_world(value: &Value) -> bool {
+    match value.as_object() {
+        Some(entities) => {
+            entities.iter().any(|(key, _)| key != WORLD_FORMAT_VERSION_KEY)
+                && entities.iter().all(|(key, value)| {
+                    if key == WORLD_FORMAT_VERSION_KEY {
+                        value.is_u64()
+                    } else {
+                        parse_entity_id(key).is_some() && value.is_object()
+                    }
+                })
+        }
+        None => false,
+    }
+}
+
+fn parse_entity_id(value: &str) -> Option<EntityId> {
+    let bytes = BASE64URL_NOPAD.decode(value.as_bytes()).ok()?;
+    Some(EntityId(u128::from_le_bytes(bytes.try_into().ok()?)))
+}
@@ -0,0 +1,220 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{Component, ComponentDesc, ComponentEntry, ComponentValue, Entity, EntityId, World};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObserverTrigger {
+    /// The component was added to an entity, either when the entity was spawned or later
+    Add,
+    /// The component was replaced with `set`, `set_entry`, `set_components` or `add_components`.
+    /// Writes through `get_mut` or mutable queries are not observed
+    Change,
+    /// The component was removed from an entity, either with `remove_components` or when the entity was despawned
+    Remove,
+}
+
+#[derive(Debug, Clone)]
+pub struct ObserverEvent {
+    pub id: EntityId,
+    pub trigger: ObserverTrigger,
+    pub component: ComponentDesc,
+    /// The previous value; set for [ObserverTrigger::Change] and [ObserverTrigger::Remove]
+    pub old: Option<ComponentEntry>,
+    /// The new value; set for [ObserverTrigger::Add] and [ObserverTrigger::Change]
+    pub new: Option<ComponentEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(u64);
+
+type ObserverFn = Arc<dyn Fn(&mut World, &ObserverEvent) + Sync + Send>;
+
+/// Observers registered on a world. Events are collected while the world is mutated, and the observers
+/// are then run at the end of the mutating call. Events caused by observers are queued and run after the
+/// current observer returns.
+#[derive(Clone, Default)]
+pub(crate) struct Observers {
+    observers: HashMap<(u32, ObserverTrigger), Vec<(ObserverId, ObserverFn)>>,
+    next_id: u64,
+    pending: Vec<ObserverEvent>,
+    flushing: bool,
+}
+impl Observers {
+    /// Observers triggering each other more than this many times in a single flush are assumed to be in an infinite loop
+    const MAX_FLUSH_ROUNDS: usize = 1000;
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+    #[inline]
+    pub(crate) fn observes(&self, component: ComponentDesc, trigger: ObserverTrigger) -> bool {
+        !self.is_empty() && self.observers.contains_key(&(component.index(), trigger))
+    }
+    /// Collects the events for an entity which is added or removed as a whole
+    pub(crate) fn entity_events(&self, ids: &[EntityId], trigger: ObserverTrigger, entity: &Entity) -> Vec<ObserverEvent> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let mut events = Vec::new();
+        for entry in entity.iter().filter(|entry| self.observes(entry.desc(), trigger)) {
+            for &id in ids {
+                let (old, new) = if trigger == ObserverTrigger::Remove { (Some(entry.clone()), None) } else { (None, Some(entry.clone())) };
+                events.push(ObserverEvent { id, trigger, component: entry.desc(), old, new });
+            }
+        }
+        events
+    }
+    pub(crate) fn push(&mut self, events: impl IntoIterator<Item = ObserverEvent>) {
+        self.pending.extend(events);
+    }
+}
+
+impl World {
+    /// Run `observer` whenever `component` is added, changed or removed (depending on `trigger`) on any entity of this world.
+    ///
+    /// Observers run synchronously at the end of the world method that caused them, with the world in its new state.
+    /// The observer may mutate the world; any events this causes are run after the observer returns.
+    pub fn observe(
+        &mut self,
+        component: impl Into<ComponentDesc>,
+        trigger: ObserverTrigger,
+        observer: impl Fn(&mut World, &ObserverEvent) + Sync + Send + 'static,
+    ) -> ObserverId {
+        let id = ObserverId(self.observers.next_id);
+        self.observers.next_id += 1;
+        self.observers.observers.entry((component.into().index(), trigger)).or_default().push((id, Arc::new(observer)));
+        id
+    }
+    /// Run `observer` with the new value whenever `component` is added to an entity
+    pub fn on_add<T: ComponentValue>(
+        &mut self,
+        component: Component<T>,
+        observer: impl Fn(&mut World, EntityId, &T) + Sync + Send + 'static,
+    ) -> ObserverId {
+        self.observe(component, ObserverTrigger::Add, move |world, event| {
+            observer(world, event.id, event.new.as_ref().unwrap().downcast_ref())
+        })
+    }
+    /// Run `observer` with the old and new value whenever `component` is changed on an entity
+    pub fn on_change<T: ComponentValue>(
+        &mut self,
+        component: Component<T>,
+        observer: impl Fn(&mut World, EntityId, &T, &T) + Sync + Send + 'static,
+    ) -> ObserverId {
+        self.observe(component, ObserverTrigger::Change, move |world, event| {
+            observer(world, event.id, event.old.as_ref().unwrap().downcast_ref(), event.new.as_ref().unwrap().downcast_ref())
+        })
+    }
+    /// Run `observer` with the old value whenever `component` is removed from an entity, or the entity is despawned
+    pub fn on_remove<T: ComponentValue>(
+        &mut self,
+        component: Component<T>,
+        observer: impl Fn(&mut World, EntityId, &T) + Sync + Send + 'static,
+    ) -> ObserverId {
+        self.observe(component, ObserverTrigger::Remove, move |world, event| {
+            observer(world, event.id, event.old.as_ref().unwrap().downcast_ref())
+        })
+    }
+    pub fn remove_observer(&mut self, id: ObserverId) {
+        self.observers.observers.retain(|_, observers| {
+            observers.retain(|(observer_id, _)| *observer_id != id);
+            !observers.is_empty()
+        });
+    }
+
+    /// Run the observers for all pending events. This is done automatically by the world methods
+    /// which mutate components, so it's only needed after using the lower level APIs.
+    pub fn flush_observers(&mut self) {
+        if self.observers.flushing || self.observers.pending.is_empty() {
+            return;
+        }
+        self.observers.flushing = true;
+        let mut rounds = 0;
+        while !self.observers.pending.is_empty() {
+            if rounds == Observers::MAX_FLUSH_ROUNDS {
+                log::error!("Observers are triggering each other in a loop; dropping {} events", self.observers.pending.len());
+                self.observers.pending.clear();
+                break;
+            }
+            rounds += 1;
+            for event in std::mem::take(&mut self.observers.pending) {
+                let observers = self.observers.observers.get(&(event.component.index(), event.trigger)).cloned().unwrap_or_default();
+                for (_, observer) in observers {
+                    observer(self, &event);
+                }
+            }
+        }
+        self.observers.flushing = false;
+    }
+
+    /// Collects the change events for replacing components of an entity. Must be called before the values are replaced
+    pub(crate) fn observe_set<'a>(&self, id: EntityId, entries: impl IntoIterator<Item = &'a ComponentEntry>) -> Vec<ObserverEvent> {
+        if self.observers.is_empty() {
+            return Vec::new();
+        }
+        entries
+            .into_iter()
+            .filter(|entry| self.observers.observes(entry.desc(), ObserverTrigger::Change))
+            .filter_map(|entry| {
+                let old = self.get_entry(id, entry.desc()).ok()?;
+                Some(ObserverEvent {
+                    id,
+                    trigger: ObserverTrigger::Change,
+                    component: entry.desc(),
+                    old: Some(old),
+                    new: Some(entry.clone()),
+                })
+            })
+            .collect()
+    }
+    /// Collects the events for adding components to an entity; change events for the components it already has, and add
+    /// events for the others. Must be called before the components are added
+    pub(crate) fn observe_add(&self, id: EntityId, entity: &Entity) -> Vec<ObserverEvent> {
+        if self.observers.is_empty() {
+            return Vec::new();
+        }
+        let mut events = Vec::new();
+        for entry in entity.iter() {
+            match self.get_entry(id, entry.desc()) {
+                Ok(old) if self.observers.observes(entry.desc(), ObserverTrigger::Change) => events.push(ObserverEvent {
+                    id,
+                    trigger: ObserverTrigger::Change,
+                    component: entry.desc(),
+                    old: Some(old),
+                    new: Some(entry.clone()),
+                }),
+                Err(_) if self.observers.observes(entry.desc(), ObserverTrigger::Add) => events.push(ObserverEvent {
+                    id,
+                    trigger: ObserverTrigger::Add,
+                    component: entry.desc(),
+                    old: None,
+                    new: Some(entry.clone()),
+                }),
+                _ => {}
+            }
+        }
+        events
+    }
+    /// Collects the events for removing components from an entity. Must be called before the components are removed
+    pub(crate) fn observe_remove(&self, id: EntityId, components: &[ComponentDesc]) -> Vec<ObserverEvent> {
+        if self.observers.is_empty() {
+            return Vec::new();
+        }
+        components
+            .iter()
+            .filter(|&&desc| self.observers.observes(desc, ObserverTrigger::Remove))
+            .filter_map(|&desc| {
+                let old = self.get_entry(id, desc).ok()?;
+                Some(ObserverEvent { id, trigger: ObserverTrigger::Remove, component: desc, old: Some(old), new: None })
+            })
+            .collect()
+    }
+    /// Queue `events` and run the observers for them, unless we're already running observers
+    pub(crate) fn notify_observers(&mut self, events: Vec<ObserverEvent>) {
+        if !events.is_empty() {
+            self.observers.push(events);
+            self.flush_observers();
+        }
+    }
+}
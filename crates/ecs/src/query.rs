@@ -522,6 +522,18 @@ impl Query {
             commands.soft_apply(world);
         })))
     }
+    /// The access needed to run this query; all components it matches on or listens to changes of are read
+    pub fn access(&self) -> SystemAccess {
+        let mut access = SystemAccess::new();
+        access.reads.union_with(&self.filter.components);
+        if let QueryEvent::Changed { components } = &self.event {
+            for &component in components {
+                access.reads.insert(component);
+            }
+        }
+        access.event_queries = !self.event.is_frame();
+        access
+    }
     fn add_component(&mut self, query: &Self, component: ComponentDesc) {
         self.filter = query.filter.clone().incl(component);
         if query.event.is_spawned() {
@@ -636,6 +648,15 @@ impl<'a, R: ComponentQuery<'a> + Clone + 'static> TypedReadQuery<R> {
         let r = self.read_components.clone();
         self.query.iter(world, state).into_iter().map(move |acc| (acc.id(), r.get_data(world, &acc)))
     }
+    /// Iterate the query from a system running in parallel with others. Panics if the system didn't declare that it
+    /// reads all components of this query
+    pub fn iter_system(
+        &self,
+        world: SystemWorld<'a>,
+        state: Option<&'a mut QueryState>,
+    ) -> impl Iterator<Item = (EntityId, <R as ComponentQuery<'a>>::Data)> + 'a {
+        self.iter(world.world_covering(&self.query.access()), state)
+    }
     pub fn iter_cloned(
         &self,
         world: &'a World,
@@ -693,11 +714,25 @@ impl<'a, R: ComponentQuery<'a> + Clone + 'static> TypedReadQuery<R> {
             commands.soft_apply(world);
         })))
     }
+    /// Like [TypedReadQuery::with_commands], but the system declares that it only reads the components of this query, so
+    /// that a [ScheduledSystemGroup] can run it in parallel with other systems
+    pub fn to_parallel_system<F, E>(self, name: &'static str, update: F) -> DynSharedSystem<E>
+    where
+        F: Fn(&Self, SystemWorld, Option<&mut QueryState>, &E, &mut Commands) + Send + Sync + 'static,
+        E: 'static,
+    {
+        let mut state = QueryState::new();
+        Box::new(ParallelFnSystem::new(name, self.query.access(), move |world, commands, event| {
+            profiling::scope!(name);
+            update(&self, world, Some(&mut state), event, commands);
+        }))
+    }
 }
 
 pub struct TypedReadWriteQuery<RW, R> {
     read_write_components: RW,
     read_components: R,
+    write_set: ComponentSet,
     query: Query,
 }
 impl<'a, RW: ComponentQuery<'a> + Clone + 'static, R: ComponentQuery<'a> + Clone + 'static> TypedReadWriteQuery<RW, R> {
@@ -717,7 +752,7 @@ impl<'a, RW: ComponentQuery<'a> + Clone + 'static, R: ComponentQuery<'a> + Clone
         let mut changed_components = Vec::new();
         read_write_components.get_change_filtered(&mut changed_components);
         read_components.get_change_filtered(&mut changed_components);
-        Self { query: Query::new_for_typed_query(component_ids, changed_components), read_write_components, read_components }
+        Self { query: Query::new_for_typed_query(component_ids, changed_components), read_write_components, read_components, write_set }
     }
     pub fn read_write<T: ComponentValue>(&self, component: Component<T>) -> TypedReadWriteQuery<<RW as ComponentsTupleAppend<T>>::Output, R>
     where
@@ -774,6 +809,24 @@ impl<'a, RW: ComponentQuery<'a> + Clone + 'static, R: ComponentQuery<'a> + Clone
         let world = &*world;
         self.query.iter(world, state).into_iter().map(move |acc| (acc.id(), rw.get_data_mut(world, &acc), r.get_data(world, &acc)))
     }
+    /// Iterate the query from a system running in parallel with others. Panics if the system didn't declare that it
+    /// writes to the read/write components of this query, or reads the rest
+    pub fn iter_system(
+        &self,
+        world: SystemWorld<'a>,
+        state: Option<&'a mut QueryState>,
+    ) -> impl Iterator<Item = (EntityId, <RW as ComponentQuery<'a>>::DataMut, <R as ComponentQuery<'a>>::Data)> + 'a {
+        let world = world.world_covering(&self.access());
+        let rw = self.read_write_components.clone();
+        let r = self.read_components.clone();
+        self.query.iter(world, state).into_iter().map(move |acc| (acc.id(), rw.get_data_mut(world, &acc), r.get_data(world, &acc)))
+    }
+    /// The access needed to run this query; the read/write components are written, and everything else is read
+    pub fn access(&self) -> SystemAccess {
+        let mut access = self.query.access();
+        access.writes = self.write_set.clone();
+        access
+    }
     pub fn to_system<F: Fn(&Self, &mut World, Option<&mut QueryState>, &E) + Send + Sync + 'static, E: 'static>(
         self,
         update: F,
@@ -804,6 +857,19 @@ impl<'a, RW: ComponentQuery<'a> + Clone + 'static, R: ComponentQuery<'a> + Clone
             commands.soft_apply(world);
         }))) as Box<dyn System<E> + Send + Sync + 'static>
     }
+    /// Like [TypedReadWriteQuery::with_commands], but the system declares the access of this query, so that a
+    /// [ScheduledSystemGroup] can run it in parallel with other systems. Use [TypedReadWriteQuery::iter_system] to iterate it
+    pub fn to_parallel_system<F, E>(self, name: &'static str, update: F) -> DynSharedSystem<E>
+    where
+        F: Fn(&Self, SystemWorld, Option<&mut QueryState>, &E, &mut Commands) + Send + Sync + 'static,
+        E: 'static,
+    {
+        let mut state = QueryState::new();
+        Box::new(ParallelFnSystem::new(name, self.access(), move |world, commands, event| {
+            profiling::scope!(name);
+            update(&self, world, Some(&mut state), event, commands);
+        }))
+    }
 }
 
 pub struct FrameEvent;
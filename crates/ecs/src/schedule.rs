@@ -0,0 +1,330 @@
+use std::fmt::Display;
+
+use itertools::Itertools;
+
+use crate::{Commands, Component, ComponentDesc, ComponentSet, ComponentValue, DynSystem, ECSError, EntityId, FrameEvent, System, World};
+
+/// The components a system reads and writes, which is used to decide which systems can run in parallel
+#[derive(Debug, Clone)]
+pub struct SystemAccess {
+    pub reads: ComponentSet,
+    pub writes: ComponentSet,
+    /// If the system uses `changed`, `spawned` or `despawned` queries. These queries share bookkeeping
+    /// in the archetypes, so two systems using them are never run in parallel
+    pub event_queries: bool,
+}
+impl SystemAccess {
+    pub fn new() -> Self {
+        Self { reads: ComponentSet::new(), writes: ComponentSet::new(), event_queries: false }
+    }
+    pub fn read(mut self, component: impl Into<ComponentDesc>) -> Self {
+        self.reads.insert(component.into());
+        self
+    }
+    pub fn write(mut self, component: impl Into<ComponentDesc>) -> Self {
+        self.writes.insert(component.into());
+        self
+    }
+    pub fn union_with(&mut self, other: &SystemAccess) {
+        self.reads.union_with(&other.reads);
+        self.writes.union_with(&other.writes);
+        self.event_queries |= other.event_queries;
+    }
+    /// True if the component is read or written
+    pub fn can_read(&self, component: impl Into<ComponentDesc>) -> bool {
+        let component = component.into();
+        self.reads.contains(component) || self.writes.contains(component)
+    }
+    /// True if everything `other` reads and writes is also declared here
+    pub fn covers(&self, other: &SystemAccess) -> bool {
+        let mut readable = self.reads.clone();
+        readable.union_with(&self.writes);
+        readable.is_superset(&other.reads) && self.writes.is_superset(&other.writes) && (self.event_queries || !other.event_queries)
+    }
+    pub fn conflicts_with(&self, other: &SystemAccess) -> bool {
+        !self.writes.is_disjoint(&other.writes)
+            || !self.writes.is_disjoint(&other.reads)
+            || !self.reads.is_disjoint(&other.writes)
+            || (self.event_queries && other.event_queries)
+    }
+}
+impl Default for SystemAccess {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Shared access to the world for a system which has declared its [SystemAccess]. Other systems may be writing to the
+/// world at the same time, so only the components the system declared can be accessed; accessing anything else panics.
+#[derive(Clone, Copy)]
+pub struct SystemWorld<'a> {
+    world: &'a World,
+    pub(crate) access: &'a SystemAccess,
+}
+impl<'a> SystemWorld<'a> {
+    pub(crate) fn new(world: &'a World, access: &'a SystemAccess) -> Self {
+        Self { world, access }
+    }
+    /// The world, for queries that have checked that the access covers them
+    pub(crate) fn world_covering(&self, access: &SystemAccess) -> &'a World {
+        assert!(self.access.covers(access), "The system doesn't declare access to all components of this query");
+        self.world
+    }
+    fn check_read(&self, component: impl Into<ComponentDesc>) {
+        let component = component.into();
+        assert!(self.access.can_read(component), "The system doesn't declare access to {component:?}");
+    }
+    pub fn access(&self) -> &'a SystemAccess {
+        self.access
+    }
+    pub fn exists(&self, entity_id: EntityId) -> bool {
+        self.world.exists(entity_id)
+    }
+    pub fn has_component(&self, entity_id: EntityId, component: impl Into<ComponentDesc>) -> bool {
+        self.world.has_component(entity_id, component)
+    }
+    pub fn get<T: Copy + ComponentValue>(&self, entity_id: EntityId, component: Component<T>) -> Result<T, ECSError> {
+        self.check_read(component);
+        self.world.get(entity_id, component)
+    }
+    pub fn get_cloned<T: Clone + ComponentValue>(&self, entity_id: EntityId, component: Component<T>) -> Result<T, ECSError> {
+        self.check_read(component);
+        self.world.get_cloned(entity_id, component)
+    }
+    pub fn get_ref<T: ComponentValue>(&self, entity_id: EntityId, component: Component<T>) -> Result<&'a T, ECSError> {
+        self.check_read(component);
+        self.world.get_ref(entity_id, component)
+    }
+    pub fn resource_entity(&self) -> EntityId {
+        self.world.resource_entity()
+    }
+    pub fn resource_opt<T: ComponentValue>(&self, component: Component<T>) -> Option<&'a T> {
+        self.check_read(component);
+        self.world.resource_opt(component)
+    }
+    pub fn resource<T: ComponentValue>(&self, component: Component<T>) -> &'a T {
+        self.check_read(component);
+        self.world.resource(component)
+    }
+}
+
+/// A system which declares its [SystemAccess], so that a [ScheduledSystemGroup] can run it alongside other systems
+pub trait SharedSystem<E = FrameEvent>: System<E> {
+    /// The components this system reads and writes
+    fn access(&self) -> SystemAccess;
+    /// Run the system with shared access to the world, alongside other systems. Structural changes must go through
+    /// `commands`, which are applied once all systems in the stage have run
+    fn run_shared(&mut self, world: &World, commands: &mut Commands, event: &E);
+}
+pub type DynSharedSystem<E = FrameEvent> = Box<dyn SharedSystem<E> + Send + Sync>;
+/// Allows a shared system to be used anywhere a [DynSystem] is expected, for instance in a [crate::SystemGroup]
+impl<E> System<E> for DynSharedSystem<E> {
+    fn run(&mut self, world: &mut World, event: &E) {
+        (**self).run(world, event)
+    }
+}
+
+type ParallelFn<E> = Box<dyn FnMut(SystemWorld, &mut Commands, &E) + Sync + Send>;
+
+/// A system with declared access, which can be run in parallel with other systems. See
+/// `TypedReadQuery::to_parallel_system` and `TypedReadWriteQuery::to_parallel_system`.
+pub struct ParallelFnSystem<E = FrameEvent> {
+    name: &'static str,
+    access: SystemAccess,
+    func: ParallelFn<E>,
+}
+impl<E> ParallelFnSystem<E> {
+    pub fn new(name: &'static str, access: SystemAccess, func: impl FnMut(SystemWorld, &mut Commands, &E) + Sync + Send + 'static) -> Self {
+        Self { name, access, func: Box::new(func) }
+    }
+}
+impl<E> System<E> for ParallelFnSystem<E> {
+    fn run(&mut self, world: &mut World, event: &E) {
+        let mut commands = Commands::new();
+        self.run_shared(world, &mut commands, event);
+        commands.soft_apply(world);
+    }
+}
+impl<E> SharedSystem<E> for ParallelFnSystem<E> {
+    fn access(&self) -> SystemAccess {
+        self.access.clone()
+    }
+    fn run_shared(&mut self, world: &World, commands: &mut Commands, event: &E) {
+        (self.func)(SystemWorld::new(world, &self.access), commands, event);
+    }
+}
+impl<E> std::fmt::Debug for ParallelFnSystem<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ParallelFnSystem({})", self.name)
+    }
+}
+
+/// A system added to a [ScheduledSystemGroup]
+pub enum GroupSystem<E = FrameEvent> {
+    /// A system which needs exclusive access to the world
+    Exclusive(DynSystem<E>),
+    /// A system which can run alongside others it doesn't conflict with
+    Shared(DynSharedSystem<E>),
+}
+impl<E> From<DynSystem<E>> for GroupSystem<E> {
+    fn from(system: DynSystem<E>) -> Self {
+        Self::Exclusive(system)
+    }
+}
+impl<E> From<DynSharedSystem<E>> for GroupSystem<E> {
+    fn from(system: DynSharedSystem<E>) -> Self {
+        Self::Shared(system)
+    }
+}
+
+struct ScheduledSystem<E> {
+    name: String,
+    system: GroupSystem<E>,
+    access: Option<SystemAccess>,
+    after: Vec<String>,
+}
+
+/// The stages of a [ScheduledSystemGroup]. The systems within a stage don't conflict with each other and
+/// are run in parallel; the stages are run in order.
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    pub stages: Vec<ScheduleStage>,
+}
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleStage {
+    /// Indices of the systems in the order they were added to the group
+    pub systems: Vec<usize>,
+    pub names: Vec<String>,
+    /// This stage consists of a single system which didn't declare its access, and thus needs exclusive access to the world
+    pub exclusive: bool,
+}
+impl Display for Schedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, stage) in self.stages.iter().enumerate() {
+            writeln!(f, "Stage {i}{}: {}", if stage.exclusive { " (exclusive)" } else { "" }, stage.names.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Like a [crate::SystemGroup], but runs systems which declare their [SystemAccess] in parallel when they don't conflict.
+///
+/// The schedule is deterministic: systems that conflict (or are ordered with `add_after`) always run in the order they were
+/// added, and systems that don't declare their access get exclusive access to the world, in the order they were added.
+pub struct ScheduledSystemGroup<E = FrameEvent> {
+    label: &'static str,
+    systems: Vec<ScheduledSystem<E>>,
+    schedule: Option<Schedule>,
+}
+impl<E> ScheduledSystemGroup<E> {
+    pub fn new(label: &'static str) -> Self {
+        Self { label, systems: Vec::new(), schedule: None }
+    }
+    pub fn add(&mut self, name: impl Into<String>, system: impl Into<GroupSystem<E>>) -> &mut Self {
+        self.add_after(name, system, &[])
+    }
+    /// Add a system which must run after the systems named in `after`, even if they don't conflict. The systems in `after`
+    /// must already have been added
+    pub fn add_after(&mut self, name: impl Into<String>, system: impl Into<GroupSystem<E>>, after: &[&str]) -> &mut Self {
+        let name = name.into();
+        for dependency in after {
+            assert!(
+                self.systems.iter().any(|s| s.name == *dependency),
+                "System {name} depends on {dependency}, which has not been added yet"
+            );
+        }
+        let system = system.into();
+        let access = match &system {
+            GroupSystem::Exclusive(_) => None,
+            GroupSystem::Shared(system) => Some(system.access()),
+        };
+        self.systems.push(ScheduledSystem { name, system, access, after: after.iter().map(|s| s.to_string()).collect() });
+        self.schedule = None;
+        self
+    }
+    /// Returns the schedule, computing it if the systems have changed since it was last computed
+    pub fn schedule(&mut self) -> &Schedule {
+        if self.schedule.is_none() {
+            let schedule = self.compute_schedule();
+            log::debug!("Schedule for {}:\n{schedule}", self.label);
+            self.schedule = Some(schedule);
+        }
+        self.schedule.as_ref().unwrap()
+    }
+    fn compute_schedule(&self) -> Schedule {
+        let mut system_stages: Vec<usize> = Vec::with_capacity(self.systems.len());
+        for (i, system) in self.systems.iter().enumerate() {
+            let mut stage = 0;
+            for (j, other) in self.systems[..i].iter().enumerate() {
+                let conflicts = match (&system.access, &other.access) {
+                    (Some(a), Some(b)) => a.conflicts_with(b),
+                    _ => true,
+                };
+                if conflicts || system.after.contains(&other.name) {
+                    stage = stage.max(system_stages[j] + 1);
+                }
+            }
+            system_stages.push(stage);
+        }
+        let mut schedule = Schedule::default();
+        for (i, stage) in system_stages.into_iter().enumerate() {
+            if schedule.stages.len() <= stage {
+                schedule.stages.resize_with(stage + 1, Default::default);
+            }
+            let stage = &mut schedule.stages[stage];
+            stage.systems.push(i);
+            stage.names.push(self.systems[i].name.clone());
+            stage.exclusive = self.systems[i].access.is_none();
+        }
+        schedule
+    }
+}
+impl<E: Sync> System<E> for ScheduledSystemGroup<E> {
+    fn run(&mut self, world: &mut World, event: &E) {
+        profiling::scope!(self.label);
+        let stages = self.schedule().stages.iter().map(|stage| stage.systems.clone()).collect_vec();
+        for stage in stages {
+            if stage.len() == 1 {
+                match &mut self.systems[stage[0]].system {
+                    GroupSystem::Exclusive(system) => system.run(world, event),
+                    GroupSystem::Shared(system) => system.run(world, event),
+                }
+                continue;
+            }
+            // Exclusive systems always get a stage of their own
+            let mut systems = self
+                .systems
+                .iter_mut()
+                .enumerate()
+                .filter(|(i, _)| stage.contains(i))
+                .map(|(_, s)| match &mut s.system {
+                    GroupSystem::Shared(system) => system,
+                    GroupSystem::Exclusive(_) => unreachable!("{} doesn't declare its access", s.name),
+                })
+                .collect_vec();
+            let mut commands = systems.iter().map(|_| Commands::new()).collect_vec();
+            {
+                let world = &*world;
+                #[cfg(not(target_os = "unknown"))]
+                rayon::scope(|scope| {
+                    for (system, commands) in systems.iter_mut().zip(commands.iter_mut()) {
+                        scope.spawn(move |_| system.run_shared(world, commands, event));
+                    }
+                });
+                #[cfg(target_os = "unknown")]
+                for (system, commands) in systems.iter_mut().zip(commands.iter_mut()) {
+                    system.run_shared(world, commands, event);
+                }
+            }
+            // Structural changes are applied in the order the systems were added, to keep the result deterministic
+            for mut commands in commands {
+                commands.soft_apply(world);
+            }
+        }
+    }
+}
+impl<E> std::fmt::Debug for ScheduledSystemGroup<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ScheduledSystemGroup({}, _)", self.label)
+    }
+}
@@ -2,18 +2,22 @@ use std::fmt;
 
 use itertools::Itertools;
 use serde::{
-    de::{MapAccess, Visitor},
+    de::{self, MapAccess, Visitor},
     ser::SerializeMap,
     Deserialize, Deserializer, Serialize, Serializer,
 };
 
-use crate::{dont_store, query, DeserEntityDataWithWarnings, Entity, EntityId, Serializable, World};
+use crate::{
+    dont_store, is_binary_world, migrate_serialized_world, query, world_format_version, world_requires_migration,
+    DeserEntityDataWithWarnings, Entity, EntityId, Serializable, World, UNVERSIONED_WORLD_FORMAT_VERSION, WORLD_FORMAT_VERSION_KEY,
+};
 
 impl Serialize for World {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         let len = self.len();
 
-        let mut entities = serializer.serialize_map(Some(len))?;
+        let mut entities = serializer.serialize_map(Some(len + 1))?;
+        entities.serialize_entry(WORLD_FORMAT_VERSION_KEY, &world_format_version())?;
         for (id, _) in query(()).excl(dont_store()).iter(self, None) {
             entities.serialize_entry(&id, &SerWorldEntity { world: self, id })?;
         }
@@ -58,8 +62,15 @@ impl<'de> Deserialize<'de> for World {
                 V: MapAccess<'de>,
             {
                 let mut res = World::new_with_config_internal("deserialized-world", false);
-                while let Some((id, entity)) = map.next_entry::<EntityId, Entity>()? {
-                    res.spawn_with_id(id, entity);
+                while let Some(key) = map.next_key::<WorldKey>()? {
+                    match key {
+                        WorldKey::FormatVersion => {
+                            map.next_value::<u32>()?;
+                        }
+                        WorldKey::Entity(id) => {
+                            res.spawn_with_id(id, map.next_value::<Entity>()?);
+                        }
+                    }
                 }
                 Ok(res)
             }
@@ -69,12 +80,84 @@ impl<'de> Deserialize<'de> for World {
     }
 }
 
+/// A key in a serialized world; either the format version stamp or an entity id
+enum WorldKey {
+    FormatVersion,
+    Entity(EntityId),
+}
+impl<'de> Deserialize<'de> for WorldKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct WorldKeyVisitor;
+
+        impl<'de> Visitor<'de> for WorldKeyVisitor {
+            type Value = WorldKey;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an entity id or the world format version")
+            }
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                if v == WORLD_FORMAT_VERSION_KEY {
+                    Ok(WorldKey::FormatVersion)
+                } else {
+                    EntityId::from_base64(v).map(WorldKey::Entity).map_err(de::Error::custom)
+                }
+            }
+        }
+
+        deserializer.deserialize_str(WorldKeyVisitor)
+    }
+}
+
 /// Use this struct while de-serializing a World to also get warnings
 /// about missing/bad components. Only works with json.
+///
+/// Note that deserializing this directly will not run any migrations; use [DeserWorldWithWarnings::from_slice] for that.
 pub struct DeserWorldWithWarnings {
     pub world: World,
     pub warnings: ECSDeserializationWarnings,
 }
+impl DeserWorldWithWarnings {
+    /// Deserialize a json world, upgrading it to the current format version with the registered migrations.
+    /// Binary worlds (see [World::to_binary]) are detected and deserialized as well
+    pub fn from_slice(content: &[u8]) -> anyhow::Result<Self> {
+        if is_binary_world(content) {
+            return Self::from_binary_slice(content);
+        }
+        // Only the version is read here; everything else is skipped without being allocated
+        #[derive(Deserialize)]
+        struct FormatVersion {
+            #[serde(rename = "$format_version", default)]
+            format_version: Option<u32>,
+        }
+        let FormatVersion { format_version } = serde_json::from_slice(content)?;
+        if !world_requires_migration(format_version.unwrap_or(UNVERSIONED_WORLD_FORMAT_VERSION)) {
+            return Ok(serde_json::from_slice(content)?);
+        }
+        Self::from_value(serde_json::from_slice(content)?)
+    }
+    /// Deserialize the json representation of a world, upgrading it to the current format version with the registered migrations
+    pub fn from_value(mut value: serde_json::Value) -> anyhow::Result<Self> {
+        let migration_warnings = migrate_world_value(&mut value)?;
+        let mut res: Self = serde_json::from_value(value)?;
+        res.warnings.warnings.extend(migration_warnings.warnings);
+        Ok(res)
+    }
+}
+
+/// Runs the registered migrations on the json representation of a world, and returns their warnings
+pub(crate) fn migrate_world_value(value: &mut serde_json::Value) -> anyhow::Result<ECSDeserializationWarnings> {
+    let report = migrate_serialized_world(value)?;
+    if report.migrated() {
+        log::info!("Migrated world from format version {} to {}", report.from_version, report.to_version);
+    }
+    Ok(report.warnings)
+}
 
 impl<'de> Deserialize<'de> for DeserWorldWithWarnings {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -96,9 +179,17 @@ impl<'de> Deserialize<'de> for DeserWorldWithWarnings {
             {
                 let mut res =
                     DeserWorldWithWarnings { world: World::new_with_config_internal("deserialized", false), warnings: Default::default() };
-                while let Some((id, entity)) = map.next_entry::<EntityId, DeserEntityDataWithWarnings>()? {
-                    res.world.spawn_with_id(id, entity.entity);
-                    res.warnings.warnings.extend(entity.warnings.warnings.into_iter().map(|(_, key, err)| (id, key, err)));
+                while let Some(key) = map.next_key::<WorldKey>()? {
+                    match key {
+                        WorldKey::FormatVersion => {
+                            map.next_value::<u32>()?;
+                        }
+                        WorldKey::Entity(id) => {
+                            let entity = map.next_value::<DeserEntityDataWithWarnings>()?;
+                            res.world.spawn_with_id(id, entity.entity);
+                            res.warnings.warnings.extend(entity.warnings.warnings.into_iter().map(|(_, key, err)| (id, key, err)));
+                        }
+                    }
                 }
                 Ok(res)
             }
@@ -162,7 +253,10 @@ mod test {
         let id = Entity::new().with(ser_test3(), "hi".to_string()).spawn(&mut world);
 
         let ser = serde_json::to_string(&world).unwrap();
-        assert_eq!(&ser, &format!("{{\"AQAAAAAAAAAAAAAAAAAAAA\":{{}},\"{id}\":{{\"core::test::ser_test3\":\"hi\"}}}}"));
+        assert_eq!(
+            &ser,
+            &format!("{{\"$format_version\":1,\"AQAAAAAAAAAAAAAAAAAAAA\":{{}},\"{id}\":{{\"core::test::ser_test3\":\"hi\"}}}}")
+        );
 
         let deser: DeserWorldWithWarnings = serde_json::from_str(&ser).unwrap();
         assert_eq!(deser.world.get_ref(id, ser_test3()).unwrap(), "hi");
@@ -177,7 +271,7 @@ mod test {
         let mut world = World::new("test");
         world.add_resource(ser_test3(), "hi".to_string());
         let ser = serde_json::to_string(&world).unwrap();
-        assert_eq!(&ser, r#"{"AQAAAAAAAAAAAAAAAAAAAA":{"core::test::ser_test3":"hi"}}"#);
+        assert_eq!(&ser, r#"{"$format_version":1,"AQAAAAAAAAAAAAAAAAAAAA":{"core::test::ser_test3":"hi"}}"#);
         let deser: World = serde_json::from_str(&ser).unwrap();
         assert_eq!(deser.resource(ser_test3()), "hi");
     }
@@ -187,7 +281,7 @@ mod test {
         init();
         let world = World::new_with_config("test", false);
         let ser = serde_json::to_string(&world).unwrap();
-        assert_eq!(&ser, r#"{}"#);
+        assert_eq!(&ser, r#"{"$format_version":1}"#);
         let deser: World = serde_json::from_str(&ser).unwrap();
         assert!(!deser.exists(deser.resource_entity()));
     }
@@ -201,8 +295,43 @@ mod test {
         assert_eq!(deser.world.get_ref(EntityId::from_str("L9wH6h4qgcNBfRv2Rv2FIQ").unwrap(), ser_test4()).unwrap(), "hello");
         assert_eq!(deser.warnings.warnings.len(), 2);
         let ser = serde_json::to_string(&deser.world).unwrap();
-        assert_eq!(&ser, r#"{"AQAAAAAAAAAAAAAAAAAAAA":{},"L9wH6h4qgcNBfRv2Rv2FIQ":{"core::test::ser_test4":"hello"}}"#);
+        assert_eq!(&ser, r#"{"$format_version":1,"AQAAAAAAAAAAAAAAAAAAAA":{},"L9wH6h4qgcNBfRv2Rv2FIQ":{"core::test::ser_test4":"hello"}}"#);
 
         assert!(serde_json::from_str::<World>(source).is_err());
     }
+
+    #[test]
+    pub fn test_migrate_world() {
+        init();
+        let mut migrations = WorldMigrations::new();
+        migrations.register(WorldMigration::rename(2, "core::test::old_ser_test3", "core::test::ser_test3"));
+        migrations.register(WorldMigration::convert(3, "core::test::ser_test4", |value| {
+            Ok(serde_json::Value::String(value.as_i64().ok_or_else(|| anyhow::anyhow!("Not a number"))?.to_string()))
+        }));
+        assert_eq!(migrations.current_version(), 3);
+
+        let source = r#"{"L9wH6h4qgcNBfRv2Rv2FIQ":{"core::test::old_ser_test3":"hi","core::test::ser_test4":5},"AQAAAAAAAAAAAAAAAAAAAA":{"core::test::ser_test4":"bad"}}"#;
+        let mut value: serde_json::Value = serde_json::from_str(source).unwrap();
+        assert!(is_serialized_world(&value));
+        let report = migrations.migrate(&mut value).unwrap();
+        assert_eq!((report.from_version, report.to_version), (0, 3));
+        assert_eq!(report.warnings.len(), 1);
+
+        let deser: DeserWorldWithWarnings = serde_json::from_value(value.clone()).unwrap();
+        let id = EntityId::from_str("L9wH6h4qgcNBfRv2Rv2FIQ").unwrap();
+        assert_eq!(deser.world.get_ref(id, ser_test3()).unwrap(), "hi");
+        assert_eq!(deser.world.get_ref(id, ser_test4()).unwrap(), "5");
+        assert!(!deser.world.has_component(deser.world.resource_entity(), ser_test4()));
+
+        // Already up to date; nothing should change
+        let report = migrations.migrate(&mut value).unwrap();
+        assert!(!report.migrated());
+        assert!(migrations.migrate(&mut serde_json::json!({ "$format_version": 4 })).is_err());
+
+        assert!(migrations.requires_migration(UNVERSIONED_WORLD_FORMAT_VERSION));
+        assert!(migrations.requires_migration(2));
+        assert!(!migrations.requires_migration(3));
+        assert!(migrations.requires_migration(4));
+        assert!(!WorldMigrations::new().requires_migration(UNVERSIONED_WORLD_FORMAT_VERSION));
+    }
 }
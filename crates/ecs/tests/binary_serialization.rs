@@ -0,0 +1,107 @@
+use ambient_ecs::{
+    binary_world_to_json, components, dont_store, query, DeserWorldWithWarnings, Entity, EntityId, Serializable, World, WorldMigration,
+    WorldMigrations,
+};
+use glam::Vec3;
+
+components!("test", {
+    @[Serializable]
+    bin_name: String,
+    @[Serializable]
+    bin_label: String,
+    @[Serializable]
+    bin_position: Vec3,
+    @[Serializable]
+    bin_targets: Vec<EntityId>,
+    @[Serializable]
+    bin_flag: (),
+    bin_not_serializable: f32,
+});
+
+fn init() {
+    ambient_ecs::init_components();
+    init_components();
+}
+
+fn test_world() -> World {
+    let mut world = World::new("binary");
+    world.add_resource(bin_name(), "resources".to_string());
+    let a = Entity::new().with(bin_name(), "a".to_string()).with(bin_position(), Vec3::X).spawn(&mut world);
+    let b = Entity::new()
+        .with(bin_position(), Vec3::new(1., 2., 3.))
+        .with(bin_targets(), vec![a])
+        .with_default(bin_flag())
+        .with(bin_not_serializable(), 5.)
+        .spawn(&mut world);
+    Entity::new().with(bin_name(), "c".to_string()).with(bin_targets(), vec![a, b]).spawn(&mut world);
+    Entity::new().with(bin_name(), "not stored".to_string()).with_default(dont_store()).spawn(&mut world);
+    world
+}
+
+#[test]
+fn binary_matches_json() {
+    init();
+    let world = test_world();
+
+    let binary = world.to_binary().unwrap();
+    let json = serde_json::to_vec(&world).unwrap();
+    assert!(binary.len() < json.len());
+
+    let from_binary = World::from_slice(&binary).unwrap();
+    let from_json = World::from_slice(&json).unwrap();
+    assert_eq!(from_binary.len(), 4);
+    assert_eq!(from_binary.len(), from_json.len());
+    for (id, _) in from_json.entities() {
+        assert_eq!(format!("{:?}", from_binary.clone_entity(id).unwrap()), format!("{:?}", from_json.clone_entity(id).unwrap()));
+    }
+    assert_eq!(from_binary.resource(bin_name()), "resources");
+
+    // Round-tripping through binary should give the same json
+    let json_value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json_value, serde_json::to_value(&from_binary).unwrap());
+}
+
+#[test]
+fn binary_entity() {
+    init();
+    let entity = Entity::new().with(bin_name(), "a".to_string()).with(bin_position(), Vec3::Y).with(bin_not_serializable(), 1.);
+    let deser = Entity::from_binary_slice(&entity.to_binary().unwrap()).unwrap();
+    assert_eq!(deser.get_ref(bin_name()).unwrap(), "a");
+    assert_eq!(deser.get(bin_position()).unwrap(), Vec3::Y);
+    assert!(!deser.contains(bin_not_serializable()));
+}
+
+#[test]
+fn binary_with_unknown_component() {
+    init();
+    let world = test_world();
+    let mut binary = world.to_binary().unwrap();
+    // Rename a component in the path table so that it no longer exists
+    let path = b"core::test::bin_name";
+    let start = binary.windows(path.len()).position(|w| w == path).unwrap();
+    binary[start + path.len() - 1] = b'X';
+
+    let DeserWorldWithWarnings { world: deser, warnings } = DeserWorldWithWarnings::from_slice(&binary).unwrap();
+    assert_eq!(deser.len(), 4);
+    assert_eq!(warnings.len(), 3);
+}
+
+#[test]
+fn binary_migration() {
+    init();
+    let world = test_world();
+    let a = query(bin_name()).iter(&world, None).find(|(_, name)| name.as_str() == "a").unwrap().0;
+    let mut value = binary_world_to_json(&world.to_binary().unwrap()).unwrap();
+    assert_eq!(value, serde_json::to_value(&world).unwrap());
+
+    // Binary worlds are upgraded like json worlds; here the name has been renamed to a label since the world was written
+    let mut migrations = WorldMigrations::new();
+    migrations.register(WorldMigration::rename(migrations.current_version() + 1, "core::test::bin_name", "core::test::bin_label"));
+    assert!(migrations.migrate(&mut value).unwrap().migrated());
+
+    let DeserWorldWithWarnings { world: migrated, warnings } = serde_json::from_value(value).unwrap();
+    assert!(warnings.is_empty());
+    assert_eq!(migrated.get_ref(a, bin_label()).unwrap(), "a");
+    assert!(!migrated.has_component(a, bin_name()));
+    assert_eq!(migrated.get(a, bin_position()).unwrap(), Vec3::X);
+}
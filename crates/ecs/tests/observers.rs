@@ -0,0 +1,86 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use ambient_ecs::{components, Entity, ObserverTrigger, World};
+use parking_lot::Mutex;
+
+components!("test", {
+    scale: f32,
+    shape_size: f32,
+    tag: (),
+});
+
+fn init() {
+    init_components();
+}
+
+#[test]
+fn observers_maintain_invariant() {
+    init();
+    let mut world = World::new("observers");
+    world.on_add(scale(), |world, id, scale| world.add_component(id, shape_size(), *scale * 2.).unwrap());
+    world.on_change(scale(), |world, id, _, scale| world.set(id, shape_size(), *scale * 2.).map(|_| ()).unwrap());
+    world.on_remove(scale(), |world, id, _| {
+        world.remove_component(id, shape_size()).ok();
+    });
+
+    let id = Entity::new().with(scale(), 1.).spawn(&mut world);
+    assert_eq!(world.get(id, shape_size()).unwrap(), 2.);
+
+    world.set(id, scale(), 3.).unwrap();
+    assert_eq!(world.get(id, shape_size()).unwrap(), 6.);
+
+    world.add_component(id, scale(), 4.).unwrap();
+    assert_eq!(world.get(id, shape_size()).unwrap(), 8.);
+
+    world.remove_component(id, scale()).unwrap();
+    assert!(!world.has_component(id, shape_size()));
+}
+
+#[test]
+fn observers_receive_old_and_new_values() {
+    init();
+    let mut world = World::new("observers");
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    world.on_change(scale(), {
+        let changes = changes.clone();
+        move |_, _, old, new| changes.lock().push((*old, *new))
+    });
+    let removed = Arc::new(Mutex::new(Vec::new()));
+    world.on_remove(scale(), {
+        let removed = removed.clone();
+        move |_, id, old| removed.lock().push((id, *old))
+    });
+
+    let id = Entity::new().with(scale(), 1.).spawn(&mut world);
+    world.set(id, scale(), 2.).unwrap();
+    world.set_components(id, Entity::new().with(scale(), 3.)).unwrap();
+    // Changes through get_mut are not observed
+    *world.get_mut(id, scale()).unwrap() = 10.;
+    world.despawn(id);
+
+    assert_eq!(*changes.lock(), vec![(1., 2.), (2., 3.)]);
+    assert_eq!(*removed.lock(), vec![(id, 10.)]);
+}
+
+#[test]
+fn remove_observer() {
+    init();
+    let mut world = World::new("observers");
+    let count = Arc::new(AtomicUsize::new(0));
+    let observer = world.observe(tag(), ObserverTrigger::Add, {
+        let count = count.clone();
+        move |_, event| {
+            assert!(event.old.is_none() && event.new.is_some());
+            count.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    world.batch_spawn(Entity::new().with_default(tag()), 3);
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+
+    world.remove_observer(observer);
+    Entity::new().with_default(tag()).spawn(&mut world);
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+}
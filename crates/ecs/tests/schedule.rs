@@ -0,0 +1,121 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use ambient_ecs::{components, query, query_mut, DynSystem, Entity, FnSystem, FrameEvent, ScheduledSystemGroup, System, World};
+
+components!("test", {
+    position: f32,
+    velocity: f32,
+    health: f32,
+    damage: f32,
+});
+
+fn init() -> World {
+    init_components();
+    let mut world = World::new("schedule");
+    for i in 0..100 {
+        Entity::new().with(position(), 0.).with(velocity(), i as f32).with(health(), 100.).with(damage(), 1.).spawn(&mut world);
+    }
+    world
+}
+
+fn systems() -> ScheduledSystemGroup {
+    let mut group = ScheduledSystemGroup::new("test");
+    group.add(
+        "movement",
+        query_mut(position(), velocity()).to_parallel_system("movement", |q, world, qs, _, _| {
+            for (_, position, velocity) in q.iter_system(world, qs) {
+                *position += *velocity;
+            }
+        }),
+    );
+    group.add(
+        "damage",
+        query_mut(health(), damage()).to_parallel_system("damage", |q, world, qs, _, _| {
+            for (_, health, damage) in q.iter_system(world, qs) {
+                *health -= *damage;
+            }
+        }),
+    );
+    group.add(
+        "read_position",
+        query(position()).to_parallel_system("read_position", |q, world, qs, _, _| {
+            assert!(q.iter_system(world, qs).all(|(_, position)| *position >= 0.));
+        }),
+    );
+    group
+}
+
+#[test]
+fn schedule_stages() {
+    let _world = init();
+    let mut group = systems();
+    group.add("exclusive", Box::new(FnSystem::new(|_, _| {})) as DynSystem);
+    group.add_after("after_exclusive", query(velocity()).to_parallel_system("after_exclusive", |_, _, _, _, _| {}), &["damage"]);
+    let schedule = group.schedule();
+    assert_eq!(
+        schedule.to_string(),
+        "Stage 0: movement, damage\nStage 1: read_position\nStage 2 (exclusive): exclusive\nStage 3: after_exclusive\n"
+    );
+}
+
+#[test]
+fn parallel_results() {
+    let mut world = init();
+    let mut group = systems();
+    for _ in 0..3 {
+        group.run(&mut world, &FrameEvent);
+    }
+    for (_, (position, velocity, health)) in query((position(), velocity(), health())).iter(&world, None) {
+        assert_eq!(*position, velocity * 3.);
+        assert_eq!(*health, 97.);
+    }
+}
+
+#[test]
+fn commands_applied_in_order() {
+    let mut world = init();
+    let count = Arc::new(AtomicUsize::new(0));
+    let mut group = ScheduledSystemGroup::new("test");
+    group.add(
+        "despawn",
+        query(damage()).to_parallel_system("despawn", |q, world, qs, _, commands| {
+            for (id, _) in q.iter_system(world, qs) {
+                commands.despawn(id);
+            }
+        }),
+    );
+    group.add(
+        "count",
+        query(velocity()).to_parallel_system("count", {
+            let count = count.clone();
+            move |q, world, qs, _, _| {
+                count.store(q.iter_system(world, qs).count(), Ordering::SeqCst);
+            }
+        }),
+    );
+    group.run(&mut world, &FrameEvent);
+    // Both systems only read, so they run in the same stage and the despawns are applied after it
+    assert_eq!(count.load(Ordering::SeqCst), 100);
+    // Only the resource entity is left
+    assert_eq!(world.len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "doesn't declare access")]
+fn undeclared_access_panics() {
+    let mut world = init();
+    let mut group = ScheduledSystemGroup::new("test");
+    group.add(
+        "read_health",
+        query(position()).to_parallel_system("read_health", |q, world, qs, _, _| {
+            for (id, _) in q.iter_system(world, qs) {
+                // Another system could be writing to health at the same time
+                world.get(id, health()).unwrap();
+            }
+        }),
+    );
+    group.run(&mut world, &FrameEvent);
+}
@@ -1,6 +1,6 @@
 use ambient_ecs::{ArchetypeFilter, Entity, EntityId};
 use ambient_intent::server_push_intent;
-use ambient_network::client::GameRpcArgs;
+use ambient_network::{client::GameRpcArgs, permissions::Role};
 use ambient_physics::visualization::{visualize_collider, visualizing};
 use ambient_physics::{
     helpers::{convert_rigid_dynamic_to_static, convert_rigid_static_to_dynamic, unweld_multi, weld_multi},
@@ -37,17 +37,18 @@ impl AxisFlags {
 }
 
 pub fn register_rpcs(reg: &mut RpcRegistry<GameRpcArgs>) {
-    reg.register(rpc_pick);
-    reg.register(rpc_select);
-    reg.register(rpc_weld);
-    reg.register(rpc_unweld);
-    // reg.register(rpc_scan);
-    reg.register(rpc_freeze);
-    reg.register(rpc_unfreeze);
-    reg.register(rpc_toggle_visualize_colliders);
-    // reg.register(rpc_save);
-    reg.register(rpc_spawn);
-    // reg.register(rpc_teleport_player);
+    ambient_network::rpc::register_ecs_schemas(reg);
+    reg.register_with("editor::pick", 1, Role::Editor, rpc_pick);
+    reg.register_with("editor::select", 1, Role::Editor, rpc_select);
+    reg.register_with("editor::weld", 1, Role::Editor, rpc_weld);
+    reg.register_with("editor::unweld", 1, Role::Editor, rpc_unweld);
+    // reg.register_with("editor::scan", 1, Role::Editor, rpc_scan);
+    reg.register_with("editor::freeze", 1, Role::Editor, rpc_freeze);
+    reg.register_with("editor::unfreeze", 1, Role::Editor, rpc_unfreeze);
+    reg.register_with("editor::toggle_visualize_colliders", 1, Role::Editor, rpc_toggle_visualize_colliders);
+    // reg.register_with("editor::save", 1, Role::Editor, rpc_save);
+    reg.register_with("editor::spawn", 1, Role::Editor, rpc_spawn);
+    // reg.register_with("editor::teleport_player", 1, Role::Editor, rpc_teleport_player);
 }
 
 pub async fn rpc_select(args: GameRpcArgs, (method, mode): (SelectMethod, SelectMode)) {
@@ -86,6 +86,10 @@ impl AsyncAssetKey<AssetResult<Arc<GpuMesh>>> for GpuMeshFromUrl {
         let mesh = MeshFromUrl::new(self.url, self.cache_on_disk).get(&assets).await?;
         Ok(GpuMesh::from_mesh(assets, &mesh))
     }
+
+    fn gpu_size(&self, asset: &AssetResult<Arc<GpuMesh>>) -> Option<u64> {
+        asset.as_ref().ok().map(|mesh| mesh.size_in_bytes() as u64)
+    }
 }
 
 /// Gpu mesh buffer which holds all meshes in an Elements application.
@@ -22,6 +22,9 @@ impl AsyncAssetKey<Result<Arc<DynamicImage>, AssetError>> for ImageFromUrl {
     async fn load(self, assets: AssetCache) -> Result<Arc<DynamicImage>, AssetError> {
         image_from_url(assets, self.url).await.map(Arc::new)
     }
+    fn cpu_size(&self, asset: &Result<Arc<DynamicImage>, AssetError>) -> Option<u64> {
+        asset.as_ref().ok().map(|image| image.as_bytes().len() as u64)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -33,6 +36,9 @@ impl AsyncAssetKey<Result<Arc<image::RgbaImage>, AssetError>> for Rgba8ImageFrom
     async fn load(self, assets: AssetCache) -> Result<Arc<image::RgbaImage>, AssetError> {
         image_from_url(assets, self.url).await.map(|x| Arc::new(x.into_rgba8()))
     }
+    fn cpu_size(&self, asset: &Result<Arc<image::RgbaImage>, AssetError>) -> Option<u64> {
+        asset.as_ref().ok().map(|image| image.as_raw().len() as u64)
+    }
 }
 
 async fn image_from_url(assets: AssetCache, url: AbsAssetUrl) -> Result<DynamicImage, AssetError> {
@@ -15,18 +15,23 @@ pub mod components {
     }
     pub mod ui {
         pub use ambient_layout::{
-            gpu_ui_size,
+            flex_basis, flex_grow, flex_shrink, gpu_ui_size, grid_column, grid_column_gap, grid_column_span, grid_row, grid_row_gap,
+            grid_row_span,
             guest_api::{
                 align_horizontal_begin, align_horizontal_center, align_horizontal_end, align_vertical_begin, align_vertical_center,
                 align_vertical_end, docking_bottom, docking_fill, docking_left, docking_right, docking_top, fit_horizontal_children,
-                fit_horizontal_none, fit_horizontal_parent, fit_vertical_children, fit_vertical_none, fit_vertical_parent, layout_bookcase,
-                layout_dock, layout_flow, layout_width_to_children, margin_bottom, margin_left, margin_right, margin_top,
-                orientation_horizontal, orientation_vertical, padding_bottom, padding_left, padding_right, padding_top,
+                fit_horizontal_none, fit_horizontal_parent, fit_vertical_children, fit_vertical_none, fit_vertical_parent,
+                grid_template_columns, grid_template_rows, layout_bookcase, layout_dock, layout_flow, layout_grid,
+                layout_width_to_children, layout_wrap, margin_bottom, margin_left, margin_right, margin_top, orientation_horizontal,
+                orientation_vertical, padding_bottom, padding_left, padding_right, padding_top,
             },
             height, is_book_file, mesh_to_local_from_size, min_height, min_width, space_between_items, width,
         };
+        pub use ambient_localization::{text_args, text_key};
         pub use ambient_rect::{background_color, border_color, border_radius, border_thickness, rect};
-        pub use ambient_text::{font_size, text};
+        pub use ambient_text::{
+            font_size, rich_text, text, text_align_begin, text_align_center, text_align_end, text_line_height, text_wrap_width,
+        };
     }
     pub mod rendering {
         pub use ambient_renderer::color;
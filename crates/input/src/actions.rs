@@ -0,0 +1,310 @@
+use std::collections::{BTreeMap, HashSet};
+
+use ambient_ecs::{components, Debuggable, Description, Name, Networked, Resource};
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+use winit::event::{MouseButton, VirtualKeyCode};
+
+use crate::{gamepad::GamepadButton, PlayerRawInput};
+
+components!("input", {
+    /// The actions of the game and their default bindings. Lives on the synced resources entity of the server, so that
+    /// clients can evaluate it
+    @[
+        Debuggable, Networked,
+        Name["Action map"],
+        Description["The actions of the game, and the inputs they are bound to by default."]
+    ]
+    action_map: ActionMap,
+    /// The bindings the local user changed, which take precedence over those of the [action_map]
+    @[Debuggable, Resource]
+    action_bindings: ActionBindings,
+
+    @[Debuggable]
+    player_actions: PlayerActions,
+    @[Debuggable]
+    player_prev_actions: PlayerActions,
+});
+
+/// An input which can be part of a [Binding]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputSource {
+    Key(VirtualKeyCode),
+    MouseButton(MouseButton),
+    /// The button on any of the connected gamepads
+    GamepadButton(GamepadButton),
+    /// The mouse wheel, which scales the value of its binding by how much it was scrolled since the last update
+    MouseWheel,
+}
+
+/// A combination of inputs which drives an action
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Binding {
+    /// The inputs which all have to be held for the binding to be active, e.g. `[{ key = "LControl" }, { key = "S" }]`
+    pub chord: Vec<InputSource>,
+    /// What the binding adds to the value of its action while it's active
+    #[serde(default = "default_binding_value")]
+    pub value: Vec2,
+}
+fn default_binding_value() -> Vec2 {
+    Vec2::X
+}
+impl Binding {
+    pub fn new(chord: impl IntoIterator<Item = InputSource>) -> Self {
+        Self { chord: chord.into_iter().collect(), value: default_binding_value() }
+    }
+    pub fn with_value(mut self, value: Vec2) -> Self {
+        self.value = value;
+        self
+    }
+    fn held_sources(&self) -> HashSet<InputSource> {
+        self.chord.iter().copied().filter(|source| *source != InputSource::MouseWheel).collect()
+    }
+    /// The value of the binding, or None if any of its inputs isn't held
+    fn evaluate(&self, input: &PlayerRawInput, wheel_delta: f32) -> Option<Vec2> {
+        let mut value = self.value;
+        for source in &self.chord {
+            match source {
+                InputSource::Key(key) if !input.keys.contains(key) => return None,
+                InputSource::MouseButton(button) if !input.mouse_buttons.contains(button) => return None,
+                InputSource::GamepadButton(button) if !input.gamepads.values().any(|gamepad| gamepad.buttons.contains(button)) => {
+                    return None
+                }
+                InputSource::MouseWheel if wheel_delta == 0. => return None,
+                InputSource::MouseWheel => value *= wheel_delta,
+                _ => {}
+            }
+        }
+        Some(value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionKind {
+    /// A digital action, which is either pressed or not
+    #[default]
+    Button,
+    /// The sum of the x values of its active bindings
+    Axis,
+    /// The sum of the values of its active bindings
+    Axis2d,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct Action {
+    #[serde(default)]
+    pub kind: ActionKind,
+    #[serde(default)]
+    pub bindings: Vec<Binding>,
+}
+
+/// Named actions and the inputs they are bound to, e.g. the `[actions]` table of a project's `ambient.toml`:
+///
+/// ```toml
+/// [actions.jump]
+/// bindings = [{ chord = [{ key = "Space" }] }]
+///
+/// [actions.move]
+/// kind = "axis2d"
+/// bindings = [
+///     { chord = [{ key = "W" }], value = [0.0, 1.0] },
+///     { chord = [{ key = "S" }], value = [0.0, -1.0] },
+/// ]
+///
+/// [actions.zoom]
+/// kind = "axis"
+/// bindings = [{ chord = ["mouse_wheel"] }]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct ActionMap {
+    pub actions: BTreeMap<String, Action>,
+}
+impl ActionMap {
+    /// Parses an action map on its own, e.g. from a data asset
+    pub fn parse(toml: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(toml)
+    }
+    /// Parses the `[actions]` table of a project manifest, see `ambient_project::Manifest::actions`
+    pub fn from_table(table: toml::Table) -> Result<Self, toml::de::Error> {
+        toml::Value::Table(table).try_into()
+    }
+
+    /// This map with the bindings of the actions in `bindings` replaced
+    pub fn with_bindings(&self, bindings: &ActionBindings) -> ActionMap {
+        let mut map = self.clone();
+        for (name, action) in map.actions.iter_mut() {
+            if let Some(bindings) = bindings.overrides.get(name) {
+                action.bindings = bindings.clone();
+            }
+        }
+        map
+    }
+
+    /// Evaluates the actions for `input`, where `prev_input` is the input of the last update.
+    ///
+    /// When the chords of several active bindings overlap, only the longest ones are active, so that e.g. `LControl + S`
+    /// doesn't also trigger an action bound to `S`. Bindings to just the mouse wheel are always active.
+    pub fn evaluate(&self, input: &PlayerRawInput, prev_input: &PlayerRawInput) -> PlayerActions {
+        let wheel_delta = input.mouse_wheel - prev_input.mouse_wheel;
+        let active = self
+            .actions
+            .iter()
+            .flat_map(|(name, action)| action.bindings.iter().map(move |binding| (name, binding)))
+            .filter_map(|(name, binding)| Some((name, binding.held_sources(), binding.evaluate(input, wheel_delta)?)))
+            .collect::<Vec<_>>();
+
+        let mut actions = PlayerActions::default();
+        for (name, action) in &self.actions {
+            let value = active
+                .iter()
+                .filter(|(active_name, held, _)| {
+                    *active_name == name
+                        && (held.is_empty() || !active.iter().any(|(_, other, _)| other.len() > held.len() && other.is_superset(held)))
+                })
+                .map(|(_, _, value)| *value)
+                .fold(None, |sum: Option<Vec2>, value| Some(sum.unwrap_or_default() + value));
+            let value = match (action.kind, value) {
+                (_, None) => Vec2::ZERO,
+                (ActionKind::Button, Some(_)) => Vec2::X,
+                (ActionKind::Axis, Some(value)) => Vec2::new(value.x, 0.),
+                (ActionKind::Axis2d, Some(value)) => value,
+            };
+            actions.0.insert(name.clone(), value);
+        }
+        actions
+    }
+
+    /// Restricts actions received from a player to the ones in this map, with values of the right kind.
+    ///
+    /// The actions are evaluated by the client, with bindings the user can change, so the server can't tell whether a
+    /// player actually pressed anything; this only ensures that the values are ones the map could produce.
+    pub fn validate(&self, actions: PlayerActions) -> PlayerActions {
+        let mut valid = PlayerActions::default();
+        for (name, action) in &self.actions {
+            let value = actions.get(name);
+            let value = match action.kind {
+                _ if !value.is_finite() || value == Vec2::ZERO => Vec2::ZERO,
+                ActionKind::Button => Vec2::X,
+                ActionKind::Axis => Vec2::new(value.x, 0.),
+                ActionKind::Axis2d => value,
+            };
+            valid.0.insert(name.clone(), value);
+        }
+        valid
+    }
+}
+
+/// Bindings chosen by a user, which replace the bindings of the actions of an [ActionMap]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct ActionBindings {
+    pub overrides: BTreeMap<String, Vec<Binding>>,
+}
+impl ActionBindings {
+    pub fn parse(toml: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(toml)
+    }
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string_pretty(self)
+    }
+    pub fn set(&mut self, action: impl Into<String>, bindings: Vec<Binding>) {
+        self.overrides.insert(action.into(), bindings);
+    }
+    /// Goes back to the default bindings of `action`
+    pub fn reset(&mut self, action: &str) {
+        self.overrides.remove(action);
+    }
+}
+
+/// The values of the actions of a player, see [ActionKind] for what the values mean
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct PlayerActions(pub BTreeMap<String, Vec2>);
+impl PlayerActions {
+    pub fn get(&self, action: &str) -> Vec2 {
+        self.0.get(action).copied().unwrap_or_default()
+    }
+    pub fn is_pressed(&self, action: &str) -> bool {
+        self.get(action) != Vec2::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::vec2;
+
+    use super::*;
+
+    fn input(keys: &[VirtualKeyCode], mouse_wheel: f32) -> PlayerRawInput {
+        PlayerRawInput { keys: keys.iter().copied().collect(), mouse_wheel, ..Default::default() }
+    }
+
+    const MAP: &str = r#"
+    [save]
+    bindings = [{ chord = [{ key = "LControl" }, { key = "S" }] }]
+
+    [move]
+    kind = "axis2d"
+    bindings = [
+        { chord = [{ key = "W" }], value = [0.0, 1.0] },
+        { chord = [{ key = "S" }], value = [0.0, -1.0] },
+        { chord = [{ key = "D" }], value = [1.0, 0.0] },
+    ]
+
+    [zoom]
+    kind = "axis"
+    bindings = [{ chord = ["mouse_wheel"], value = [0.5, 0.0] }]
+
+    [fire]
+    bindings = [{ chord = [{ mouse_button = "Left" }] }]
+    "#;
+
+    #[test]
+    fn evaluate() {
+        let map = ActionMap::parse(MAP).unwrap();
+        assert_eq!(map.actions.len(), 4);
+
+        let actions = map.evaluate(&input(&[VirtualKeyCode::W, VirtualKeyCode::D], 4.), &input(&[], 2.));
+        assert_eq!(actions.get("move"), vec2(1., 1.));
+        assert_eq!(actions.get("zoom"), vec2(1., 0.));
+        assert!(!actions.is_pressed("save"));
+        assert!(!actions.is_pressed("fire"));
+
+        // The longer chord wins
+        let actions = map.evaluate(&input(&[VirtualKeyCode::LControl, VirtualKeyCode::S], 0.), &input(&[], 0.));
+        assert!(actions.is_pressed("save"));
+        assert_eq!(actions.get("move"), Vec2::ZERO);
+    }
+
+    #[test]
+    fn bindings() {
+        let map = ActionMap::from_table(toml::from_str("[fire]\nbindings = []\n").unwrap()).unwrap();
+        assert!(map.actions.contains_key("fire"));
+
+        let mut bindings = ActionBindings::default();
+        bindings.set("fire", vec![Binding::new([InputSource::Key(VirtualKeyCode::F)])]);
+        let bindings = ActionBindings::parse(&bindings.to_toml().unwrap()).unwrap();
+
+        let map = map.with_bindings(&bindings);
+        assert!(map.evaluate(&input(&[VirtualKeyCode::F], 0.), &input(&[], 0.)).is_pressed("fire"));
+    }
+
+    #[test]
+    fn validate() {
+        let map = ActionMap::parse(MAP).unwrap();
+        let actions = PlayerActions(
+            [("save", vec2(0.2, 3.)), ("zoom", vec2(2., 2.)), ("move", vec2(f32::NAN, 1.)), ("fly", Vec2::X)]
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        );
+        let actions = map.validate(actions);
+        assert_eq!(actions.get("save"), Vec2::X);
+        assert_eq!(actions.get("zoom"), vec2(2., 0.));
+        assert_eq!(actions.get("move"), Vec2::ZERO);
+        assert_eq!(actions.get("fire"), Vec2::ZERO);
+        assert!(!actions.0.contains_key("fly"));
+    }
+}
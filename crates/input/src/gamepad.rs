@@ -0,0 +1,414 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+};
+
+use ambient_ecs::{components, world_events, Debuggable, Description, Entity, Name, Resource, System, World};
+use glam::Vec2;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use winit::event::Event;
+
+/// Stick and trigger values closer to zero than this are ignored, unless the [gamepad_dead_zone] resource is set
+pub const DEFAULT_DEAD_ZONE: f32 = 0.15;
+
+components!("input", {
+    /// The connected gamepads of this client, by id
+    @[Debuggable, Resource]
+    gamepads: BTreeMap<u32, GamepadState>,
+    /// Overrides [DEFAULT_DEAD_ZONE]
+    @[Debuggable, Resource]
+    gamepad_dead_zone: f32,
+    /// Gamepads driven by code rather than hardware, e.g. for tests or on-screen controls
+    @[Debuggable, Resource]
+    virtual_gamepads: VirtualGamepads,
+
+    @[Debuggable, Name["Event gamepad connected"], Description["A gamepad was connected. The value is the id of the gamepad."]]
+    event_gamepad_connected: u32,
+    @[Debuggable, Name["Event gamepad disconnected"], Description["A gamepad was disconnected. The value is the id of the gamepad."]]
+    event_gamepad_disconnected: u32,
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GamepadButton {
+    /// The bottom face button, e.g. A on Xbox controllers
+    South,
+    East,
+    North,
+    West,
+    LeftBumper,
+    RightBumper,
+    /// The left trigger, when it's pressed far enough. See [GamepadState::left_trigger] for how far it's pressed
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    /// The button in the middle of the gamepad, e.g. the Xbox button
+    Mode,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+/// The state of a gamepad, with the dead zone applied to its sticks and triggers
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GamepadState {
+    pub name: String,
+    pub buttons: HashSet<GamepadButton>,
+    /// From -1 to 1, with positive y up
+    pub left_stick: Vec2,
+    pub right_stick: Vec2,
+    /// From 0 to 1
+    pub left_trigger: f32,
+    pub right_trigger: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GamepadEvent {
+    Connected { id: u32, name: String },
+    Disconnected { id: u32 },
+    Button { id: u32, button: GamepadButton, pressed: bool },
+    Axis { id: u32, axis: GamepadAxis, value: f32 },
+}
+
+/// Where the [GamepadSystem] gets its gamepad events from. The ids are local to the source
+pub trait GamepadSource: Send + std::fmt::Debug {
+    fn poll(&mut self, events: &mut Vec<GamepadEvent>);
+}
+
+/// A [GamepadSource] which is driven by code. Add it as the [virtual_gamepads] resource to use it
+#[derive(Debug, Clone, Default)]
+pub struct VirtualGamepads {
+    events: Arc<Mutex<Vec<GamepadEvent>>>,
+}
+impl VirtualGamepads {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn send(&self, event: GamepadEvent) {
+        self.events.lock().push(event);
+    }
+    pub fn connect(&self, id: u32, name: impl Into<String>) {
+        self.send(GamepadEvent::Connected { id, name: name.into() });
+    }
+    pub fn disconnect(&self, id: u32) {
+        self.send(GamepadEvent::Disconnected { id });
+    }
+    pub fn set_button(&self, id: u32, button: GamepadButton, pressed: bool) {
+        self.send(GamepadEvent::Button { id, button, pressed });
+    }
+    pub fn set_axis(&self, id: u32, axis: GamepadAxis, value: f32) {
+        self.send(GamepadEvent::Axis { id, axis, value });
+    }
+}
+impl GamepadSource for VirtualGamepads {
+    fn poll(&mut self, events: &mut Vec<GamepadEvent>) {
+        events.append(&mut self.events.lock());
+    }
+}
+
+#[cfg(feature = "gilrs")]
+mod hardware {
+    use super::{GamepadAxis, GamepadButton, GamepadEvent, GamepadSource};
+
+    /// The gamepads connected to this machine
+    pub struct GilrsGamepads {
+        gilrs: gilrs::Gilrs,
+        /// The gamepads which were connected before we started don't send connected events
+        connected: Vec<GamepadEvent>,
+    }
+    impl GilrsGamepads {
+        pub fn new() -> Option<Self> {
+            match gilrs::Gilrs::new() {
+                Ok(gilrs) => {
+                    let connected = gilrs
+                        .gamepads()
+                        .map(|(id, gamepad)| GamepadEvent::Connected { id: usize::from(id) as u32, name: gamepad.name().to_string() })
+                        .collect();
+                    Some(Self { gilrs, connected })
+                }
+                Err(err) => {
+                    tracing::warn!("Gamepads are not available: {err:?}");
+                    None
+                }
+            }
+        }
+    }
+    impl std::fmt::Debug for GilrsGamepads {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("GilrsGamepads").finish()
+        }
+    }
+    impl GamepadSource for GilrsGamepads {
+        fn poll(&mut self, events: &mut Vec<GamepadEvent>) {
+            events.append(&mut self.connected);
+            while let Some(gilrs::Event { id: gamepad_id, event, .. }) = self.gilrs.next_event() {
+                let id = usize::from(gamepad_id) as u32;
+                events.push(match event {
+                    gilrs::EventType::Connected => GamepadEvent::Connected { id, name: self.gilrs.gamepad(gamepad_id).name().to_string() },
+                    gilrs::EventType::Disconnected => GamepadEvent::Disconnected { id },
+                    gilrs::EventType::ButtonPressed(button, _) => match convert_button(button) {
+                        Some(button) => GamepadEvent::Button { id, button, pressed: true },
+                        None => continue,
+                    },
+                    gilrs::EventType::ButtonReleased(button, _) => match convert_button(button) {
+                        Some(button) => GamepadEvent::Button { id, button, pressed: false },
+                        None => continue,
+                    },
+                    gilrs::EventType::ButtonChanged(gilrs::Button::LeftTrigger2, value, _) => {
+                        GamepadEvent::Axis { id, axis: GamepadAxis::LeftTrigger, value }
+                    }
+                    gilrs::EventType::ButtonChanged(gilrs::Button::RightTrigger2, value, _) => {
+                        GamepadEvent::Axis { id, axis: GamepadAxis::RightTrigger, value }
+                    }
+                    gilrs::EventType::AxisChanged(axis, value, _) => match convert_axis(axis) {
+                        Some(axis) => GamepadEvent::Axis { id, axis, value },
+                        None => continue,
+                    },
+                    _ => continue,
+                });
+            }
+        }
+    }
+
+    fn convert_button(button: gilrs::Button) -> Option<GamepadButton> {
+        use gilrs::Button;
+        Some(match button {
+            Button::South => GamepadButton::South,
+            Button::East => GamepadButton::East,
+            Button::North => GamepadButton::North,
+            Button::West => GamepadButton::West,
+            Button::LeftTrigger => GamepadButton::LeftBumper,
+            Button::RightTrigger => GamepadButton::RightBumper,
+            Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+            Button::RightTrigger2 => GamepadButton::RightTrigger,
+            Button::Select => GamepadButton::Select,
+            Button::Start => GamepadButton::Start,
+            Button::Mode => GamepadButton::Mode,
+            Button::LeftThumb => GamepadButton::LeftStick,
+            Button::RightThumb => GamepadButton::RightStick,
+            Button::DPadUp => GamepadButton::DPadUp,
+            Button::DPadDown => GamepadButton::DPadDown,
+            Button::DPadLeft => GamepadButton::DPadLeft,
+            Button::DPadRight => GamepadButton::DPadRight,
+            _ => return None,
+        })
+    }
+
+    fn convert_axis(axis: gilrs::Axis) -> Option<GamepadAxis> {
+        use gilrs::Axis;
+        Some(match axis {
+            Axis::LeftStickX => GamepadAxis::LeftStickX,
+            Axis::LeftStickY => GamepadAxis::LeftStickY,
+            Axis::RightStickX => GamepadAxis::RightStickX,
+            Axis::RightStickY => GamepadAxis::RightStickY,
+            Axis::LeftZ => GamepadAxis::LeftTrigger,
+            Axis::RightZ => GamepadAxis::RightTrigger,
+            _ => return None,
+        })
+    }
+}
+#[cfg(feature = "gilrs")]
+pub use hardware::GilrsGamepads;
+
+/// The values of the axes of a gamepad, before the dead zone is applied
+#[derive(Debug, Clone, Copy, Default)]
+struct RawAxes {
+    left_stick: Vec2,
+    right_stick: Vec2,
+    left_trigger: f32,
+    right_trigger: f32,
+}
+
+/// Polls the gamepad sources every frame, and keeps the [gamepads] resource up to date
+#[derive(Debug)]
+pub struct GamepadSystem {
+    sources: Vec<Box<dyn GamepadSource>>,
+    /// Maps the (source, id) of each gamepad to its id in [gamepads]; the virtual gamepads are the last source
+    ids: HashMap<(usize, u32), u32>,
+    next_id: u32,
+    axes: HashMap<u32, RawAxes>,
+}
+impl GamepadSystem {
+    /// A system which polls the gamepads of this machine, if the `gilrs` feature is enabled
+    pub fn new() -> Self {
+        #[allow(unused_mut)]
+        let mut sources: Vec<Box<dyn GamepadSource>> = Vec::new();
+        #[cfg(feature = "gilrs")]
+        if let Some(gilrs) = GilrsGamepads::new() {
+            sources.push(Box::new(gilrs));
+        }
+        Self::with_sources(sources)
+    }
+    pub fn with_sources(sources: Vec<Box<dyn GamepadSource>>) -> Self {
+        Self { sources, ids: HashMap::new(), next_id: 0, axes: HashMap::new() }
+    }
+
+    fn handle_event(&mut self, world: &mut World, source: usize, event: GamepadEvent) {
+        let dead_zone = world.resource_opt(gamepad_dead_zone()).copied().unwrap_or(DEFAULT_DEAD_ZONE);
+        let mut events = Vec::new();
+        {
+            let gamepads = match world.resource_mut_opt(gamepads()) {
+                Some(gamepads) => gamepads,
+                None => return,
+            };
+            match event {
+                GamepadEvent::Connected { id, name } => {
+                    let id = *self.ids.entry((source, id)).or_insert_with(|| {
+                        self.next_id += 1;
+                        self.next_id - 1
+                    });
+                    gamepads.insert(id, GamepadState { name, ..Default::default() });
+                    self.axes.insert(id, RawAxes::default());
+                    events.push(Entity::new().with(event_gamepad_connected(), id));
+                }
+                GamepadEvent::Disconnected { id } => {
+                    if let Some(id) = self.ids.remove(&(source, id)) {
+                        gamepads.remove(&id);
+                        self.axes.remove(&id);
+                        events.push(Entity::new().with(event_gamepad_disconnected(), id));
+                    }
+                }
+                GamepadEvent::Button { id, button, pressed } => {
+                    if let Some(state) = self.ids.get(&(source, id)).and_then(|id| gamepads.get_mut(id)) {
+                        if pressed {
+                            state.buttons.insert(button);
+                        } else {
+                            state.buttons.remove(&button);
+                        }
+                    }
+                }
+                GamepadEvent::Axis { id, axis, value } => {
+                    let id = match self.ids.get(&(source, id)) {
+                        Some(id) => *id,
+                        None => return,
+                    };
+                    let (state, raw) = match (gamepads.get_mut(&id), self.axes.get_mut(&id)) {
+                        (Some(state), Some(raw)) => (state, raw),
+                        _ => return,
+                    };
+                    match axis {
+                        GamepadAxis::LeftStickX => raw.left_stick.x = value,
+                        GamepadAxis::LeftStickY => raw.left_stick.y = value,
+                        GamepadAxis::RightStickX => raw.right_stick.x = value,
+                        GamepadAxis::RightStickY => raw.right_stick.y = value,
+                        GamepadAxis::LeftTrigger => raw.left_trigger = value,
+                        GamepadAxis::RightTrigger => raw.right_trigger = value,
+                    }
+                    state.left_stick = apply_stick_dead_zone(raw.left_stick, dead_zone);
+                    state.right_stick = apply_stick_dead_zone(raw.right_stick, dead_zone);
+                    state.left_trigger = apply_dead_zone(raw.left_trigger, dead_zone);
+                    state.right_trigger = apply_dead_zone(raw.right_trigger, dead_zone);
+                }
+            }
+        }
+        world.resource_mut(world_events()).add_events(events);
+    }
+}
+impl Default for GamepadSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl System<Event<'static, ()>> for GamepadSystem {
+    fn run(&mut self, world: &mut World, event: &Event<'static, ()>) {
+        if !matches!(event, Event::MainEventsCleared) {
+            return;
+        }
+        if world.resource_opt(gamepads()).is_none() {
+            world.add_resource(gamepads(), BTreeMap::new());
+        }
+
+        let mut polled = Vec::new();
+        for (index, source) in self.sources.iter_mut().enumerate() {
+            let mut events = Vec::new();
+            source.poll(&mut events);
+            polled.extend(events.into_iter().map(|event| (index, event)));
+        }
+        if let Some(mut virtual_gamepads) = world.resource_opt(virtual_gamepads()).cloned() {
+            let mut events = Vec::new();
+            virtual_gamepads.poll(&mut events);
+            polled.extend(events.into_iter().map(|event| (self.sources.len(), event)));
+        }
+        for (source, event) in polled {
+            self.handle_event(world, source, event);
+        }
+    }
+}
+
+/// Scales `value` so that it's 0 inside the dead zone, and goes smoothly to 1 outside of it
+pub fn apply_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    if value.abs() <= dead_zone {
+        0.
+    } else {
+        value.signum() * ((value.abs() - dead_zone) / (1. - dead_zone)).min(1.)
+    }
+}
+
+/// Like [apply_dead_zone], but for the distance of a stick from its center, so that the dead zone is round
+pub fn apply_stick_dead_zone(value: Vec2, dead_zone: f32) -> Vec2 {
+    let length = value.length();
+    if length <= dead_zone {
+        Vec2::ZERO
+    } else {
+        value / length * apply_dead_zone(length, dead_zone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn virtual_gamepads_and_dead_zone() {
+        ambient_ecs::init_components();
+        crate::init_all_components();
+        let mut world = World::new("gamepad_test");
+        world.add_resource(world_events(), Default::default());
+        let pads = VirtualGamepads::new();
+        world.add_resource(virtual_gamepads(), pads.clone());
+        let mut system = GamepadSystem::with_sources(Vec::new());
+
+        pads.connect(7, "First");
+        pads.connect(3, "Second");
+        pads.set_button(7, GamepadButton::South, true);
+        pads.set_axis(7, GamepadAxis::LeftStickX, 0.1);
+        pads.set_axis(7, GamepadAxis::RightStickY, -1.);
+        pads.set_axis(7, GamepadAxis::RightTrigger, 1.);
+        system.run(&mut world, &Event::MainEventsCleared);
+
+        let state = world.resource(gamepads());
+        assert_eq!(state.len(), 2);
+        assert_eq!(state[&0].name, "First");
+        assert_eq!(state[&1].name, "Second");
+        assert!(state[&0].buttons.contains(&GamepadButton::South));
+        assert_eq!(state[&0].left_stick, Vec2::ZERO);
+        assert_eq!(state[&0].right_stick, Vec2::new(0., -1.));
+        assert_eq!(state[&0].right_trigger, 1.);
+
+        pads.disconnect(7);
+        system.run(&mut world, &Event::MainEventsCleared);
+        assert_eq!(world.resource(gamepads()).keys().copied().collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn dead_zone() {
+        assert_eq!(apply_dead_zone(0.1, 0.2), 0.);
+        assert_eq!(apply_dead_zone(-1., 0.2), -1.);
+        assert!((apply_dead_zone(0.6, 0.2) - 0.5).abs() < 1e-6);
+        assert!((apply_stick_dead_zone(Vec2::new(0.6, 0.), 0.2) - Vec2::new(0.5, 0.)).length() < 1e-6);
+    }
+}
@@ -1,11 +1,14 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 use ambient_ecs::{components, world_events, Debuggable, Description, Entity, Name, Networked, Store, System, SystemGroup};
+use gamepad::{GamepadState, GamepadSystem};
 use glam::{vec2, Vec2};
 use serde::{Deserialize, Serialize};
 pub use winit::event::{DeviceEvent, ElementState, Event, KeyboardInput, MouseButton, MouseScrollDelta, VirtualKeyCode, WindowEvent};
 use winit::event::{ModifiersState, ScanCode};
 
+pub mod actions;
+pub mod gamepad;
 pub mod picking;
 
 #[derive(Debug, Clone)]
@@ -26,6 +29,8 @@ pub struct PlayerRawInput {
     pub cursor_position: Vec2,
     pub mouse_wheel: f32,
     pub mouse_buttons: HashSet<MouseButton>,
+    /// The connected gamepads, by id
+    pub gamepads: BTreeMap<u32, GamepadState>,
 }
 
 components!("input", {
@@ -54,11 +59,13 @@ components!("input", {
 
 pub fn init_all_components() {
     picking::init_components();
+    actions::init_components();
+    gamepad::init_components();
     init_components();
 }
 
 pub fn event_systems() -> SystemGroup<Event<'static, ()>> {
-    SystemGroup::new("inputs", vec![Box::new(InputSystem::new())])
+    SystemGroup::new("inputs", vec![Box::new(InputSystem::new()), Box::new(GamepadSystem::new())])
 }
 
 #[derive(Debug)]
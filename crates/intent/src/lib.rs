@@ -11,10 +11,11 @@ use ambient_element::{Element, ElementComponent, ElementComponentExt, Hooks};
 use ambient_network::{
     client::{GameClient, GameRpcArgs},
     hooks::{use_remote_component, use_remote_world_system},
+    permissions::Role,
     server::SharedServerState,
     unwrap_log_network_err,
 };
-use ambient_rpc::RpcRegistry;
+use ambient_rpc::{RpcRegistry, RpcRequirement};
 use ambient_ui::{FlowColumn, StylesExt, Text};
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
@@ -119,11 +120,32 @@ pub async fn rpc_redo(args: GameRpcArgs, _: ()) -> Option<()> {
     Some(())
 }
 
+/// Checks that the player has the role the intent requires, see [IntentRegistry::set_required_role]
+pub struct IntentRequirement;
+impl RpcRequirement<GameRpcArgs, Entity> for IntentRequirement {
+    fn check(&self, args: &GameRpcArgs, data: &Entity) -> Result<(), String> {
+        let intent = data.get(intent()).ok_or_else(|| "Missing intent kind for intent".to_string())?;
+        let state = args.state.lock();
+        let role = state.player_role(&args.user_id).ok_or_else(|| format!("No such player: {}", args.user_id))?;
+        let registry = state
+            .get_player_world(&args.user_id)
+            .and_then(|world| world.resource_opt(intent_registry()))
+            .ok_or_else(|| "No intent registry".to_string())?;
+        let required = registry.required_role(intent);
+        if role < required {
+            let name = registry.get_intent_name(intent).unwrap_or_else(|| intent.to_string());
+            return Err(format!("The intent {name} requires the {required} role, but {} has the {role} role", args.user_id));
+        }
+        Ok(())
+    }
+}
+
 pub fn register_rpcs(reg: &mut RpcRegistry<GameRpcArgs>) {
-    reg.register(rpc_push_intent);
-    reg.register(rpc_undo_head);
-    reg.register(rpc_undo_head_exact);
-    reg.register(rpc_redo);
+    ambient_network::rpc::register_ecs_schemas(reg);
+    reg.register_with("intent::push_intent", 1, IntentRequirement, rpc_push_intent);
+    reg.register_with("intent::undo_head", 1, Role::Editor, rpc_undo_head);
+    reg.register_with("intent::undo_head_exact", 1, Role::Editor, rpc_undo_head_exact);
+    reg.register_with("intent::redo", 1, Role::Editor, rpc_redo);
 }
 
 pub fn common_intent_systems() -> SystemGroup {
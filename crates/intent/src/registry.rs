@@ -3,6 +3,7 @@ use std::{collections::HashMap, fmt::Debug, marker::Send};
 use ambient_ecs::{ArchetypeFilter, Component, ComponentValue, Entity, EntityId, IndexExt, SystemGroup, World};
 use ambient_network::{
     assert_networked,
+    permissions::Role,
     server::{ServerState, SharedServerState},
 };
 use futures::Future;
@@ -208,6 +209,7 @@ where
 
 pub struct IntentRegistry {
     handlers: HashMap<u32, Box<dyn for<'x> Handler<'x>>>,
+    required_roles: HashMap<u32, Role>,
 }
 
 impl Debug for IntentRegistry {
@@ -223,7 +225,7 @@ impl Debug for IntentRegistry {
 
 impl IntentRegistry {
     pub fn new() -> Self {
-        Self { handlers: HashMap::new() }
+        Self { handlers: HashMap::new(), required_roles: HashMap::new() }
     }
 
     /// Register a new intent.
@@ -273,6 +275,15 @@ impl IntentRegistry {
         self.handlers.insert(intent.index(), Box::new(handler));
     }
 
+    /// Sets the role players need to push this intent from the client. By default, intents require the [Role::Editor] role.
+    pub fn set_required_role<Arg: ComponentValue>(&mut self, intent: Component<Arg>, role: Role) {
+        self.required_roles.insert(intent.index(), role);
+    }
+
+    pub fn required_role(&self, intent: u32) -> Role {
+        self.required_roles.get(&intent).copied().unwrap_or(Role::Editor)
+    }
+
     pub fn get_intent_name(&self, intent: u32) -> Option<String> {
         Some(self.handlers.get(&intent)?.name().to_string())
     }
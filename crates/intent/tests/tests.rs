@@ -4,9 +4,14 @@ use ambient_ecs::{components, query, query_mut, Entity, EntityId, FrameEvent, Ne
 use ambient_intent::{
     common_intent_systems, intent_registry,
     logic::{create_intent, push_intent, redo_intent, undo_head},
-    use_old_state, IntentRegistry,
+    use_old_state, IntentRegistry, IntentRequirement,
 };
-use ambient_network::server::{Player, ServerState, SharedServerState, MAIN_INSTANCE_ID};
+use ambient_network::{
+    client::GameRpcArgs,
+    permissions::Role,
+    server::{Player, ServerState, SharedServerState, MAIN_INSTANCE_ID},
+};
+use ambient_rpc::RpcRequirement;
 use ambient_std::friendly_id;
 use anyhow::bail;
 use itertools::Itertools;
@@ -594,3 +599,33 @@ async fn collapse() {
         assert!(world.exists(y));
     }
 }
+
+#[tokio::test]
+async fn required_roles() {
+    init_components();
+    ambient_intent::init_components();
+
+    let state = setup_state();
+    let user_id = "user1".to_string();
+
+    let mut reg = IntentRegistry::new();
+    register_intents(&mut reg);
+    reg.set_required_role(intent_mul(), Role::Player);
+    {
+        let mut guard = state.lock();
+        guard.default_role = Role::Player;
+        guard.get_player_world_mut(&user_id).unwrap().add_resource(intent_registry(), Arc::new(reg));
+    }
+
+    let args = GameRpcArgs { state: state.clone(), user_id: user_id.clone() };
+    let add = create_intent(intent_add(), 2.0, None);
+    let mul = create_intent(intent_mul(), 2.0, None);
+    assert!(IntentRequirement.check(&args, &add).is_err());
+    assert!(IntentRequirement.check(&args, &mul).is_ok());
+
+    state.lock().set_role(&user_id, Role::Editor);
+    assert!(IntentRequirement.check(&args, &add).is_ok());
+
+    let stranger = GameRpcArgs { state, user_id: "user2".to_string() };
+    assert!(IntentRequirement.check(&stranger, &mul).is_err());
+}
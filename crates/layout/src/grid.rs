@@ -0,0 +1,315 @@
+use std::{collections::HashSet, fmt::Display, str::FromStr};
+
+use ambient_core::transform::{local_to_parent, translation};
+use ambient_ecs::{Component, EntityId, World};
+use glam::{vec2, Vec2};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    align_horizontal, align_vertical, fit_horizontal, fit_vertical, grid_column, grid_column_gap, grid_column_span, grid_columns, grid_row,
+    grid_row_gap, grid_row_span, grid_rows, height, invalidate_parent_layout, margin, padding, width, Align, Borders, Fit, Orientation,
+    Z_DELTA,
+};
+
+/// The size of a row or column of a [crate::Layout::Grid]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GridTrack {
+    /// A fixed size, in pixels
+    Fixed(f32),
+    /// A share of the space left after the other tracks; a `Fraction(2.)` track is twice as large as a `Fraction(1.)` track
+    Fraction(f32),
+    /// Fits the largest item in the track
+    Auto,
+}
+impl GridTrack {
+    /// Parses a list of tracks separated by whitespace, e.g. `100 1fr 2fr auto`
+    pub fn parse_list(tracks: &str) -> Result<Vec<GridTrack>, String> {
+        tracks.split_whitespace().map(|track| track.parse()).collect()
+    }
+}
+impl FromStr for GridTrack {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = |n: &str| n.parse::<f32>().ok().filter(|n| n.is_finite() && *n >= 0.);
+        if s == "auto" {
+            Ok(GridTrack::Auto)
+        } else if let Some(fraction) = s.strip_suffix("fr").and_then(number) {
+            Ok(GridTrack::Fraction(fraction))
+        } else if let Some(size) = s.strip_suffix("px").or(Some(s)).and_then(number) {
+            Ok(GridTrack::Fixed(size))
+        } else {
+            Err(format!("Invalid grid track {s:?}, expected a size like 100, a fraction like 1fr, or auto"))
+        }
+    }
+}
+impl Display for GridTrack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridTrack::Fixed(size) => write!(f, "{size}"),
+            GridTrack::Fraction(fraction) => write!(f, "{fraction}fr"),
+            GridTrack::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+/// Rows are added as needed, so the rows and row spans of the children are clamped to keep the grid to a sane size
+const MAX_GRID_ROWS: usize = 1000;
+
+/// A child of the grid and the cells it covers
+struct GridItem {
+    id: EntityId,
+    column: usize,
+    row: usize,
+    column_span: usize,
+    row_span: usize,
+    margin: Borders,
+    size: Vec2,
+    fit_horizontal: Fit,
+    fit_vertical: Fit,
+}
+
+/// Places the children in cells, row by row. Children with a [grid_column] and [grid_row] are placed there, and the
+/// others in the next cells which are free
+fn place_items(world: &World, children: &[EntityId], column_count: usize) -> Vec<GridItem> {
+    fn take(taken: &mut HashSet<(usize, usize)>, item: &GridItem) {
+        for column in item.column..item.column + item.column_span {
+            for row in item.row..item.row + item.row_span {
+                taken.insert((column, row));
+            }
+        }
+    }
+    let span = |id: EntityId, component: Component<u32>| (world.get(id, component).unwrap_or(1) as usize).max(1);
+    let mut taken = HashSet::new();
+    let mut items = Vec::new();
+    let mut auto_placed = Vec::new();
+    for &id in children {
+        let column_span = span(id, grid_column_span()).min(column_count);
+        let row_span = span(id, grid_row_span()).min(MAX_GRID_ROWS);
+        let item = GridItem {
+            id,
+            column: 0,
+            row: 0,
+            column_span,
+            row_span,
+            margin: world.get(id, margin()).unwrap_or(Borders::ZERO),
+            size: vec2(world.get(id, width()).unwrap_or(0.), world.get(id, height()).unwrap_or(0.)),
+            fit_horizontal: world.get(id, fit_horizontal()).unwrap_or(Fit::None),
+            fit_vertical: world.get(id, fit_vertical()).unwrap_or(Fit::None),
+        };
+        match (world.get(id, grid_column()), world.get(id, grid_row())) {
+            (Ok(column), Ok(row)) => {
+                let item = GridItem {
+                    column: (column as usize).min(column_count - column_span),
+                    row: (row as usize).min(MAX_GRID_ROWS - row_span),
+                    ..item
+                };
+                take(&mut taken, &item);
+                items.push(item);
+            }
+            _ => auto_placed.push(item),
+        }
+    }
+    let mut cursor = 0;
+    for item in auto_placed {
+        let fits = |taken: &HashSet<(usize, usize)>, cell: usize| {
+            let (column, row) = (cell % column_count, cell / column_count);
+            column + item.column_span <= column_count
+                && (column..column + item.column_span).all(|c| (row..row + item.row_span).all(|r| !taken.contains(&(c, r))))
+        };
+        while !fits(&taken, cursor) {
+            cursor += 1;
+        }
+        let item = GridItem { column: cursor % column_count, row: cursor / column_count, ..item };
+        take(&mut taken, &item);
+        items.push(item);
+    }
+    items
+}
+
+/// The sizes of the tracks along one axis. `available` is None when the grid fits its children, in which case
+/// fractions are sized like [GridTrack::Auto]
+fn track_sizes(tracks: &[GridTrack], content: &[f32], gap: f32, available: Option<f32>) -> Vec<f32> {
+    let mut sizes = tracks
+        .iter()
+        .zip(content)
+        .map(|(track, &content)| match track {
+            GridTrack::Fixed(size) => *size,
+            GridTrack::Auto => content,
+            GridTrack::Fraction(_) => 0.,
+        })
+        .collect_vec();
+    let total_fraction: f32 = tracks.iter().map(|track| if let GridTrack::Fraction(fraction) = track { *fraction } else { 0. }).sum();
+    if total_fraction > 0. {
+        let used = sizes.iter().sum::<f32>() + gap * tracks.len().saturating_sub(1) as f32;
+        for ((size, track), &content) in sizes.iter_mut().zip(tracks).zip(content) {
+            if let GridTrack::Fraction(fraction) = track {
+                *size = match available {
+                    Some(available) => ((available - used).max(0.) * fraction / total_fraction).floor(),
+                    None => content,
+                };
+            }
+        }
+    }
+    sizes
+}
+
+/// Where a track starts, and the size of `span` tracks from it including the gaps between them
+fn track_area(sizes: &[f32], gap: f32, start: usize, span: usize) -> (f32, f32) {
+    let offset = sizes[..start].iter().map(|size| size + gap).sum();
+    let size = sizes[start..start + span].iter().sum::<f32>() + gap * (span - 1) as f32;
+    (offset, size)
+}
+
+fn align_in_cell(align: Align, cell_size: f32, size: f32) -> f32 {
+    match align {
+        Align::Begin => 0.,
+        Align::Center => ((cell_size - size) / 2.).floor(),
+        Align::End => cell_size - size,
+    }
+}
+
+pub(crate) fn grid_layout(world: &mut World, id: EntityId, children: Vec<EntityId>) {
+    let mut columns = world.get_cloned(id, grid_columns()).unwrap_or_default();
+    if columns.is_empty() {
+        columns.push(GridTrack::Fraction(1.));
+    }
+    let mut rows = world.get_cloned(id, grid_rows()).unwrap_or_default();
+    let column_gap = world.get(id, grid_column_gap()).unwrap_or(0.);
+    let row_gap = world.get(id, grid_row_gap()).unwrap_or(0.);
+    let self_padding = world.get(id, padding()).unwrap_or(Borders::ZERO);
+    let self_size = vec2(world.get(id, width()).unwrap_or(0.), world.get(id, height()).unwrap_or(0.));
+    let self_fit_horizontal = world.get(id, fit_horizontal()).unwrap_or(Fit::None);
+    let self_fit_vertical = world.get(id, fit_vertical()).unwrap_or(Fit::None);
+    let align_horizontal = world.get(id, align_horizontal()).unwrap_or(Align::Begin);
+    let align_vertical = world.get(id, align_vertical()).unwrap_or(Align::Begin);
+
+    let children = children.iter().filter(|id| world.has_component(**id, local_to_parent())).copied().collect_vec();
+    let items = place_items(world, &children, columns.len());
+    // Rows which aren't in the template fit their items
+    let row_count = items.iter().map(|item| item.row + item.row_span).max().unwrap_or(0);
+    if rows.len() < row_count {
+        rows.resize(row_count, GridTrack::Auto);
+    }
+
+    // Only the items which span a single track, and don't take their size from it, are used to size the tracks
+    let mut column_content = vec![0.; columns.len()];
+    let mut row_content = vec![0.; rows.len()];
+    for item in &items {
+        if item.column_span == 1 && item.fit_horizontal != Fit::Parent {
+            column_content[item.column] = f32::max(column_content[item.column], item.size.x + item.margin.get_horizontal());
+        }
+        if item.row_span == 1 && item.fit_vertical != Fit::Parent {
+            row_content[item.row] = f32::max(row_content[item.row], item.size.y + item.margin.get_vertical());
+        }
+    }
+    let available = |fit: Fit, size: f32, padding: f32| if fit == Fit::Children { None } else { Some(size - padding) };
+    let column_sizes =
+        track_sizes(&columns, &column_content, column_gap, available(self_fit_horizontal, self_size.x, self_padding.get_horizontal()));
+    let row_sizes = track_sizes(&rows, &row_content, row_gap, available(self_fit_vertical, self_size.y, self_padding.get_vertical()));
+
+    for item in &items {
+        let (x, cell_width) = track_area(&column_sizes, column_gap, item.column, item.column_span);
+        let (y, cell_height) = track_area(&row_sizes, row_gap, item.row, item.row_span);
+        let cell_width = cell_width - item.margin.get_horizontal();
+        let cell_height = cell_height - item.margin.get_vertical();
+        let child_width = if item.fit_horizontal == Fit::Parent {
+            world.set_if_changed(item.id, width(), cell_width).ok();
+            cell_width
+        } else {
+            item.size.x
+        };
+        let child_height = if item.fit_vertical == Fit::Parent {
+            world.set_if_changed(item.id, height(), cell_height).ok();
+            cell_height
+        } else {
+            item.size.y
+        };
+        let position = self_padding.offset()
+            + item.margin.offset()
+            + vec2(
+                x + align_in_cell(align_horizontal, cell_width, child_width),
+                y + align_in_cell(align_vertical, cell_height, child_height),
+            );
+        world.set_if_changed(item.id, translation(), position.extend(Z_DELTA)).ok();
+    }
+
+    let gaps = |sizes: &[f32], gap: f32| sizes.iter().sum::<f32>() + gap * sizes.len().saturating_sub(1) as f32;
+    let new_self_width = gaps(&column_sizes, column_gap) + self_padding.get_horizontal();
+    let new_self_height = gaps(&row_sizes, row_gap) + self_padding.get_vertical();
+    if self_fit_horizontal == Fit::Children && self_size.x != new_self_width {
+        world.set(id, width(), new_self_width).ok();
+        invalidate_parent_layout(world, id, Orientation::Horizontal);
+    }
+    if self_fit_vertical == Fit::Children && self_size.y != new_self_height {
+        world.set(id, height(), new_self_height).ok();
+        invalidate_parent_layout(world, id, Orientation::Vertical);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tracks() {
+        assert_eq!(
+            GridTrack::parse_list("100 1fr  2.5fr auto 20px").unwrap(),
+            vec![GridTrack::Fixed(100.), GridTrack::Fraction(1.), GridTrack::Fraction(2.5), GridTrack::Auto, GridTrack::Fixed(20.)]
+        );
+        assert!(GridTrack::parse_list("1fr wide").is_err());
+        assert!(GridTrack::parse_list("-10").is_err());
+    }
+
+    #[test]
+    fn fractions_share_remaining_space() {
+        let tracks = [GridTrack::Fixed(100.), GridTrack::Fraction(1.), GridTrack::Fraction(3.), GridTrack::Auto];
+        let sizes = track_sizes(&tracks, &[0., 0., 0., 50.], 10., Some(530.));
+        assert_eq!(sizes, vec![100., 87., 262., 50.]);
+    }
+
+    #[test]
+    fn spans_and_auto_placement() {
+        ambient_core::init_all_components();
+        crate::init_all_components();
+        let mut world = World::new("spans_and_auto_placement");
+        let mut spawn = |components: &[(Component<u32>, u32)]| {
+            let id = world.spawn(Default::default());
+            for &(component, value) in components {
+                world.add_component(id, component, value).unwrap();
+            }
+            id
+        };
+        let a = spawn(&[(grid_column_span(), 2)]);
+        let b = spawn(&[(grid_column(), 2), (grid_row(), 0)]);
+        let c = spawn(&[]);
+        let d = spawn(&[(grid_column_span(), 3)]);
+        let f = spawn(&[(grid_row_span(), 2)]);
+        let g = spawn(&[]);
+        let clamped_column = spawn(&[(grid_column(), 5), (grid_row(), 5)]);
+        let clamped_span = spawn(&[(grid_column_span(), 5)]);
+
+        let huge_row = spawn(&[(grid_column(), 1), (grid_row(), u32::MAX)]);
+        let huge_row_span = spawn(&[(grid_row_span(), u32::MAX)]);
+
+        let items = place_items(&world, &[a, b, c, d, f, g, clamped_column, clamped_span], 3);
+        let cell = |items: &[GridItem], id: EntityId| {
+            let item = items.iter().find(|item| item.id == id).unwrap();
+            (item.column, item.row, item.column_span, item.row_span)
+        };
+        assert_eq!(cell(&items, a), (0, 0, 2, 1));
+        assert_eq!(cell(&items, b), (2, 0, 1, 1));
+        assert_eq!(cell(&items, c), (0, 1, 1, 1));
+        // Doesn't fit next to c, so it wraps onto the next row
+        assert_eq!(cell(&items, d), (0, 2, 3, 1));
+        assert_eq!(cell(&items, f), (0, 3, 1, 2));
+        assert_eq!(cell(&items, g), (1, 3, 1, 1));
+        assert_eq!(cell(&items, clamped_column), (2, 5, 1, 1));
+        assert_eq!(cell(&items, clamped_span), (0, 6, 3, 1));
+
+        let items = place_items(&world, &[huge_row, huge_row_span], 3);
+        assert_eq!(cell(&items, huge_row), (1, MAX_GRID_ROWS - 1, 1, 1));
+        assert_eq!(cell(&items, huge_row_span), (0, 0, 1, MAX_GRID_ROWS));
+    }
+}
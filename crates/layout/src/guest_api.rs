@@ -1,5 +1,6 @@
 use crate::{
-    align_horizontal, align_vertical, docking, fit_horizontal, fit_vertical, layout, margin, orientation, padding, Borders, Docking, Layout,
+    align_horizontal, align_vertical, docking, fit_horizontal, fit_vertical, grid_columns, grid_rows, layout, margin, orientation, padding,
+    Borders, Docking, GridTrack, Layout,
 };
 use ambient_ecs::{components, ensure_has_component, query, Debuggable, Description, Name, Networked, Store, SystemGroup};
 
@@ -13,6 +14,15 @@ components!("ui", {
     layout_bookcase: (),
     @[Debuggable, Networked, Store, Name["Layout width to children"], Description["Width to children."]]
     layout_width_to_children: (),
+    @[Debuggable, Networked, Store, Name["Grid layout"], Description["Grid layout, with the children placed in the cells of the rows and columns."]]
+    layout_grid: (),
+    @[Debuggable, Networked, Store, Name["Wrap layout"], Description["Flow layout which wraps onto new lines, with the children growing or shrinking to fill each line."]]
+    layout_wrap: (),
+
+    @[Debuggable, Networked, Store, Name["Grid template columns"], Description["The columns of a layout_grid, separated by spaces: a size in pixels like `100`, a fraction of the remaining space like `1fr`, or `auto` to fit the items."]]
+    grid_template_columns: String,
+    @[Debuggable, Networked, Store, Name["Grid template rows"], Description["The rows of a layout_grid, in the same format as grid_template_columns. Rows past these fit their items."]]
+    grid_template_rows: String,
 
     @[Debuggable, Networked, Store, Name["Orientation horizontal"], Description["Layout orientation."]]
     orientation_horizontal: (),
@@ -83,6 +93,8 @@ pub fn systems() -> SystemGroup {
             ensure_has_component(layout_dock(), layout(), Layout::Dock),
             ensure_has_component(layout_bookcase(), layout(), Layout::Bookcase),
             ensure_has_component(layout_width_to_children(), layout(), Layout::WidthToChildren),
+            ensure_has_component(layout_grid(), layout(), Layout::Grid),
+            ensure_has_component(layout_wrap(), layout(), Layout::Wrap),
             ensure_has_component(orientation_horizontal(), orientation(), crate::Orientation::Horizontal),
             ensure_has_component(orientation_vertical(), orientation(), crate::Orientation::Vertical),
             ensure_has_component(align_horizontal_begin(), align_horizontal(), crate::Align::Begin),
@@ -116,6 +128,22 @@ pub fn systems() -> SystemGroup {
                     }
                 },
             ),
+            query(grid_template_columns().changed()).to_system(|q, world, qs, _| {
+                for (id, template) in q.collect_cloned(world, qs) {
+                    match GridTrack::parse_list(&template) {
+                        Ok(tracks) => world.add_component(id, grid_columns(), tracks).unwrap(),
+                        Err(err) => log::warn!("Invalid grid_template_columns {template:?}: {err}"),
+                    }
+                }
+            }),
+            query(grid_template_rows().changed()).to_system(|q, world, qs, _| {
+                for (id, template) in q.collect_cloned(world, qs) {
+                    match GridTrack::parse_list(&template) {
+                        Ok(tracks) => world.add_component(id, grid_rows(), tracks).unwrap(),
+                        Err(err) => log::warn!("Invalid grid_template_rows {template:?}: {err}"),
+                    }
+                }
+            }),
         ],
     )
 }
@@ -4,13 +4,20 @@ use ambient_core::{
     hierarchy::{children, parent},
     transform::{local_to_parent, mesh_to_local, translation},
 };
-use ambient_ecs::{components, query, query_mut, Debuggable, Description, DynSystem, EntityId, Name, Networked, Store, SystemGroup, World};
+use ambient_ecs::{
+    components, query, query_mut, Component, ComponentValue, Debuggable, Description, DynSystem, EntityId, Name, Networked, Store,
+    SystemGroup, World,
+};
 use ambient_input::picking::mouse_pickable;
 use glam::{vec2, vec3, vec4, Mat4, Vec2, Vec4};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
+mod grid;
 pub mod guest_api;
+mod wrap;
+
+pub use grid::GridTrack;
 
 components!("ui", {
     @[Debuggable, Networked, Store, Name["Layout"], Description["The layout to apply to this entity's children."]]
@@ -39,6 +46,28 @@ components!("ui", {
     is_book_file: (),
     @[Debuggable, Networked, Store, Name["Gpu ui size"], Description["Upload the width and height of this to the gpu."]]
     gpu_ui_size: Vec4,
+    @[Debuggable, Networked, Store, Name["Grid columns"], Description["The columns of a layout_grid."]]
+    grid_columns: Vec<GridTrack>,
+    @[Debuggable, Networked, Store, Name["Grid rows"], Description["The rows of a layout_grid. Rows past these fit their items."]]
+    grid_rows: Vec<GridTrack>,
+    @[Debuggable, Networked, Store, Name["Grid column gap"], Description["Space between the columns of a layout_grid."]]
+    grid_column_gap: f32,
+    @[Debuggable, Networked, Store, Name["Grid row gap"], Description["Space between the rows of a layout_grid."]]
+    grid_row_gap: f32,
+    @[Debuggable, Networked, Store, Name["Grid column"], Description["The column of this item in a layout_grid, starting at 0. Items without a column and row fill the free cells in order."]]
+    grid_column: u32,
+    @[Debuggable, Networked, Store, Name["Grid row"], Description["The row of this item in a layout_grid, starting at 0."]]
+    grid_row: u32,
+    @[Debuggable, Networked, Store, Name["Grid column span"], Description["The number of columns this item covers in a layout_grid."]]
+    grid_column_span: u32,
+    @[Debuggable, Networked, Store, Name["Grid row span"], Description["The number of rows this item covers in a layout_grid."]]
+    grid_row_span: u32,
+    @[Debuggable, Networked, Store, Name["Flex grow"], Description["How much of the free space of its line this item takes in a layout_wrap, relative to the other items."]]
+    flex_grow: f32,
+    @[Debuggable, Networked, Store, Name["Flex shrink"], Description["How much this item shrinks when its line overflows in a layout_wrap, relative to the other items."]]
+    flex_shrink: f32,
+    @[Debuggable, Networked, Store, Name["Flex basis"], Description["The size of this item in a layout_wrap before growing or shrinking. Items which grow default to their minimum size, and the others to their size."]]
+    flex_basis: f32,
 });
 gpu_components! {
     gpu_ui_size() => ui_size: GpuComponentFormat::Vec4,
@@ -157,6 +186,10 @@ pub enum Layout {
     Bookcase,
     /// Just copy the width of this component to it's children. Used for the ScrollArea
     WidthToChildren,
+    /// Places the children in the cells of [grid_columns] and [grid_rows]
+    Grid,
+    /// Like [Layout::Flow], with the children growing or shrinking to fill each line
+    Wrap,
 }
 
 pub fn layout_systems() -> SystemGroup {
@@ -175,6 +208,17 @@ pub fn layout_systems() -> SystemGroup {
                     invalidate_parent_layout(world, id, Orientation::Vertical);
                 }
             }),
+            relayout_on_change(grid_columns(), false),
+            relayout_on_change(grid_rows(), false),
+            relayout_on_change(grid_column_gap(), false),
+            relayout_on_change(grid_row_gap(), false),
+            relayout_on_change(grid_column(), true),
+            relayout_on_change(grid_row(), true),
+            relayout_on_change(grid_column_span(), true),
+            relayout_on_change(grid_row_span(), true),
+            relayout_on_change(flex_grow(), true),
+            relayout_on_change(flex_shrink(), true),
+            relayout_on_change(flex_basis(), true),
             query((width().changed(), height().changed(), children().changed(), layout().changed())).optional_changed(parent()).to_system(
                 |q, world, qs, _| {
                     let qs = qs.unwrap();
@@ -196,6 +240,12 @@ pub fn layout_systems() -> SystemGroup {
                                 Layout::WidthToChildren => {
                                     width_to_children(world, id, children);
                                 }
+                                Layout::Grid => {
+                                    grid::grid_layout(world, id, children);
+                                }
+                                Layout::Wrap => {
+                                    wrap::wrap_layout(world, id, children);
+                                }
                             }
                         }
                         if !changed {
@@ -531,6 +581,18 @@ fn invalidate_parent_layout(world: &mut World, id: EntityId, orientation: Orient
     }
 }
 
+/// Lays out the entity again, or its parent if `of_parent`, when `component` changes
+fn relayout_on_change<T: ComponentValue>(component: Component<T>, of_parent: bool) -> DynSystem {
+    query((component.changed(),)).to_system(move |q, world, qs, _| {
+        for id in q.collect_ids(world, qs) {
+            let id = if of_parent { world.get(id, parent()).ok() } else { Some(id) };
+            if let Some(id) = id {
+                world.get_mut(id, layout()).ok();
+            }
+        }
+    })
+}
+
 fn node_clickable_system() -> DynSystem {
     query_mut((mouse_pickable(),), (width().changed(), height().changed())).to_system(|q, world, qs, _| {
         for (_, (pickable,), (&width, &height)) in q.iter(world, qs) {
@@ -0,0 +1,225 @@
+use ambient_core::transform::{local_to_parent, translation};
+use ambient_ecs::{EntityId, World};
+use glam::{vec2, Vec2};
+use itertools::Itertools;
+
+use crate::{
+    align_horizontal, align_vertical, fit_horizontal, fit_vertical, flex_basis, flex_grow, flex_shrink, height, invalidate_parent_layout,
+    margin, min_height, min_width, orientation, padding, space_between_items, width, Align, Borders, Fit, Orientation, Z_DELTA,
+};
+
+/// A child of the wrap layout. The sizes along the main and cross axes include the margins
+struct WrapItem {
+    id: EntityId,
+    margin: Borders,
+    basis: f32,
+    cross: f32,
+    grow: f32,
+    shrink: f32,
+}
+
+pub(crate) fn wrap_layout(world: &mut World, id: EntityId, children: Vec<EntityId>) {
+    let orientation = world.get(id, orientation()).unwrap_or(Orientation::Horizontal);
+    // Index of the main axis in the Vec2s
+    let (main, cross) = match orientation {
+        Orientation::Horizontal => (0, 1),
+        Orientation::Vertical => (1, 0),
+    };
+    let (main_size, cross_size, main_min_size, fit_main, fit_cross) = match orientation {
+        Orientation::Horizontal => (width(), height(), min_width(), fit_horizontal(), fit_vertical()),
+        Orientation::Vertical => (height(), width(), min_height(), fit_vertical(), fit_horizontal()),
+    };
+    let space_between_items = world.get(id, space_between_items()).unwrap_or(0.);
+    let self_padding = world.get(id, padding()).unwrap_or(Borders::ZERO);
+    let self_size = vec2(world.get(id, width()).unwrap_or(0.), world.get(id, height()).unwrap_or(0.));
+    let self_fit = (world.get(id, fit_main).unwrap_or(Fit::None), world.get(id, fit_cross).unwrap_or(Fit::None));
+    let inner_size = self_size - self_padding.border_size();
+    let line_length = if self_fit.0 == Fit::Children { f32::INFINITY } else { inner_size[main] };
+    let main_align = match orientation {
+        Orientation::Horizontal => world.get(id, align_horizontal()).unwrap_or(Align::Begin),
+        Orientation::Vertical => world.get(id, align_vertical()).unwrap_or(Align::Begin),
+    };
+    let cross_align = match orientation {
+        Orientation::Horizontal => world.get(id, align_vertical()).unwrap_or(Align::Begin),
+        Orientation::Vertical => world.get(id, align_horizontal()).unwrap_or(Align::Begin),
+    };
+
+    let children = children.iter().filter(|id| world.has_component(**id, local_to_parent())).copied().collect_vec();
+    let items = children
+        .iter()
+        .map(|&c| {
+            let margin = world.get(c, margin()).unwrap_or(Borders::ZERO);
+            let margin_size = margin.border_size();
+            let grow = world.get(c, flex_grow()).unwrap_or(0.);
+            let shrink = world.get(c, flex_shrink()).unwrap_or(0.);
+            // The size of items which grow is set by the layout, so it can't be their basis. Items which only shrink start
+            // from their current size
+            let basis = match world.get(c, flex_basis()) {
+                Ok(basis) => basis,
+                Err(_) if grow > 0. => world.get(c, main_min_size).unwrap_or(0.),
+                Err(_) => world.get(c, main_size).unwrap_or(0.),
+            };
+            WrapItem {
+                id: c,
+                margin,
+                basis: basis + margin_size[main],
+                cross: world.get(c, cross_size).unwrap_or(0.) + margin_size[cross],
+                grow,
+                shrink,
+            }
+        })
+        .collect_vec();
+
+    // Breaks the items into lines, each at least one item long
+    let mut lines: Vec<Vec<WrapItem>> = Vec::new();
+    let mut length = 0.;
+    for item in items {
+        match lines.last_mut() {
+            Some(line) if length + space_between_items + item.basis <= line_length => {
+                length += space_between_items + item.basis;
+                line.push(item);
+            }
+            _ => {
+                length = item.basis;
+                lines.push(vec![item]);
+            }
+        }
+    }
+
+    let mut offset_cross = 0.;
+    let mut children_main: f32 = 0.;
+    for line in &lines {
+        let gaps = space_between_items * (line.len() - 1) as f32;
+        let bases = line.iter().map(|item| item.basis).sum::<f32>();
+        let free = if line_length.is_finite() { line_length - bases - gaps } else { 0. };
+        let total_grow = line.iter().map(|item| item.grow).sum::<f32>();
+        let total_shrink = line.iter().map(|item| item.shrink * item.basis).sum::<f32>();
+        let sizes = line
+            .iter()
+            .map(|item| {
+                let size = if free > 0. && total_grow > 0. {
+                    item.basis + free * item.grow / total_grow
+                } else if free < 0. && total_shrink > 0. {
+                    item.basis + free * item.shrink * item.basis / total_shrink
+                } else {
+                    item.basis
+                };
+                size.max(item.margin.border_size()[main]).floor()
+            })
+            .collect_vec();
+        let line_main = sizes.iter().sum::<f32>() + gaps;
+        let line_cross = line.iter().map(|item| item.cross).fold(0., f32::max);
+        children_main = children_main.max(line_main);
+
+        let free_after = if line_length.is_finite() { (line_length - line_main).max(0.) } else { 0. };
+        let mut offset_main = match main_align {
+            Align::Begin => 0.,
+            Align::Center => (free_after / 2.).floor(),
+            Align::End => free_after,
+        };
+        for (item, &size) in line.iter().zip(&sizes) {
+            let margin_size = item.margin.border_size();
+            if item.grow > 0. || item.shrink > 0. {
+                world.set_if_changed(item.id, main_size, size - margin_size[main]).ok();
+            }
+            let cross_offset = match cross_align {
+                Align::Begin => 0.,
+                Align::Center => ((line_cross - item.cross) / 2.).floor(),
+                Align::End => line_cross - item.cross,
+            };
+            let mut position = Vec2::ZERO;
+            position[main] = offset_main;
+            position[cross] = offset_cross + cross_offset;
+            let position = self_padding.offset() + item.margin.offset() + position;
+            world.set_if_changed(item.id, translation(), position.extend(Z_DELTA)).ok();
+            offset_main += size + space_between_items;
+        }
+        offset_cross += line_cross + space_between_items;
+    }
+    let children_cross = if lines.is_empty() { 0. } else { offset_cross - space_between_items };
+
+    let mut new_self_size = self_size;
+    if self_fit.0 == Fit::Children {
+        new_self_size[main] = children_main + self_padding.border_size()[main];
+    }
+    if self_fit.1 == Fit::Children {
+        new_self_size[cross] = children_cross + self_padding.border_size()[cross];
+    }
+    if new_self_size.x != self_size.x {
+        world.set(id, width(), new_self_size.x).ok();
+        invalidate_parent_layout(world, id, Orientation::Horizontal);
+    }
+    if new_self_size.y != self_size.y {
+        world.set(id, height(), new_self_size.y).ok();
+        invalidate_parent_layout(world, id, Orientation::Vertical);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ambient_ecs::Entity;
+    use glam::Vec3Swizzles;
+
+    use super::*;
+
+    fn item(world: &mut World, entity: Entity) -> EntityId {
+        entity.with_default(local_to_parent()).with(translation(), Default::default()).spawn(world)
+    }
+
+    fn position(world: &World, id: EntityId) -> Vec2 {
+        world.get(id, translation()).unwrap().xy()
+    }
+
+    #[test]
+    fn wraps_onto_new_lines() {
+        ambient_core::init_all_components();
+        crate::init_all_components();
+        let mut world = World::new("wraps_onto_new_lines");
+        let container = Entity::new()
+            .with(width(), 100.)
+            .with(height(), 0.)
+            .with(fit_vertical(), Fit::Children)
+            .with(space_between_items(), 10.)
+            .spawn(&mut world);
+        let children = (0..3).map(|_| item(&mut world, Entity::new().with(width(), 40.).with(height(), 20.))).collect_vec();
+
+        wrap_layout(&mut world, container, children.clone());
+        assert_eq!(position(&world, children[0]), vec2(0., 0.));
+        assert_eq!(position(&world, children[1]), vec2(50., 0.));
+        assert_eq!(position(&world, children[2]), vec2(0., 30.));
+        assert_eq!(world.get(container, height()).unwrap(), 50.);
+    }
+
+    #[test]
+    fn grow_and_shrink() {
+        ambient_core::init_all_components();
+        crate::init_all_components();
+        let mut world = World::new("grow_and_shrink");
+        let container = Entity::new().with(width(), 100.).with(height(), 20.).with(space_between_items(), 10.).spawn(&mut world);
+        let fixed = item(&mut world, Entity::new().with(width(), 30.));
+
+        let grow = item(&mut world, Entity::new().with(width(), 0.).with(flex_basis(), 30.).with(flex_grow(), 1.));
+        for _ in 0..2 {
+            wrap_layout(&mut world, container, vec![grow, fixed]);
+            assert_eq!(world.get(grow, width()).unwrap(), 60.);
+        }
+
+        // Without a basis, an item which grows starts from its minimum size, and its basis isn't stored
+        let grow_from_min = item(&mut world, Entity::new().with(width(), 50.).with(min_width(), 20.).with(flex_grow(), 1.));
+        for _ in 0..2 {
+            wrap_layout(&mut world, container, vec![grow_from_min, fixed]);
+            assert_eq!(world.get(grow_from_min, width()).unwrap(), 60.);
+        }
+        assert!(!world.has_component(grow_from_min, flex_basis()));
+
+        let shrink = item(&mut world, Entity::new().with(width(), 0.).with(flex_basis(), 150.).with(flex_shrink(), 1.));
+        wrap_layout(&mut world, container, vec![shrink]);
+        assert_eq!(world.get(shrink, width()).unwrap(), 100.);
+        assert_eq!(position(&world, shrink), vec2(0., 0.));
+
+        // Without a basis, an item which only shrinks starts from its size rather than its minimum size
+        let shrink_from_size = item(&mut world, Entity::new().with(width(), 150.).with(min_width(), 20.).with(flex_shrink(), 1.));
+        wrap_layout(&mut world, container, vec![shrink_from_size]);
+        assert_eq!(world.get(shrink_from_size, width()).unwrap(), 100.);
+    }
+}
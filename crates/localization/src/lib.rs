@@ -0,0 +1,227 @@
+use std::sync::Arc;
+
+use ambient_core::{
+    asset_cache,
+    async_ecs::async_run,
+    network::synced_resources,
+    player::{get_player_by_user_id, local_user_id},
+    runtime,
+};
+use ambient_ecs::{
+    components, query, Component, ComponentValue, Debuggable, Description, EntityId, FnSystem, Name, Networked, Resource, Store,
+    SystemGroup, World,
+};
+use ambient_std::{asset_cache::AssetCache, asset_url::AbsAssetUrl};
+use ambient_text::text;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+mod string_table;
+pub use string_table::*;
+
+/// The directory of the build output where the localization pipeline writes the string tables
+pub const LOCALIZATION_DIR: &str = "localization";
+/// The name of the [LocalizationIndex] in [LOCALIZATION_DIR]
+pub const LOCALIZATION_INDEX: &str = "index.json";
+
+components!("ui", {
+    @[
+        Debuggable, Networked, Store,
+        Name["Text key"],
+        Description["The key of a localized string. The `text` of this entity is set to the string in the active locale, formatted with the `text_args`."]
+    ]
+    text_key: String,
+    @[
+        Debuggable, Networked, Store,
+        Name["Text arguments"],
+        Description["The arguments of the `text_key` string, as `name=value` pairs. The `count` argument selects the plural form."]
+    ]
+    text_args: Vec<String>,
+    @[
+        Debuggable, Networked, Store,
+        Name["Locale"],
+        Description["The locale of the localized text, like `en` or `fr-CA`.\nOn the synced resources, it's the locale of all players; on a player, it overrides it for that player."]
+    ]
+    locale: String,
+    @[
+        Debuggable, Networked, Store,
+        Name["Localization URL"],
+        Description["The URL of the directory with the string tables of the project, on the synced resources."]
+    ]
+    localization_url: String,
+
+    @[Resource]
+    string_tables: Arc<StringTables>,
+    @[Resource]
+    string_tables_request: (String, Option<String>),
+});
+
+/// Written by the localization pipeline next to the string tables
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LocalizationIndex {
+    /// The locale with all the strings, which the other locales fall back to
+    pub default_locale: String,
+    pub locales: Vec<String>,
+}
+impl LocalizationIndex {
+    /// The locales to look up strings in for `locale`: the locale itself, its language (e.g. `fr` for `fr-CA`) and the
+    /// default locale, when the project has them
+    pub fn fallbacks(&self, locale: Option<&str>) -> Vec<String> {
+        let language = locale.and_then(|locale| locale.split(['-', '_']).next());
+        [locale, language, Some(self.default_locale.as_str())]
+            .into_iter()
+            .flatten()
+            .filter(|locale| self.locales.iter().any(|l| l == locale))
+            .map(|locale| locale.to_string())
+            .unique()
+            .collect()
+    }
+}
+
+/// The string tables of the active locale, with its fallbacks
+#[derive(Debug, Clone, Default)]
+pub struct StringTables {
+    /// The active locale, which selects the plural forms
+    pub locale: String,
+    /// The tables of the locale and its fallbacks, in order
+    pub tables: Vec<StringTable>,
+}
+impl StringTables {
+    pub async fn load(url: &AbsAssetUrl, locale: Option<String>, assets: &AssetCache) -> anyhow::Result<Self> {
+        let index: LocalizationIndex = url.push(LOCALIZATION_INDEX)?.download_json(assets).await?;
+        let mut tables = Vec::new();
+        for locale in index.fallbacks(locale.as_deref()) {
+            tables.push(url.push(format!("{locale}.json"))?.download_json(assets).await?);
+        }
+        Ok(Self { locale: locale.unwrap_or(index.default_locale), tables })
+    }
+    pub fn get(&self, key: &str) -> Option<&Message> {
+        self.tables.iter().find_map(|table| table.get(key))
+    }
+    /// Formats the string of `key`. Missing strings show the key, so that they can be spotted
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        match self.get(key) {
+            Some(message) => message.format(&self.locale, args),
+            None => key.to_string(),
+        }
+    }
+}
+
+/// Splits `name=value` pairs, as in [text_args]
+pub fn parse_text_args(args: &[String]) -> Vec<(&str, &str)> {
+    args.iter().map(|arg| arg.split_once('=').unwrap_or((arg, ""))).collect()
+}
+
+/// The locale of the local player, or of all the players
+pub fn active_locale(world: &World) -> Option<String> {
+    let player_locale = world
+        .resource_opt(local_user_id())
+        .and_then(|user_id| get_player_by_user_id(world, user_id))
+        .and_then(|player| world.get_cloned(player, locale()).ok());
+    player_locale.or_else(|| synced_resource(world, locale()))
+}
+
+/// The value of `component` on the synced resources entity, if it has it
+fn synced_resource<T: ComponentValue + Clone>(world: &World, component: Component<T>) -> Option<T> {
+    query(component).incl(synced_resources()).iter(world, None).map(|(_, value)| value.clone()).next()
+}
+
+fn resolve_text(world: &mut World, id: EntityId, tables: &StringTables) {
+    let Ok(key) = world.get_cloned(id, text_key()) else { return };
+    let args = world.get_cloned(id, text_args()).unwrap_or_default();
+    let value = tables.format(&key, &parse_text_args(&args));
+    if world.get_ref(id, text()).ok() != Some(&value) {
+        world.add_component(id, text(), value).ok();
+    }
+}
+
+/// Loads the string tables of the active locale, and sets the `text` of the entities with a [text_key]
+pub fn systems() -> SystemGroup {
+    SystemGroup::new(
+        "localization",
+        vec![
+            Box::new(FnSystem::new(|world, _| {
+                let url = synced_resource(world, localization_url()).unwrap_or_default();
+                let request = (url, active_locale(world));
+                if world.resource_opt(string_tables_request()) == Some(&request) {
+                    return;
+                }
+                world.add_resource(string_tables_request(), request.clone());
+                let (url, locale) = request.clone();
+                if url.is_empty() {
+                    set_string_tables(world, StringTables { locale: locale.unwrap_or_default(), tables: Vec::new() });
+                    return;
+                }
+                let assets = world.resource(asset_cache()).clone();
+                let async_run = world.resource(async_run()).clone();
+                world.resource(runtime()).spawn(async move {
+                    let tables = match AbsAssetUrl::parse(&url) {
+                        Ok(url) => StringTables::load(&url, locale.clone(), &assets).await,
+                        Err(err) => Err(err),
+                    };
+                    match tables {
+                        Ok(tables) => async_run.run(move |world| {
+                            // The locale may have changed while loading
+                            if world.resource_opt(string_tables_request()) == Some(&request) {
+                                set_string_tables(world, tables);
+                            }
+                        }),
+                        Err(err) => log::error!("Failed to load the string tables of {url} for locale {locale:?}: {err:?}"),
+                    }
+                });
+            })),
+            query(text_key().changed()).optional_changed(text_args()).to_system(|q, world, qs, _| {
+                let Some(tables) = world.resource_opt(string_tables()).cloned() else { return };
+                for (id, _) in q.collect_cloned(world, qs) {
+                    resolve_text(world, id, &tables);
+                }
+            }),
+        ],
+    )
+}
+
+fn set_string_tables(world: &mut World, tables: StringTables) {
+    let tables = Arc::new(tables);
+    world.add_resource(string_tables(), tables.clone());
+    for id in query(()).incl(text_key()).collect_ids(world, None) {
+        resolve_text(world, id, &tables);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fallbacks() {
+        let index = LocalizationIndex { default_locale: "en".into(), locales: vec!["en".into(), "fr".into(), "fr-CA".into()] };
+        assert_eq!(index.fallbacks(Some("fr-CA")), vec!["fr-CA", "fr", "en"]);
+        assert_eq!(index.fallbacks(Some("fr_BE")), vec!["fr", "en"]);
+        assert_eq!(index.fallbacks(Some("de")), vec!["en"]);
+        assert_eq!(index.fallbacks(Some("en")), vec!["en"]);
+        assert_eq!(index.fallbacks(None), vec!["en"]);
+    }
+
+    #[test]
+    fn string_tables() {
+        let table = |json: &str| serde_json::from_str::<StringTable>(json).unwrap();
+        let tables = StringTables {
+            locale: "fr-CA".into(),
+            tables: vec![
+                table(r#"{ "color": "Couleur (CA)" }"#),
+                table(r#"{ "color": "Couleur", "items": { "one": "{count} objet", "other": "{count} objets" } }"#),
+                table(
+                    r#"{ "color": "Color", "greeting": "Hello, {name}!", "items": { "one": "{count} item", "other": "{count} items" } }"#,
+                ),
+            ],
+        };
+        assert_eq!(tables.format("color", &[]), "Couleur (CA)");
+        assert_eq!(tables.format("items", &[("count", "1.5")]), "1.5 objet");
+        assert_eq!(tables.format("items", &[("count", "2")]), "2 objets");
+        let args = vec!["name=Ada".to_string(), "unused".to_string()];
+        assert_eq!(parse_text_args(&args), vec![("name", "Ada"), ("unused", "")]);
+        assert_eq!(tables.format("greeting", &parse_text_args(&args)), "Hello, Ada!");
+        assert_eq!(tables.format("missing", &[]), "missing");
+        assert_eq!(StringTables::default().format("color", &[]), "color");
+    }
+}
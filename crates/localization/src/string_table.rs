@@ -0,0 +1,251 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+/// The strings of one locale, by key. In JSON, it's an object where each value is either a string, or an object with a
+/// string for each plural form:
+///
+/// ```json
+/// {
+///     "menu.play": "Play",
+///     "greeting": "Hello, {name}!",
+///     "inventory.items": { "zero": "No items", "one": "{count} item", "other": "{count} items" }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StringTable(pub BTreeMap<String, Message>);
+impl StringTable {
+    pub fn get(&self, key: &str) -> Option<&Message> {
+        self.0.get(key)
+    }
+    /// The keys of `reference` which aren't in this table
+    pub fn missing_keys<'a>(&self, reference: &'a StringTable) -> Vec<&'a str> {
+        reference.0.keys().filter(|key| !self.0.contains_key(*key)).map(|key| key.as_str()).collect()
+    }
+    /// Checks that all the patterns of the table are valid
+    pub fn validate(&self) -> Result<(), String> {
+        for (key, message) in &self.0 {
+            message.arguments().map_err(|err| format!("Invalid string {key:?}: {err}"))?;
+        }
+        Ok(())
+    }
+}
+
+/// A localized string, which can have arguments like `{name}`. Braces are escaped by doubling them, e.g. `{{`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Message {
+    Text(String),
+    /// Chooses a form from the `count` argument
+    Plural(PluralForms),
+}
+impl Message {
+    /// The pattern to use for `count`, in `locale`
+    pub fn pattern(&self, locale: &str, count: Option<f64>) -> &str {
+        match (self, count) {
+            (Message::Text(text), _) => text,
+            (Message::Plural(forms), Some(count)) => forms.get(locale, count),
+            (Message::Plural(forms), None) => &forms.other,
+        }
+    }
+    pub fn format(&self, locale: &str, args: &[(&str, &str)]) -> String {
+        let count = args.iter().find(|(name, _)| *name == "count").and_then(|(_, value)| value.parse().ok());
+        format_pattern(self.pattern(locale, count), args)
+    }
+    /// The names of the arguments used by all the forms of this message
+    pub fn arguments(&self) -> Result<BTreeSet<String>, String> {
+        let patterns = match self {
+            Message::Text(text) => vec![text.as_str()],
+            Message::Plural(forms) => forms.iter().collect(),
+        };
+        let mut arguments = BTreeSet::new();
+        for pattern in patterns {
+            for segment in parse_pattern(pattern)? {
+                if let Segment::Argument(name) = segment {
+                    arguments.insert(name.to_string());
+                }
+            }
+        }
+        Ok(arguments)
+    }
+}
+
+/// The forms of a [Message::Plural]. `zero` is used for a count of 0 in every language, and the other forms only in
+/// the languages which have them (see [plural_category])
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluralForms {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zero: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub one: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub two: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub few: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub many: Option<String>,
+    pub other: String,
+}
+impl PluralForms {
+    pub fn get(&self, locale: &str, count: f64) -> &str {
+        let form = match plural_category(locale, count) {
+            _ if count == 0. && self.zero.is_some() => &self.zero,
+            PluralCategory::Zero => &self.zero,
+            PluralCategory::One => &self.one,
+            PluralCategory::Two => &self.two,
+            PluralCategory::Few => &self.few,
+            PluralCategory::Many => &self.many,
+            PluralCategory::Other => &None,
+        };
+        form.as_deref().unwrap_or(&self.other)
+    }
+    fn iter(&self) -> impl Iterator<Item = &str> {
+        [&self.zero, &self.one, &self.two, &self.few, &self.many]
+            .into_iter()
+            .flatten()
+            .map(|form| form.as_str())
+            .chain([self.other.as_str()])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+/// The plural category of `count` in the language of `locale`, following the CLDR rules of the most common languages.
+/// Other languages use the English rules
+pub fn plural_category(locale: &str, count: f64) -> PluralCategory {
+    let language = locale.split(['-', '_']).next().unwrap_or_default().to_lowercase();
+    let integer = count.fract() == 0.;
+    let n = count.abs() as u64;
+    let (n10, n100) = (n % 10, n % 100);
+    match language.as_str() {
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" => PluralCategory::Other,
+        "fr" | "pt" if count.abs() < 2. => PluralCategory::One,
+        "ru" | "uk" | "be" if integer => match (n10, n100) {
+            (1, n100) if n100 != 11 => PluralCategory::One,
+            (2..=4, n100) if !(12..=14).contains(&n100) => PluralCategory::Few,
+            _ => PluralCategory::Many,
+        },
+        "pl" if integer => match (n, n10, n100) {
+            (1, _, _) => PluralCategory::One,
+            (_, 2..=4, n100) if !(12..=14).contains(&n100) => PluralCategory::Few,
+            _ => PluralCategory::Many,
+        },
+        "cs" | "sk" if integer => match n {
+            1 => PluralCategory::One,
+            2..=4 => PluralCategory::Few,
+            _ => PluralCategory::Other,
+        },
+        "ar" if integer => match (n, n100) {
+            (0, _) => PluralCategory::Zero,
+            (1, _) => PluralCategory::One,
+            (2, _) => PluralCategory::Two,
+            (_, 3..=10) => PluralCategory::Few,
+            (_, 11..=99) => PluralCategory::Many,
+            _ => PluralCategory::Other,
+        },
+        "ru" | "uk" | "be" | "pl" | "cs" | "sk" | "ar" | "fr" | "pt" => PluralCategory::Other,
+        _ if count == 1. => PluralCategory::One,
+        _ => PluralCategory::Other,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment<'a> {
+    Literal(&'a str),
+    Argument(&'a str),
+}
+
+fn parse_pattern(pattern: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut rest = pattern;
+    while let Some(index) = rest.find(['{', '}']) {
+        if index > 0 {
+            segments.push(Segment::Literal(&rest[..index]));
+        }
+        let brace = &rest[index..index + 1];
+        rest = &rest[index + 1..];
+        if let Some(escaped) = rest.strip_prefix(brace) {
+            segments.push(Segment::Literal(brace));
+            rest = escaped;
+        } else if brace == "{" {
+            let end = rest.find('}').ok_or_else(|| format!("Unclosed argument in {pattern:?}"))?;
+            let name = &rest[..end];
+            if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                return Err(format!("Invalid argument name {name:?} in {pattern:?}"));
+            }
+            segments.push(Segment::Argument(name));
+            rest = &rest[end + 1..];
+        } else {
+            return Err(format!("Unmatched }} in {pattern:?}, use }}}} for a brace"));
+        }
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest));
+    }
+    Ok(segments)
+}
+
+/// Replaces the arguments of `pattern` with their values. Arguments without a value are kept as they are, and an
+/// invalid pattern is returned unchanged
+pub fn format_pattern(pattern: &str, args: &[(&str, &str)]) -> String {
+    let Ok(segments) = parse_pattern(pattern) else {
+        return pattern.to_string();
+    };
+    let mut text = String::with_capacity(pattern.len());
+    for segment in segments {
+        match segment {
+            Segment::Literal(literal) => text.push_str(literal),
+            Segment::Argument(name) => match args.iter().find(|(arg, _)| *arg == name) {
+                Some((_, value)) => text.push_str(value),
+                None => {
+                    text.push('{');
+                    text.push_str(name);
+                    text.push('}');
+                }
+            },
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_arguments() {
+        assert_eq!(format_pattern("Hello, {name}!", &[("name", "Ada")]), "Hello, Ada!");
+        assert_eq!(format_pattern("{{literal}} {a}{b}", &[("a", "1"), ("b", "2")]), "{literal} 12");
+        assert_eq!(format_pattern("Missing {arg}", &[]), "Missing {arg}");
+        assert!(parse_pattern("Unclosed {arg").is_err());
+        assert!(parse_pattern("Unmatched }").is_err());
+        assert!(parse_pattern("{not an argument}").is_err());
+    }
+
+    #[test]
+    fn plurals() {
+        let table: StringTable = serde_json::from_str(
+            r#"{ "items": { "zero": "No items", "one": "{count} item", "few": "{count} items (few)", "other": "{count} items" } }"#,
+        )
+        .unwrap();
+        let items = table.get("items").unwrap();
+        assert_eq!(items.format("en", &[("count", "0")]), "No items");
+        assert_eq!(items.format("en", &[("count", "1")]), "1 item");
+        assert_eq!(items.format("en-GB", &[("count", "3")]), "3 items");
+        assert_eq!(items.format("fr", &[("count", "1.5")]), "1.5 item");
+        assert_eq!(items.format("ru", &[("count", "22")]), "22 items (few)");
+        assert_eq!(items.format("ru", &[("count", "12")]), "12 items");
+        assert_eq!(items.format("ja", &[("count", "1")]), "1 items");
+        assert_eq!(items.arguments().unwrap(), BTreeSet::from(["count".to_string()]));
+    }
+}
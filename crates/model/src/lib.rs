@@ -22,7 +22,7 @@ use ambient_renderer::{
     primitives, RenderPrimitive, StandardShaderKey,
 };
 use ambient_std::{
-    asset_cache::{AssetCache, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKey, SyncAssetKeyExt},
+    asset_cache::{AssetCache, AssetSize, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKey, SyncAssetKeyExt},
     asset_url::{AbsAssetUrl, AssetUrl, ModelAssetType, TypedAssetUrl},
     cb,
     download_asset::{AssetError, BytesFromUrl},
@@ -252,6 +252,10 @@ impl AsyncAssetKey<Result<Arc<Model>, AssetError>> for ModelFromUrl {
         model.load(&assets, &url).await?;
         Ok(Arc::new(model))
     }
+
+    fn cpu_size(&self, asset: &Result<Arc<Model>, AssetError>) -> Option<u64> {
+        asset.size_bytes()
+    }
 }
 
 /// Limit the number of concurent model loads to 10
@@ -18,7 +18,7 @@ use ambient_renderer::{
     skinning::{self, Skin, SkinsBuffer, SkinsBufferKey},
 };
 use ambient_std::{
-    asset_cache::{AssetCache, AsyncAssetKeyExt, SyncAssetKeyExt},
+    asset_cache::{AssetCache, AssetSize, AsyncAssetKeyExt, SyncAssetKeyExt},
     asset_url::AbsAssetUrl,
     download_asset::AssetError,
     shapes::AABB,
@@ -67,6 +67,16 @@ impl Default for ModelSpawnOpts {
 /// instead of a raw World ensure those conventions are maintained.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Model(pub World);
+/// A rough estimate of the components of a model node, which are mostly transforms and small values
+const MODEL_NODE_SIZE_BYTES: u64 = 512;
+impl AssetSize for Model {
+    /// The size of the nodes and skins of the model. The meshes and materials are separate assets, with their own sizes
+    fn size_bytes(&self) -> Option<u64> {
+        let skin_size = |skin: &ModelSkin| std::mem::size_of_val(&skin.inverse_bind_matrices[..]) + std::mem::size_of_val(&skin.joints[..]);
+        let skins = self.skins().into_iter().flatten().map(skin_size).sum::<usize>();
+        Some(self.0.len() as u64 * MODEL_NODE_SIZE_BYTES + skins as u64)
+    }
+}
 impl Model {
     #[cfg(not(target_os = "unknown"))]
     pub async fn from_file(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use gltf::{buffer, image::Format, Document, Gltf};
 use image::{
@@ -13,22 +13,47 @@ pub struct GltfImport {
 }
 impl GltfImport {
     pub fn from_slice<S: AsRef<[u8]>>(name: String, import_images: bool, slice: S) -> gltf::Result<Self> {
+        Self::from_slice_with_resources(name, import_images, slice, &HashMap::new())
+    }
+    /// Like [GltfImport::from_slice], for `.gltf` files which keep their buffers and images in separate files.
+    /// `resources` maps the uris in [GltfImport::external_uris] to the content of those files
+    pub fn from_slice_with_resources<S: AsRef<[u8]>>(
+        name: String,
+        import_images: bool,
+        slice: S,
+        resources: &HashMap<String, Vec<u8>>,
+    ) -> gltf::Result<Self> {
         let Gltf { document, blob } = Gltf::from_slice(slice.as_ref())?;
-        let buffers = import_buffer_data(&document, None, blob)?;
-        let images = if import_images { import_image_data(&document, None, &buffers)? } else { Vec::new() };
+        let buffers = import_buffer_data(&document, resources, blob)?;
+        let images = if import_images { import_image_data(&document, resources, &buffers)? } else { Vec::new() };
         Ok(Self { name, document, buffers, images })
     }
+    /// The relative uris of the buffers and images of the document, which have to be loaded separately
+    pub fn external_uris(document: &Document) -> Vec<String> {
+        let buffers = document.buffers().filter_map(|buffer| match buffer.source() {
+            buffer::Source::Uri(uri) => Some(uri),
+            buffer::Source::Bin => None,
+        });
+        let images = document.images().filter_map(|image| match image.source() {
+            gltf::image::Source::Uri { uri, .. } => Some(uri),
+            gltf::image::Source::View { .. } => None,
+        });
+        buffers.chain(images).filter(|uri| Scheme::parse(uri) == Scheme::Relative).map(|uri| uri.to_string()).collect()
+    }
 }
 
 // All of the below is basically just copied from the gltf crate, except it doesn't panic on bad resource references
 
-fn import_buffer_data(document: &Document, base: Option<&Path>, mut blob: Option<Vec<u8>>) -> gltf::Result<Vec<buffer::Data>> {
+fn import_buffer_data(
+    document: &Document,
+    resources: &HashMap<String, Vec<u8>>,
+    mut blob: Option<Vec<u8>>,
+) -> gltf::Result<Vec<buffer::Data>> {
     let mut buffers = Vec::new();
     for buffer in document.buffers() {
         let mut data = match buffer.source() {
-            buffer::Source::Uri(uri) if base.is_some() => Scheme::read(base.unwrap(), uri),
+            buffer::Source::Uri(uri) => Scheme::read(resources, uri),
             buffer::Source::Bin => blob.take().ok_or(gltf::Error::MissingBlob),
-            _ => Ok(Vec::new()),
         }?;
         // if data.len() < buffer.length() {
         //     return Err(
@@ -47,7 +72,11 @@ fn import_buffer_data(document: &Document, base: Option<&Path>, mut blob: Option
     Ok(buffers)
 }
 
-fn import_image_data(document: &Document, base: Option<&Path>, buffer_data: &[buffer::Data]) -> gltf::Result<Vec<gltf::image::Data>> {
+fn import_image_data(
+    document: &Document,
+    resources: &HashMap<String, Vec<u8>>,
+    buffer_data: &[buffer::Data],
+) -> gltf::Result<Vec<gltf::image::Data>> {
     let mut images = Vec::new();
     #[cfg(feature = "guess_mime_type")]
     let guess_format = |encoded_image: &[u8]| match image_crate::guess_format(encoded_image) {
@@ -59,7 +88,7 @@ fn import_image_data(document: &Document, base: Option<&Path>, buffer_data: &[bu
     let guess_format = |_encoded_image: &[u8]| None;
     for image in document.images() {
         match image.source() {
-            gltf::image::Source::Uri { uri, mime_type } if base.is_some() => {
+            gltf::image::Source::Uri { uri, mime_type } => {
                 match Scheme::parse(uri) {
                     Scheme::Data(Some(annoying_case), base64) => {
                         let encoded_image = base64::decode(base64).map_err(gltf::Error::Base64)?;
@@ -78,7 +107,7 @@ fn import_image_data(document: &Document, base: Option<&Path>, buffer_data: &[bu
                     Scheme::Unsupported => return Err(gltf::Error::UnsupportedScheme),
                     _ => {}
                 }
-                let encoded_image = Scheme::read(base.unwrap(), uri)?;
+                let encoded_image = Scheme::read(resources, uri)?;
                 let encoded_format = match mime_type {
                     Some("image/png") => Png,
                     Some("image/jpeg") => Jpeg,
@@ -186,11 +215,14 @@ impl Scheme<'_> {
         }
     }
 
-    fn read(base: &Path, uri: &str) -> gltf::Result<Vec<u8>> {
+    fn read(resources: &HashMap<String, Vec<u8>>, uri: &str) -> gltf::Result<Vec<u8>> {
         match Scheme::parse(uri) {
             Scheme::Data(_, base64) => base64::decode(base64).map_err(gltf::Error::Base64),
             Scheme::File(path) => read_to_end(path),
-            Scheme::Relative => read_to_end(base.join(uri)),
+            Scheme::Relative => resources
+                .get(uri)
+                .cloned()
+                .ok_or_else(|| gltf::Error::Io(std::io::Error::new(std::io::ErrorKind::NotFound, format!("Missing gltf resource {uri}")))),
             Scheme::Unsupported => Err(gltf::Error::UnsupportedScheme),
         }
     }
@@ -1,30 +1,68 @@
-use std::sync::Arc;
+use std::{collections::HashMap, f32::consts::FRAC_PI_2, sync::Arc};
 
 use ambient_animation::{animation_bind_id_from_name, AnimationClip, AnimationOutputs, AnimationTarget, AnimationTrack};
 use ambient_core::{
-    bounding::local_bounding_aabb, hierarchy::{children, parent}, name, transform::{local_to_parent, local_to_world, rotation, scale, translation}
+    bounding::local_bounding_aabb, camera::{
+        aspect_ratio, far, fovy, near, orthographic, orthographic_bottom, orthographic_left, orthographic_right, orthographic_top, perspective, perspective_infinite_reverse, projection, projection_view
+    }, hierarchy::{children, parent}, name, transform::{inv_local_to_world, local_to_parent, local_to_world, rotation, scale, translation}
 };
-use ambient_ecs::{Entity, World};
+use ambient_ecs::{with_component_registry, ComponentEntry, Entity, Serializable, World};
 use ambient_model::{model_skin_ix, model_skins, pbr_renderer_primitives_from_url, Model, ModelSkin, PbrRenderPrimitiveFromUrl};
-use ambient_renderer::materials::pbr_material::PbrMaterialDesc;
+use ambient_renderer::{light_diffuse, materials::pbr_material::PbrMaterialDesc, sun};
 use ambient_std::{asset_cache::AssetCache, asset_url::AbsAssetUrl, mesh::Mesh, shapes::AABB};
 use glam::{uvec4, Mat4, Quat, UVec4, Vec2, Vec3, Vec4, Vec4Swizzles};
 use gltf::animation::util::ReadOutputs;
 use itertools::Itertools;
 use relative_path::RelativePathBuf;
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
 
-use self::gltf_import::GltfImport;
+pub use self::gltf_import::GltfImport;
 use crate::{dotdot_path, model_crate::ModelCrate};
 
 mod gltf_import;
 
-pub async fn import_url(assets: &AssetCache, url: &AbsAssetUrl, asset_crate: &mut ModelCrate) -> anyhow::Result<RelativePathBuf> {
+/// Controls which of the optional parts of a glTF (`.glb` or `.gltf`) file are imported. Files imported with assimp,
+/// which includes all glTF files when assimp is forced, ignore these
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GltfImportOptions {
+    /// Map the `extras` (i.e. custom properties in Blender) of each node to components. The keys are component paths
+    /// (`core::` may be left out), and must be registered by the runtime or the project manifest. Off by default, as
+    /// exporters often write extras which aren't meant for the engine.
+    pub extras: bool,
+    /// Convert directional lights from `KHR_lights_punctual` to suns. Point and spot lights are skipped with a warning, as
+    /// suns are the only lights the renderer supports. On by default.
+    pub lights: bool,
+    /// Convert cameras to camera entities. The cameras are not made active. Off by default.
+    pub cameras: bool,
+}
+impl Default for GltfImportOptions {
+    fn default() -> Self {
+        Self { extras: false, lights: true, cameras: false }
+    }
+}
+
+pub async fn import_url(
+    assets: &AssetCache,
+    url: &AbsAssetUrl,
+    asset_crate: &mut ModelCrate,
+    options: &GltfImportOptions,
+) -> anyhow::Result<RelativePathBuf> {
     let content = url.download_bytes(assets).await?;
-    let gltf = GltfImport::from_slice(url.to_string(), true, &content)?;
-    import(&gltf, asset_crate).await
+    // `.gltf` files keep their buffers and images in separate files, next to the `.gltf`
+    let mut resources = HashMap::new();
+    if !url.extension_is("glb") {
+        let document = gltf::Gltf::from_slice(&content)?.document;
+        for uri in GltfImport::external_uris(&document) {
+            let data = url.join(&uri)?.download_bytes(assets).await?;
+            resources.insert(uri, data);
+        }
+    }
+    let gltf = GltfImport::from_slice_with_resources(url.to_string(), true, &content, &resources)?;
+    import(&gltf, asset_crate, options).await
 }
 
-pub async fn import(import: &GltfImport, asset_crate: &mut ModelCrate) -> anyhow::Result<RelativePathBuf> {
+pub async fn import(import: &GltfImport, asset_crate: &mut ModelCrate, options: &GltfImportOptions) -> anyhow::Result<RelativePathBuf> {
     let name_ = |name: Option<&str>| name.map(|x| format!("{x}_")).unwrap_or_default();
 
     let mut meshes = import.document.meshes().map(|mesh| mesh.primitives().map(|_| RelativePathBuf::new()).collect_vec()).collect_vec();
@@ -197,6 +235,8 @@ pub async fn import(import: &GltfImport, asset_crate: &mut ModelCrate) -> anyhow
     }
 
     let mut world = World::new("gltf");
+    // Entities which are attached below a node, but aren't nodes themselves
+    let mut extra_children = Vec::new();
     let nodes = import
         .document
         .nodes()
@@ -240,9 +280,28 @@ pub async fn import(import: &GltfImport, asset_crate: &mut ModelCrate) -> anyhow
                 ed.set(model_skin_ix(), skin.index());
             }
 
+            if options.extras {
+                if let Some(extras) = node.extras() {
+                    extras_to_components(node.name().unwrap_or_default(), extras.get(), &mut ed);
+                }
+            }
+            if options.cameras {
+                if let Some(camera) = node.camera() {
+                    ed.merge(camera_entity(&camera));
+                }
+            }
+            if options.lights {
+                if let Some(light) = node.light() {
+                    if let Some(light) = light_entity(&light) {
+                        extra_children.push((node.index(), light));
+                    }
+                }
+            }
+
             ed.spawn(&mut world)
         })
         .collect_vec();
+    let mut extra_children = extra_children.into_iter().map(|(node_index, ed)| (node_index, ed.spawn(&mut world))).into_group_map();
 
     let mut skins = Vec::new();
     for skin in import.document.skins() {
@@ -259,7 +318,8 @@ pub async fn import(import: &GltfImport, asset_crate: &mut ModelCrate) -> anyhow
     world.add_resource(model_skins(), skins);
 
     for (id, node) in nodes.iter().zip(import.document.nodes()) {
-        let childs = node.children().map(|x| nodes[x.index()]).collect_vec();
+        let childs =
+            node.children().map(|x| nodes[x.index()]).chain(extra_children.remove(&node.index()).unwrap_or_default()).collect_vec();
         if !childs.is_empty() {
             for child_id in &childs {
                 world.add_component(*child_id, parent(), *id).unwrap();
@@ -274,3 +334,73 @@ pub async fn import(import: &GltfImport, asset_crate: &mut ModelCrate) -> anyhow
 
     Ok(asset_crate.models.insert(ModelCrate::MAIN, Model(world)).path)
 }
+
+/// Adds the components in the `extras` of a node to `ed`. Extras which aren't components are skipped with a warning
+fn extras_to_components(node_name: &str, extras: &str, ed: &mut Entity) {
+    let extras = match serde_json::from_str::<serde_json::Value>(extras) {
+        Ok(serde_json::Value::Object(extras)) => extras,
+        Ok(_) => return,
+        Err(err) => {
+            log::warn!("Failed to parse the extras of glTF node {node_name:?}: {err}");
+            return;
+        }
+    };
+    for (key, value) in extras {
+        let Some(desc) = with_component_registry(|r| r.get_by_path(&key).or_else(|| r.get_by_path(&format!("core::{key}")))) else {
+            log::warn!("glTF node {node_name:?} has the extra {key:?}, which is not a known component; skipping it");
+            continue;
+        };
+        let Some(ser) = desc.attribute::<Serializable>().map(|ser| *ser) else {
+            log::warn!("glTF node {node_name:?} has the extra {key:?}, but that component can't be deserialized; skipping it");
+            continue;
+        };
+        match ser.deserializer(desc).deserialize(value) {
+            Ok(entry) => ed.set_entry(entry),
+            // Tag components can be set from any value, as most tools don't allow custom properties without a value
+            Err(_) if desc.is::<()>() => ed.set_entry(ComponentEntry::from_raw_parts(desc, ())),
+            Err(err) => log::warn!("glTF node {node_name:?} has an invalid value for the component {key:?}: {err}"),
+        }
+    }
+}
+
+fn camera_entity(camera: &gltf::Camera) -> Entity {
+    let ed = Entity::new().with(projection(), Mat4::IDENTITY).with(projection_view(), Mat4::IDENTITY).with_default(inv_local_to_world());
+    match camera.projection() {
+        gltf::camera::Projection::Perspective(p) => {
+            let ed = ed.with(near(), p.znear()).with(fovy(), p.yfov()).with(aspect_ratio(), p.aspect_ratio().unwrap_or(1.));
+            match p.zfar() {
+                Some(zfar) => ed.with(far(), zfar).with_default(perspective()),
+                None => ed.with_default(perspective_infinite_reverse()),
+            }
+        }
+        gltf::camera::Projection::Orthographic(o) => ed
+            .with_default(orthographic())
+            .with(orthographic_left(), -o.xmag())
+            .with(orthographic_right(), o.xmag())
+            .with(orthographic_top(), o.ymag())
+            .with(orthographic_bottom(), -o.ymag())
+            .with(near(), o.znear())
+            .with(far(), o.zfar()),
+    }
+}
+
+/// Ambient only supports directional lights (suns) at the moment, so other lights are skipped
+fn light_entity(light: &gltf::khr_lights_punctual::Light) -> Option<Entity> {
+    match light.kind() {
+        gltf::khr_lights_punctual::Kind::Directional => Some(
+            Entity::new()
+                .with(translation(), Vec3::ZERO)
+                // glTF lights point down -Z, while suns point down X
+                .with(rotation(), Quat::from_rotation_y(FRAC_PI_2))
+                .with(scale(), Vec3::ONE)
+                .with_default(local_to_world())
+                .with(name(), light.name().unwrap_or("sun").to_string())
+                .with(sun(), 0.)
+                .with(light_diffuse(), Vec3::from(light.color()) * light.intensity()),
+        ),
+        kind => {
+            log::warn!("Skipping glTF light {:?}; {kind:?} lights are not supported", light.name());
+            None
+        }
+    }
+}
@@ -127,7 +127,9 @@ impl ModelImportTransform {
     pub async fn run(&self, assets: &AssetCache, model_crate: &mut ModelCrate) -> anyhow::Result<()> {
         match self {
             ModelImportTransform::ImportModelFromUrl { url, normalize, force_assimp } => {
-                model_crate.import(assets, url, *normalize, *force_assimp, Arc::new(|_| async move { None }.boxed())).await?;
+                model_crate
+                    .import(assets, url, *normalize, *force_assimp, Arc::new(|_| async move { None }.boxed()), &Default::default())
+                    .await?;
             }
             ModelImportTransform::MergeMeshLods { lods, lod_cutoffs } => {
                 let mut res_lods = Vec::new();
@@ -321,6 +323,28 @@ impl Default for ModelTextureSize {
     }
 }
 
+/// Controls the generation of lods for the meshes of a model, see [ModelCrate::generate_lods]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LodGenerationOptions {
+    /// The number of lods to generate, in addition to the original meshes. Defaults to 3.
+    pub levels: usize,
+    /// The triangle count of each lod, relative to the previous one. Defaults to 0.5.
+    pub triangle_ratio: f32,
+    /// The largest error each lod may add while simplifying, relative to the size of the mesh. Defaults to 0.05.
+    pub max_error: f32,
+    /// If specified, the lod cutoffs are computed from the error of each lod, so that a lod is only shown when its error
+    /// is smaller than this fraction of the screen height (e.g. `0.002` is about two pixels at 1080p).
+    pub screen_space_error: Option<f32>,
+    /// If specified, the clip space sizes at which to switch lods. This overrides `screen_space_error`.
+    pub lod_cutoffs: Option<Vec<f32>>,
+}
+impl Default for LodGenerationOptions {
+    fn default() -> Self {
+        Self { levels: 3, triangle_ratio: 0.5, max_error: 0.05, screen_space_error: None, lod_cutoffs: None }
+    }
+}
+
 // #[derive(Debug, Clone)]
 // pub struct ModelFromAssetPipeline(pub ModelImportPipeline);
 // impl ModelFromAssetPipeline {
@@ -23,7 +23,7 @@ use ambient_renderer::{
 };
 use ambient_std::{
     asset_cache::{AssetCache, SyncAssetKeyExt},
-    asset_url::AbsAssetUrl,
+    asset_url::{AbsAssetUrl, BINARY_PREFAB_EXTENSION},
     download_asset::AssetsCacheDir,
     mesh::Mesh,
     shapes::AABB,
@@ -37,7 +37,7 @@ use ordered_float::Float;
 use physxx::{PxConvexFlag, PxConvexMeshDesc, PxDefaultMemoryOutputStream, PxMeshFlag, PxTriangleMeshDesc};
 use relative_path::RelativePathBuf;
 
-use crate::{dotdot_path, MaterialFilter, TextureResolver};
+use crate::{dotdot_path, gltf::GltfImportOptions, LodGenerationOptions, MaterialFilter, TextureResolver};
 
 #[derive(Debug, Clone)]
 pub struct AssetLoc {
@@ -70,10 +70,10 @@ impl AssetMapLoc {
 pub struct AssetMap<T> {
     pub loc: AssetMapLoc,
     pub content: HashMap<String, T>,
-    pub serialize: fn(&T) -> Vec<u8>,
+    pub serialize: fn(&T) -> anyhow::Result<Vec<u8>>,
 }
 impl<T: Send + 'static> AssetMap<T> {
-    fn new(store: &str, extension: &str, serialize: fn(&T) -> Vec<u8>) -> Self {
+    fn new(store: &str, extension: &str, serialize: fn(&T) -> anyhow::Result<Vec<u8>>) -> Self {
         Self { loc: AssetMapLoc { store: store.to_string(), extension: extension.into() }, content: Default::default(), serialize }
     }
 
@@ -85,8 +85,15 @@ impl<T: Send + 'static> AssetMap<T> {
         self.content.insert(id.clone(), content);
         AssetLoc { path: self.loc.path(&id), id }
     }
-    pub fn to_items(&self) -> Vec<AssetItem> {
-        self.content.iter().map(|(id, content)| AssetItem { path: self.loc.path(id), data: Arc::new((self.serialize)(content)) }).collect()
+    pub fn to_items(&self) -> anyhow::Result<Vec<AssetItem>> {
+        self.content
+            .iter()
+            .map(|(id, content)| {
+                let path = self.loc.path(id);
+                let data = (self.serialize)(content).with_context(|| format!("Failed to serialize {path}"))?;
+                Ok(AssetItem { path, data: Arc::new(data) })
+            })
+            .collect()
     }
 }
 
@@ -104,21 +111,27 @@ pub struct ModelCrate {
 impl ModelCrate {
     pub fn new() -> Self {
         Self {
-            models: AssetMap::new("models", "json", |v| serde_json::to_vec(v).unwrap()),
-            prefabs: AssetMap::new("prefabs", "json", |v| serde_json::to_vec(v).unwrap()),
-            meshes: AssetMap::new("meshes", "mesh", |v| bincode::serialize(v).unwrap()),
-            animations: AssetMap::new("animations", "anim", |v| bincode::serialize(v).unwrap()),
+            models: AssetMap::new("models", "json", |v| Ok(serde_json::to_vec(v)?)),
+            prefabs: AssetMap::new("prefabs", "json", |v| Ok(serde_json::to_vec(v)?)),
+            meshes: AssetMap::new("meshes", "mesh", |v| Ok(bincode::serialize(v)?)),
+            animations: AssetMap::new("animations", "anim", |v| Ok(bincode::serialize(v)?)),
             images: AssetMap::new("images", "png", |v| {
                 let mut data = Cursor::new(Vec::new());
-                v.write_to(&mut data, ImageOutputFormat::Png).unwrap();
-                data.into_inner()
+                v.write_to(&mut data, ImageOutputFormat::Png)?;
+                Ok(data.into_inner())
             }),
-            materials: AssetMap::new("materials", "json", |v| serde_json::to_vec(v).unwrap()),
-            px_triangle_meshes: AssetMap::new("px_triangle_meshes", "pxtm", |v| v.clone()),
-            px_convex_meshes: AssetMap::new("px_convex_meshes", "pxcm", |v| v.clone()),
-            colliders: AssetMap::new("colliders", "json", |v| serde_json::to_vec(v).unwrap()),
+            materials: AssetMap::new("materials", "json", |v| Ok(serde_json::to_vec(v)?)),
+            px_triangle_meshes: AssetMap::new("px_triangle_meshes", "pxtm", |v| Ok(v.clone())),
+            px_convex_meshes: AssetMap::new("px_convex_meshes", "pxcm", |v| Ok(v.clone())),
+            colliders: AssetMap::new("colliders", "json", |v| Ok(serde_json::to_vec(v)?)),
         }
     }
+    /// Serialize prefabs with the compact binary world format instead of json. They are written with the
+    /// [BINARY_PREFAB_EXTENSION] extension, so the main prefab is at `prefabs/main.world`
+    pub fn use_binary_prefabs(&mut self) {
+        self.prefabs.loc.extension = BINARY_PREFAB_EXTENSION.to_string();
+        self.prefabs.serialize = |v| v.to_binary();
+    }
     pub async fn local_import(assets: &AssetCache, url: &AbsAssetUrl, normalize: bool, force_assimp: bool) -> anyhow::Result<Model> {
         let cache_path = AssetsCacheDir.get(assets).join("pipelines").join(url.relative_cache_path());
         let mut model = Self::new();
@@ -143,30 +156,30 @@ impl ModelCrate {
         model.model_mut().update_model_aabb();
         model.produce_local_model(assets, cache_path).await
     }
-    pub async fn write_to_fs(&self, path: &PathBuf) {
-        for item in self.to_items() {
+    pub async fn write_to_fs(&self, path: &PathBuf) -> anyhow::Result<()> {
+        for item in self.to_items()? {
             let item_path = item.path.to_path(path);
             std::fs::create_dir_all(item_path.parent().unwrap())
-                .context(format!("Failed to create dir: {:?}", item_path.parent().unwrap()))
-                .unwrap();
-            tokio::fs::write(&item_path, &*item.data).await.context(format!("Failed to write file: {item_path:?}")).unwrap();
+                .context(format!("Failed to create dir: {:?}", item_path.parent().unwrap()))?;
+            tokio::fs::write(&item_path, &*item.data).await.context(format!("Failed to write file: {item_path:?}"))?;
         }
+        Ok(())
     }
-    pub fn to_items(&self) -> Vec<AssetItem> {
-        [
-            self.models.to_items().into_iter(),
-            self.prefabs.to_items().into_iter(),
-            self.meshes.to_items().into_iter(),
-            self.animations.to_items().into_iter(),
-            self.images.to_items().into_iter(),
-            self.materials.to_items().into_iter(),
-            self.px_triangle_meshes.to_items().into_iter(),
-            self.px_convex_meshes.to_items().into_iter(),
-            self.colliders.to_items().into_iter(),
+    pub fn to_items(&self) -> anyhow::Result<Vec<AssetItem>> {
+        Ok([
+            self.models.to_items()?,
+            self.prefabs.to_items()?,
+            self.meshes.to_items()?,
+            self.animations.to_items()?,
+            self.images.to_items()?,
+            self.materials.to_items()?,
+            self.px_triangle_meshes.to_items()?,
+            self.px_convex_meshes.to_items()?,
+            self.colliders.to_items()?,
         ]
         .into_iter()
         .flatten()
-        .collect_vec()
+        .collect_vec())
     }
     pub const MAIN: &str = "main";
     pub fn model(&self) -> &Model {
@@ -189,7 +202,7 @@ impl ModelCrate {
     }
 
     pub async fn produce_local_model_url(&self, path: PathBuf) -> anyhow::Result<PathBuf> {
-        self.write_to_fs(&path).await;
+        self.write_to_fs(&path).await?;
         let model_id = self.models.content.keys().next().unwrap();
         Ok(self.models.loc.path(model_id).to_path(path))
     }
@@ -207,9 +220,10 @@ impl ModelCrate {
         normalize: bool,
         force_assimp: bool,
         resolve_texture: TextureResolver,
+        gltf_options: &GltfImportOptions,
     ) -> anyhow::Result<()> {
         let is_fbx = url.extension().unwrap_or_default() == "fbx";
-        let is_glb = url.extension().unwrap_or_default() == "glb";
+        let is_gltf = matches!(url.extension().unwrap_or_default().as_str(), "glb" | "gltf");
         if force_assimp {
             crate::assimp::import_url(assets, url, self, resolve_texture).await?;
         } else if is_fbx {
@@ -228,8 +242,8 @@ impl ModelCrate {
                     Err(err) => return Err(err),
                 }
             }
-        } else if is_glb {
-            crate::gltf::import_url(assets, url, self).await?;
+        } else if is_gltf {
+            crate::gltf::import_url(assets, url, self, gltf_options).await?;
         } else {
             crate::assimp::import_url(assets, url, self, resolve_texture).await?;
         }
@@ -242,9 +256,7 @@ impl ModelCrate {
         Ok(())
     }
     pub fn merge_mesh_lods(&mut self, cutoffs: Option<Vec<f32>>, lods: Vec<ModelNodeRef>) {
-        let default_min_screen_size = 0.04; // i.e. 4%
-        let lod_step = (1. / default_min_screen_size).powf(1. / (lods.len() - 1) as f32);
-        let cutoffs = cutoffs.unwrap_or_else(|| (0..lods.len()).map(|i| 1. / lod_step.powi(i as i32)).collect_vec());
+        let cutoffs = cutoffs.unwrap_or_else(|| default_lod_cutoffs(lods.len()));
 
         let lod_0_node = lods[0].get_node_id();
         let lod_0_world = lods[0].world();
@@ -295,6 +307,99 @@ impl ModelCrate {
         world.add_resource(children(), vec![root]);
         self.models.insert(ModelCrate::MAIN, Model(world));
     }
+    /// Adds simplified versions of the meshes of every node as lods, unless the node already has lods. The lods of a mesh
+    /// stop at the first level that doesn't reduce its triangle count
+    pub fn generate_lods(&mut self, options: &LodGenerationOptions) {
+        if options.levels == 0 {
+            return;
+        }
+        let world = self.model_world();
+        let nodes = query(pbr_renderer_primitives_from_url())
+            .iter(world, None)
+            .filter(|(id, primitives)| !primitives.is_empty() && !world.has_component(*id, lod_cutoffs()))
+            .map(|(id, primitives)| (id, primitives.clone()))
+            .collect_vec();
+
+        // The lods of each mesh, and the accumulated error of each of them. Meshes may be shared between nodes
+        let mut mesh_lods = HashMap::<String, Vec<(RelativePathBuf, f32)>>::new();
+        for (id, primitives) in nodes {
+            let mut lods_of_primitives = Vec::new();
+            for primitive in &primitives {
+                let mesh_id = match self.meshes.loc.id_from_path(primitive.mesh.path()) {
+                    Some(mesh_id) => mesh_id,
+                    None => continue,
+                };
+                if !mesh_lods.contains_key(&mesh_id) {
+                    let mut mesh = match self.meshes.content.get(&mesh_id) {
+                        Some(mesh) => mesh.clone(),
+                        None => continue,
+                    };
+                    let mut error = 0.;
+                    let mut lods = Vec::new();
+                    for level in 1..=options.levels {
+                        let (simplified, lod_error) = match mesh.simplified(options.triangle_ratio, options.max_error) {
+                            Some(lod) => lod,
+                            None => break,
+                        };
+                        if simplified.indices.as_ref().map_or(0, |i| i.len()) >= mesh.indices.as_ref().map_or(0, |i| i.len()) {
+                            break;
+                        }
+                        mesh = simplified;
+                        error += lod_error;
+                        lods.push((self.meshes.insert(format!("{mesh_id}_lod{level}"), mesh.clone()).path, error));
+                    }
+                    mesh_lods.insert(mesh_id.clone(), lods);
+                }
+                lods_of_primitives.push((primitive, mesh_lods[&mesh_id].clone()));
+            }
+
+            let levels = lods_of_primitives.iter().map(|(_, lods)| lods.len()).max().unwrap_or(0);
+            if levels == 0 {
+                continue;
+            }
+            // Meshes with fewer lods than the node keep using their last one
+            let mut errors = vec![0f32; levels];
+            let mut lod_primitives = primitives.clone();
+            for (primitive, lods) in lods_of_primitives {
+                for level in 0..levels {
+                    let mesh = match lods.get(level).or(lods.last()) {
+                        Some((path, error)) => {
+                            errors[level] = errors[level].max(*error);
+                            dotdot_path(path.clone()).into()
+                        }
+                        None => primitive.mesh.clone(),
+                    };
+                    lod_primitives.push(PbrRenderPrimitiveFromUrl { mesh, material: primitive.material.clone(), lod: level + 1 });
+                }
+            }
+
+            let cutoffs = match (&options.lod_cutoffs, options.screen_space_error) {
+                (Some(cutoffs), _) => cutoffs[..levels.min(cutoffs.len())].to_vec(),
+                // The clip space size of the node's bounding sphere is about its size relative to half the screen height,
+                // so lod i + 1 can be used once `errors[i] * clip_space_size` is below the screen space error
+                (None, Some(screen_space_error)) => {
+                    let mut cutoffs = errors.iter().map(|&error| (screen_space_error / error).min(f32::MAX)).collect_vec();
+                    for i in 1..cutoffs.len() {
+                        cutoffs[i] = cutoffs[i].min(cutoffs[i - 1]);
+                    }
+                    cutoffs
+                }
+                // The last lod is never culled
+                (None, None) => default_lod_cutoffs(levels + 1)[..levels].to_vec(),
+            };
+
+            let world = self.model_world_mut();
+            world.set(id, pbr_renderer_primitives_from_url(), lod_primitives).unwrap();
+            world.add_component(id, lod_cutoffs(), LodCutoffs::new(&cutoffs)).unwrap();
+            world.add_component(id, gpu_lod(), ()).unwrap();
+        }
+    }
+    /// Reorders the triangles of all meshes to render faster, see [Mesh::optimize]
+    pub fn optimize_meshes(&mut self) {
+        for mesh in self.meshes.content.values_mut() {
+            mesh.optimize();
+        }
+    }
     pub fn merge_unity_style_mesh_lods(&mut self, source: &ModelCrate, cutoffs: Option<Vec<f32>>) {
         let mut lods = source.model_world().resource(children()).clone();
         lods.sort_by_key(|id| {
@@ -554,6 +659,13 @@ pub struct AssetItem {
     pub data: Arc<Vec<u8>>,
 }
 
+/// Cutoffs for `n` lods, spaced evenly in log space from the full screen down to 4% of it
+pub fn default_lod_cutoffs(n: usize) -> Vec<f32> {
+    let default_min_screen_size = 0.04f32; // i.e. 4%
+    let lod_step = (1. / default_min_screen_size).powf(1. / (n - 1) as f32);
+    (0..n).map(|i| 1. / lod_step.powi(i as i32)).collect_vec()
+}
+
 pub fn cap_texture_size(image: &mut RgbaImage, max_size: u32) {
     if image.width() > max_size || image.height() > max_size {
         let (width, height) = if image.width() >= image.height() {
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use ambient_core::{
+    camera::{fovy, perspective},
+    hierarchy::children,
+    main_scene, name,
+};
+use ambient_ecs::{query, EntityId, World};
+use ambient_model_import::{
+    gltf::{GltfImport, GltfImportOptions},
+    model_crate::ModelCrate,
+};
+use ambient_renderer::sun;
+use serde_json::json;
+
+/// A root node with extras, a directional light, a point light and a camera below it
+fn scene_gltf() -> Vec<u8> {
+    json!({
+        "asset": { "version": "2.0" },
+        "extensionsUsed": ["KHR_lights_punctual"],
+        "extensions": {
+            "KHR_lights_punctual": {
+                "lights": [
+                    { "type": "directional", "name": "key", "intensity": 2.0 },
+                    { "type": "point", "name": "bulb" }
+                ]
+            }
+        },
+        "cameras": [{ "type": "perspective", "perspective": { "yfov": 0.8, "znear": 0.1, "zfar": 100.0 } }],
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [
+            { "name": "root", "children": [1, 2, 3], "extras": { "app::main_scene": true, "not_a_component": 1 } },
+            { "name": "key_node", "extensions": { "KHR_lights_punctual": { "light": 0 } } },
+            { "name": "bulb_node", "extensions": { "KHR_lights_punctual": { "light": 1 } } },
+            { "name": "camera_node", "camera": 0 }
+        ]
+    })
+    .to_string()
+    .into_bytes()
+}
+
+async fn import(options: &GltfImportOptions) -> ModelCrate {
+    let gltf = GltfImport::from_slice("scene.gltf".to_string(), true, scene_gltf()).unwrap();
+    let mut model_crate = ModelCrate::new();
+    ambient_model_import::gltf::import(&gltf, &mut model_crate, options).await.unwrap();
+    model_crate
+}
+
+fn find_by_name(world: &World, node_name: &str) -> EntityId {
+    query(name()).iter(world, None).find(|(_, n)| n.as_str() == node_name).unwrap().0
+}
+
+#[tokio::test]
+async fn extras_are_opt_in() {
+    ambient_app::init_all_components();
+    let model_crate = import(&GltfImportOptions::default()).await;
+    let world = model_crate.model_world();
+    assert!(!world.has_component(find_by_name(world, "root"), main_scene()));
+
+    let model_crate = import(&GltfImportOptions { extras: true, ..Default::default() }).await;
+    let world = model_crate.model_world();
+    assert!(world.has_component(find_by_name(world, "root"), main_scene()));
+}
+
+#[tokio::test]
+async fn only_directional_lights_are_imported() {
+    ambient_app::init_all_components();
+    let model_crate = import(&GltfImportOptions::default()).await;
+    let world = model_crate.model_world();
+
+    let suns = query(sun()).iter(world, None).map(|(id, _)| id).collect::<Vec<_>>();
+    assert_eq!(suns.len(), 1);
+    assert_eq!(world.get_ref(suns[0], name()).unwrap(), "key");
+    assert!(world.get_ref(find_by_name(world, "key_node"), children()).unwrap().contains(&suns[0]));
+    assert!(world.get_ref(find_by_name(world, "bulb_node"), children()).is_err());
+
+    let model_crate = import(&GltfImportOptions { lights: false, ..Default::default() }).await;
+    assert_eq!(query(sun()).iter(model_crate.model_world(), None).count(), 0);
+}
+
+#[tokio::test]
+async fn cameras_are_opt_in() {
+    ambient_app::init_all_components();
+    let model_crate = import(&GltfImportOptions::default()).await;
+    let world = model_crate.model_world();
+    assert!(!world.has_component(find_by_name(world, "camera_node"), perspective()));
+
+    let model_crate = import(&GltfImportOptions { cameras: true, ..Default::default() }).await;
+    let world = model_crate.model_world();
+    let camera = find_by_name(world, "camera_node");
+    assert!(world.has_component(camera, perspective()));
+    assert_eq!(world.get(camera, fovy()).unwrap(), 0.8);
+}
+
+#[test]
+fn external_buffers() {
+    let gltf = json!({
+        "asset": { "version": "2.0" },
+        "buffers": [
+            { "uri": "data.bin", "byteLength": 4 },
+            { "uri": "data:application/octet-stream;base64,AAAAAA==", "byteLength": 4 }
+        ]
+    })
+    .to_string();
+    let document = gltf::Gltf::from_slice(gltf.as_bytes()).unwrap().document;
+    assert_eq!(GltfImport::external_uris(&document), vec!["data.bin".to_string()]);
+
+    assert!(GltfImport::from_slice("scene.gltf".to_string(), true, &gltf).is_err());
+    let resources = HashMap::from([("data.bin".to_string(), vec![1, 2, 3, 4])]);
+    let import = GltfImport::from_slice_with_resources("scene.gltf".to_string(), true, &gltf, &resources).unwrap();
+    assert_eq!(&import.buffers[0][..], &[1, 2, 3, 4]);
+    assert_eq!(&import.buffers[1][..], &[0, 0, 0, 0]);
+}
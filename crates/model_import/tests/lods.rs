@@ -0,0 +1,121 @@
+use ambient_core::name;
+use ambient_ecs::{query, Entity, World};
+use ambient_model::{pbr_renderer_primitives_from_url, Model, PbrRenderPrimitiveFromUrl};
+use ambient_model_import::{dotdot_path, model_crate::ModelCrate, LodGenerationOptions};
+use ambient_renderer::lod::{gpu_lod, lod_cutoffs, LodCutoffs};
+use ambient_std::mesh::Mesh;
+use glam::vec3;
+use itertools::Itertools;
+
+/// A bumpy grid, so that the simplification has some error
+fn grid_mesh(size: u32) -> Mesh {
+    let positions = (0..=size)
+        .flat_map(|y| (0..=size).map(move |x| (x as f32, y as f32)))
+        .map(|(x, y)| vec3(x, y, (x * 0.3).sin() + (y * 0.2).cos()))
+        .collect_vec();
+    let indices = (0..size)
+        .flat_map(|y| (0..size).map(move |x| y * (size + 1) + x))
+        .flat_map(|i| [i, i + 1, i + size + 2, i, i + size + 2, i + size + 1])
+        .collect_vec();
+    Mesh { positions: Some(positions), indices: Some(indices), ..Default::default() }
+}
+
+fn model_crate_with_mesh() -> ModelCrate {
+    model_crate_with(grid_mesh(32))
+}
+
+fn model_crate_with(mesh: Mesh) -> ModelCrate {
+    let mut model_crate = ModelCrate::new();
+    let mesh = model_crate.meshes.insert("grid", mesh).path;
+    let mut world = World::new("model");
+    Entity::new()
+        .with(name(), "grid".to_string())
+        .with(
+            pbr_renderer_primitives_from_url(),
+            vec![PbrRenderPrimitiveFromUrl { mesh: dotdot_path(mesh).into(), material: None, lod: 0 }],
+        )
+        .spawn(&mut world);
+    model_crate.models.insert(ModelCrate::MAIN, Model(world));
+    model_crate
+}
+
+fn primitive_triangle_counts(model_crate: &ModelCrate) -> Vec<(usize, usize)> {
+    let world = model_crate.model_world();
+    let (_, primitives) = query(pbr_renderer_primitives_from_url()).iter(world, None).next().unwrap();
+    primitives
+        .iter()
+        .map(|primitive| {
+            let mesh = model_crate.meshes.get_by_path(primitive.mesh.path()).unwrap();
+            (primitive.lod, mesh.indices.as_ref().unwrap().len() / 3)
+        })
+        .collect()
+}
+
+#[test]
+fn generate_lods() {
+    ambient_app::init_all_components();
+    let mut model_crate = model_crate_with_mesh();
+    model_crate.generate_lods(&LodGenerationOptions { levels: 3, triangle_ratio: 0.5, max_error: 0.1, ..Default::default() });
+
+    let counts = primitive_triangle_counts(&model_crate);
+    assert_eq!(counts.iter().map(|(lod, _)| *lod).collect_vec(), vec![0, 1, 2, 3]);
+    for ((_, a), (_, b)) in counts.iter().tuple_windows() {
+        assert!(b < a, "{counts:?}");
+    }
+
+    let world = model_crate.model_world();
+    let (id, _) = query(pbr_renderer_primitives_from_url()).iter(world, None).next().unwrap();
+    assert!(world.has_component(id, gpu_lod()));
+    let cutoffs = world.get(id, lod_cutoffs()).unwrap();
+    // The last lod is never culled
+    let expected = ambient_model_import::model_crate::default_lod_cutoffs(4);
+    assert!(cutoffs == LodCutoffs::new(&expected[..3]));
+
+    // Nodes which already have lods are left as they are
+    model_crate.generate_lods(&LodGenerationOptions::default());
+    assert_eq!(primitive_triangle_counts(&model_crate).len(), 4);
+}
+
+#[test]
+fn generate_lods_from_screen_space_error() {
+    ambient_app::init_all_components();
+    let mut model_crate = model_crate_with_mesh();
+    model_crate.generate_lods(&LodGenerationOptions { levels: 2, screen_space_error: Some(0.002), ..Default::default() });
+    let world = model_crate.model_world();
+    let (id, _) = query(pbr_renderer_primitives_from_url()).iter(world, None).next().unwrap();
+    let cutoffs = world.get(id, lod_cutoffs()).unwrap();
+    assert!(cutoffs != LodCutoffs::default());
+}
+
+#[test]
+fn generate_lods_stops_when_the_mesh_stops_shrinking() {
+    ambient_app::init_all_components();
+    // Only the borders of a flat quad are left, so it can't be simplified at all
+    let mut quad = grid_mesh(1);
+    quad.positions.as_mut().unwrap().iter_mut().for_each(|p| p.z = 0.);
+    let mut model_crate = model_crate_with(quad);
+    model_crate.generate_lods(&LodGenerationOptions::default());
+    assert_eq!(primitive_triangle_counts(&model_crate), vec![(0, 2)]);
+    assert_eq!(model_crate.meshes.content.len(), 1);
+
+    let mut model_crate = model_crate_with_mesh();
+    model_crate.generate_lods(&LodGenerationOptions { levels: 20, triangle_ratio: 0.5, max_error: 0.1, ..Default::default() });
+    let counts = primitive_triangle_counts(&model_crate);
+    assert!(counts.len() < 21, "{counts:?}");
+    for ((_, a), (_, b)) in counts.iter().tuple_windows() {
+        assert!(b < a, "{counts:?}");
+    }
+    let world = model_crate.model_world();
+    let (id, _) = query(pbr_renderer_primitives_from_url()).iter(world, None).next().unwrap();
+    let expected = ambient_model_import::model_crate::default_lod_cutoffs(counts.len());
+    assert!(world.get(id, lod_cutoffs()).unwrap() == LodCutoffs::new(&expected[..counts.len() - 1]));
+}
+
+#[test]
+fn optimize_meshes_keeps_triangles() {
+    ambient_app::init_all_components();
+    let mut model_crate = model_crate_with_mesh();
+    let before = primitive_triangle_counts(&model_crate);
+    model_crate.optimize_meshes();
+    assert_eq!(primitive_triangle_counts(&model_crate), before);
+}
@@ -16,13 +16,14 @@ use ambient_ecs::{components, query, Entity, EntityId, Resource, SystemGroup, Wo
 use ambient_element::{Element, ElementComponent, ElementComponentExt, Hooks};
 use ambient_renderer::RenderTarget;
 use ambient_rpc::RpcRegistry;
-use ambient_std::{cb, fps_counter::FpsSample, log_result, to_byte_unit, CallbackFn, Cb};
+use ambient_std::{
+    asset_cache::SyncAssetKeyExt, asset_url::ServerBaseUrlKey, cb, fps_counter::FpsSample, log_result, to_byte_unit, CallbackFn, Cb,
+};
 use ambient_ui::{Button, Centered, FlowColumn, FlowRow, Image, Text, Throbber};
 use anyhow::Context;
 use futures::{io::BufReader, AsyncBufReadExt, AsyncReadExt, Future, StreamExt};
 use glam::UVec2;
 use parking_lot::Mutex;
-use quinn::{Connection, NewConnection};
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
@@ -33,7 +34,8 @@ use crate::{
     protocol::{ClientInfo, ClientProtocol},
     rpc_request,
     server::{ServerInfo, SharedServerState},
-    NetworkError,
+    transport::{Connection, NewConnection, Transport},
+    websocket, NetworkError,
 };
 
 components!("network", {
@@ -139,6 +141,7 @@ pub type InitCallback = Box<dyn FnOnce(&mut World, Arc<RenderTarget>) + Send + S
 #[derive(Debug)]
 pub struct GameClientView {
     pub server_addr: SocketAddr,
+    pub transport: Transport,
     pub user_id: String,
     pub resolution: UVec2,
     pub systems_and_resources: Cb<dyn Fn() -> (SystemGroup, Entity) + Sync + Send>,
@@ -157,6 +160,7 @@ impl Clone for GameClientView {
     fn clone(&self) -> Self {
         Self {
             server_addr: self.server_addr,
+            transport: self.transport,
             user_id: self.user_id.clone(),
             resolution: self.resolution,
             systems_and_resources: self.systems_and_resources.clone(),
@@ -177,6 +181,7 @@ impl ElementComponent for GameClientView {
     fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
         let Self {
             server_addr,
+            transport,
             user_id,
             resolution,
             init_world,
@@ -246,11 +251,15 @@ impl ElementComponent for GameClientView {
                         }
                     };
 
+                    let rpc_registry = Arc::new(create_rpc_registry());
+
                     let mut on_init = {
                         let game_state = game_state.clone();
+                        let rpc_registry = rpc_registry.clone();
+                        let assets = assets.clone();
                         move |conn, client_info: ClientInfo, server_info: ServerInfo| {
-                            let game_client =
-                                GameClient::new(conn, Arc::new(create_rpc_registry()), game_state.clone(), client_info.user_id);
+                            let game_client = GameClient::new(conn, rpc_registry.clone(), game_state.clone(), client_info.user_id);
+                            ServerBaseUrlKey.insert(&assets, server_info.content_base_url);
 
                             game_state.lock().world.add_resource(self::game_client(), Some(game_client.clone()));
 
@@ -281,7 +290,9 @@ impl ElementComponent for GameClientView {
                     let client_loop = ClientInstance {
                         set_connection_status,
                         server_addr,
+                        transport,
                         user_id,
+                        rpc_registry,
                         on_init: &mut on_init,
                         on_diff: &mut on_diff,
                         on_server_stats: &mut on_server_stats,
@@ -343,7 +354,10 @@ impl ElementComponent for GameClientView {
 struct ClientInstance<'a> {
     set_connection_status: CallbackFn<String>,
     server_addr: SocketAddr,
+    transport: Transport,
     user_id: String,
+    /// Checked against the RPCs of the server before anything else happens
+    rpc_registry: Arc<RpcRegistry<GameRpcArgs>>,
 
     /// Called when the client connected and received the world.
     on_init: &'a mut (dyn FnMut(Connection, ClientInfo, ServerInfo) -> anyhow::Result<Box<dyn FnOnce() + Sync + Send>> + Send + Sync),
@@ -368,15 +382,18 @@ impl<'a> Drop for ClientInstance<'a> {
 impl<'a> ClientInstance<'a> {
     #[tracing::instrument(skip(self))]
     async fn run(mut self) -> anyhow::Result<()> {
-        log::info!("Connecting to server at {}", self.server_addr);
+        log::info!("Connecting to server at {} over {}", self.server_addr, self.transport);
         (self.set_connection_status)(format!("Connecting to {}", self.server_addr));
-        let conn = open_connection(self.server_addr).await?;
+        let conn = open_connection(self.server_addr, self.transport).await?;
 
         (self.set_connection_status)("Waiting for server to respond".to_string());
 
         // Set up the protocol.
         let mut protocol = ClientProtocol::new(conn, self.user_id.clone()).await?;
 
+        // Fail before receiving the world if the RPCs this client calls can't be called on the server
+        self.rpc_registry.check_compatibility(&protocol.server_info.rpc_table).context("The server is incompatible with this client")?;
+
         let stats_interval = 5;
         let mut stats_timer = tokio::time::interval(Duration::from_secs_f32(stats_interval as f32));
         let mut prev_stats = protocol.connection().stats();
@@ -404,8 +421,8 @@ impl<'a> ClientInstance<'a> {
 
                     (self.on_client_stats)(GameClientNetworkStats {
                         latency_ms: protocol.connection().rtt().as_millis() as u64,
-                        bytes_sent: (stats.udp_tx.bytes - prev_stats.udp_tx.bytes) / stats_interval,
-                        bytes_received: (stats.udp_rx.bytes - prev_stats.udp_rx.bytes) / stats_interval,
+                        bytes_sent: (stats.bytes_sent - prev_stats.bytes_sent) / stats_interval,
+                        bytes_received: (stats.bytes_received - prev_stats.bytes_received) / stats_interval,
                     });
 
                     prev_stats = stats;
@@ -425,6 +442,19 @@ impl<'a> ClientInstance<'a> {
 
                     (self.on_event)(event_name, event_data.into_boxed_slice());
                 }
+                Some(Ok(datagram)) = protocol.conn.datagrams.next() => {
+                    // Datagrams from the server are encoded like the events on uni streams
+                    let split = match datagram.iter().position(|&b| b == b'\n') {
+                        Some(split) => split,
+                        None => {
+                            log::warn!("Received a datagram without an event name");
+                            continue;
+                        }
+                    };
+                    let event_name = std::str::from_utf8(&datagram[..split]).context("Event did not contain valid UTF-8")?;
+
+                    (self.on_event)(event_name.to_string(), datagram[split + 1..].into());
+                }
             }
         }
     }
@@ -450,13 +480,18 @@ pub struct GameClientServerStats(pub FpsSample);
 /// Connnect to the server endpoint.
 /// Does not handle a protocol.
 #[tracing::instrument(level = "debug")]
-pub async fn open_connection(server_addr: SocketAddr) -> anyhow::Result<NewConnection> {
-    log::debug!("Connecting to world instance: {server_addr:?}");
+pub async fn open_connection(server_addr: SocketAddr, transport: Transport) -> anyhow::Result<NewConnection> {
+    log::debug!("Connecting to world instance: {server_addr:?} over {transport}");
 
-    let endpoint = create_client_endpoint_random_port().context("Failed to create client endpoint")?;
+    let conn: NewConnection = match transport {
+        Transport::Quic => {
+            let endpoint = create_client_endpoint_random_port().context("Failed to create client endpoint")?;
 
-    log::debug!("Got endpoint");
-    let conn = endpoint.connect(server_addr, "localhost")?.await?;
+            log::debug!("Got endpoint");
+            endpoint.connect(server_addr, "localhost")?.await?.into()
+        }
+        Transport::WebSocket => websocket::connect(server_addr).await?,
+    };
 
     log::debug!("Got connection");
     Ok(conn)
@@ -0,0 +1,319 @@
+use ambient_core::{
+    camera::get_active_camera,
+    main_scene,
+    player::{get_player_by_user_id, local_user_id, player},
+    transform::{local_to_world, mesh_to_local, mesh_to_world, rotation, scale, translation},
+};
+use ambient_ecs::{components, query, Debuggable, Description, Entity, EntityId, FnSystem, Name, Networked, Resource, SystemGroup, World};
+use ambient_gizmos::{gizmos, Cuboid, GizmoPrimitive, DEFAULT_WIDTH};
+use ambient_renderer::color;
+use ambient_std::line_hash;
+use ambient_sys::time::Instant;
+use ambient_ui::text;
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::{send_event, ServerEventRegistry},
+    ServerWorldExt,
+};
+
+components!("network::debug_draw", {
+    @[
+        Debuggable, Networked,
+        Name["Debug draw categories"],
+        Description["The categories of debug drawings which are sent to this player. Only editors can change them."]
+    ]
+    debug_draw_categories: Vec<String>,
+    @[
+        Debuggable, Networked,
+        Name["Debug draw known categories"],
+        Description["The categories of the debug drawings made so far on the server, up to a limit."]
+    ]
+    debug_draw_known_categories: Vec<String>,
+    /// The drawings made this frame on the server, which are sent to the players at the end of it
+    @[Resource]
+    debug_draw_queue: Vec<DebugDraw>,
+    /// The drawings received by the client, until they expire
+    @[Resource]
+    debug_draws: Vec<ActiveDebugDraw>,
+});
+
+/// Drawings last at least this long, in seconds, so that drawings which are redrawn every frame don't flicker
+const MIN_LIFETIME: f32 = 0.05;
+const TEXT_SCALE: f32 = 0.01;
+/// Categories beyond this many aren't added to [debug_draw_known_categories], so that categories made up at runtime
+/// (e.g. with an entity id in them) don't grow it forever. Drawings in them are still sent to the players which enable them
+pub const MAX_KNOWN_CATEGORIES: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DebugShape {
+    Line { start: Vec3, end: Vec3 },
+    Arrow { start: Vec3, end: Vec3 },
+    Sphere { center: Vec3, radius: f32 },
+    Box { center: Vec3, half_extents: Vec3 },
+    Text { position: Vec3, text: String },
+}
+
+/// A shape drawn by the server, which is shown on the clients which have its category enabled
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DebugDraw {
+    pub category: String,
+    pub shape: DebugShape,
+    pub color: Vec3,
+    /// How long the shape is shown, in seconds
+    pub lifetime: f32,
+}
+impl DebugDraw {
+    /// The gizmos which draw the shape. Text is drawn separately, so it has none
+    pub fn gizmo_primitives(&self) -> Vec<GizmoPrimitive> {
+        match &self.shape {
+            DebugShape::Line { start, end } => vec![GizmoPrimitive::line(*start, *end, DEFAULT_WIDTH)],
+            DebugShape::Arrow { start, end } => {
+                let dir = *end - *start;
+                let length = dir.length();
+                if length <= f32::EPSILON {
+                    return Vec::new();
+                }
+                let dir = dir / length;
+                let side = dir.any_orthonormal_vector() * length * 0.1;
+                let back = *end - dir * length * 0.2;
+                vec![
+                    GizmoPrimitive::line(*start, *end, DEFAULT_WIDTH),
+                    GizmoPrimitive::line(*end, back + side, DEFAULT_WIDTH),
+                    GizmoPrimitive::line(*end, back - side, DEFAULT_WIDTH),
+                ]
+            }
+            DebugShape::Sphere { center, radius } => vec![GizmoPrimitive::torus(*center, *radius, DEFAULT_WIDTH)],
+            DebugShape::Box { center, half_extents } => {
+                Cuboid::new(*center, *half_extents, self.color, DEFAULT_WIDTH).into_iter().collect()
+            }
+            DebugShape::Text { .. } => Vec::new(),
+        }
+        .into_iter()
+        .map(|primitive| primitive.with_color(self.color))
+        .collect()
+    }
+}
+
+/// A drawing shown on the client
+#[derive(Debug, Clone)]
+pub struct ActiveDebugDraw {
+    pub draw: DebugDraw,
+    pub expires: Instant,
+    /// Text is drawn with an entity of its own
+    text_entity: Option<EntityId>,
+}
+
+/// The debug drawings made during one frame of the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DebugDrawBatch(Vec<DebugDraw>);
+
+/// Draws a shape for the players which have its category enabled. The drawings are sent at the end of the frame
+pub fn debug_draw(world: &mut World, draw: DebugDraw) {
+    if let Some(synced) = world.synced_resource_entity() {
+        let known = world.get_ref(synced, debug_draw_known_categories()).ok();
+        let is_known = known.map(|known| known.contains(&draw.category)).unwrap_or(false);
+        let is_full = known.map(|known| known.len() >= MAX_KNOWN_CATEGORIES).unwrap_or(false);
+        if is_full && !is_known {
+            log::debug!("Not adding the debug draw category {:?}; there are already {MAX_KNOWN_CATEGORIES}", draw.category);
+        } else if !is_known {
+            let mut known = known.cloned().unwrap_or_default();
+            known.push(draw.category.clone());
+            known.sort();
+            world.add_component(synced, debug_draw_known_categories(), known).unwrap();
+        }
+    }
+    match world.resource_mut_opt(debug_draw_queue()) {
+        Some(queue) => queue.push(draw),
+        None => world.add_resource(debug_draw_queue(), vec![draw]),
+    }
+}
+
+/// Sends the debug drawings of the frame to the players which have their categories enabled. Should run at the end of the frame
+pub fn server_systems() -> SystemGroup {
+    SystemGroup::new(
+        "debug_draw/server",
+        vec![
+            query(player()).spawned().to_system(|q, world, qs, _| {
+                for id in q.collect_ids(world, qs) {
+                    world.add_component(id, debug_draw_categories(), Vec::new()).ok();
+                }
+            }),
+            Box::new(FnSystem::new(|world, _| {
+                let queue = match world.resource_mut_opt(debug_draw_queue()) {
+                    Some(queue) if !queue.is_empty() => std::mem::take(queue),
+                    _ => return,
+                };
+                for (player_id, (_, categories)) in query((player(), debug_draw_categories())).iter(world, None) {
+                    let draws = in_categories(&queue, categories);
+                    if !draws.is_empty() {
+                        send_event(world, player_id, DebugDrawBatch(draws));
+                    }
+                }
+            })),
+        ],
+    )
+}
+
+/// The drawings in `draws` which are in one of the `categories`
+fn in_categories(draws: &[DebugDraw], categories: &[String]) -> Vec<DebugDraw> {
+    draws.iter().filter(|draw| categories.contains(&draw.category)).cloned().collect()
+}
+
+/// Splits `draws` into those which should still be shown and those which should be removed, because they expired or
+/// their category was disabled
+fn partition_active(draws: Vec<ActiveDebugDraw>, categories: &[String], now: Instant) -> (Vec<ActiveDebugDraw>, Vec<ActiveDebugDraw>) {
+    draws.into_iter().partition(|draw| draw.expires > now && categories.contains(&draw.draw.category))
+}
+
+/// Makes the debug drawings from the server show up on the client
+pub fn register_client_debug_draw_handler(registry: &ServerEventRegistry) {
+    registry.register(|world: &mut World, DebugDrawBatch(draws): DebugDrawBatch| {
+        let now = Instant::now();
+        let active = draws
+            .into_iter()
+            .map(|draw| {
+                let text_entity = match &draw.shape {
+                    DebugShape::Text { position, text: content } => Some(
+                        Entity::new()
+                            .with(text(), content.clone())
+                            .with(color(), draw.color.extend(1.))
+                            .with(translation(), *position)
+                            .with_default(rotation())
+                            // Text meshes are laid out with y going down, like the UI
+                            .with(scale(), Vec3::new(TEXT_SCALE, -TEXT_SCALE, TEXT_SCALE))
+                            .with_default(local_to_world())
+                            .with_default(mesh_to_local())
+                            .with_default(mesh_to_world())
+                            .with_default(main_scene())
+                            .spawn(world),
+                    ),
+                    _ => None,
+                };
+                let expires = now + std::time::Duration::from_secs_f32(draw.lifetime.max(MIN_LIFETIME));
+                ActiveDebugDraw { draw, expires, text_entity }
+            })
+            .collect::<Vec<_>>();
+        match world.resource_mut_opt(debug_draws()) {
+            Some(draws) => draws.extend(active),
+            None => world.add_resource(debug_draws(), active),
+        }
+        Ok(())
+    });
+}
+
+/// Removes the expired debug drawings, and draws the others with gizmos. Only the categories enabled for the local player
+/// are drawn, so disabling a category hides its drawings right away
+pub fn client_systems() -> SystemGroup {
+    SystemGroup::new(
+        "debug_draw/client",
+        vec![Box::new(FnSystem::new(|world, _| {
+            let draws = match world.resource_opt(debug_draws()) {
+                Some(draws) => draws.clone(),
+                None => return,
+            };
+            let categories = world
+                .resource_opt(local_user_id())
+                .and_then(|user_id| get_player_by_user_id(world, user_id))
+                .and_then(|player_id| world.get_cloned(player_id, debug_draw_categories()).ok())
+                .unwrap_or_default();
+            let (active, expired) = partition_active(draws, &categories, Instant::now());
+            for draw in &expired {
+                if let Some(id) = draw.text_entity {
+                    world.despawn(id);
+                }
+            }
+
+            // Text faces the camera
+            let camera_rotation = get_active_camera(world, main_scene(), world.resource_opt(local_user_id()))
+                .and_then(|camera| world.get(camera, local_to_world()).ok())
+                .map(|transform| transform.to_scale_rotation_translation().1);
+            let mut scope = world.resource(gizmos()).scope(line_hash!());
+            for draw in &active {
+                scope.draw(draw.draw.gizmo_primitives());
+            }
+            drop(scope);
+            if let Some(camera_rotation) = camera_rotation {
+                for id in active.iter().filter_map(|draw| draw.text_entity) {
+                    world.set(id, rotation(), camera_rotation).ok();
+                }
+            }
+            *world.resource_mut(debug_draws()) = active;
+        }))],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use glam::vec3;
+
+    use super::*;
+    use crate::synced_resources;
+
+    fn draw(category: &str, shape: DebugShape) -> DebugDraw {
+        DebugDraw { category: category.to_string(), shape, color: vec3(1., 0., 0.), lifetime: 1. }
+    }
+
+    #[test]
+    fn gizmo_primitives() {
+        let line = draw("a", DebugShape::Line { start: Vec3::ZERO, end: Vec3::X });
+        assert_eq!(line.gizmo_primitives(), vec![GizmoPrimitive::line(Vec3::ZERO, Vec3::X, DEFAULT_WIDTH).with_color(line.color)]);
+
+        let arrow = draw("a", DebugShape::Arrow { start: Vec3::ZERO, end: Vec3::X });
+        let primitives = arrow.gizmo_primitives();
+        assert_eq!(primitives.len(), 3);
+        // The head ends at the tip
+        for primitive in &primitives[1..] {
+            assert!(matches!(primitive, GizmoPrimitive::Line { start, .. } if *start == Vec3::X));
+        }
+        assert!(draw("a", DebugShape::Arrow { start: Vec3::X, end: Vec3::X }).gizmo_primitives().is_empty());
+
+        let sphere = draw("a", DebugShape::Sphere { center: Vec3::Y, radius: 2. });
+        assert!(matches!(sphere.gizmo_primitives()[..], [GizmoPrimitive::Sphere { radius, .. }] if radius == 2.));
+        assert_eq!(draw("a", DebugShape::Box { center: Vec3::ZERO, half_extents: Vec3::ONE }).gizmo_primitives().len(), 6);
+        assert!(draw("a", DebugShape::Text { position: Vec3::ZERO, text: "hi".to_string() }).gizmo_primitives().is_empty());
+
+        let mut colors = [line, arrow, sphere].iter().flat_map(|draw| draw.gizmo_primitives()).map(|primitive| match primitive {
+            GizmoPrimitive::Sphere { color, .. } | GizmoPrimitive::Line { color, .. } | GizmoPrimitive::Rect { color, .. } => color,
+        });
+        assert!(colors.all(|color| color == vec3(1., 0., 0.)));
+    }
+
+    #[test]
+    fn category_filtering() {
+        let shape = DebugShape::Line { start: Vec3::ZERO, end: Vec3::X };
+        let queue = vec![draw("physics", shape.clone()), draw("ai", shape.clone()), draw("physics", shape)];
+        assert_eq!(in_categories(&queue, &["physics".to_string()]).len(), 2);
+        assert_eq!(in_categories(&queue, &["physics".to_string(), "ai".to_string()]).len(), 3);
+        assert!(in_categories(&queue, &[]).is_empty());
+
+        let now = Instant::now();
+        let active =
+            |category: &str, expires: Instant| ActiveDebugDraw { draw: draw(category, queue[0].shape.clone()), expires, text_entity: None };
+        let draws =
+            vec![active("physics", now + Duration::from_secs(1)), active("physics", now), active("ai", now + Duration::from_secs(1))];
+        let (active, expired) = partition_active(draws, &["physics".to_string()], now);
+        assert_eq!(active.len(), 1);
+        // Drawings are removed when they expire, and when their category is disabled
+        assert_eq!(expired.len(), 2);
+    }
+
+    #[test]
+    fn known_categories_are_capped() {
+        ambient_core::init_all_components();
+        crate::init_all_components();
+        let mut world = World::new("test");
+        let synced = Entity::new().with_default(synced_resources()).spawn(&mut world);
+        for i in 0..MAX_KNOWN_CATEGORIES + 10 {
+            debug_draw(&mut world, draw(&format!("category_{i}"), DebugShape::Line { start: Vec3::ZERO, end: Vec3::X }));
+        }
+        debug_draw(&mut world, draw("category_0", DebugShape::Line { start: Vec3::ZERO, end: Vec3::X }));
+
+        assert_eq!(world.get_ref(synced, debug_draw_known_categories()).unwrap().len(), MAX_KNOWN_CATEGORIES);
+        // Drawings in the other categories are still queued
+        assert_eq!(world.resource(debug_draw_queue()).len(), MAX_KNOWN_CATEGORIES + 11);
+    }
+}
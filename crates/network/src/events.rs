@@ -51,8 +51,8 @@ impl Handler {
 
         Self { func: cb(func) }
     }
-    pub fn run(&self, gs: &Mutex<ClientGameState>, event: Box<[u8]>) -> anyhow::Result<()> {
-        (self.func)(&mut gs.lock().world, &event)
+    pub fn run(&self, world: &mut World, event: &[u8]) -> anyhow::Result<()> {
+        (self.func)(world, event)
     }
 }
 
@@ -96,9 +96,14 @@ impl ServerEventRegistry {
     /// Handle an event of any type from the incoming stream.
     /// Requires the locked game state as std::MutexGuard is non-send.
     pub fn handle_event(&self, gs: &Mutex<ClientGameState>, event_name: &str, event_data: Box<[u8]>) -> anyhow::Result<()> {
+        self.handle_event_in_world(&mut gs.lock().world, event_name, &event_data)
+    }
+
+    /// Handles an event like [ServerEventRegistry::handle_event], directly in `world`
+    pub(crate) fn handle_event_in_world(&self, world: &mut World, event_name: &str, event_data: &[u8]) -> anyhow::Result<()> {
         let handler = self.handlers.get(event_name);
         if let Some(handler) = handler {
-            handler.run(gs, event_data).context(format!("Failed to run event handler for {event_name:?}"))?;
+            handler.run(world, event_data).context(format!("Failed to run event handler for {event_name:?}"))?;
         } else {
             return Err(anyhow::anyhow!(format!("No handler for {event_name:?}")));
         };
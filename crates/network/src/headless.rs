@@ -0,0 +1,164 @@
+use std::{fmt::Display, net::SocketAddr, time::Duration};
+
+use ambient_ecs::{Entity, World, WorldDiff};
+use ambient_rpc::RpcRegistry;
+use ambient_std::{fps_counter::FpsSample, to_byte_unit};
+use ambient_sys::time::Instant;
+use anyhow::Context;
+use bytes::Bytes;
+use futures::StreamExt;
+
+use crate::{
+    client::{open_connection, GameRpcArgs},
+    is_remote_entity,
+    protocol::{ClientInfo, ClientProtocol},
+    server::ServerInfo,
+    transport::{Connection, Transport},
+};
+
+/// A client which keeps a copy of the world of the server in a plain [World], without a window or a renderer.
+///
+/// Used for bots and load tests; the events and datagrams from the server are received but ignored
+pub struct HeadlessClient {
+    pub world: World,
+    protocol: ClientProtocol,
+    stats: HeadlessClientStats,
+    last_diff: Instant,
+}
+impl HeadlessClient {
+    /// Connects to the server and waits until the world has been received
+    pub async fn connect(
+        server_addr: SocketAddr,
+        transport: Transport,
+        user_id: String,
+        rpc_registry: &RpcRegistry<GameRpcArgs>,
+    ) -> anyhow::Result<Self> {
+        let conn = open_connection(server_addr, transport).await?;
+        let mut protocol = ClientProtocol::new(conn, user_id).await?;
+        rpc_registry.check_compatibility(&protocol.server_info.rpc_table).context("The server is incompatible with this client")?;
+
+        let diff = protocol.next_diff().await?;
+        let mut client = Self { world: World::new("headless_client"), protocol, stats: Default::default(), last_diff: Instant::now() };
+        client.apply_diff(diff);
+        Ok(client)
+    }
+
+    pub fn client_info(&self) -> &ClientInfo {
+        self.protocol.client_info()
+    }
+    pub fn server_info(&self) -> &ServerInfo {
+        &self.protocol.server_info
+    }
+    pub fn connection(&self) -> Connection {
+        self.protocol.connection()
+    }
+    pub fn send_datagram(&self, data: Bytes) -> anyhow::Result<()> {
+        Ok(self.protocol.connection().send_datagram(data)?)
+    }
+
+    /// Waits for the next message from the server and handles it. Returns an error when the connection is lost
+    ///
+    /// Safe to cancel, e.g. in a `select!`
+    pub async fn update(&mut self) -> anyhow::Result<()> {
+        tokio::select! {
+            diff = self.protocol.diff_stream.next::<WorldDiff>() => {
+                self.apply_diff(diff.context("Failed to read world diff")?);
+            }
+            stats = self.protocol.stat_stream.next::<FpsSample>() => {
+                self.stats.server = Some(stats.context("Failed to read server stats")?);
+            }
+            Some(stream) = self.protocol.conn.uni_streams.next() => {
+                let stream = stream?;
+                // Read the events in the background, so that a slow event doesn't hold up the diffs
+                tokio::spawn(async move {
+                    stream.read_to_end(usize::MAX).await.ok();
+                });
+            }
+            Some(datagram) = self.protocol.conn.datagrams.next() => {
+                datagram?;
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_diff(&mut self, diff: WorldDiff) {
+        let interval = self.last_diff.elapsed();
+        self.last_diff = Instant::now();
+
+        let start = Instant::now();
+        diff.apply(&mut self.world, Entity::new().with(is_remote_entity(), ()), false);
+        let apply_time = start.elapsed();
+
+        let stats = &mut self.stats;
+        if stats.diffs > 0 {
+            stats.total_diff_interval += interval;
+            stats.max_diff_interval = stats.max_diff_interval.max(interval);
+        }
+        stats.diffs += 1;
+        stats.total_diff_apply_time += apply_time;
+    }
+
+    pub fn stats(&self) -> HeadlessClientStats {
+        let connection = self.protocol.connection();
+        let stats = connection.stats();
+        HeadlessClientStats {
+            bytes_sent: stats.bytes_sent,
+            bytes_received: stats.bytes_received,
+            rtt: connection.rtt(),
+            ..self.stats.clone()
+        }
+    }
+}
+
+/// The totals since a [HeadlessClient] connected
+#[derive(Debug, Clone, Default)]
+pub struct HeadlessClientStats {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub rtt: Duration,
+    /// The number of world diffs received
+    pub diffs: u64,
+    /// The longest wait between two world diffs
+    pub max_diff_interval: Duration,
+    pub total_diff_interval: Duration,
+    pub total_diff_apply_time: Duration,
+    /// The last frame stats sent by the server
+    pub server: Option<FpsSample>,
+}
+impl HeadlessClientStats {
+    /// The mean time between two world diffs
+    pub fn mean_diff_interval(&self) -> Duration {
+        self.total_diff_interval.checked_div(self.diffs.saturating_sub(1) as u32).unwrap_or_default()
+    }
+    /// The expected time between the server changing the world and this client applying the change: half the round
+    /// trip, plus on average half the time between diffs, plus the time to apply the diff
+    pub fn mean_diff_latency(&self) -> Duration {
+        self.rtt / 2 + self.mean_diff_interval() / 2 + self.total_diff_apply_time.checked_div(self.diffs as u32).unwrap_or_default()
+    }
+    /// The bandwidth used in each direction since `prev`, in bytes per second
+    pub fn bandwidth_since(&self, prev: &HeadlessClientStats, elapsed: Duration) -> (f32, f32) {
+        let secs = elapsed.as_secs_f32().max(f32::EPSILON);
+        (
+            self.bytes_sent.saturating_sub(prev.bytes_sent) as f32 / secs,
+            self.bytes_received.saturating_sub(prev.bytes_received) as f32 / secs,
+        )
+    }
+}
+impl Display for HeadlessClientStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} out, {} in, rtt {:?}, {} diffs, diff latency {:?} (max interval {:?})",
+            to_byte_unit(self.bytes_sent),
+            to_byte_unit(self.bytes_received),
+            self.rtt,
+            self.diffs,
+            self.mean_diff_latency(),
+            self.max_diff_interval
+        )?;
+        if let Some(server) = &self.server {
+            write!(f, ", server tick {:.2}ms (slowest {:?})", server.frame_time_ms(), server.slowest_frame)?;
+        }
+        Ok(())
+    }
+}
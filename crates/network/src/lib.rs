@@ -9,37 +9,49 @@ use std::{
 use ambient_ecs::{
     components, query, Component, ComponentValue, Debuggable, Description, EntityId, Name, Networked, Resource, Serializable, Store, World,
 };
-use ambient_rpc::{RpcError, RpcRegistry};
+use ambient_rpc::{RpcError, RpcRegistry, RpcTable};
 use ambient_std::{asset_cache::AssetCache, log_error, log_result};
 use bytes::Bytes;
 use client::GameRpcArgs;
 use futures::{Future, SinkExt, StreamExt};
-use quinn::{
-    ClientConfig, Connection, ConnectionClose, ConnectionError::ConnectionClosed, Endpoint, Incoming, NewConnection, RecvStream,
-    SendStream, ServerConfig, TransportConfig,
-};
+use quinn::{ClientConfig, ConnectionClose, ConnectionError::ConnectionClosed, Endpoint, Incoming, ServerConfig, TransportConfig};
 use rand::Rng;
 use rustls::{Certificate, PrivateKey, RootCertStore};
 use serde::{de::DeserializeOwned, Serialize};
 use server::SharedServerState;
 use thiserror::Error;
 use tokio::io::AsyncWriteExt;
+use tokio_tungstenite::tungstenite;
 use tokio_util::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
+use transport::{Connection, NewConnection, RecvStream, SendStream};
+
+/// Defined in `ambient_core`, so that the crates below this one can read the synced resources too
+pub use ambient_core::network::synced_resources;
 
 pub type AsyncMutex<T> = tokio::sync::Mutex<T>;
 pub mod client;
 pub mod client_game_state;
+pub mod debug_draw;
 pub mod events;
+pub mod headless;
 pub mod hooks;
+pub mod messages;
+pub mod permissions;
+pub mod persistence;
 pub mod protocol;
 pub mod rpc;
 pub mod server;
+pub mod transport;
+pub mod websocket;
 
 components!("network", {
     @[Resource]
     bi_stream_handlers: BiStreamHandlers,
     @[Resource]
     datagram_handlers: DatagramHandlers,
+    /// The RPCs the server can run, which are sent to clients when they connect
+    @[Resource]
+    rpc_table: RpcTable,
 
     /// Works like `world.resource_entity` for server worlds, except it's also persisted to disk, and synchronized to clients
     @[
@@ -48,13 +60,6 @@ components!("network", {
         Description["If attached, this entity contains global resources that are persisted to disk and synchronized to clients."]
     ]
     persistent_resources: (),
-    /// Works like `world.resource_entity` for server worlds, except it's synchronized to clients. State is not persisted to disk.
-    @[
-        Debuggable, Networked,
-        Name["Synced resources"],
-        Description["If attached, this entity contains global resources that are synchronized to clients, but not persisted."]
-    ]
-    synced_resources: (),
 
     @[
         Debuggable, Networked,
@@ -67,7 +72,10 @@ components!("network", {
 pub fn init_all_components() {
     init_components();
     client::init_components();
+    debug_draw::init_components();
     events::init_components();
+    messages::init_components();
+    persistence::init_components();
     server::init_components();
     client_game_state::init_components();
 }
@@ -140,10 +148,9 @@ pub async fn rpc_request<
     req: Req,
     size_limit: usize,
 ) -> Result<Resp, NetworkError> {
-    let stream = conn.open_bi();
-    let (mut send, recv) = stream.await.map_err(NetworkError::ConnectionError)?;
+    let (mut send, recv) = conn.open_bi().await?;
     send.write_u32(RPC_STREAM_ID).await?;
-    let req = reg.serialize_req(func, req);
+    let req = reg.serialize_req(func, req)?;
     send.write_all(&req).await.map_err(NetworkError::from)?;
     send.finish().await.map_err(NetworkError::from)?;
     drop(send);
@@ -163,7 +170,14 @@ pub fn register_rpc_bi_stream_handler(handlers: &mut BiStreamHandlers, rpc_regis
                 let try_block = || async {
                     let req = recv.read_to_end(100_000_000).await?;
                     let args = GameRpcArgs { state, user_id: user_id.to_string() };
-                    let resp = rpc_registry.run_req(args, &req).await?;
+                    let resp = match rpc_registry.run_req(args, &req).await {
+                        Ok(resp) => resp,
+                        Err(RpcError::Unauthorized(err)) => {
+                            log::warn!("[{}] {}", user_id, err);
+                            RpcRegistry::<GameRpcArgs>::serialize_unauthorized(&err)
+                        }
+                        Err(err) => return Err(err.into()),
+                    };
                     send.write_all(&resp).await?;
                     send.finish().await?;
                     Ok(()) as Result<(), NetworkError>
@@ -191,6 +205,10 @@ pub enum NetworkError {
     #[error(transparent)]
     WriteError(#[from] quinn::WriteError),
     #[error(transparent)]
+    SendDatagramError(#[from] quinn::SendDatagramError),
+    #[error("WebSocket connection failed")]
+    WebSocketError(#[source] Box<tungstenite::Error>),
+    #[error(transparent)]
     RpcError(#[from] RpcError),
 }
 
@@ -206,6 +224,7 @@ impl NetworkError {
             // for example by dropping the [`quinn::Connection`]
             Self::ConnectionError(ConnectionClosed(ConnectionClose { error_code, .. })) if u64::from(*error_code) == 0 => true,
             Self::IOError(err) if matches!(err.kind(), ErrorKind::ConnectionReset) => true,
+            Self::WebSocketError(err) if matches!(**err, tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => true,
             _ => false,
         }
     }
@@ -219,10 +238,16 @@ impl NetworkError {
     }
 }
 
+impl From<tungstenite::Error> for NetworkError {
+    fn from(err: tungstenite::Error) -> Self {
+        Self::WebSocketError(Box::new(err))
+    }
+}
+
 /// Abstracts the serialization for a fixed size stream.
 #[derive(Debug)]
 pub struct IncomingStream {
-    pub stream: FramedRead<quinn::RecvStream, LengthDelimitedCodec>,
+    pub stream: FramedRead<RecvStream, LengthDelimitedCodec>,
 }
 impl IncomingStream {
     /// Accept a new uni-directional peer stream. Waits for the server to open a
@@ -232,7 +257,7 @@ impl IncomingStream {
         Ok(Self::new(stream))
     }
 
-    pub fn new(stream: quinn::RecvStream) -> Self {
+    pub fn new(stream: RecvStream) -> Self {
         let mut codec = LengthDelimitedCodec::new();
         codec.set_max_frame_length(1_024 * 1_024 * 1_024);
         Self { stream: FramedRead::new(stream, codec) }
@@ -256,14 +281,14 @@ impl IncomingStream {
 
 #[derive(Debug)]
 pub struct OutgoingStream {
-    pub stream: FramedWrite<quinn::SendStream, LengthDelimitedCodec>,
+    pub stream: FramedWrite<SendStream, LengthDelimitedCodec>,
 }
 impl OutgoingStream {
     pub async fn open_uni(conn: &Connection) -> Result<Self, NetworkError> {
         Ok(OutgoingStream::new(conn.open_uni().await?))
     }
 
-    pub fn new(stream: quinn::SendStream) -> Self {
+    pub fn new(stream: SendStream) -> Self {
         let mut codec = LengthDelimitedCodec::new();
         codec.set_max_frame_length(1_024 * 1_024 * 1_024);
         Self { stream: FramedWrite::new(stream, codec) }
@@ -271,7 +296,7 @@ impl OutgoingStream {
 
     /// Sends raw bytes over the network
     pub async fn send_bytes(&mut self, bytes: Vec<u8>) -> Result<(), NetworkError> {
-        self.stream.send(bytes.into()).await?;
+        self.stream.send(Bytes::from(bytes)).await?;
 
         Ok(())
     }
@@ -0,0 +1,236 @@
+use std::sync::Arc;
+
+use ambient_core::{name, player::get_player_by_user_id};
+use ambient_ecs::{components, query, world_events, Debuggable, Description, Entity, EntityId, Name, World};
+use ambient_std::unwrap_log_err;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::GameClient,
+    events::{broadcast_event, send_event, ServerEventRegistry},
+    rpc::rpc_send_message,
+    server::{player_datagram_stream, SharedServerState},
+    DatagramHandlers, NetworkError,
+};
+
+components!("network", {
+    @[
+        Debuggable,
+        Name["Message source"],
+        Description["The player that sent this message. Only attached to messages received by the server."]
+    ]
+    message_source: EntityId,
+});
+
+/// The id of the datagrams which carry unreliable messages from players to the server
+pub const MESSAGE_DATAGRAM_ID: u32 = 6;
+
+/// How a [Message] is delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Reliability {
+    /// The message is sent on its own stream; it will arrive, but not necessarily in order with other messages
+    Reliable,
+    /// The message is sent as a datagram; it may be dropped, e.g. when the connection is congested or the message is
+    /// larger than a datagram
+    Unreliable,
+}
+
+/// A named message exchanged between the server and its players. The payload is encoded like any other entity, so only
+/// its serializable components are sent.
+///
+/// Received messages are added to the world events of the receiver as `data` with [name] set to `name`, which is how
+/// guest modules receive them. Messages received by the server also have [message_source] set to the sending player.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    pub name: String,
+    pub data: Entity,
+}
+impl Message {
+    pub fn new(name: impl Into<String>, data: Entity) -> Self {
+        Self { name: name.into(), data }
+    }
+    /// Core events are sent by the runtime, so they can't be sent as messages
+    pub fn is_allowed(&self) -> bool {
+        !self.name.starts_with("core/")
+    }
+    fn into_event(self) -> Entity {
+        self.data.with(name(), self.name)
+    }
+}
+
+/// Sends a message from the server to a specific player
+pub fn send_message(world: &World, player_id: EntityId, message: Message, reliability: Reliability) {
+    match reliability {
+        Reliability::Reliable => send_event(world, player_id, message),
+        Reliability::Unreliable => {
+            let datagram = serialize_datagram(message);
+            if let Ok(tx) = world.get_ref(player_id, player_datagram_stream()) {
+                if tx.send(datagram).is_err() {
+                    log::warn!("Attempt to send a message to a disconnected player")
+                }
+            }
+        }
+    }
+}
+
+/// Sends a message from the server to all connected players
+pub fn broadcast_message(world: &World, message: Message, reliability: Reliability) {
+    match reliability {
+        Reliability::Reliable => broadcast_event(world, message),
+        Reliability::Unreliable => {
+            let datagram = serialize_datagram(message);
+            for (_, tx) in query(player_datagram_stream()).iter(world, None) {
+                if tx.send(datagram.clone()).is_err() {
+                    log::warn!("Attempt to broadcast a message to a disconnected player")
+                }
+            }
+        }
+    }
+}
+
+/// Datagrams from the server use the same encoding as the events sent on uni streams
+fn serialize_datagram(message: Message) -> Bytes {
+    let mut buf = Vec::new();
+    ServerEventRegistry::serialize(&mut buf, message).expect("Failed to serialize message");
+    buf.into()
+}
+
+/// Sends a message from this client to the server
+pub async fn client_send_message(game_client: &GameClient, message: Message, reliability: Reliability) -> Result<(), NetworkError> {
+    match reliability {
+        Reliability::Reliable => game_client.rpc(rpc_send_message, message).await,
+        Reliability::Unreliable => {
+            game_client.connection.send_datagram(serialize_client_datagram(&message))?;
+            Ok(())
+        }
+    }
+}
+
+/// Datagrams to the server start with [MESSAGE_DATAGRAM_ID], which the server strips before passing them to the handler
+fn serialize_client_datagram(message: &Message) -> Bytes {
+    let mut datagram = MESSAGE_DATAGRAM_ID.to_be_bytes().to_vec();
+    datagram.extend(bincode::serialize(message).expect("Failed to serialize message"));
+    datagram.into()
+}
+
+/// Makes messages from the server show up as world events on the client
+pub fn register_client_message_handler(registry: &ServerEventRegistry) {
+    registry.register(|world: &mut World, message: Message| {
+        world.resource_mut(world_events()).add_event(message.into_event());
+        Ok(())
+    });
+}
+
+/// Makes unreliable messages from players show up as world events on the server
+pub fn register_server_message_datagram_handler(handlers: &mut DatagramHandlers) {
+    handlers.insert(
+        MESSAGE_DATAGRAM_ID,
+        Arc::new(|state, _assets, user_id, data| {
+            let message: Message = unwrap_log_err!(bincode::deserialize(&data));
+            receive_message(&state, user_id, message);
+        }),
+    );
+}
+
+/// Adds a message from the player with `user_id` to the world events of their world instance
+pub(crate) fn receive_message(state: &SharedServerState, user_id: &str, message: Message) {
+    if !message.is_allowed() {
+        log::warn!("[{user_id}] Attempt to send the core event {:?} as a message", message.name);
+        return;
+    }
+    let mut state = state.lock();
+    if let Some(world) = state.get_player_world_mut(user_id) {
+        if let Some(player_id) = get_player_by_user_id(world, user_id) {
+            let event = message.into_event().with(message_source(), player_id);
+            world.resource_mut(world_events()).add_event(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ambient_core::{
+        player::{player, user_id},
+        transform::translation,
+    };
+    use ambient_ecs::{FramedEventsReader, WorldEvents};
+    use glam::Vec3;
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::server::{Player, ServerState, MAIN_INSTANCE_ID};
+
+    fn message() -> Message {
+        Message::new("game/hello", Entity::new().with(translation(), Vec3::X))
+    }
+
+    fn events(world: &World) -> Vec<Entity> {
+        let events = world.resource(world_events());
+        FramedEventsReader::new().iter(events).map(|(_, event)| event.clone()).collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        crate::init_all_components();
+        ambient_core::init_all_components();
+        let message = message();
+
+        // Server datagrams are encoded like events, with the type name on the first line
+        let datagram = serialize_datagram(message.clone());
+        let split = datagram.iter().position(|&b| b == b'\n').unwrap();
+        assert_eq!(std::str::from_utf8(&datagram[..split]).unwrap(), std::any::type_name::<Message>());
+        let decoded: Message = bincode::deserialize(&datagram[split + 1..]).unwrap();
+        assert_eq!(decoded.name, message.name);
+        assert_eq!(decoded.data.get(translation()), Some(Vec3::X));
+
+        let datagram = serialize_client_datagram(&message);
+        assert_eq!(datagram[..4], MESSAGE_DATAGRAM_ID.to_be_bytes());
+        let decoded: Message = bincode::deserialize(&datagram[4..]).unwrap();
+        assert_eq!(decoded.name, message.name);
+        assert_eq!(decoded.data.get(translation()), Some(Vec3::X));
+    }
+
+    #[test]
+    fn client_dispatch() {
+        crate::init_all_components();
+        ambient_core::init_all_components();
+        let registry = ServerEventRegistry::new();
+        register_client_message_handler(&registry);
+        let mut world = World::new("client");
+        world.add_resource(world_events(), WorldEvents::new());
+
+        let datagram = serialize_datagram(message());
+        let split = datagram.iter().position(|&b| b == b'\n').unwrap();
+        let event_name = std::str::from_utf8(&datagram[..split]).unwrap();
+        registry.handle_event_in_world(&mut world, event_name, &datagram[split + 1..]).unwrap();
+
+        let events = events(&world);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].get_ref(name()).map(|name| name.as_str()), Some("game/hello"));
+        assert_eq!(events[0].get(translation()), Some(Vec3::X));
+    }
+
+    #[test]
+    fn server_dispatch() {
+        crate::init_all_components();
+        ambient_core::init_all_components();
+        let mut state = ServerState::new_local();
+        state.players.insert("user".to_string(), Player::new_local(MAIN_INSTANCE_ID.to_string()));
+        let world = &mut state.instances.get_mut(MAIN_INSTANCE_ID).unwrap().world;
+        world.add_resource(world_events(), WorldEvents::new());
+        let player_id = Entity::new().with_default(player()).with(user_id(), "user".to_string()).spawn(world);
+        let state: SharedServerState = Arc::new(Mutex::new(state));
+
+        receive_message(&state, "user", message());
+        // Core events can't be sent by players, and messages from unknown players are dropped
+        receive_message(&state, "user", Message::new("core/frame", Entity::new()));
+        receive_message(&state, "stranger", message());
+
+        let state = state.lock();
+        let events = events(&state.instances[MAIN_INSTANCE_ID].world);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].get_ref(name()).map(|name| name.as_str()), Some("game/hello"));
+        assert_eq!(events[0].get(message_source()), Some(player_id));
+    }
+}
@@ -0,0 +1,96 @@
+use std::{collections::HashSet, fmt::Display, str::FromStr};
+
+use ambient_core::window::{window_logical_size, window_physical_size};
+use ambient_ecs::{ComponentDesc, WorldChange, WorldDiff};
+use ambient_rpc::RpcRequirement;
+use serde::{Deserialize, Serialize};
+
+use crate::client::GameRpcArgs;
+
+/// What a player is allowed to do on the server. Roles are ordered, so that a role is allowed to do everything
+/// the roles below it can do.
+///
+/// Players get the default role, [Role::Editor], unless they've been assigned another one. This lets anyone edit a
+/// local project; servers open to the public should lower it to [Role::Player].
+///
+/// Roles are not a security boundary yet: they're assigned by user id, which clients choose themselves when they connect
+/// and the server doesn't verify, so anyone who knows an admin's or editor's user id can connect with their role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default)]
+pub enum Role {
+    /// Can play the game; i.e. call the RPCs the game needs, and only change the components in the world diff allowlist
+    Player,
+    /// Can edit the world; apply any world diff and intent, and use the editor RPCs
+    #[default]
+    Editor,
+    /// Can do everything, including assigning roles to other players
+    Admin,
+}
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Role::Player => write!(f, "player"),
+            Role::Editor => write!(f, "editor"),
+            Role::Admin => write!(f, "admin"),
+        }
+    }
+}
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "player" => Ok(Role::Player),
+            "editor" => Ok(Role::Editor),
+            "admin" => Ok(Role::Admin),
+            _ => Err(format!("Unknown role {s:?}, expected one of player, editor or admin")),
+        }
+    }
+}
+
+/// Checks that the player calling the function has at least this role
+impl<Req> RpcRequirement<GameRpcArgs, Req> for Role {
+    fn check(&self, args: &GameRpcArgs, _req: &Req) -> Result<(), String> {
+        check_role(args, *self)
+    }
+}
+
+fn check_role(args: &GameRpcArgs, required: Role) -> Result<(), String> {
+    match args.state.lock().player_role(&args.user_id) {
+        Some(role) if role >= required => Ok(()),
+        Some(role) => Err(format!("Requires the {required} role, but {} has the {role} role", args.user_id)),
+        None => Err(format!("No such player: {}", args.user_id)),
+    }
+}
+
+/// The components players without the [Role::Editor] role are allowed to add, set and remove through world diffs
+pub fn default_world_diff_allowlist() -> HashSet<ComponentDesc> {
+    [window_physical_size().desc(), window_logical_size().desc()].into_iter().collect()
+}
+
+/// Editors can apply any world diff. Other players can only change the components in the
+/// [crate::server::ServerState::world_diff_allowlist], and can't spawn or despawn entities.
+pub struct WorldDiffRequirement;
+impl RpcRequirement<GameRpcArgs, WorldDiff> for WorldDiffRequirement {
+    fn check(&self, args: &GameRpcArgs, diff: &WorldDiff) -> Result<(), String> {
+        if check_role(args, Role::Editor).is_ok() {
+            return Ok(());
+        }
+        let state = args.state.lock();
+        let allowed = |component: &ComponentDesc| {
+            if state.world_diff_allowlist.contains(component) {
+                Ok(())
+            } else {
+                Err(format!("Component {} is not in the world diff allowlist", component.path()))
+            }
+        };
+        for change in &diff.changes {
+            match change {
+                WorldChange::Spawn(..) | WorldChange::Despawn(_) => return Err("Only editors can spawn and despawn entities".to_string()),
+                WorldChange::AddComponents(_, data) => data.iter().try_for_each(|entry| allowed(&entry.desc()))?,
+                WorldChange::RemoveComponents(_, components) => components.iter().try_for_each(allowed)?,
+                WorldChange::Set(_, entry) => allowed(&entry.desc())?,
+            }
+        }
+        Ok(())
+    }
+}
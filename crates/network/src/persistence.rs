@@ -0,0 +1,234 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use ambient_core::runtime;
+use ambient_ecs::{
+    components, dont_store, query, ComponentDesc, Debuggable, DeserWorldWithWarnings, EntityId, FnSystem, Resource, Store, SystemGroup,
+    World,
+};
+use ambient_sys::time::Instant;
+use anyhow::Context;
+use parking_lot::Mutex;
+
+use crate::{
+    server::{world_instance_id, ShutdownEvent, MAIN_INSTANCE_ID},
+    synced_resources,
+};
+
+components!("network::persistence", {
+    /// Where and how often the main instance is saved
+    @[Debuggable, Resource]
+    world_persistence: WorldPersistence,
+    /// Saves the world at the end of the frame, see [request_world_save]
+    @[Debuggable, Resource]
+    world_save_requested: (),
+    /// Set when the world was restored from a save, until the modules have been told about it
+    @[Debuggable, Resource]
+    world_loaded: (),
+});
+
+/// Saving of the world of the main instance to a file, so that it survives restarts of the server.
+///
+/// Only the [Store] components are saved. Entities with [dont_store] (e.g. players), the resources, the synced resources
+/// and the entities with any of the `excluded` components are skipped.
+///
+/// The saved entities are copied on the frame of the save, and written to the file on a blocking task.
+#[derive(Debug, Clone)]
+pub struct WorldPersistence {
+    pub path: PathBuf,
+    /// Saves the world this often; the world is always saved when the server shuts down
+    pub autosave_interval: Option<Duration>,
+    /// Entities with any of these components are recreated when the server starts, so they are not saved
+    pub excluded: Vec<ComponentDesc>,
+    last_save: Instant,
+    /// The number of snapshots taken so far, and the number of the last one which was written, so that a slow write
+    /// never replaces a newer save
+    snapshots: Arc<AtomicU64>,
+    written: Arc<Mutex<u64>>,
+}
+impl WorldPersistence {
+    pub fn new(path: impl Into<PathBuf>, autosave_interval: Option<Duration>) -> Self {
+        Self {
+            path: path.into(),
+            autosave_interval,
+            excluded: Vec::new(),
+            last_save: Instant::now(),
+            snapshots: Default::default(),
+            written: Default::default(),
+        }
+    }
+    pub fn exclude(mut self, component: impl Into<ComponentDesc>) -> Self {
+        self.excluded.push(component.into());
+        self
+    }
+
+    fn is_saved(&self, world: &World, id: EntityId) -> bool {
+        id != world.resource_entity()
+            && !world.has_component(id, synced_resources())
+            && !self.excluded.iter().any(|desc| world.has_component(id, *desc))
+    }
+
+    /// Writes the saved entities and their [Store] components to [WorldPersistence::path], as versioned json so that
+    /// saves can be migrated when components change. The file is replaced atomically, so an interrupted save leaves the
+    /// previous one intact. Returns the number of saved entities
+    pub fn save(&self, world: &World) -> anyhow::Result<usize> {
+        self.snapshot(world)?.write()
+    }
+
+    /// Copies the saved entities and their [Store] components, to be written with [WorldSnapshot::write]
+    pub fn snapshot(&self, world: &World) -> anyhow::Result<WorldSnapshot> {
+        let mut snapshot = World::new_with_config("snapshot", false);
+        for (id, _) in query(()).excl(dont_store()).iter(world, None) {
+            if !self.is_saved(world, id) {
+                continue;
+            }
+            let mut entity = world.clone_entity(id)?;
+            entity.filter(&|desc| desc.has_attribute::<Store>());
+            if !entity.is_empty() {
+                snapshot.spawn_with_id(id, entity);
+            }
+        }
+        Ok(WorldSnapshot {
+            world: snapshot,
+            path: self.path.clone(),
+            number: self.snapshots.fetch_add(1, Ordering::AcqRel) + 1,
+            written: self.written.clone(),
+        })
+    }
+
+    /// Spawns the entities of the save at [WorldPersistence::path] into `world` with their original ids, and sets
+    /// [world_loaded]. The save is migrated to the current world format version first. Entities whose id is already in
+    /// use are skipped. Returns the number of restored entities, or None if there is no save yet
+    pub fn load(&self, world: &mut World) -> anyhow::Result<Option<usize>> {
+        let content = match std::fs::read(&self.path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).with_context(|| format!("Failed to read {:?}", self.path)),
+        };
+        let saved =
+            DeserWorldWithWarnings::from_slice(&content).with_context(|| format!("Failed to load the world save {:?}", self.path))?;
+        saved.log_warnings();
+        let saved = saved.world;
+
+        let mut count = 0;
+        for (id, _) in query(()).iter(&saved, None) {
+            let entity = saved.clone_entity(id)?;
+            if world.spawn_with_id(id, entity) {
+                count += 1;
+            } else {
+                log::warn!("Entity {id} of the world save already exists, skipping it");
+            }
+        }
+        world.add_resource(world_loaded(), ());
+        Ok(Some(count))
+    }
+}
+
+/// The entities to save, copied out of the world by [WorldPersistence::snapshot]
+pub struct WorldSnapshot {
+    world: World,
+    path: PathBuf,
+    number: u64,
+    written: Arc<Mutex<u64>>,
+}
+impl WorldSnapshot {
+    /// Writes the snapshot to the save, unless a later snapshot was written already. Returns the number of saved entities
+    pub fn write(self) -> anyhow::Result<usize> {
+        let mut written = self.written.lock();
+        if *written > self.number {
+            return Ok(0);
+        }
+        write_atomically(&self.path, &serde_json::to_vec(&self.world)?)?;
+        *written = self.number;
+        Ok(self.world.len())
+    }
+}
+
+fn write_atomically(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, content)?;
+    std::fs::rename(&tmp_path, path)
+}
+
+/// Saves the world at the end of the frame, if it's the main instance and saving is enabled
+pub fn request_world_save(world: &mut World) {
+    world.add_resource(world_save_requested(), ());
+}
+
+fn is_main_instance(world: &World) -> bool {
+    world.resource_opt(world_instance_id()).map(|id| id == MAIN_INSTANCE_ID).unwrap_or(true)
+}
+
+/// Saves the world on a blocking task, unless `wait` is set
+fn save(world: &mut World, reason: &'static str, wait: bool) {
+    let persistence = match world.resource_opt(world_persistence()) {
+        Some(persistence) => persistence.clone(),
+        None => return,
+    };
+    world.resource_mut(world_persistence()).last_save = Instant::now();
+    let snapshot = match persistence.snapshot(world) {
+        Ok(snapshot) => snapshot,
+        Err(err) => {
+            log::error!("Failed to save the world to {:?}: {err:?}", persistence.path);
+            return;
+        }
+    };
+    let write = move || match snapshot.write() {
+        Ok(count) => log::info!("Saved {count} entities to {:?} ({reason})", persistence.path),
+        Err(err) => log::error!("Failed to save the world to {:?}: {err:?}", persistence.path),
+    };
+    match world.resource_opt(runtime()) {
+        Some(runtime) if !wait => {
+            runtime.spawn_blocking(write);
+        }
+        _ => write(),
+    }
+}
+
+/// Saves the world when requested and on the autosave interval. Should run at the end of the frame
+pub fn persistence_systems() -> SystemGroup {
+    SystemGroup::new(
+        "persistence",
+        vec![Box::new(FnSystem::new(|world, _| {
+            let requested = world.resource_opt(world_save_requested()).is_some();
+            if requested {
+                world.remove_component(world.resource_entity(), world_save_requested()).unwrap();
+            }
+            if !is_main_instance(world) {
+                return;
+            }
+            let autosave = match world.resource_opt(world_persistence()) {
+                Some(persistence) => {
+                    persistence.autosave_interval.map(|interval| persistence.last_save.elapsed() >= interval).unwrap_or(false)
+                }
+                None => return,
+            };
+            if requested {
+                save(world, "requested", false);
+            } else if autosave {
+                save(world, "autosave", false);
+            }
+        }))],
+    )
+}
+
+/// Saves the world when the server shuts down, waiting for the save to be written
+pub fn persistence_on_shutdown_systems() -> SystemGroup<ShutdownEvent> {
+    SystemGroup::new(
+        "persistence/on_shutdown",
+        vec![Box::new(FnSystem::new(|world, _| {
+            if is_main_instance(world) {
+                save(world, "shutdown", true);
+            }
+        }))],
+    )
+}
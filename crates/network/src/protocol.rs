@@ -1,9 +1,13 @@
 use ambient_ecs::{ComponentRegistry, ExternalComponentDesc, WorldDiff};
 use anyhow::{Context, Result};
 use futures::{io::BufReader, StreamExt};
-use quinn::{NewConnection, RecvStream};
 
-use crate::{next_bincode_bi_stream, open_bincode_bi_stream, server::ServerInfo, IncomingStream, NetworkError, OutgoingStream};
+use crate::{
+    next_bincode_bi_stream, open_bincode_bi_stream,
+    server::ServerInfo,
+    transport::{Connection, IncomingUniStreams, NewConnection, RecvStream},
+    IncomingStream, NetworkError, OutgoingStream,
+};
 
 #[derive(Debug)]
 pub struct ClientProtocol {
@@ -56,11 +60,11 @@ impl ClientProtocol {
         &self.client_info
     }
 
-    pub(crate) fn connection(&self) -> quinn::Connection {
+    pub(crate) fn connection(&self) -> Connection {
         self.conn.connection.clone()
     }
 
-    pub fn uni_streams(&self) -> &quinn::IncomingUniStreams {
+    pub fn uni_streams(&self) -> &IncomingUniStreams {
         &self.conn.uni_streams
     }
 }
@@ -79,6 +83,7 @@ impl ServerProtocol {
         // The client now sends the player id
         let (mut tx, mut rx) = next_bincode_bi_stream(&mut conn).await?;
 
+        // Not authenticated; the client can claim any user id, including one which has been given a role
         let user_id: String = rx.next().await?;
 
         log::debug!("Received handshake from {user_id:?}");
@@ -107,7 +112,7 @@ impl ServerProtocol {
         &self.client_info
     }
 
-    pub(crate) fn connection(&self) -> quinn::Connection {
+    pub(crate) fn connection(&self) -> Connection {
         self.conn.connection.clone()
     }
 }
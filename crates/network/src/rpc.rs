@@ -1,25 +1,34 @@
 use std::collections::HashMap;
 
-use ambient_ecs::{query, Entity, System, WorldDiff};
+use ambient_core::name;
+use ambient_ecs::{ComponentEntry, Entity, EntityId, WorldDiff};
 use ambient_rpc::RpcRegistry;
 use ambient_std::friendly_id;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     client::GameRpcArgs,
-    server::{
-        create_player_entity_data, player_entity_stream, player_event_stream, player_stats_stream, ForkingEvent, WorldInstance,
-        MAIN_INSTANCE_ID,
-    },
-    ServerWorldExt,
+    messages::{receive_message, Message},
+    permissions::{Role, WorldDiffRequirement},
 };
-use ambient_core::player::user_id;
+
+/// Gives the ECS types with custom `Deserialize` implementations explicit schemas, so that functions using them can be
+/// registered; see [RpcRegistry::explicit_schema]. Called by [register_rpcs]
+pub fn register_ecs_schemas(reg: &mut RpcRegistry<GameRpcArgs>) {
+    reg.explicit_schema("entity id: base64 string", EntityId::null());
+    reg.explicit_schema("component desc: path string", name().desc());
+    reg.explicit_schema("component entry: (path string, component value)", ComponentEntry::new(name(), String::new()));
+    reg.explicit_schema("entity: map of path string to component value", Entity::new());
+}
 
 pub fn register_rpcs(reg: &mut RpcRegistry<GameRpcArgs>) {
-    reg.register(rpc_world_diff);
-    reg.register(rpc_fork_instance);
-    reg.register(rpc_join_instance);
-    reg.register(rpc_get_instances_info);
+    register_ecs_schemas(reg);
+    reg.register_with("network::world_diff", 1, WorldDiffRequirement, rpc_world_diff);
+    reg.register_with("network::fork_instance", 1, Role::Editor, rpc_fork_instance);
+    reg.register_with("network::join_instance", 1, Role::Editor, rpc_join_instance);
+    reg.register("network::get_instances_info", 1, rpc_get_instances_info);
+    reg.register_with("network::set_role", 1, Role::Admin, rpc_set_role);
+    reg.register("network::send_message", 1, rpc_send_message);
 }
 
 pub async fn rpc_world_diff(args: GameRpcArgs, diff: WorldDiff) {
@@ -37,76 +46,12 @@ pub struct RpcForkInstance {
 pub async fn rpc_fork_instance(args: GameRpcArgs, RpcForkInstance { resources, synced_res, id }: RpcForkInstance) -> String {
     let mut state = args.state.lock();
     let id = id.unwrap_or(friendly_id());
-    if !state.instances.contains_key(&id) {
-        let new_instance = {
-            let instance = state.get_player_world_instance(&args.user_id).unwrap();
-            let mut world = instance.world.clone();
-
-            for (id, _) in query(user_id()).collect_cloned(&world, None) {
-                world.despawn(id);
-            }
-            world.add_components(world.resource_entity(), resources.with_merge(ambient_core::async_ecs::async_ecs_resources())).unwrap();
-            world.add_components(world.synced_resource_entity().unwrap(), synced_res).unwrap();
-
-            let mut on_forking = (state.create_on_forking_systems)();
-            on_forking.run(&mut world, &ForkingEvent);
-
-            world.reset_events();
-
-            WorldInstance { systems: (state.create_server_systems)(&mut world), world, world_stream: instance.world_stream.clone() }
-        };
-        state.instances.insert(id.clone(), new_instance);
-    }
+    let from = state.players.get(&args.user_id).unwrap().instance.clone();
+    state.create_instance(&from, id.clone(), resources, synced_res, false);
     id
 }
 pub async fn rpc_join_instance(args: GameRpcArgs, new_instance_id: String) {
-    let mut state = args.state.lock();
-    let old_instance_id = state.players.get(&args.user_id).unwrap().instance.clone();
-    if old_instance_id == new_instance_id {
-        return;
-    }
-
-    let instances = &mut state.instances;
-
-    // Borrow the new world mutably to broadcast its diffs.
-    instances.get_mut(&new_instance_id).unwrap().broadcast_diffs();
-
-    // Borrow both worlds immutably to extract the old world's player count and the diff between the two, and
-    // to broadcast the latest diffs for the new instance.
-    let (old_player_count, diff) = {
-        let (old_instance, new_instance) = instances.get(&old_instance_id).zip(instances.get(&new_instance_id)).unwrap();
-        (
-            old_instance.player_count(),
-            WorldDiff::from_a_to_b(old_instance.world_stream.filter().clone(), &old_instance.world, &new_instance.world),
-        )
-    };
-
-    // Borrow the old world mutably to remove the player and their streams.
-    let (entities_tx, events_tx, stats_tx) = {
-        let mut ed = instances.get_mut(&old_instance_id).unwrap().despawn_player(&args.user_id).unwrap();
-        (
-            ed.remove_self(player_entity_stream()).unwrap(),
-            ed.remove_self(player_event_stream()).unwrap(),
-            ed.remove_self(player_stats_stream()).unwrap(),
-        )
-    };
-
-    // Borrow the new world mutably to spawn the player in with their old streams.
-    instances.get_mut(&new_instance_id).unwrap().spawn_player(create_player_entity_data(
-        &args.user_id,
-        entities_tx.clone(),
-        events_tx,
-        stats_tx,
-    ));
-    state.players.get_mut(&args.user_id).unwrap().instance = new_instance_id.to_string();
-
-    let msg = bincode::serialize(&diff).unwrap();
-    entities_tx.send(msg).ok();
-
-    // Remove old instance
-    if old_player_count == 1 && old_instance_id != MAIN_INSTANCE_ID {
-        state.remove_instance(&old_instance_id);
-    }
+    args.state.lock().move_player(&args.user_id, &new_instance_id);
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -119,12 +64,15 @@ pub struct InstanceInfo {
 }
 
 pub async fn rpc_get_instances_info(args: GameRpcArgs, _: ()) -> InstancesInfo {
-    let state = args.state.lock();
-    InstancesInfo {
-        instances: state
-            .instances
-            .iter()
-            .map(|(key, instance)| (key.clone(), InstanceInfo { n_players: instance.player_count() as u32 }))
-            .collect(),
-    }
+    args.state.lock().instances_info()
+}
+
+/// Assigns a role to a user, which is kept when they reconnect
+pub async fn rpc_set_role(args: GameRpcArgs, (user_id, role): (String, Role)) {
+    args.state.lock().set_role(&user_id, role);
+}
+
+/// Delivers a reliable message from a player to their world instance, see [crate::messages]
+pub async fn rpc_send_message(args: GameRpcArgs, message: Message) {
+    receive_message(&args.state, &args.user_id, message);
 }
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     net::{IpAddr, Ipv4Addr, SocketAddr},
     ops::Range,
     sync::Arc,
@@ -12,11 +12,13 @@ use ambient_core::{
     project_name,
 };
 use ambient_ecs::{
-    components, dont_store, query, ArchetypeFilter, ComponentDesc, Entity, EntityId, FrameEvent, System, SystemGroup, World, WorldStream,
-    WorldStreamCompEvent, WorldStreamFilter,
+    components, dont_store, query, ArchetypeFilter, ComponentDesc, Entity, EntityId, FrameEvent, Resource, System, SystemGroup, World,
+    WorldDiff, WorldStream, WorldStreamCompEvent, WorldStreamFilter,
 };
+use ambient_rpc::RpcTable;
 use ambient_std::{
-    asset_cache::AssetCache,
+    asset_cache::{AssetCache, SyncAssetKeyExt},
+    asset_url::{AbsAssetUrl, ServerBaseUrlKey},
     fps_counter::{FpsCounter, FpsSample},
     friendly_id, log_result,
 };
@@ -25,28 +27,68 @@ use anyhow::bail;
 use bytes::Bytes;
 use flume::Sender;
 use futures::StreamExt;
+use itertools::Itertools;
 use once_cell::sync::OnceCell;
 use parking_lot::Mutex;
-use quinn::{Endpoint, Incoming, NewConnection, RecvStream, SendStream};
+use quinn::{Endpoint, Incoming};
 use serde::{Deserialize, Serialize};
 use tokio::{
-    io::AsyncReadExt,
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
     time::{interval, MissedTickBehavior},
 };
 use tracing::{debug_span, Instrument};
 
 use crate::{
     bi_stream_handlers, create_server, datagram_handlers,
+    permissions::{default_world_diff_allowlist, Role},
+    persistent_resources,
     protocol::{ClientInfo, ServerProtocol},
-    NetworkError,
+    rpc::{InstanceInfo, InstancesInfo},
+    rpc_table, synced_resources,
+    transport::{NewConnection, RecvStream, SendStream},
+    websocket, NetworkError, ServerWorldExt,
 };
 
 components!("network", {
     player_entity_stream: Sender<Vec<u8>>,
     player_event_stream: Sender<Vec<u8>>,
+    player_datagram_stream: Sender<Bytes>,
     player_stats_stream: Sender<FpsSample>,
+    /// The id of the world instance this world belongs to
+    @[Resource]
+    world_instance_id: String,
+    /// All world instances on the server, as of the start of this frame
+    @[Resource]
+    world_instances: InstancesInfo,
+    /// Changes to the world instances requested during this frame, which are applied by the server after the frame
+    @[Resource]
+    world_instance_requests: Vec<WorldInstanceRequest>,
+    /// Entities with this component are kept when an empty world instance is created from this world, e.g. the modules
+    /// running the game logic
+    keep_in_empty_instances: (),
 });
 
+/// A change to the world instances of a server, see [request_world_instance_change]
+#[derive(Debug, Clone)]
+pub enum WorldInstanceRequest {
+    /// Create the instance `id` from the world of the instance `from`. If `empty` is true, only the resources and the
+    /// entities with [keep_in_empty_instances] are kept, otherwise everything but the players is
+    Create { from: String, id: String, empty: bool },
+    /// Move the player with `user_id` into the instance `instance_id`
+    MovePlayer { user_id: String, instance_id: String },
+    /// Remove the instance `instance_id` if it has no players
+    RemoveIfEmpty { instance_id: String },
+}
+
+/// Queues a change to the world instances of the server, which is applied after the current frame
+pub fn request_world_instance_change(world: &mut World, request: WorldInstanceRequest) {
+    match world.resource_mut_opt(world_instance_requests()) {
+        Some(requests) => requests.push(request),
+        None => world.add_resource(world_instance_requests(), vec![request]),
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ForkingEvent;
 
@@ -66,6 +108,7 @@ pub fn create_player_entity_data(
     user_id: &str,
     entities_tx: Sender<Vec<u8>>,
     events_tx: Sender<Vec<u8>>,
+    datagrams_tx: Sender<Bytes>,
     stats_tx: Sender<FpsSample>,
 ) -> Entity {
     Entity::new()
@@ -74,6 +117,7 @@ pub fn create_player_entity_data(
         .with(player_entity_stream(), entities_tx)
         .with(player_stats_stream(), stats_tx)
         .with(player_event_stream(), events_tx)
+        .with(player_datagram_stream(), datagrams_tx)
         .with_default(dont_store())
 }
 
@@ -132,6 +176,13 @@ pub type SharedServerState = Arc<Mutex<ServerState>>;
 pub struct ServerState {
     pub instances: HashMap<String, WorldInstance>,
     pub players: HashMap<String, Player>,
+    /// The roles assigned to specific users, by user id. These are kept when the users disconnect.
+    /// User ids aren't authenticated, so this is not a security boundary; see [Role]
+    pub roles: HashMap<String, Role>,
+    /// The role of users that haven't been assigned one in `roles`
+    pub default_role: Role,
+    /// The components users without the [Role::Editor] role are allowed to change with world diffs
+    pub world_diff_allowlist: HashSet<ComponentDesc>,
     pub create_server_systems: Arc<dyn Fn(&mut World) -> SystemGroup + Sync + Send>,
     pub create_on_forking_systems: Arc<dyn Fn() -> SystemGroup<ForkingEvent> + Sync + Send>,
     pub create_shutdown_systems: Arc<dyn Fn() -> SystemGroup<ShutdownEvent> + Sync + Send>,
@@ -150,6 +201,9 @@ impl ServerState {
             )]
             .into(),
             players: Default::default(),
+            roles: Default::default(),
+            default_role: Role::default(),
+            world_diff_allowlist: default_world_diff_allowlist(),
             create_server_systems: Arc::new(|_| SystemGroup::new("", vec![])),
             create_on_forking_systems: Arc::new(|| SystemGroup::new("", vec![])),
             create_shutdown_systems: Arc::new(|| SystemGroup::new("", vec![])),
@@ -161,14 +215,56 @@ impl ServerState {
         create_on_forking_systems: Arc<dyn Fn() -> SystemGroup<ForkingEvent> + Sync + Send>,
         create_shutdown_systems: Arc<dyn Fn() -> SystemGroup<ShutdownEvent> + Sync + Send>,
     ) -> Self {
-        Self { instances, players: Default::default(), create_server_systems, create_on_forking_systems, create_shutdown_systems }
+        Self {
+            instances,
+            players: Default::default(),
+            roles: Default::default(),
+            default_role: Role::default(),
+            world_diff_allowlist: default_world_diff_allowlist(),
+            create_server_systems,
+            create_on_forking_systems,
+            create_shutdown_systems,
+        }
     }
 
     pub fn step(&mut self) {
         let time = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap();
+        let instances_info = self.instances_info();
         for instance in self.instances.values_mut() {
+            instance.world.add_resource(world_instances(), instances_info.clone());
             instance.step(time);
         }
+        self.apply_world_instance_requests();
+    }
+    fn apply_world_instance_requests(&mut self) {
+        let requests = self
+            .instances
+            .values_mut()
+            .flat_map(|instance| instance.world.resource_mut_opt(world_instance_requests()).map(std::mem::take).unwrap_or_default())
+            .collect_vec();
+        // Instances are created first, so that players can be moved into them in the same frame
+        let requests = requests.into_iter().sorted_by_key(|request| match request {
+            WorldInstanceRequest::Create { .. } => 0,
+            WorldInstanceRequest::MovePlayer { .. } => 1,
+            WorldInstanceRequest::RemoveIfEmpty { .. } => 2,
+        });
+        for request in requests {
+            match request {
+                WorldInstanceRequest::Create { from, id, empty } => {
+                    if !self.create_instance(&from, id.clone(), Entity::new(), Entity::new(), empty) {
+                        log::warn!("Failed to create world instance {id} from {from}");
+                    }
+                }
+                WorldInstanceRequest::MovePlayer { user_id, instance_id } => {
+                    if !self.move_player(&user_id, &instance_id) {
+                        log::warn!("Failed to move player {user_id} to world instance {instance_id}");
+                    }
+                }
+                WorldInstanceRequest::RemoveIfEmpty { instance_id } => {
+                    self.remove_instance_if_empty(&instance_id);
+                }
+            }
+        }
     }
     pub fn broadcast_diffs(&mut self) {
         for instance in self.instances.values_mut() {
@@ -190,6 +286,132 @@ impl ServerState {
     pub fn get_player_world(&self, user_id: &str) -> Option<&World> {
         self.get_player_world_instance(user_id).map(|i| &i.world)
     }
+    /// The role of a connected player, or None if there's no such player. Note that the user id is the one the client
+    /// claimed in its handshake, see [Role]
+    pub fn player_role(&self, user_id: &str) -> Option<Role> {
+        if !self.players.contains_key(user_id) {
+            return None;
+        }
+        Some(self.roles.get(user_id).copied().unwrap_or(self.default_role))
+    }
+    pub fn set_role(&mut self, user_id: &str, role: Role) {
+        self.roles.insert(user_id.to_string(), role);
+    }
+    pub fn instances_info(&self) -> InstancesInfo {
+        InstancesInfo {
+            instances: self
+                .instances
+                .iter()
+                .map(|(key, instance)| (key.clone(), InstanceInfo { n_players: instance.player_count() as u32 }))
+                .collect(),
+        }
+    }
+    /// Creates the instance `id` from the world of the instance `from`, see [WorldInstanceRequest::Create]. Returns false if
+    /// `from` doesn't exist or `id` already exists
+    pub fn create_instance(&mut self, from: &str, id: String, resources: Entity, synced_res: Entity, empty: bool) -> bool {
+        if self.instances.contains_key(&id) {
+            return false;
+        }
+        let instance = match self.instances.get(from) {
+            Some(instance) => instance,
+            None => return false,
+        };
+        let mut world = instance.world.clone();
+        let world_stream = instance.world_stream.clone();
+
+        for player in query(ambient_core::player::user_id()).collect_ids(&world, None) {
+            world.despawn(player);
+        }
+        if empty {
+            let entities =
+                query(()).excl(keep_in_empty_instances()).excl(synced_resources()).excl(persistent_resources()).collect_ids(&world, None);
+            for entity in entities.into_iter().filter(|&entity| entity != world.resource_entity()) {
+                world.despawn(entity);
+            }
+        }
+        world.add_components(world.resource_entity(), resources.with_merge(ambient_core::async_ecs::async_ecs_resources())).unwrap();
+        world.add_components(world.synced_resource_entity().unwrap(), synced_res).unwrap();
+        world.add_resource(world_instance_id(), id.clone());
+        world.add_resource(world_instance_requests(), Vec::new());
+
+        let mut on_forking = (self.create_on_forking_systems)();
+        on_forking.run(&mut world, &ForkingEvent);
+
+        world.reset_events();
+
+        let instance = WorldInstance { systems: (self.create_server_systems)(&mut world), world, world_stream };
+        self.instances.insert(id, instance);
+        true
+    }
+    /// Moves the player with `user_id` into the instance `new_instance_id`, and removes their old instance if it's empty and
+    /// not the main instance. Returns false if the player or the instance doesn't exist
+    pub fn move_player(&mut self, user_id: &str, new_instance_id: &str) -> bool {
+        let old_instance_id = match self.players.get(user_id) {
+            Some(player) => player.instance.clone(),
+            None => return false,
+        };
+        if !self.instances.contains_key(new_instance_id) {
+            return false;
+        }
+        if old_instance_id == new_instance_id {
+            return true;
+        }
+
+        let instances = &mut self.instances;
+
+        // Borrow the new world mutably to broadcast its diffs.
+        instances.get_mut(new_instance_id).unwrap().broadcast_diffs();
+
+        // Borrow both worlds immutably to extract the old world's player count and the diff between the two, and
+        // to broadcast the latest diffs for the new instance.
+        let (old_player_count, diff) = {
+            let (old_instance, new_instance) = instances.get(&old_instance_id).zip(instances.get(new_instance_id)).unwrap();
+            (
+                old_instance.player_count(),
+                WorldDiff::from_a_to_b(old_instance.world_stream.filter().clone(), &old_instance.world, &new_instance.world),
+            )
+        };
+
+        // Borrow the old world mutably to remove the player and their streams.
+        let (entities_tx, events_tx, datagrams_tx, stats_tx) = {
+            let mut ed = instances.get_mut(&old_instance_id).unwrap().despawn_player(user_id).unwrap();
+            (
+                ed.remove_self(player_entity_stream()).unwrap(),
+                ed.remove_self(player_event_stream()).unwrap(),
+                ed.remove_self(player_datagram_stream()).unwrap(),
+                ed.remove_self(player_stats_stream()).unwrap(),
+            )
+        };
+
+        // Borrow the new world mutably to spawn the player in with their old streams.
+        instances.get_mut(new_instance_id).unwrap().spawn_player(create_player_entity_data(
+            user_id,
+            entities_tx.clone(),
+            events_tx,
+            datagrams_tx,
+            stats_tx,
+        ));
+        self.players.get_mut(user_id).unwrap().instance = new_instance_id.to_string();
+
+        let msg = bincode::serialize(&diff).unwrap();
+        entities_tx.send(msg).ok();
+
+        // Remove old instance
+        if old_player_count == 1 && old_instance_id != MAIN_INSTANCE_ID {
+            self.remove_instance(&old_instance_id);
+        }
+        true
+    }
+    /// Removes the instance if it exists, has no players and isn't the main instance. Returns true if it was removed
+    pub fn remove_instance_if_empty(&mut self, instance_id: &str) -> bool {
+        match self.instances.get(instance_id) {
+            Some(instance) if instance_id != MAIN_INSTANCE_ID && instance.player_count() == 0 => {
+                self.remove_instance(instance_id);
+                true
+            }
+            _ => false,
+        }
+    }
     pub fn remove_instance(&mut self, instance_id: &str) {
         log::debug!("Removing server instance id={}", instance_id);
         let mut sys = (self.create_shutdown_systems)();
@@ -202,22 +424,38 @@ impl ServerState {
 pub struct GameServer {
     _endpoint: Endpoint,
     incoming: Incoming,
+    /// Accepts the clients connecting with WebSockets, on the TCP port with the same number as the QUIC one, if enabled
+    websocket_listener: Option<TcpListener>,
     pub port: u16,
     /// Shuts down the server if there are no players
     pub use_inactivity_shutdown: bool,
+    /// The role of players that haven't been assigned one, see [ServerState::set_role]
+    pub default_role: Role,
+    /// The user ids which have the [Role::Admin] role. User ids are chosen by the clients, so anyone can claim these
+    pub admins: Vec<String>,
 }
 impl GameServer {
-    pub async fn new_with_port(port: u16, use_inactivity_shutdown: bool) -> anyhow::Result<Self> {
+    /// Creates a server listening on `port`, and also accepting clients over WebSockets if `websocket` is set
+    pub async fn new_with_port(port: u16, use_inactivity_shutdown: bool, websocket: bool) -> anyhow::Result<Self> {
         let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), port);
 
         let (endpoint, incoming) = create_server(server_addr)?;
+        let websocket_listener = if websocket { Some(TcpListener::bind(server_addr).await?) } else { None };
 
         log::debug!("GameServer listening on port {}", port);
-        Ok(Self { _endpoint: endpoint, incoming, port, use_inactivity_shutdown })
+        Ok(Self {
+            _endpoint: endpoint,
+            incoming,
+            websocket_listener,
+            port,
+            use_inactivity_shutdown,
+            default_role: Role::default(),
+            admins: Vec::new(),
+        })
     }
-    pub async fn new_with_port_in_range(port_range: Range<u16>, use_inactivity_shutdown: bool) -> anyhow::Result<Self> {
+    pub async fn new_with_port_in_range(port_range: Range<u16>, use_inactivity_shutdown: bool, websocket: bool) -> anyhow::Result<Self> {
         for port in port_range {
-            match Self::new_with_port(port, use_inactivity_shutdown).await {
+            match Self::new_with_port(port, use_inactivity_shutdown, websocket).await {
                 Ok(server) => {
                     return Ok(server);
                 }
@@ -237,9 +475,11 @@ impl GameServer {
         create_shutdown_systems: Arc<dyn Fn() -> SystemGroup<ShutdownEvent> + Sync + Send>,
         is_sync_component: Arc<dyn Fn(ComponentDesc, WorldStreamCompEvent) -> bool + Sync + Send>,
     ) -> SharedServerState {
-        let Self { mut incoming, .. } = self;
+        let Self { mut incoming, websocket_listener, default_role, admins, .. } = self;
         let assets = world.resource(asset_cache()).clone();
         let world_stream_filter = WorldStreamFilter::new(ArchetypeFilter::new().excl(no_sync()), is_sync_component);
+        world.add_resource(world_instance_id(), MAIN_INSTANCE_ID.to_string());
+        world.add_resource(world_instance_requests(), Vec::new());
         let state = Arc::new(Mutex::new(ServerState::new(
             [(
                 MAIN_INSTANCE_ID.to_string(),
@@ -255,6 +495,13 @@ impl GameServer {
             create_on_forking_systems,
             create_shutdown_systems,
         )));
+        {
+            let mut state = state.lock();
+            state.default_role = default_role;
+            for user_id in &admins {
+                state.set_role(user_id, Role::Admin);
+            }
+        }
 
         let mut fps_counter = FpsCounter::new();
         let mut sim_interval = interval(Duration::from_secs_f32(1. / 60.));
@@ -279,7 +526,22 @@ impl GameServer {
 
 
                     log::debug!("Accepted connection");
-                    run_connection(conn, state.clone(), world_stream_filter.clone(), assets.clone());
+                    run_connection(conn.into(), state.clone(), world_stream_filter.clone(), assets.clone());
+                }
+                Ok((stream, addr)) = accept_websocket(&websocket_listener) => {
+                    log::debug!("Received WebSocket connection from {addr}");
+
+                    // The handshake happens in the background, so that a slow client doesn't hold up the server
+                    let (state, world_stream_filter, assets) = (state.clone(), world_stream_filter.clone(), assets.clone());
+                    tokio::spawn(async move {
+                        match websocket::accept(stream).await {
+                            Ok(conn) => {
+                                log::debug!("Accepted WebSocket connection");
+                                run_connection(conn, state, world_stream_filter, assets);
+                            }
+                            Err(e) => log::error!("Failed to accept incoming WebSocket connection. {e}"),
+                        }
+                    });
                 }
                 _ = sim_interval.tick() => {
                     fps_counter.frame_start();
@@ -340,6 +602,7 @@ fn run_connection(connection: NewConnection, state: SharedServerState, world_str
                 let (diffs_tx, diffs_rx) = flume::unbounded();
                 let (stats_tx, stats_rx) = flume::unbounded();
                 let (events_tx, events_rx) = flume::unbounded();
+                let (datagrams_tx, datagrams_rx) = flume::unbounded();
 
                 let on_init = |client: ClientInfo| {
                     let user_id = &client.user_id;
@@ -380,13 +643,20 @@ fn run_connection(connection: NewConnection, state: SharedServerState, world_str
                     log::debug!("[{}] Init diff sent", user_id);
 
                     if !reconnecting {
-                        instance.spawn_player(create_player_entity_data(user_id, diffs_tx.clone(), events_tx.clone(), stats_tx.clone()));
+                        instance.spawn_player(create_player_entity_data(
+                            user_id,
+                            diffs_tx.clone(),
+                            events_tx.clone(),
+                            datagrams_tx.clone(),
+                            stats_tx.clone(),
+                        ));
                         log::info!("[{}] Player spawned", user_id);
                     } else {
                         let entity = get_player_by_user_id(&instance.world, user_id).unwrap();
                         instance.world.set(entity, player_entity_stream(), diffs_tx.clone()).unwrap();
                         instance.world.set(entity, player_stats_stream(), stats_tx.clone()).unwrap();
                         instance.world.set(entity, player_event_stream(), events_tx.clone()).unwrap();
+                        instance.world.set(entity, player_datagram_stream(), datagrams_tx.clone()).unwrap();
                         log::info!("[{}] Player reconnected", user_id);
                     }
                 };
@@ -457,6 +727,7 @@ fn run_connection(connection: NewConnection, state: SharedServerState, world_str
                     diffs_rx,
                     stats_rx,
                     events_rx,
+                    datagrams_rx,
                     on_init: &on_init,
                     on_rpc: &on_rpc,
                     on_datagram: &on_datagram,
@@ -468,7 +739,11 @@ fn run_connection(connection: NewConnection, state: SharedServerState, world_str
                     let state = state.lock();
                     let instance = state.instances.get(MAIN_INSTANCE_ID).unwrap();
                     let world = &instance.world;
-                    ServerInfo { project_name: world.resource(project_name()).clone() }
+                    ServerInfo {
+                        project_name: world.resource(project_name()).clone(),
+                        rpc_table: world.resource_opt(rpc_table()).cloned().unwrap_or_default(),
+                        content_base_url: ServerBaseUrlKey.get(world.resource(asset_cache())),
+                    }
                 };
 
                 match client.run(connection, server_info).await {
@@ -496,6 +771,7 @@ struct ClientInstance<'a> {
     diffs_rx: flume::Receiver<Vec<u8>>,
     stats_rx: flume::Receiver<FpsSample>,
     events_rx: flume::Receiver<Vec<u8>>,
+    datagrams_rx: flume::Receiver<Bytes>,
 
     on_init: &'a (dyn Fn(ClientInfo) + Send + Sync),
     on_datagram: &'a (dyn Fn(&String, Bytes) + Send + Sync),
@@ -523,6 +799,7 @@ impl<'a> ClientInstance<'a> {
         let mut entities_rx = self.diffs_rx.stream();
         let mut stats_rx = self.stats_rx.stream();
         let mut events_rx = self.events_rx.stream();
+        let mut datagrams_rx = self.datagrams_rx.stream();
 
         tokio::task::block_in_place(|| {
             (self.on_init)(proto.client_info().clone());
@@ -545,7 +822,14 @@ impl<'a> ClientInstance<'a> {
                     let span =tracing::debug_span!("server_event");
                     let mut stream = proto.connection().open_uni().instrument(span).await?;
 
-                    stream.write(&msg).await?;
+                    stream.write_all(&msg).await?;
+                }
+                Some(msg) = datagrams_rx.next() => {
+                    let _span = tracing::debug_span!("server_datagram").entered();
+                    // Unreliable by definition, so datagrams which can't be sent are dropped
+                    if let Err(err) = proto.connection().send_datagram(msg) {
+                        log::debug!("Failed to send datagram: {err:?}");
+                    }
                 }
                 Some(Ok(datagram)) = proto.conn.datagrams.next() => {
                     let _span =tracing::debug_span!("datagram").entered();
@@ -564,15 +848,31 @@ impl<'a> ClientInstance<'a> {
     }
 }
 
+/// Waits for a WebSocket connection, or forever if the server doesn't accept them
+async fn accept_websocket(listener: &Option<TcpListener>) -> std::io::Result<(TcpStream, SocketAddr)> {
+    match listener {
+        Some(listener) => listener.accept().await,
+        None => futures::future::pending().await,
+    }
+}
+
 /// Miscellaneous information about the server that needs to be sent to the client during the handshake.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ServerInfo {
     /// The name of the project. Used by the client to figure out what to title its window. Defaults to "Ambient".
     pub project_name: String,
+    /// The RPCs the server can run. Used by the client to check that it's compatible with the server.
+    pub rpc_table: RpcTable,
+    /// The url the server serves its content at, which is where the client mounts the content pack of the server.
+    pub content_base_url: AbsAssetUrl,
 }
 
 impl Default for ServerInfo {
     fn default() -> Self {
-        Self { project_name: "Ambient".into() }
+        Self {
+            project_name: "Ambient".into(),
+            rpc_table: Default::default(),
+            content_base_url: AbsAssetUrl::parse("http://localhost:8999/content/").unwrap(),
+        }
     }
 }
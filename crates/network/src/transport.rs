@@ -0,0 +1,209 @@
+use std::{
+    fmt::Display,
+    io,
+    pin::Pin,
+    str::FromStr,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use futures::{stream::BoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::io::ReadBuf;
+
+use crate::{
+    websocket::{WsConnection, WsRecvStream, WsSendStream},
+    NetworkError,
+};
+
+/// How a client connects to a server. The server accepts both
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum Transport {
+    /// QUIC over UDP, which is the fastest
+    #[default]
+    Quic,
+    /// WebSockets over TCP, for clients which can't use QUIC, like browsers
+    WebSocket,
+}
+impl Display for Transport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Transport::Quic => write!(f, "quic"),
+            Transport::WebSocket => write!(f, "websocket"),
+        }
+    }
+}
+impl FromStr for Transport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "quic" => Ok(Transport::Quic),
+            "websocket" | "ws" => Ok(Transport::WebSocket),
+            _ => Err(format!("Unknown transport {s:?}, expected one of quic or websocket")),
+        }
+    }
+}
+
+/// A connection between a client and a server, over any [Transport]
+#[derive(Debug, Clone)]
+pub enum Connection {
+    Quic(quinn::Connection),
+    WebSocket(WsConnection),
+}
+impl Connection {
+    pub fn transport(&self) -> Transport {
+        match self {
+            Connection::Quic(_) => Transport::Quic,
+            Connection::WebSocket(_) => Transport::WebSocket,
+        }
+    }
+    pub async fn open_uni(&self) -> Result<SendStream, NetworkError> {
+        match self {
+            Connection::Quic(conn) => Ok(SendStream::Quic(conn.open_uni().await?)),
+            Connection::WebSocket(conn) => Ok(SendStream::WebSocket(conn.open_uni().await?)),
+        }
+    }
+    pub async fn open_bi(&self) -> Result<(SendStream, RecvStream), NetworkError> {
+        match self {
+            Connection::Quic(conn) => {
+                let (send, recv) = conn.open_bi().await?;
+                Ok((SendStream::Quic(send), RecvStream::Quic(recv)))
+            }
+            Connection::WebSocket(conn) => {
+                let (send, recv) = conn.open_bi().await?;
+                Ok((SendStream::WebSocket(send), RecvStream::WebSocket(recv)))
+            }
+        }
+    }
+    /// Sends an unreliable message. Over WebSockets, datagrams are sent in order with everything else, and dropped when
+    /// the connection can't keep up
+    pub fn send_datagram(&self, data: Bytes) -> Result<(), NetworkError> {
+        match self {
+            Connection::Quic(conn) => Ok(conn.send_datagram(data)?),
+            Connection::WebSocket(conn) => conn.send_datagram(data),
+        }
+    }
+    /// The current estimate of the round trip time
+    pub fn rtt(&self) -> Duration {
+        match self {
+            Connection::Quic(conn) => conn.rtt(),
+            Connection::WebSocket(conn) => conn.rtt(),
+        }
+    }
+    /// The totals since the connection was opened
+    pub fn stats(&self) -> ConnectionStats {
+        match self {
+            Connection::Quic(conn) => {
+                let stats = conn.stats();
+                ConnectionStats { bytes_sent: stats.udp_tx.bytes, bytes_received: stats.udp_rx.bytes }
+            }
+            Connection::WebSocket(conn) => conn.stats(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConnectionStats {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+pub type IncomingUniStreams = BoxStream<'static, Result<RecvStream, NetworkError>>;
+pub type IncomingBiStreams = BoxStream<'static, Result<(SendStream, RecvStream), NetworkError>>;
+pub type Datagrams = BoxStream<'static, Result<Bytes, NetworkError>>;
+
+/// An established [Connection], with the streams and datagrams opened by the peer
+pub struct NewConnection {
+    pub connection: Connection,
+    pub uni_streams: IncomingUniStreams,
+    pub bi_streams: IncomingBiStreams,
+    pub datagrams: Datagrams,
+}
+impl From<quinn::NewConnection> for NewConnection {
+    fn from(conn: quinn::NewConnection) -> Self {
+        Self {
+            connection: Connection::Quic(conn.connection),
+            uni_streams: conn.uni_streams.map(|stream| Ok(RecvStream::Quic(stream?))).boxed(),
+            bi_streams: conn
+                .bi_streams
+                .map(|stream| stream.map(|(send, recv)| (SendStream::Quic(send), RecvStream::Quic(recv))).map_err(Into::into))
+                .boxed(),
+            datagrams: conn.datagrams.map(|datagram| Ok(datagram?)).boxed(),
+        }
+    }
+}
+impl std::fmt::Debug for NewConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NewConnection").field("connection", &self.connection).finish_non_exhaustive()
+    }
+}
+
+/// The sending half of a stream. Dropping it finishes the stream
+#[derive(Debug)]
+pub enum SendStream {
+    Quic(quinn::SendStream),
+    WebSocket(WsSendStream),
+}
+impl SendStream {
+    /// Finishes the stream. Over QUIC, this also waits for the peer to acknowledge everything that was sent
+    pub async fn finish(&mut self) -> Result<(), NetworkError> {
+        match self {
+            SendStream::Quic(stream) => Ok(stream.finish().await?),
+            SendStream::WebSocket(stream) => stream.finish().await,
+        }
+    }
+}
+impl tokio::io::AsyncWrite for SendStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            SendStream::Quic(stream) => Pin::new(stream).poll_write(cx, buf),
+            SendStream::WebSocket(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            SendStream::Quic(stream) => Pin::new(stream).poll_flush(cx),
+            SendStream::WebSocket(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            SendStream::Quic(stream) => Pin::new(stream).poll_shutdown(cx),
+            SendStream::WebSocket(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
+
+/// The receiving half of a stream
+#[derive(Debug)]
+pub enum RecvStream {
+    Quic(quinn::RecvStream),
+    WebSocket(WsRecvStream),
+}
+impl RecvStream {
+    /// Reads the rest of the stream, failing if it's longer than `size_limit`
+    pub async fn read_to_end(self, size_limit: usize) -> Result<Vec<u8>, NetworkError> {
+        match self {
+            RecvStream::Quic(stream) => Ok(stream.read_to_end(size_limit).await?),
+            RecvStream::WebSocket(stream) => stream.read_to_end(size_limit).await,
+        }
+    }
+}
+impl tokio::io::AsyncRead for RecvStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            RecvStream::Quic(stream) => tokio::io::AsyncRead::poll_read(Pin::new(stream), cx, buf),
+            RecvStream::WebSocket(stream) => tokio::io::AsyncRead::poll_read(Pin::new(stream), cx, buf),
+        }
+    }
+}
+impl futures::AsyncRead for RecvStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            RecvStream::Quic(stream) => futures::AsyncRead::poll_read(Pin::new(stream), cx, buf),
+            RecvStream::WebSocket(stream) => futures::AsyncRead::poll_read(Pin::new(stream), cx, buf),
+        }
+    }
+}
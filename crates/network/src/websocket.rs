@@ -0,0 +1,468 @@
+//! The game protocol over a WebSocket, for clients which can't use QUIC.
+//!
+//! A WebSocket is a single ordered stream of messages, so the streams and datagrams of a [Connection] are multiplexed
+//! over it: each binary message is a frame starting with its kind and the id of its stream. Ids opened by the client
+//! are even and ids opened by the server are odd, like in QUIC. There is no flow control per stream; everything
+//! shares the TCP connection, so a large stream holds up the others, and so does a stream which isn't read once its
+//! buffer is full. Datagrams are dropped when the connection can't keep up with them
+
+use std::{
+    collections::HashMap,
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+        Arc, Weak,
+    },
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use ambient_sys::time::Instant;
+use bytes::Bytes;
+use flume::r#async::SendSink;
+use futures::{
+    stream::{SplitSink, SplitStream},
+    SinkExt, StreamExt,
+};
+use parking_lot::Mutex;
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::TcpStream,
+};
+use tokio_tungstenite::{tungstenite::Message, WebSocketStream};
+
+use crate::{
+    transport::{Connection, ConnectionStats, NewConnection, RecvStream, SendStream},
+    NetworkError,
+};
+
+const OPEN_UNI: u8 = 0;
+const OPEN_BI: u8 = 1;
+const DATA: u8 = 2;
+const FINISH: u8 = 3;
+const DATAGRAM: u8 = 4;
+
+const HEADER_SIZE: usize = 5;
+/// Larger writes are split, so that a large stream doesn't hold up the others for too long
+const MAX_CHUNK_SIZE: usize = 64 * 1024;
+/// The number of frames waiting to be sent, after which writing to a stream waits
+const OUTGOING_CAPACITY: usize = 64;
+/// The number of chunks received for a stream which haven't been read yet, after which the connection stops reading
+const STREAM_CAPACITY: usize = 64;
+/// The number of datagrams received which haven't been handled yet, after which they're dropped
+const DATAGRAM_CAPACITY: usize = 256;
+/// The number of streams the peer can have open at once. Opening more is a protocol violation which closes the connection
+const MAX_PEER_STREAMS: usize = 256;
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Connects to the WebSocket endpoint of the server at `server_addr`, which is on the same port as its QUIC endpoint
+pub async fn connect(server_addr: SocketAddr) -> Result<NewConnection, NetworkError> {
+    let stream = TcpStream::connect(server_addr).await?;
+    stream.set_nodelay(true)?;
+    let (ws, _) = tokio_tungstenite::client_async(format!("ws://{server_addr}"), stream).await?;
+    Ok(WsConnection::start(ws, false))
+}
+
+/// Accepts a WebSocket connection from a client. Fails if the client doesn't complete the handshake in time
+pub async fn accept(stream: TcpStream) -> Result<NewConnection, NetworkError> {
+    stream.set_nodelay(true)?;
+    let ws = tokio::time::timeout(HANDSHAKE_TIMEOUT, tokio_tungstenite::accept_async(stream))
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "The WebSocket handshake timed out"))??;
+    Ok(WsConnection::start(ws, true))
+}
+
+fn frame(kind: u8, id: u32, payload: &[u8]) -> Message {
+    let mut frame = Vec::with_capacity(HEADER_SIZE + payload.len());
+    frame.push(kind);
+    frame.extend_from_slice(&id.to_be_bytes());
+    frame.extend_from_slice(payload);
+    Message::Binary(frame)
+}
+
+fn connection_lost() -> io::Error {
+    io::Error::new(io::ErrorKind::ConnectionReset, "Connection lost")
+}
+
+struct Shared {
+    outgoing: flume::Sender<Message>,
+    next_stream_id: AtomicU32,
+    /// Where the data of the streams opened by the peer, and of the bi streams opened by us, goes. An empty chunk
+    /// marks the end of a stream, and a stream which ends without it was reset
+    recv_streams: Mutex<HashMap<u32, flume::Sender<Bytes>>>,
+    closed: AtomicBool,
+    rtt: Mutex<Duration>,
+    bytes_sent: Arc<AtomicU64>,
+    bytes_received: AtomicU64,
+}
+impl Shared {
+    async fn send(&self, kind: u8, id: u32, payload: &[u8]) -> Result<(), NetworkError> {
+        if self.closed.load(Ordering::Acquire) {
+            return Err(NetworkError::ConnectionClosed);
+        }
+        self.outgoing.send_async(frame(kind, id, payload)).await.map_err(|_| NetworkError::ConnectionClosed)
+    }
+    fn add_recv_stream(self: &Arc<Self>, id: u32) -> WsRecvStream {
+        let (tx, rx) = flume::bounded(STREAM_CAPACITY);
+        self.recv_streams.lock().insert(id, tx);
+        WsRecvStream { id, chunks: rx.into_stream(), chunk: Bytes::new(), finished: false, shared: Arc::downgrade(self) }
+    }
+    /// Resets the streams which are still open, and stops sending
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.recv_streams.lock().clear();
+        self.outgoing.try_send(Message::Close(None)).ok();
+    }
+}
+
+/// A connection over a WebSocket. The connection is closed once all its handles and streams have been dropped
+#[derive(Clone)]
+pub struct WsConnection {
+    shared: Arc<Shared>,
+}
+impl WsConnection {
+    fn start<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static>(
+        ws: WebSocketStream<S>,
+        is_server: bool,
+    ) -> NewConnection {
+        let (outgoing, outgoing_rx) = flume::bounded(OUTGOING_CAPACITY);
+        let shared = Arc::new(Shared {
+            outgoing,
+            next_stream_id: AtomicU32::new(if is_server { 1 } else { 0 }),
+            recv_streams: Default::default(),
+            closed: AtomicBool::new(false),
+            rtt: Mutex::new(Duration::ZERO),
+            bytes_sent: Default::default(),
+            bytes_received: AtomicU64::new(0),
+        });
+        // The streams opened by the peer are limited by MAX_PEER_STREAMS
+        let (uni_streams, uni_streams_rx) = flume::unbounded();
+        let (bi_streams, bi_streams_rx) = flume::unbounded();
+        let (datagrams, datagrams_rx) = flume::bounded(DATAGRAM_CAPACITY);
+
+        let (sink, stream) = ws.split();
+        let start = Instant::now();
+        tokio::spawn(write_messages(sink, outgoing_rx, shared.bytes_sent.clone(), start));
+        let incoming = Incoming { uni_streams, bi_streams, datagrams, next_stream_id: if is_server { 0 } else { 1 } };
+        tokio::spawn(read_messages(stream, Arc::downgrade(&shared), incoming, start));
+
+        NewConnection {
+            connection: Connection::WebSocket(Self { shared }),
+            uni_streams: uni_streams_rx.into_stream().boxed(),
+            bi_streams: bi_streams_rx.into_stream().boxed(),
+            datagrams: datagrams_rx.into_stream().boxed(),
+        }
+    }
+    fn next_stream_id(&self) -> u32 {
+        self.shared.next_stream_id.fetch_add(2, Ordering::Relaxed)
+    }
+    pub async fn open_uni(&self) -> Result<WsSendStream, NetworkError> {
+        let id = self.next_stream_id();
+        self.shared.send(OPEN_UNI, id, &[]).await?;
+        Ok(WsSendStream::new(id, self.shared.clone()))
+    }
+    pub async fn open_bi(&self) -> Result<(WsSendStream, WsRecvStream), NetworkError> {
+        let id = self.next_stream_id();
+        // Listen before the peer can answer
+        let recv = self.shared.add_recv_stream(id);
+        self.shared.send(OPEN_BI, id, &[]).await?;
+        Ok((WsSendStream::new(id, self.shared.clone()), recv))
+    }
+    /// Sends a datagram, or drops it if too many frames are waiting to be sent
+    pub fn send_datagram(&self, data: Bytes) -> Result<(), NetworkError> {
+        if self.shared.closed.load(Ordering::Acquire) {
+            return Err(NetworkError::ConnectionClosed);
+        }
+        match self.shared.outgoing.try_send(frame(DATAGRAM, 0, &data)) {
+            Ok(()) | Err(flume::TrySendError::Full(_)) => Ok(()),
+            Err(flume::TrySendError::Disconnected(_)) => Err(NetworkError::ConnectionClosed),
+        }
+    }
+    pub fn rtt(&self) -> Duration {
+        *self.shared.rtt.lock()
+    }
+    pub fn stats(&self) -> ConnectionStats {
+        ConnectionStats {
+            bytes_sent: self.shared.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.shared.bytes_received.load(Ordering::Relaxed),
+        }
+    }
+}
+impl std::fmt::Debug for WsConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WsConnection").field("closed", &self.shared.closed.load(Ordering::Relaxed)).finish_non_exhaustive()
+    }
+}
+
+/// Where the streams and datagrams opened by the peer go
+struct Incoming {
+    uni_streams: flume::Sender<Result<RecvStream, NetworkError>>,
+    bi_streams: flume::Sender<Result<(SendStream, RecvStream), NetworkError>>,
+    datagrams: flume::Sender<Result<Bytes, NetworkError>>,
+    /// Like in QUIC, the peer opens its streams in order, so that their ids can't be reused
+    next_stream_id: u64,
+}
+impl Incoming {
+    /// Checks that the peer can open the stream `id`
+    fn open_stream(&mut self, shared: &Shared, id: u32) -> Result<(), String> {
+        let id = u64::from(id);
+        if id % 2 != self.next_stream_id % 2 {
+            return Err(format!("The peer opened stream {id}, which has our parity"));
+        }
+        if id < self.next_stream_id {
+            return Err(format!("The peer opened stream {id}, which was already opened"));
+        }
+        let open_streams = shared.recv_streams.lock().keys().filter(|&&id| u64::from(id) % 2 == self.next_stream_id % 2).count();
+        if open_streams >= MAX_PEER_STREAMS {
+            return Err(format!("The peer opened more than {MAX_PEER_STREAMS} streams"));
+        }
+        self.next_stream_id = id + 2;
+        Ok(())
+    }
+}
+
+async fn write_messages<S: AsyncRead + AsyncWrite + Unpin>(
+    mut sink: SplitSink<WebSocketStream<S>, Message>,
+    outgoing: flume::Receiver<Message>,
+    bytes_sent: Arc<AtomicU64>,
+    start: Instant,
+) {
+    let mut ping_interval = tokio::time::interval(PING_INTERVAL);
+    loop {
+        let message = tokio::select! {
+            message = outgoing.recv_async() => match message {
+                Ok(message) => message,
+                // All the handles to the connection were dropped
+                Err(_) => break,
+            },
+            _ = ping_interval.tick() => Message::Ping((start.elapsed().as_nanos() as u64).to_be_bytes().to_vec()),
+        };
+        if let Message::Binary(frame) = &message {
+            bytes_sent.fetch_add(frame.len() as u64, Ordering::Relaxed);
+        }
+        let is_close = matches!(message, Message::Close(_));
+        if sink.send(message).await.is_err() || is_close {
+            break;
+        }
+    }
+    sink.close().await.ok();
+}
+
+async fn read_messages<S: AsyncRead + AsyncWrite + Unpin>(
+    mut stream: SplitStream<WebSocketStream<S>>,
+    shared: Weak<Shared>,
+    mut incoming: Incoming,
+    start: Instant,
+) {
+    while let Some(message) = stream.next().await {
+        let Some(shared) = shared.upgrade() else { return };
+        let frame = match message {
+            Ok(Message::Binary(frame)) => frame,
+            Ok(Message::Pong(sent)) => {
+                if let Ok(sent) = sent.try_into() {
+                    let sent = Duration::from_nanos(u64::from_be_bytes(sent));
+                    *shared.rtt.lock() = start.elapsed().saturating_sub(sent);
+                }
+                continue;
+            }
+            Ok(Message::Close(_)) => break,
+            Ok(_) => continue,
+            Err(err) => {
+                log::debug!("WebSocket error: {err:?}");
+                break;
+            }
+        };
+        shared.bytes_received.fetch_add(frame.len() as u64, Ordering::Relaxed);
+        if frame.len() < HEADER_SIZE {
+            log::warn!("Received an invalid WebSocket frame");
+            continue;
+        }
+        let kind = frame[0];
+        let id = u32::from_be_bytes(frame[1..HEADER_SIZE].try_into().unwrap());
+        let payload = Bytes::from(frame).split_off(HEADER_SIZE);
+        match kind {
+            OPEN_UNI | OPEN_BI => {
+                if let Err(err) = incoming.open_stream(&shared, id) {
+                    log::warn!("{err}, closing the WebSocket connection");
+                    break;
+                }
+                let recv = RecvStream::WebSocket(shared.add_recv_stream(id));
+                if kind == OPEN_UNI {
+                    incoming.uni_streams.send(Ok(recv)).ok();
+                } else {
+                    let send = SendStream::WebSocket(WsSendStream::new(id, shared.clone()));
+                    incoming.bi_streams.send(Ok((send, recv))).ok();
+                }
+            }
+            DATA => {
+                // The stream may have been dropped on this side, in which case its data is ignored
+                let stream = shared.recv_streams.lock().get(&id).cloned();
+                if let Some(stream) = stream.filter(|_| !payload.is_empty()) {
+                    drop(shared);
+                    stream.send_async(payload).await.ok();
+                }
+            }
+            FINISH => {
+                let stream = shared.recv_streams.lock().remove(&id);
+                if let Some(stream) = stream {
+                    drop(shared);
+                    stream.send_async(Bytes::new()).await.ok();
+                }
+            }
+            DATAGRAM => {
+                incoming.datagrams.try_send(Ok(payload)).ok();
+            }
+            kind => log::warn!("Received a WebSocket frame of unknown kind {kind}"),
+        }
+    }
+    if let Some(shared) = shared.upgrade() {
+        shared.close();
+    }
+}
+
+/// The sending half of a stream over a WebSocket
+pub struct WsSendStream {
+    id: u32,
+    /// Holds the last frame written until there's room for it in the outgoing queue
+    sink: SendSink<'static, Message>,
+    shared: Arc<Shared>,
+    finished: bool,
+}
+impl WsSendStream {
+    fn new(id: u32, shared: Arc<Shared>) -> Self {
+        Self { id, sink: shared.outgoing.clone().into_sink(), shared, finished: false }
+    }
+    /// Finishes the stream. Unlike QUIC, this doesn't wait for the peer to receive it
+    pub async fn finish(&mut self) -> Result<(), NetworkError> {
+        Ok(tokio::io::AsyncWriteExt::shutdown(self).await?)
+    }
+}
+impl AsyncWrite for WsSendStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.finished {
+            return Poll::Ready(Err(io::Error::new(io::ErrorKind::BrokenPipe, "Stream was finished")));
+        }
+        if this.shared.closed.load(Ordering::Acquire) {
+            return Poll::Ready(Err(connection_lost()));
+        }
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        // Waits until the previous frame is queued
+        ready!(this.sink.poll_ready_unpin(cx)).map_err(|_| connection_lost())?;
+        let len = buf.len().min(MAX_CHUNK_SIZE);
+        this.sink.start_send_unpin(frame(DATA, this.id, &buf[..len])).map_err(|_| connection_lost())?;
+        if let Poll::Ready(Err(_)) = this.sink.poll_flush_unpin(cx) {
+            return Poll::Ready(Err(connection_lost()));
+        }
+        Poll::Ready(Ok(len))
+    }
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().sink.poll_flush_unpin(cx).map_err(|_| connection_lost())
+    }
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if !this.finished {
+            if this.shared.closed.load(Ordering::Acquire) {
+                return Poll::Ready(Err(connection_lost()));
+            }
+            ready!(this.sink.poll_ready_unpin(cx)).map_err(|_| connection_lost())?;
+            this.sink.start_send_unpin(frame(FINISH, this.id, &[])).map_err(|_| connection_lost())?;
+            this.finished = true;
+        }
+        this.sink.poll_flush_unpin(cx).map_err(|_| connection_lost())
+    }
+}
+impl Drop for WsSendStream {
+    fn drop(&mut self) {
+        if self.finished || self.shared.closed.load(Ordering::Acquire) {
+            return;
+        }
+        // The stream is finished once the frames written before are queued
+        let mut sink = std::mem::replace(&mut self.sink, self.shared.outgoing.clone().into_sink());
+        let id = self.id;
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move { sink.send(frame(FINISH, id, &[])).await.ok() });
+        }
+    }
+}
+impl std::fmt::Debug for WsSendStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WsSendStream").field("id", &self.id).field("finished", &self.finished).finish()
+    }
+}
+
+/// The receiving half of a stream over a WebSocket
+pub struct WsRecvStream {
+    id: u32,
+    chunks: flume::r#async::RecvStream<'static, Bytes>,
+    /// What's left of the last chunk received
+    chunk: Bytes,
+    /// If the end of the stream was received
+    finished: bool,
+    shared: Weak<Shared>,
+}
+impl WsRecvStream {
+    /// Waits until there is data to read. Returns false at the end of the stream
+    fn poll_chunk(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<bool>> {
+        while self.chunk.is_empty() && !self.finished {
+            match ready!(self.chunks.poll_next_unpin(cx)) {
+                Some(chunk) if chunk.is_empty() => self.finished = true,
+                Some(chunk) => self.chunk = chunk,
+                None => return Poll::Ready(Err(connection_lost())),
+            }
+        }
+        Poll::Ready(Ok(!self.chunk.is_empty()))
+    }
+    pub async fn read_to_end(mut self, size_limit: usize) -> Result<Vec<u8>, NetworkError> {
+        let mut data = std::mem::take(&mut self.chunk).to_vec();
+        while !self.finished {
+            match self.chunks.next().await {
+                Some(chunk) if chunk.is_empty() => self.finished = true,
+                Some(chunk) => data.extend_from_slice(&chunk),
+                None => return Err(connection_lost().into()),
+            }
+            if data.len() > size_limit {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "The stream is longer than its size limit").into());
+            }
+        }
+        Ok(data)
+    }
+}
+impl AsyncRead for WsRecvStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if ready!(this.poll_chunk(cx))? {
+            let len = buf.remaining().min(this.chunk.len());
+            buf.put_slice(&this.chunk.split_to(len));
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+impl futures::AsyncRead for WsRecvStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if !ready!(this.poll_chunk(cx))? {
+            return Poll::Ready(Ok(0));
+        }
+        let len = buf.len().min(this.chunk.len());
+        buf[..len].copy_from_slice(&this.chunk.split_to(len));
+        Poll::Ready(Ok(len))
+    }
+}
+impl Drop for WsRecvStream {
+    fn drop(&mut self) {
+        if let Some(shared) = self.shared.upgrade() {
+            shared.recv_streams.lock().remove(&self.id);
+        }
+    }
+}
+impl std::fmt::Debug for WsRecvStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WsRecvStream").field("id", &self.id).finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use ambient_network::headless::HeadlessClientStats;
+
+#[test]
+fn diff_timings() {
+    let stats = HeadlessClientStats::default();
+    assert_eq!(stats.mean_diff_interval(), Duration::ZERO);
+    assert_eq!(stats.mean_diff_latency(), Duration::ZERO);
+
+    // The first diff has no interval
+    let stats = HeadlessClientStats {
+        rtt: Duration::from_millis(40),
+        diffs: 3,
+        total_diff_interval: Duration::from_millis(200),
+        total_diff_apply_time: Duration::from_millis(30),
+        ..Default::default()
+    };
+    assert_eq!(stats.mean_diff_interval(), Duration::from_millis(100));
+    assert_eq!(stats.mean_diff_latency(), Duration::from_millis(20 + 50 + 10));
+}
+
+#[test]
+fn bandwidth() {
+    let prev = HeadlessClientStats { bytes_sent: 1000, bytes_received: 5000, ..Default::default() };
+    let stats = HeadlessClientStats { bytes_sent: 3000, bytes_received: 9000, ..Default::default() };
+    assert_eq!(stats.bandwidth_since(&prev, Duration::from_secs(2)), (1000., 2000.));
+    // The counters are reset when a client reconnects
+    assert_eq!(prev.bandwidth_since(&stats, Duration::from_secs(2)), (0., 0.));
+}
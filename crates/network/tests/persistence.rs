@@ -0,0 +1,72 @@
+use ambient_ecs::{components, dont_store, Entity, Store, World};
+use ambient_network::persistence::{world_loaded, WorldPersistence};
+
+components!("persistence_test", {
+    @[Store]
+    saved_value: f32,
+    unsaved_value: f32,
+});
+
+fn init() {
+    ambient_ecs::init_components();
+    ambient_core::init_all_components();
+    ambient_network::init_all_components();
+    init_components();
+}
+
+#[test]
+fn save_and_load() {
+    init();
+    let dir = std::env::temp_dir().join(format!("ambient_persistence_{}", std::process::id()));
+    let persistence = WorldPersistence::new(dir.join("world.json"), None);
+
+    let mut world = World::new("test");
+    let saved = Entity::new().with(saved_value(), 1.).with(unsaved_value(), 2.).spawn(&mut world);
+    let skipped = Entity::new().with(saved_value(), 3.).with_default(dont_store()).spawn(&mut world);
+    assert_eq!(persistence.save(&world).unwrap(), 1);
+    // Saves are versioned json, so that they can be migrated
+    let content = std::fs::read_to_string(&persistence.path).unwrap();
+    assert!(content.starts_with(r#"{"$format_version":"#), "{content}");
+
+    let mut loaded = World::new("test");
+    assert_eq!(persistence.load(&mut loaded).unwrap(), Some(1));
+    assert_eq!(loaded.get(saved, saved_value()).unwrap(), 1.);
+    assert!(!loaded.has_component(saved, unsaved_value()));
+    assert!(!loaded.exists(skipped));
+    assert!(loaded.resource_opt(world_loaded()).is_some());
+
+    // Entities which already exist are skipped
+    assert_eq!(persistence.load(&mut loaded).unwrap(), Some(0));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn older_snapshots_dont_replace_newer_saves() {
+    init();
+    let dir = std::env::temp_dir().join(format!("ambient_persistence_order_{}", std::process::id()));
+    let persistence = WorldPersistence::new(dir.join("world.json"), None);
+
+    let mut world = World::new("test");
+    let id = Entity::new().with(saved_value(), 1.).spawn(&mut world);
+    let older = persistence.snapshot(&world).unwrap();
+    world.set(id, saved_value(), 2.).unwrap();
+    let newer = persistence.snapshot(&world).unwrap();
+    assert_eq!(newer.write().unwrap(), 1);
+    assert_eq!(older.write().unwrap(), 0);
+
+    let mut loaded = World::new("test");
+    persistence.load(&mut loaded).unwrap();
+    assert_eq!(loaded.get(id, saved_value()).unwrap(), 2.);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn load_without_save() {
+    init();
+    let persistence = WorldPersistence::new(std::env::temp_dir().join("ambient_persistence_missing").join("world.json"), None);
+    let mut world = World::new("test");
+    assert_eq!(persistence.load(&mut world).unwrap(), None);
+    assert!(world.resource_opt(world_loaded()).is_none());
+}
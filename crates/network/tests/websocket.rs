@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use ambient_network::{
+    next_bincode_bi_stream, open_bincode_bi_stream, send_single_bincode_uni_msg,
+    transport::{NewConnection, Transport},
+    websocket, IncomingStream, NetworkError, OutgoingStream,
+};
+use futures::{SinkExt, StreamExt};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpListener, TcpStream},
+};
+use tokio_tungstenite::{tungstenite::Message, WebSocketStream};
+
+/// Returns the client and the server side of a new connection
+async fn connect() -> (NewConnection, NewConnection) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (client, server) = tokio::join!(websocket::connect(addr), async { websocket::accept(listener.accept().await.unwrap().0).await });
+    (client.unwrap(), server.unwrap())
+}
+
+/// Returns a plain WebSocket client and the server side of its connection, to send the server frames a client wouldn't
+async fn connect_raw() -> (WebSocketStream<TcpStream>, NewConnection) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (client, server) = tokio::join!(
+        async { tokio_tungstenite::client_async(format!("ws://{addr}"), TcpStream::connect(addr).await.unwrap()).await },
+        async { websocket::accept(listener.accept().await.unwrap().0).await }
+    );
+    (client.unwrap().0, server.unwrap())
+}
+
+fn open_uni_frame(id: u32) -> Message {
+    let mut frame = vec![0];
+    frame.extend_from_slice(&id.to_be_bytes());
+    Message::Binary(frame)
+}
+
+#[tokio::test]
+async fn bi_streams() {
+    let (client, mut server) = connect().await;
+    assert_eq!(client.connection.transport(), Transport::WebSocket);
+
+    let (mut tx, mut rx) = open_bincode_bi_stream(&client.connection).await.unwrap();
+    tx.send(&"hello".to_string()).await.unwrap();
+
+    let (mut server_tx, mut server_rx) = next_bincode_bi_stream(&mut server).await.unwrap();
+    assert_eq!(server_rx.next::<String>().await.unwrap(), "hello");
+    server_tx.send(&42_u32).await.unwrap();
+    assert_eq!(rx.next::<u32>().await.unwrap(), 42);
+
+    // Finishing the stream ends it on the other side
+    drop(tx);
+    assert!(server_rx.next::<String>().await.unwrap_err().is_end_of_stream());
+}
+
+#[tokio::test]
+async fn uni_streams_and_datagrams() {
+    let (mut client, server) = connect().await;
+
+    let mut diffs = OutgoingStream::open_uni(&server.connection).await.unwrap();
+    // Larger than a frame, so it's split
+    let large = vec![7_u8; 1_000_000];
+    diffs.send(&large).await.unwrap();
+    diffs.send(&1_u32).await.unwrap();
+    send_single_bincode_uni_msg(&server.connection, &"event".to_string()).await.unwrap();
+
+    let mut incoming = IncomingStream::accept_incoming(&mut client).await.unwrap();
+    assert_eq!(incoming.next::<Vec<u8>>().await.unwrap(), large);
+    assert_eq!(incoming.next::<u32>().await.unwrap(), 1);
+    let event = client.uni_streams.next().await.unwrap().unwrap().read_to_end(100).await.unwrap();
+    assert_eq!(bincode::deserialize::<String>(&event).unwrap(), "event");
+
+    server.connection.send_datagram(vec![1, 2, 3].into()).unwrap();
+    assert_eq!(&client.datagrams.next().await.unwrap().unwrap()[..], &[1, 2, 3]);
+
+    assert!(server.connection.stats().bytes_sent > 1_000_000);
+    assert_eq!(server.connection.stats().bytes_sent, client.connection.stats().bytes_received);
+}
+
+#[tokio::test]
+async fn rtt() {
+    let (client, _server) = connect().await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(client.connection.rtt() > Duration::ZERO);
+}
+
+#[tokio::test]
+async fn closed() {
+    let (mut client, server) = connect().await;
+
+    let mut diffs = OutgoingStream::open_uni(&server.connection).await.unwrap();
+    diffs.send(&()).await.unwrap();
+    let mut incoming = IncomingStream::accept_incoming(&mut client).await.unwrap();
+    incoming.next::<()>().await.unwrap();
+
+    // Dropping all the handles to the connection finishes its streams and closes it
+    drop((diffs, server));
+    assert!(incoming.next::<()>().await.unwrap_err().is_end_of_stream());
+    assert!(client.uni_streams.next().await.is_none());
+    assert!(client.connection.open_uni().await.unwrap_err().is_closed());
+}
+
+#[tokio::test]
+async fn unread_streams_hold_up_the_writer() {
+    let (mut client, server) = connect().await;
+    let mut stream = server.connection.open_uni().await.unwrap();
+
+    // The client doesn't read the stream, so the queues fill up and writing waits
+    let large = vec![7_u8; 64 << 20];
+    assert!(tokio::time::timeout(Duration::from_millis(500), stream.write_all(&large)).await.is_err());
+
+    let incoming = client.uni_streams.next().await.unwrap().unwrap();
+    let reader = tokio::spawn(async move { incoming.read_to_end(usize::MAX).await.unwrap().len() });
+    stream.write_all(&large).await.unwrap();
+    stream.finish().await.unwrap();
+    assert!(reader.await.unwrap() > large.len());
+}
+
+#[tokio::test]
+async fn invalid_peer_streams_close_the_connection() {
+    // A stream with the parity of the server, a stream opened twice and a stream opened out of order
+    for ids in [vec![1], vec![0, 0], vec![4, 2]] {
+        let (mut client, mut server) = connect_raw().await;
+        for id in ids {
+            client.send(open_uni_frame(id)).await.unwrap();
+        }
+        while let Some(stream) = server.uni_streams.next().await {
+            stream.unwrap();
+        }
+        assert!(server.connection.open_uni().await.unwrap_err().is_closed());
+    }
+}
+
+#[tokio::test]
+async fn peer_stream_limit() {
+    let (mut client, mut server) = connect_raw().await;
+    for id in (0..=256).map(|i| i * 2) {
+        client.send(open_uni_frame(id)).await.unwrap();
+    }
+    let streams = server.uni_streams.by_ref().map(|stream| stream.unwrap()).collect::<Vec<_>>().await;
+    assert_eq!(streams.len(), 256);
+    assert!(server.connection.open_uni().await.unwrap_err().is_closed());
+}
+
+#[tokio::test(start_paused = true)]
+async fn handshake_timeout() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let _client = TcpStream::connect(listener.local_addr().unwrap()).await.unwrap();
+    let err = websocket::accept(listener.accept().await.unwrap().0).await.unwrap_err();
+    assert!(matches!(err, NetworkError::IOError(err) if err.kind() == std::io::ErrorKind::TimedOut));
+}
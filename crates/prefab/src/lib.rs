@@ -9,7 +9,7 @@ use ambient_model::model_from_url;
 use ambient_physics::collider::collider;
 use ambient_std::{
     asset_cache::{AssetCache, AsyncAssetKey, AsyncAssetKeyExt},
-    asset_url::AssetUrl,
+    asset_url::{AbsAssetUrl, AssetUrl, BINARY_PREFAB_EXTENSION},
     download_asset::{AssetError, BytesFromUrl},
     unwrap_log_err,
 };
@@ -37,17 +37,18 @@ pub fn systems() -> SystemGroup {
         vec![query(prefab_from_url()).spawned().to_system(|q, world, qs, _| {
             let mut to_load = HashMap::<String, Vec<EntityId>>::new();
             for (id, url) in q.collect_cloned(world, qs) {
-                let url = if url.ends_with("/prefabs/main.json") { url } else { format!("{url}/prefabs/main.json") };
                 to_load.entry(url).or_default().push(id);
             }
             for (url, ids) in to_load {
                 let assets = world.resource(asset_cache()).clone();
-                let url = unwrap_log_err!(AssetUrl::parse(url));
-                let url = PrefabFromUrl(url);
+                let is_prefab = url.ends_with("/prefabs/main.json") || url.ends_with(&format!("/prefabs/main.{BINARY_PREFAB_EXTENSION}"));
+                let url = unwrap_log_err!(AbsAssetUrl::parse(url));
                 let runtime = world.resource(runtime()).clone();
                 let async_run = world.resource(async_run()).clone();
                 runtime.spawn(async move {
-                    let obj = unwrap_log_err!(url.get(&assets).await);
+                    let obj =
+                        if is_prefab { PrefabFromUrl(url.into()).get(&assets).await } else { PrefabFromCrateUrl(url).get(&assets).await };
+                    let obj = unwrap_log_err!(obj);
                     let base_ent_id = obj.resource(children())[0];
                     // TODO: This only handles prefabs with a single entity
                     let entity = obj.clone_entity(base_ent_id).unwrap();
@@ -70,7 +71,7 @@ impl AsyncAssetKey<Result<Arc<World>, AssetError>> for PrefabFromUrl {
     async fn load(self, assets: AssetCache) -> Result<Arc<World>, AssetError> {
         let obj_url = self.0.abs().context(format!("PrefabFromUrl got relative url: {}", self.0))?;
         let data = BytesFromUrl::new(obj_url.clone(), true).get(&assets).await?;
-        let DeserWorldWithWarnings { mut world, warnings } = tokio::task::block_in_place(|| serde_json::from_slice(&data))
+        let DeserWorldWithWarnings { mut world, warnings } = tokio::task::block_in_place(|| DeserWorldWithWarnings::from_slice(&data))
             .with_context(|| format!("Failed to deserialize object2 from url {obj_url}"))?;
         warnings.log_warnings();
         for (_id, (url,), _) in query_mut((model_from_url(),), ()).iter(&mut world, None) {
@@ -85,3 +86,20 @@ impl AsyncAssetKey<Result<Arc<World>, AssetError>> for PrefabFromUrl {
         Ok(Arc::new(world))
     }
 }
+
+/// Loads the main prefab of a built asset crate. That's `prefabs/main.world` if the crate was built with binary prefabs,
+/// and `prefabs/main.json` otherwise
+#[derive(Debug, Clone)]
+pub struct PrefabFromCrateUrl(pub AbsAssetUrl);
+#[async_trait]
+impl AsyncAssetKey<Result<Arc<World>, AssetError>> for PrefabFromCrateUrl {
+    async fn load(self, assets: AssetCache) -> Result<Arc<World>, AssetError> {
+        let binary_url = self.0.push(format!("prefabs/main.{BINARY_PREFAB_EXTENSION}")).context("Invalid crate url")?;
+        // Only a missing binary prefab falls back to json, so that a broken one is reported as such
+        if BytesFromUrl::new(binary_url.clone(), true).get(&assets).await.is_ok() {
+            return PrefabFromUrl(binary_url.into()).get(&assets).await;
+        }
+        let json_url = self.0.push("prefabs/main.json").context("Invalid crate url")?;
+        PrefabFromUrl(json_url.into()).get(&assets).await
+    }
+}
@@ -0,0 +1,51 @@
+use std::path::Path;
+
+use ambient_core::{hierarchy::children, name};
+use ambient_ecs::Entity;
+use ambient_model_import::model_crate::ModelCrate;
+use ambient_prefab::PrefabFromCrateUrl;
+use ambient_std::{
+    asset_cache::{AssetCache, AsyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+};
+use tokio::runtime;
+
+fn init() {
+    ambient_app::init_all_components();
+    ambient_physics::init_all_components();
+    ambient_decals::init_components();
+    ambient_prefab::init_components();
+}
+
+async fn write_crate(path: &Path, prefab_name: &str, binary: bool) {
+    let mut model_crate = ModelCrate::new();
+    model_crate.create_prefab(Entity::new().with(name(), prefab_name.to_string()));
+    if binary {
+        model_crate.use_binary_prefabs();
+    }
+    model_crate.write_to_fs(&path.to_path_buf()).await.unwrap();
+}
+
+async fn load_prefab_name(assets: &AssetCache, path: &Path) -> String {
+    let prefab = PrefabFromCrateUrl(AbsAssetUrl::from_directory_path(path)).get(assets).await.unwrap();
+    let object = prefab.resource(children())[0];
+    prefab.get_ref(object, name()).unwrap().clone()
+}
+
+// Deserializing prefabs blocks in place, which needs a multi threaded runtime
+#[tokio::test(flavor = "multi_thread")]
+async fn crates_load_their_own_prefabs() {
+    init();
+    let dir = std::env::temp_dir().join(format!("ambient_prefab_{}", std::process::id()));
+    let assets = AssetCache::new(runtime::Handle::current());
+
+    write_crate(&dir.join("binary"), "binary", true).await;
+    assert!(dir.join("binary/prefabs/main.world").exists());
+    assert_eq!(load_prefab_name(&assets, &dir.join("binary")).await, "binary");
+
+    write_crate(&dir.join("json"), "json", false).await;
+    assert!(dir.join("json/prefabs/main.json").exists());
+    assert_eq!(load_prefab_name(&assets, &dir.join("json")).await, "json");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -22,6 +22,9 @@ pub struct Manifest {
     pub components: HashMap<IdentifierPathBuf, NamespaceOrComponent>,
     #[serde(default)]
     pub concepts: HashMap<IdentifierPathBuf, NamespaceOrConcept>,
+    /// The `[actions]` table, which is parsed by the input crate
+    #[serde(default)]
+    pub actions: toml::Table,
 }
 impl Manifest {
     pub fn parse(manifest: &str) -> Result<Self, toml::de::Error> {
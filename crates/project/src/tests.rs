@@ -53,6 +53,7 @@ fn can_parse_tictactoe_toml() {
                 }
                 .into()
             )]),
+            actions: Default::default(),
         })
     )
 }
@@ -98,6 +99,7 @@ fn can_parse_manifest_with_namespaces() {
                 )
             ]),
             concepts: HashMap::new(),
+            actions: Default::default(),
         })
     )
 }
@@ -205,6 +207,7 @@ fn can_generate_concepts_with_documented_namespace_from_manifest() {
                     .into()
                 )
             ]),
+            actions: Default::default(),
         })
     )
 }
@@ -1,22 +1,41 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
     io::{BufRead, Cursor, Write},
     sync::Arc,
 };
 
 use futures::{future::BoxFuture, Future, FutureExt};
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use thiserror::Error;
 
+mod schema;
+
+pub use schema::{schema_hash, try_schema_hash, ExplicitSchemas, SchemaError};
+
 #[allow(clippy::type_complexity)]
 #[derive(Clone)]
 pub struct RpcRegistry<Args> {
+    /// Keyed by the versioned name of the function, i.e. `name@version`
     registry: HashMap<String, Arc<dyn Fn(Args, &[u8]) -> BoxFuture<Result<Vec<u8>, RpcError>> + Send + Sync>>,
+    /// The names of the registered functions, keyed by their type names
+    names: HashMap<&'static str, String>,
+    table: RpcTable,
+    schemas: ExplicitSchemas,
 }
 impl<Args: Send + 'static> RpcRegistry<Args> {
     pub fn new() -> Self {
-        Self { registry: HashMap::new() }
+        Self { registry: HashMap::new(), names: HashMap::new(), table: RpcTable::new(), schemas: ExplicitSchemas::default() }
+    }
+    /// Gives `T` an explicit schema, for types with custom `Deserialize` implementations which can't be traced; see
+    /// [ExplicitSchemas::insert]. This has to be done before the functions using `T` are registered, which panics otherwise
+    pub fn explicit_schema<T: Serialize + 'static>(&mut self, schema: &str, sample: T) {
+        self.schemas.insert(schema, sample);
     }
+    /// Registers a function under an explicit `name`, which has to be the same on the client and the server.
+    ///
+    /// The `version` should be bumped when the behavior of the function changes in a way that makes it incompatible with
+    /// older callers. Changes to the request and response types are detected automatically, see [RpcRegistry::check_compatibility].
     pub fn register<
         Req: Serialize + DeserializeOwned + Send + 'static,
         Resp: Serialize + DeserializeOwned + Send,
@@ -24,12 +43,43 @@ impl<Args: Send + 'static> RpcRegistry<Args> {
         L: Future<Output = Resp> + Send,
     >(
         &mut self,
+        name: &str,
+        version: u32,
+        func: F,
+    ) {
+        self.register_with(name, version, (), func)
+    }
+    /// Registers a function which can only be called when `requirement` is met. The requirement is checked
+    /// for every call, before the function is run, and the caller receives an [RpcUnauthorized] error otherwise.
+    pub fn register_with<
+        Req: Serialize + DeserializeOwned + Send + 'static,
+        Resp: Serialize + DeserializeOwned + Send,
+        F: Fn(Args, Req) -> L + Send + Sync + Copy + 'static,
+        L: Future<Output = Resp> + Send,
+    >(
+        &mut self,
+        name: &str,
+        version: u32,
+        requirement: impl RpcRequirement<Args, Req>,
         func: F,
     ) {
-        let name = std::any::type_name::<F>().to_string();
+        assert!(!self.table.contains_key(name), "The RPC {name} is registered twice");
+        let type_name = std::any::type_name::<F>();
+        if let Some(existing) = self.names.get(type_name) {
+            panic!("{type_name} is registered both as the RPC {existing} and as {name}");
+        }
+        let schema_hash = |hash: Result<u64, SchemaError>| hash.unwrap_or_else(|err| panic!("Failed to register the RPC {name}: {err}"));
+        let request_hash = schema_hash(try_schema_hash::<Req>(&self.schemas));
+        let response_hash = schema_hash(try_schema_hash::<Resp>(&self.schemas));
+        self.names.insert(type_name, name.to_string());
+        self.table.insert(name.to_string(), RpcSignature { version, request_hash, response_hash });
+        let name = format!("{name}@{version}");
+        let requirement = Arc::new(requirement);
         self.registry.insert(
-            name,
+            name.clone(),
             Arc::new(move |args, req| {
+                let requirement = requirement.clone();
+                let name = name.clone();
                 async move {
                     let req = match bincode::deserialize(req) {
                         Ok(req) => req,
@@ -37,8 +87,11 @@ impl<Args: Send + 'static> RpcRegistry<Args> {
                             return Err(RpcError::BincodeError(err));
                         }
                     };
+                    if let Err(reason) = requirement.check(&args, &req) {
+                        return Err(RpcError::Unauthorized(RpcUnauthorized { function: name, reason }));
+                    }
                     let resp = func(args, req).await;
-                    Ok(bincode::serialize(&resp).unwrap())
+                    Ok(bincode::serialize(&Ok::<_, RpcUnauthorized>(resp)).unwrap())
                 }
                 .boxed()
             }),
@@ -53,13 +106,15 @@ impl<Args: Send + 'static> RpcRegistry<Args> {
         &self,
         _func: F,
         req: Req,
-    ) -> Vec<u8> {
-        let name = std::any::type_name::<F>().to_string();
+    ) -> Result<Vec<u8>, RpcError> {
+        let type_name = std::any::type_name::<F>();
+        let name = self.names.get(type_name).ok_or_else(|| RpcError::NoSuchFunction(type_name.to_string()))?;
+        let version = self.table[name].version;
         let mut res = Vec::new();
-        writeln!(&mut res, "{name}").unwrap();
+        writeln!(&mut res, "{name}@{version}").unwrap();
         let req = bincode::serialize(&req).unwrap();
         res.write_all(&req).unwrap();
-        res
+        Ok(res)
     }
     pub async fn run_req(&self, args: Args, req: &[u8]) -> Result<Vec<u8>, RpcError> {
         let mut reader = Cursor::new(req);
@@ -87,8 +142,41 @@ impl<Args: Send + 'static> RpcRegistry<Args> {
         &self,
         _func: F,
         resp: &[u8],
-    ) -> Result<Resp, bincode::Error> {
-        bincode::deserialize(resp)
+    ) -> Result<Resp, RpcError> {
+        Ok(bincode::deserialize::<Result<Resp, RpcUnauthorized>>(resp)??)
+    }
+    /// Serializes an error response, to let the caller know that it was not allowed to make the call
+    pub fn serialize_unauthorized(err: &RpcUnauthorized) -> Vec<u8> {
+        bincode::serialize(&Err::<(), _>(err)).unwrap()
+    }
+    /// The signatures of all registered functions, which the server advertises to clients when they connect
+    pub fn table(&self) -> &RpcTable {
+        &self.table
+    }
+    /// Checks that every function registered here can be called on a remote with the `remote` table; i.e. that it
+    /// exists there, with the same version and request and response types.
+    pub fn check_compatibility(&self, remote: &RpcTable) -> Result<(), RpcIncompatibility> {
+        let mismatches = self
+            .table
+            .iter()
+            .filter_map(|(name, local)| {
+                let name = name.clone();
+                match remote.get(&name) {
+                    None => Some(RpcMismatch::Missing { name }),
+                    Some(remote) if remote.version != local.version => {
+                        Some(RpcMismatch::Version { name, local: local.version, remote: remote.version })
+                    }
+                    Some(remote) if remote.request_hash != local.request_hash => Some(RpcMismatch::Request { name }),
+                    Some(remote) if remote.response_hash != local.response_hash => Some(RpcMismatch::Response { name }),
+                    Some(_) => None,
+                }
+            })
+            .collect::<Vec<_>>();
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(RpcIncompatibility { mismatches })
+        }
     }
 }
 impl<T> std::fmt::Debug for RpcRegistry<T> {
@@ -97,6 +185,70 @@ impl<T> std::fmt::Debug for RpcRegistry<T> {
     }
 }
 
+/// The version and a hash of the request and response types of a registered function
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcSignature {
+    pub version: u32,
+    pub request_hash: u64,
+    pub response_hash: u64,
+}
+/// The signatures of all the functions in a registry, by name
+pub type RpcTable = BTreeMap<String, RpcSignature>;
+
+/// A reason a function can't be called on a remote, see [RpcRegistry::check_compatibility]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RpcMismatch {
+    Missing { name: String },
+    Version { name: String, local: u32, remote: u32 },
+    Request { name: String },
+    Response { name: String },
+}
+impl Display for RpcMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RpcMismatch::Missing { name } => write!(f, "{name}: missing on the remote"),
+            RpcMismatch::Version { name, local, remote } => write!(f, "{name}: version {local} here, version {remote} on the remote"),
+            RpcMismatch::Request { name } => write!(f, "{name}: the request types differ"),
+            RpcMismatch::Response { name } => write!(f, "{name}: the response types differ"),
+        }
+    }
+}
+
+/// Returned by [RpcRegistry::check_compatibility] when functions can't be called on a remote
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub struct RpcIncompatibility {
+    pub mismatches: Vec<RpcMismatch>,
+}
+impl Display for RpcIncompatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} RPCs are incompatible with the remote:", self.mismatches.len())?;
+        for mismatch in &self.mismatches {
+            writeln!(f, "  {mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A condition which has to be met for a registered function to be called, see [RpcRegistry::register_with]
+pub trait RpcRequirement<Args, Req>: Send + Sync + 'static {
+    /// Returns the reason the call is not allowed, if it isn't
+    fn check(&self, args: &Args, req: &Req) -> Result<(), String>;
+}
+/// No requirement; anyone can call the function
+impl<Args, Req> RpcRequirement<Args, Req> for () {
+    fn check(&self, _args: &Args, _req: &Req) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Returned to the caller of a function whose [RpcRequirement] wasn't met
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize, Deserialize)]
+#[error("Not allowed to call {function}: {reason}")]
+pub struct RpcUnauthorized {
+    pub function: String,
+    pub reason: String,
+}
+
 #[derive(Debug, Error)]
 pub enum RpcError {
     #[error(transparent)]
@@ -105,23 +257,206 @@ pub enum RpcError {
     IOError(#[from] std::io::Error),
     #[error("No such function {0}")]
     NoSuchFunction(String),
+    #[error(transparent)]
+    Unauthorized(#[from] RpcUnauthorized),
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::RpcRegistry;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{schema_hash, try_schema_hash, ExplicitSchemas, RpcError, RpcMismatch, RpcRegistry, RpcRequirement};
 
     async fn testy(_args: (), req: i32) -> i32 {
         req * 2
     }
 
+    struct Positive;
+    impl RpcRequirement<i32, i32> for Positive {
+        fn check(&self, args: &i32, _req: &i32) -> Result<(), String> {
+            if *args > 0 {
+                Ok(())
+            } else {
+                Err("args must be positive".to_string())
+            }
+        }
+    }
+    async fn guarded(args: i32, req: i32) -> i32 {
+        args * req
+    }
+
     #[tokio::test]
     async fn it_works() {
         let mut reg = RpcRegistry::new();
-        reg.register(testy);
-        let req = reg.serialize_req(testy, 6);
+        reg.register("testy", 1, testy);
+        let req = reg.serialize_req(testy, 6).unwrap();
         let resp = reg.run_req((), &req).await.unwrap();
         let resp = reg.deserialize_resp(testy, &resp).unwrap();
         println!("resp={resp:?}");
     }
+
+    #[tokio::test]
+    async fn requirements() {
+        let mut reg = RpcRegistry::new();
+        reg.register_with("guarded", 1, Positive, guarded);
+        let req = reg.serialize_req(guarded, 3).unwrap();
+        let resp = reg.run_req(2, &req).await.unwrap();
+        assert_eq!(reg.deserialize_resp(guarded, &resp).unwrap(), 6);
+
+        let err = match reg.run_req(-2, &req).await {
+            Err(RpcError::Unauthorized(err)) => err,
+            other => panic!("Expected the call to be unauthorized: {other:?}"),
+        };
+        assert_eq!(err.reason, "args must be positive");
+        let resp = RpcRegistry::<i32>::serialize_unauthorized(&err);
+        match reg.deserialize_resp(guarded, &resp) {
+            Err(RpcError::Unauthorized(resp_err)) => assert_eq!(resp_err, err),
+            other => panic!("Expected the response to be unauthorized: {other:?}"),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "is registered both as the RPC testy and as other")]
+    fn registered_twice() {
+        let mut reg = RpcRegistry::new();
+        reg.register("testy", 1, testy);
+        reg.register("other", 1, testy);
+    }
+
+    #[tokio::test]
+    async fn unregistered() {
+        let reg = RpcRegistry::new();
+        assert!(matches!(reg.serialize_req(testy, 6), Err(RpcError::NoSuchFunction(_))));
+
+        let mut old = RpcRegistry::new();
+        old.register("testy", 1, testy);
+        let mut new = RpcRegistry::new();
+        new.register("testy", 2, testy);
+        let req = old.serialize_req(testy, 6).unwrap();
+        assert!(matches!(new.run_req((), &req).await, Err(RpcError::NoSuchFunction(_))));
+    }
+
+    async fn to_string(_args: (), req: i32) -> String {
+        req.to_string()
+    }
+    async fn halve(_args: (), req: f32) -> f32 {
+        req / 2.
+    }
+
+    async fn plus_one(_args: (), req: i32) -> i32 {
+        req + 1
+    }
+    async fn minus_one(_args: (), req: i32) -> i32 {
+        req - 1
+    }
+
+    #[test]
+    fn compatibility() {
+        let mut client = RpcRegistry::new();
+        client.register("testy", 1, testy);
+        client.register("to_string", 1, to_string);
+        client.register("halve", 1, halve);
+        client.register("missing", 1, plus_one);
+
+        let mut server = RpcRegistry::new();
+        server.register("testy", 1, testy);
+        server.register("to_string", 1, plus_one);
+        server.register("halve", 2, halve);
+        server.register("unused", 1, minus_one);
+        assert!(server.check_compatibility(server.table()).is_ok());
+
+        let err = client.check_compatibility(server.table()).unwrap_err();
+        assert_eq!(
+            err.mismatches,
+            vec![
+                RpcMismatch::Version { name: "halve".to_string(), local: 1, remote: 2 },
+                RpcMismatch::Missing { name: "missing".to_string() },
+                RpcMismatch::Response { name: "to_string".to_string() },
+            ]
+        );
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Point {
+        x: f32,
+        y: f32,
+    }
+    #[derive(Serialize, Deserialize)]
+    struct Renamed {
+        a: f32,
+        b: f32,
+    }
+    #[derive(Serialize, Deserialize)]
+    enum Shape {
+        Point(Point),
+        Circle { center: Point, radius: f32 },
+    }
+    #[derive(Serialize, Deserialize)]
+    enum OtherShape {
+        Point(Point),
+        Circle { center: Point, radius: f64 },
+    }
+    #[derive(Serialize, Deserialize)]
+    struct Tree {
+        children: Vec<Tree>,
+        parent: Option<Box<Tree>>,
+    }
+
+    #[test]
+    fn schema_hashes() {
+        assert_eq!(schema_hash::<Point>(), schema_hash::<Renamed>());
+        assert_eq!(schema_hash::<Point>(), schema_hash::<(f32, f32)>());
+        assert_ne!(schema_hash::<Point>(), schema_hash::<(f32, f64)>());
+        assert_ne!(schema_hash::<Vec<i32>>(), schema_hash::<Vec<u32>>());
+        assert_ne!(schema_hash::<Option<i32>>(), schema_hash::<i32>());
+        // The content of every variant is part of the hash, not just the first one
+        assert_ne!(schema_hash::<Shape>(), schema_hash::<OtherShape>());
+        // Recursive types are traced up to a depth
+        assert_eq!(schema_hash::<Tree>(), schema_hash::<Tree>());
+    }
+
+    /// Serialized as a string, which has to be a number
+    #[derive(Debug, PartialEq)]
+    struct Parsed(u32);
+    impl Serialize for Parsed {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&self.0.to_string())
+        }
+    }
+    impl<'de> Deserialize<'de> for Parsed {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let value = String::deserialize(deserializer)?;
+            value.parse().map(Parsed).map_err(de::Error::custom)
+        }
+    }
+    async fn parsed(_args: (), req: (Vec<Parsed>, f32)) -> Option<Parsed> {
+        req.0.into_iter().next()
+    }
+
+    #[test]
+    fn explicit_schemas() {
+        let err = try_schema_hash::<(Vec<Parsed>, f32)>(&ExplicitSchemas::default()).unwrap_err();
+        assert_eq!(err.type_name, std::any::type_name::<(Vec<Parsed>, f32)>());
+
+        let mut explicit = ExplicitSchemas::default();
+        explicit.insert("number string", Parsed(0));
+        let hash = |explicit: &ExplicitSchemas| try_schema_hash::<(Vec<Parsed>, f32)>(explicit).unwrap();
+        // The types after the explicit one are traced too
+        assert_ne!(hash(&explicit), try_schema_hash::<(Vec<Parsed>, f64)>(&explicit).unwrap());
+        assert_ne!(try_schema_hash::<Option<Parsed>>(&explicit).unwrap(), try_schema_hash::<Parsed>(&explicit).unwrap());
+        let mut other = ExplicitSchemas::default();
+        other.insert("number string v2", Parsed(0));
+        assert_ne!(hash(&explicit), hash(&other));
+
+        let mut reg = RpcRegistry::new();
+        reg.explicit_schema("number string", Parsed(0));
+        reg.register("parsed", 1, parsed);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to register the RPC parsed")]
+    fn untraceable_schema() {
+        let mut reg = RpcRegistry::new();
+        reg.register("parsed", 1, parsed);
+    }
 }
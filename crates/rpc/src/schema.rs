@@ -0,0 +1,331 @@
+use std::{any::type_name, collections::HashMap, fmt::Display, marker::PhantomData};
+
+use bincode::Options;
+use serde::{
+    de::{
+        self, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor,
+    },
+    Serialize,
+};
+
+/// Recursive types are only traced this deep
+const MAX_DEPTH: usize = 16;
+/// Enums with more variants than this only have their first `MAX_VARIANTS` variants traced
+const MAX_VARIANTS: usize = 64;
+
+/// Hashes the shape of `T` as seen by its `Deserialize` implementation; i.e. the primitives, sequences, maps, struct
+/// fields and enum variants it reads, in order. Names are not included, and structs hash like tuples, so the hash only
+/// changes when the binary representation of `T` does.
+///
+/// The hash is stable across compilers and platforms, so it can be compared between a client and a server.
+///
+/// Panics if `T` can't be traced, e.g. because it contains a type with a custom `Deserialize` implementation which
+/// rejects the values the tracer produces; see [try_schema_hash] to give such types an explicit schema.
+pub fn schema_hash<T: DeserializeOwned>() -> u64 {
+    try_schema_hash::<T>(&ExplicitSchemas::default()).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Like [schema_hash], but uses the `explicit` schemas for the types which have one, and returns an error if `T` can't
+/// be traced
+pub fn try_schema_hash<T: DeserializeOwned>(explicit: &ExplicitSchemas) -> Result<u64, SchemaError> {
+    if let Some(schema) = explicit.get::<PhantomData<T>>() {
+        return Ok(fnv1a(format!("explicit({})", schema.schema).as_bytes()));
+    }
+    let mut tracer = Tracer { trace: String::new(), depth: 0, variant: 0, max_variants: 1, explicit };
+    // Each pass picks the `variant`th variant of every enum (or its last one), so that the content of every variant is traced
+    while tracer.variant < tracer.max_variants {
+        if let Err(err) = T::deserialize(&mut tracer) {
+            return Err(SchemaError { type_name: type_name::<T>(), reason: err.0 });
+        }
+        tracer.trace.push('\n');
+        tracer.depth = 0;
+        tracer.variant += 1;
+    }
+    Ok(fnv1a(tracer.trace.as_bytes()))
+}
+
+/// A type which couldn't be traced by [try_schema_hash]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    pub type_name: &'static str,
+    pub reason: String,
+}
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The schema of {} can't be traced: {}. Types with custom Deserialize implementations may need an explicit schema",
+            self.type_name, self.reason
+        )
+    }
+}
+impl std::error::Error for SchemaError {}
+
+/// Schemas given explicitly to types whose `Deserialize` implementation can't be traced, for instance because it parses
+/// strings or looks up components. They're used for these types wherever they appear in a traced type; as an element,
+/// a field, a map value, an enum variant or an option.
+#[derive(Debug, Clone, Default)]
+pub struct ExplicitSchemas(HashMap<&'static str, ExplicitSchema>);
+impl ExplicitSchemas {
+    /// Gives `T` the `schema`, which describes its binary representation and has to be changed whenever that changes.
+    /// The `sample` is any value of `T`, which is used in its place while tracing the types that contain it.
+    pub fn insert<T: Serialize + 'static>(&mut self, schema: &str, sample: T) {
+        // Options deserialize their content directly instead of through a seed, so they need an entry of their own
+        let some_sample = bincode::serialize(&Some(&sample)).expect("Failed to serialize the sample");
+        let sample = bincode::serialize(&sample).expect("Failed to serialize the sample");
+        self.0.insert(type_name::<PhantomData<T>>(), ExplicitSchema { schema: schema.to_string(), sample });
+        self.0.insert(type_name::<PhantomData<Option<T>>>(), ExplicitSchema { schema: format!("option {schema}"), sample: some_sample });
+    }
+    fn get<S>(&self) -> Option<&ExplicitSchema> {
+        self.0.get(type_name::<S>())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ExplicitSchema {
+    schema: String,
+    /// A value of the type, serialized with bincode
+    sample: Vec<u8>,
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+#[derive(Debug)]
+struct TraceError(String);
+impl Display for TraceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl std::error::Error for TraceError {}
+impl de::Error for TraceError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// A deserializer which records what it's asked to deserialize, and produces the simplest possible value
+struct Tracer<'a> {
+    trace: String,
+    depth: usize,
+    variant: usize,
+    max_variants: usize,
+    explicit: &'a ExplicitSchemas,
+}
+impl Tracer<'_> {
+    fn record(&mut self, token: impl Display) {
+        self.trace.push_str(&token.to_string());
+        self.trace.push(' ');
+    }
+    /// The number of elements to produce for sequences and maps of unknown length
+    fn sample_len(&self) -> usize {
+        if self.depth < MAX_DEPTH {
+            1
+        } else {
+            0
+        }
+    }
+    /// Deserializes the `seed` from its explicit sample if it has one, and traces it otherwise
+    fn deserialize_seed<'de, S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, TraceError> {
+        match self.explicit.get::<S>() {
+            Some(explicit) => {
+                self.record(format!("explicit({})", explicit.schema));
+                let options = bincode::DefaultOptions::new().with_fixint_encoding().allow_trailing_bytes();
+                seed.deserialize(&mut bincode::Deserializer::with_reader(&explicit.sample[..], options)).map_err(de::Error::custom)
+            }
+            None => seed.deserialize(self),
+        }
+    }
+    fn visit_elements<'de, V: Visitor<'de>>(&mut self, len: usize, visitor: V) -> Result<V::Value, TraceError> {
+        self.depth += 1;
+        let value = visitor.visit_seq(Elements { tracer: &mut *self, remaining: len })?;
+        self.depth -= 1;
+        self.record(";");
+        Ok(value)
+    }
+}
+
+macro_rules! trace_primitives {
+    ($($method:ident => $visit:ident($($value:expr)?),)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+                self.record(stringify!($method));
+                visitor.$visit($($value)?)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for &mut Tracer<'_> {
+    type Error = TraceError;
+
+    trace_primitives! {
+        deserialize_bool => visit_bool(false),
+        deserialize_i8 => visit_i8(0),
+        deserialize_i16 => visit_i16(0),
+        deserialize_i32 => visit_i32(0),
+        deserialize_i64 => visit_i64(0),
+        deserialize_i128 => visit_i128(0),
+        deserialize_u8 => visit_u8(0),
+        deserialize_u16 => visit_u16(0),
+        deserialize_u32 => visit_u32(0),
+        deserialize_u64 => visit_u64(0),
+        deserialize_u128 => visit_u128(0),
+        deserialize_f32 => visit_f32(0.),
+        deserialize_f64 => visit_f64(0.),
+        deserialize_char => visit_char('\0'),
+        deserialize_str => visit_str(""),
+        deserialize_string => visit_string(String::new()),
+        deserialize_bytes => visit_bytes(&[]),
+        deserialize_byte_buf => visit_byte_buf(Vec::new()),
+        deserialize_unit => visit_unit(),
+        deserialize_identifier => visit_u32(0),
+        deserialize_ignored_any => visit_unit(),
+    }
+
+    fn is_human_readable(&self) -> bool {
+        // The functions are called with bincode
+        false
+    }
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, TraceError> {
+        self.record("any");
+        Err(de::Error::custom("Self-describing formats can't be traced"))
+    }
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        self.record("option");
+        if self.depth >= MAX_DEPTH {
+            return visitor.visit_none();
+        }
+        self.depth += 1;
+        let value = visitor.visit_some(&mut *self)?;
+        self.depth -= 1;
+        Ok(value)
+    }
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, TraceError> {
+        self.deserialize_unit(visitor)
+    }
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, TraceError> {
+        visitor.visit_newtype_struct(self)
+    }
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        self.record("seq");
+        let len = self.sample_len();
+        self.visit_elements(len, visitor)
+    }
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, TraceError> {
+        self.record(format!("tuple{len}"));
+        self.visit_elements(len, visitor)
+    }
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, _name: &'static str, len: usize, visitor: V) -> Result<V::Value, TraceError> {
+        self.deserialize_tuple(len, visitor)
+    }
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        self.record("map");
+        let len = self.sample_len();
+        self.depth += 1;
+        let value = visitor.visit_map(Entries { tracer: &mut *self, remaining: len })?;
+        self.depth -= 1;
+        self.record(";");
+        Ok(value)
+    }
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        self.record(format!("enum{}", variants.len()));
+        if variants.is_empty() {
+            return Err(de::Error::custom("Enum without variants"));
+        }
+        self.max_variants = self.max_variants.max(variants.len().min(MAX_VARIANTS));
+        let index = self.variant.min(variants.len() - 1);
+        self.record(format!("variant{index}"));
+        self.depth += 1;
+        let value = visitor.visit_enum(Variant { tracer: &mut *self, index: index as u32 })?;
+        self.depth -= 1;
+        Ok(value)
+    }
+}
+
+struct Elements<'a, 'b> {
+    tracer: &'a mut Tracer<'b>,
+    remaining: usize,
+}
+impl<'de> SeqAccess<'de> for Elements<'_, '_> {
+    type Error = TraceError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, TraceError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        self.tracer.deserialize_seed(seed).map(Some)
+    }
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+struct Entries<'a, 'b> {
+    tracer: &'a mut Tracer<'b>,
+    remaining: usize,
+}
+impl<'de> MapAccess<'de> for Entries<'_, '_> {
+    type Error = TraceError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, TraceError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        self.tracer.deserialize_seed(seed).map(Some)
+    }
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, TraceError> {
+        self.tracer.deserialize_seed(seed)
+    }
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+struct Variant<'a, 'b> {
+    tracer: &'a mut Tracer<'b>,
+    index: u32,
+}
+impl<'de> EnumAccess<'de> for Variant<'_, '_> {
+    type Error = TraceError;
+    type Variant = Self;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, Self), TraceError> {
+        let value = seed.deserialize(IntoDeserializer::<TraceError>::into_deserializer(self.index))?;
+        Ok((value, self))
+    }
+}
+impl<'de> VariantAccess<'de> for Variant<'_, '_> {
+    type Error = TraceError;
+
+    fn unit_variant(self) -> Result<(), TraceError> {
+        Ok(())
+    }
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, TraceError> {
+        self.tracer.deserialize_seed(seed)
+    }
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, TraceError> {
+        self.tracer.record(format!("tuple{len}"));
+        self.tracer.visit_elements(len, visitor)
+    }
+    fn struct_variant<V: Visitor<'de>>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value, TraceError> {
+        self.tuple_variant(fields.len(), visitor)
+    }
+}
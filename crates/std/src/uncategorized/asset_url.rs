@@ -17,10 +17,14 @@ use url::Url;
 
 use crate::{
     asset_cache::{AssetCache, SyncAssetKey, SyncAssetKeyExt},
+    content_pack::read_from_content_packs,
     download_asset::{download, AssetsCacheDir},
     Cb,
 };
 
+/// The extension of prefabs stored in the binary world format, as opposed to json
+pub const BINARY_PREFAB_EXTENSION: &str = "world";
+
 #[derive(Debug, Clone)]
 pub struct ServerBaseUrlKey;
 impl SyncAssetKey<AbsAssetUrl> for ServerBaseUrlKey {
@@ -171,21 +175,27 @@ impl AbsAssetUrl {
         segs.next()
     }
     pub async fn download_bytes(&self, assets: &AssetCache) -> anyhow::Result<Vec<u8>> {
-        if let Some(path) = self.to_file_path()? {
+        if let Some(data) = read_from_content_packs(assets, self).await? {
+            Ok(data)
+        } else if let Some(path) = self.to_file_path()? {
             Ok(ambient_sys::fs::read(path).await.context(format!("Failed to read file at: {:}", self.0))?)
         } else {
             Ok(download(assets, self.0.clone(), |resp| async { Ok(resp.bytes().await?) }).await?.to_vec())
         }
     }
     pub async fn download_string(&self, assets: &AssetCache) -> anyhow::Result<String> {
-        if let Some(path) = self.to_file_path()? {
+        if let Some(data) = read_from_content_packs(assets, self).await? {
+            Ok(String::from_utf8(data)?)
+        } else if let Some(path) = self.to_file_path()? {
             Ok(ambient_sys::fs::read_to_string(path).await.context(format!("Failed to read file at: {:}", self.0))?)
         } else {
             Ok(download(assets, self.0.clone(), |resp| async { Ok(resp.text().await?) }).await?)
         }
     }
     pub async fn download_json<T: 'static + Send + DeserializeOwned>(&self, assets: &AssetCache) -> anyhow::Result<T> {
-        if let Some(path) = self.to_file_path()? {
+        if let Some(data) = read_from_content_packs(assets, self).await? {
+            Ok(serde_json::from_slice(&data)?)
+        } else if let Some(path) = self.to_file_path()? {
             let content: Vec<u8> = ambient_sys::fs::read(path).await.context(format!("Failed to read file at: {:}", self.0))?;
             Ok(serde_json::from_slice(&content)?)
         } else {
@@ -473,6 +483,9 @@ pub enum AssetType {
     /// Represents a vorbis backed file
     VorbisTrack,
     SoundGraph,
+
+    /// The strings of a locale, produced by the localization pipeline
+    StringTable,
 }
 
 impl AssetType {
@@ -499,6 +512,10 @@ impl TypedAssetUrl<ModelCrateAssetType> {
     pub fn prefab(&self) -> TypedAssetUrl<PrefabAssetType> {
         self.join("prefabs/main.json").unwrap()
     }
+    /// The main prefab, when the crate was written with binary prefabs
+    pub fn binary_prefab(&self) -> TypedAssetUrl<PrefabAssetType> {
+        self.join(format!("prefabs/main.{BINARY_PREFAB_EXTENSION}")).unwrap()
+    }
     pub fn collider(&self) -> TypedAssetUrl<ColliderAssetType> {
         self.join("colliders/main.json").unwrap()
     }
@@ -0,0 +1,341 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Context;
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use parking_lot::Mutex;
+use percent_encoding::percent_decode_str;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    asset_cache::{AssetCache, SyncAssetKey, SyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+};
+
+const MAGIC: &[u8; 8] = b"AMBPACK\0";
+const VERSION: u32 = 1;
+const HEADER_LEN: u64 = 20;
+/// The most deflate can compress data, which bounds the size of a file before it's decompressed
+const MAX_DEFLATE_RATIO: u64 = 1032;
+
+/// A file in a [ContentPack]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentPackEntry {
+    /// Offset of the compressed data, from the end of the index
+    pub offset: u64,
+    pub compressed_size: u64,
+    pub size: u64,
+    /// The hex encoded SHA-256 hash of the uncompressed data
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ContentPackIndex {
+    entries: BTreeMap<String, ContentPackEntry>,
+}
+
+#[derive(Debug)]
+enum ContentPackData {
+    File(Mutex<File>),
+    Bytes(Vec<u8>),
+}
+
+/// An archive of built content: an index of paths (like `assets/models/tree.glb/models/main.json`), followed by the
+/// deflate compressed files. Each file is checked against the hash in the index when it's read.
+///
+/// Packs are made with [ContentPackWriter], and can be mounted at a url with [mount_content_pack], to read the assets
+/// under that url from the pack instead of downloading them one by one
+#[derive(Debug)]
+pub struct ContentPack {
+    index: ContentPackIndex,
+    /// Where the compressed files start
+    data_offset: u64,
+    /// The length of the whole pack
+    len: u64,
+    data: ContentPackData,
+}
+impl ContentPack {
+    /// Opens the pack at `path`. Only its index is read until its files are
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let mut file = File::open(path).with_context(|| format!("Failed to open content pack {path:?}"))?;
+        let len = file.metadata().with_context(|| format!("Failed to read content pack {path:?}"))?.len();
+        let mut header = [0; HEADER_LEN as usize];
+        file.read_exact(&mut header).with_context(|| format!("Failed to read content pack {path:?}"))?;
+        let index_len = Self::parse_header(&header, len)?;
+        let mut index = vec![0; index_len as usize];
+        file.read_exact(&mut index).with_context(|| format!("Failed to read the index of content pack {path:?}"))?;
+        let index = bincode::deserialize(&index).context("Invalid content pack index")?;
+        Ok(Self { index, data_offset: HEADER_LEN + index_len, len, data: ContentPackData::File(Mutex::new(file)) })
+    }
+    pub fn from_bytes(bytes: Vec<u8>) -> anyhow::Result<Self> {
+        let len = bytes.len() as u64;
+        let header = bytes.get(..HEADER_LEN as usize).context("Content pack is too short")?;
+        let index_len = Self::parse_header(header, len)?;
+        let index = &bytes[HEADER_LEN as usize..(HEADER_LEN + index_len) as usize];
+        let index = bincode::deserialize(index).context("Invalid content pack index")?;
+        Ok(Self { index, data_offset: HEADER_LEN + index_len, len, data: ContentPackData::Bytes(bytes) })
+    }
+    /// Returns the length of the index, which fits in the `len` bytes of the pack
+    fn parse_header(header: &[u8], len: u64) -> anyhow::Result<u64> {
+        anyhow::ensure!(&header[0..8] == MAGIC, "Not a content pack");
+        let version = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        anyhow::ensure!(version == VERSION, "Unsupported content pack version {version}, expected {VERSION}");
+        let index_len = u64::from_le_bytes(header[12..20].try_into().unwrap());
+        anyhow::ensure!(index_len <= len.saturating_sub(HEADER_LEN), "Content pack is too short for its index of {index_len} bytes");
+        Ok(index_len)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &ContentPackEntry)> {
+        self.index.entries.iter().map(|(path, entry)| (path.as_str(), entry))
+    }
+    pub fn entry(&self, path: &str) -> Option<&ContentPackEntry> {
+        self.index.entries.get(path)
+    }
+    pub fn contains(&self, path: &str) -> bool {
+        self.index.entries.contains_key(path)
+    }
+    /// Reads the file at `path`, or returns None if the pack doesn't have it. Fails if the file doesn't match its hash
+    pub fn read(&self, path: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let Some(entry) = self.index.entries.get(path) else { return Ok(None) };
+        let end = self
+            .data_offset
+            .checked_add(entry.offset)
+            .and_then(|start| start.checked_add(entry.compressed_size))
+            .filter(|&end| end <= self.len)
+            .with_context(|| format!("{path} is out of the bounds of the content pack"))?;
+        let start = end - entry.compressed_size;
+        anyhow::ensure!(
+            entry.size <= entry.compressed_size.saturating_mul(MAX_DEFLATE_RATIO),
+            "{path} in the content pack is corrupted, its size doesn't match"
+        );
+        let compressed = match &self.data {
+            ContentPackData::File(file) => {
+                let mut file = file.lock();
+                let mut compressed = vec![0; entry.compressed_size as usize];
+                file.seek(SeekFrom::Start(start))?;
+                file.read_exact(&mut compressed).with_context(|| format!("Failed to read {path} from the content pack"))?;
+                compressed
+            }
+            ContentPackData::Bytes(bytes) => bytes[start as usize..end as usize].to_vec(),
+        };
+        let mut data = Vec::with_capacity(entry.size as usize);
+        DeflateDecoder::new(&compressed[..])
+            .take(entry.size)
+            .read_to_end(&mut data)
+            .with_context(|| format!("Failed to decompress {path}"))?;
+        anyhow::ensure!(
+            data.len() as u64 == entry.size && sha256(&data) == entry.sha256,
+            "{path} in the content pack is corrupted, its hash doesn't match"
+        );
+        Ok(Some(data))
+    }
+    /// Reads all the files of the pack, to check that they match their hashes
+    pub fn verify(&self) -> anyhow::Result<()> {
+        for path in self.index.entries.keys() {
+            self.read(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// The sizes of a written [ContentPack]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContentPackStats {
+    pub entries: usize,
+    pub size: u64,
+    pub compressed_size: u64,
+}
+
+/// Builds a [ContentPack]
+#[derive(Debug, Default)]
+pub struct ContentPackWriter {
+    index: ContentPackIndex,
+    data: Vec<u8>,
+}
+impl ContentPackWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds a file at `path`, which is relative to the url the pack is mounted at
+    pub fn add(&mut self, path: impl Into<String>, data: &[u8]) -> anyhow::Result<()> {
+        let path = path.into();
+        anyhow::ensure!(!self.index.entries.contains_key(&path), "{path} is already in the content pack");
+        let offset = self.data.len() as u64;
+        let mut encoder = DeflateEncoder::new(&mut self.data, Compression::default());
+        encoder.write_all(data)?;
+        encoder.finish()?;
+        let entry =
+            ContentPackEntry { offset, compressed_size: self.data.len() as u64 - offset, size: data.len() as u64, sha256: sha256(data) };
+        self.index.entries.insert(path, entry);
+        Ok(())
+    }
+    /// Adds all the files under `dir`, at their path relative to `root`
+    pub fn add_dir(&mut self, root: &Path, dir: &Path) -> anyhow::Result<()> {
+        let mut files = vec![dir.to_path_buf()];
+        while let Some(path) = files.pop() {
+            if path.is_dir() {
+                for entry in std::fs::read_dir(&path).with_context(|| format!("Failed to read directory {path:?}"))? {
+                    files.push(entry?.path());
+                }
+            } else {
+                let relative = path.strip_prefix(root).with_context(|| format!("{path:?} is not in {root:?}"))?;
+                let relative = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+                self.add(relative, &std::fs::read(&path).with_context(|| format!("Failed to read {path:?}"))?)?;
+            }
+        }
+        Ok(())
+    }
+    pub fn stats(&self) -> ContentPackStats {
+        ContentPackStats {
+            entries: self.index.entries.len(),
+            size: self.index.entries.values().map(|entry| entry.size).sum(),
+            compressed_size: self.data.len() as u64,
+        }
+    }
+    pub fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        let index = bincode::serialize(&self.index)?;
+        let mut bytes = Vec::with_capacity(HEADER_LEN as usize + index.len() + self.data.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(index.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&index);
+        bytes.extend_from_slice(&self.data);
+        Ok(bytes)
+    }
+    pub fn write(&self, path: impl AsRef<Path>) -> anyhow::Result<ContentPackStats> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_bytes()?).with_context(|| format!("Failed to write content pack {path:?}"))?;
+        Ok(self.stats())
+    }
+}
+
+fn sha256(data: &[u8]) -> String {
+    data_encoding::HEXLOWER.encode(ring::digest::digest(&ring::digest::SHA256, data).as_ref())
+}
+
+/// The content packs mounted in an asset cache, with the url they're mounted at
+#[derive(Debug, Clone)]
+pub struct ContentPacksKey;
+impl SyncAssetKey<Arc<Vec<(AbsAssetUrl, Arc<ContentPack>)>>> for ContentPacksKey {
+    fn load(&self, _assets: AssetCache) -> Arc<Vec<(AbsAssetUrl, Arc<ContentPack>)>> {
+        Arc::new(Vec::new())
+    }
+}
+
+/// Makes the urls under `base_url` read from `pack`, for the files it has. E.g. with a pack mounted at
+/// `http://localhost:8999/content/`, `http://localhost:8999/content/assets/a.png` is the `assets/a.png` file of the pack.
+/// Mounting a pack again at the same url does nothing
+pub fn mount_content_pack(assets: &AssetCache, base_url: AbsAssetUrl, pack: Arc<ContentPack>) {
+    let base_url = base_url.as_directory();
+    let mut packs = ContentPacksKey.get(assets).as_ref().clone();
+    if packs.iter().any(|(url, mounted)| url == &base_url && Arc::ptr_eq(mounted, &pack)) {
+        return;
+    }
+    log::info!("Mounting content pack with {} files at {base_url}", pack.index.entries.len());
+    packs.push((base_url, pack));
+    ContentPacksKey.insert(assets, Arc::new(packs));
+}
+
+/// The path in a pack mounted at `base_url` of the file at `url`, or None if `url` isn't under `base_url`
+fn pack_path(base_url: &AbsAssetUrl, url: &AbsAssetUrl) -> Option<String> {
+    let path = url.0.as_str().strip_prefix(base_url.0.as_str())?;
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    Some(percent_decode_str(path).decode_utf8_lossy().into_owned())
+}
+
+/// Reads `url` from the content packs mounted in `assets`, or returns None if none of them has it. The file is read and
+/// decompressed on a blocking task
+pub(crate) async fn read_from_content_packs(assets: &AssetCache, url: &AbsAssetUrl) -> anyhow::Result<Option<Vec<u8>>> {
+    let packs = ContentPacksKey.get(assets);
+    let Some((pack, path)) = packs.iter().find_map(|(base_url, pack)| {
+        let path = pack_path(base_url, url)?;
+        pack.contains(&path).then(|| (pack.clone(), path))
+    }) else {
+        return Ok(None);
+    };
+    ambient_sys::task::spawn_blocking(move || pack.read(&path)).await.context("Failed to join")?
+}
+
+/// The path of the pack `ambient build --pack` writes for the project at `project_path`
+pub fn content_pack_path(project_path: &Path) -> PathBuf {
+    project_path.join("build").join("content.pack")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_and_read() {
+        let mut writer = ContentPackWriter::new();
+        writer.add("assets/a.txt", b"hello hello hello").unwrap();
+        writer.add("project.wasm", &[0, 1, 2, 3]).unwrap();
+        assert!(writer.add("project.wasm", &[]).is_err());
+        assert_eq!(writer.stats().entries, 2);
+
+        let pack = ContentPack::from_bytes(writer.to_bytes().unwrap()).unwrap();
+        assert_eq!(pack.read("assets/a.txt").unwrap().as_deref(), Some(&b"hello hello hello"[..]));
+        assert_eq!(pack.read("project.wasm").unwrap().as_deref(), Some(&[0, 1, 2, 3][..]));
+        assert_eq!(pack.read("assets/b.txt").unwrap(), None);
+        pack.verify().unwrap();
+    }
+
+    #[test]
+    fn detect_corruption() {
+        let mut writer = ContentPackWriter::new();
+        writer.add("a.txt", b"some text").unwrap();
+        let mut bytes = writer.to_bytes().unwrap();
+        let last = bytes.len() - 3;
+        bytes[last] ^= 0xff;
+        let pack = ContentPack::from_bytes(bytes).unwrap();
+        assert!(pack.read("a.txt").is_err());
+
+        assert!(ContentPack::from_bytes(b"not a pack at all, really".to_vec()).is_err());
+    }
+
+    #[test]
+    fn truncated_and_oversized_headers() {
+        let mut writer = ContentPackWriter::new();
+        writer.add("a.txt", b"some text").unwrap();
+        let bytes = writer.to_bytes().unwrap();
+
+        assert!(ContentPack::from_bytes(bytes[..12].to_vec()).is_err());
+        assert!(ContentPack::from_bytes(bytes[..24].to_vec()).is_err());
+
+        for index_len in [u64::MAX, u64::MAX - 10, bytes.len() as u64] {
+            let mut bytes = bytes.clone();
+            bytes[12..20].copy_from_slice(&index_len.to_le_bytes());
+            assert!(ContentPack::from_bytes(bytes.clone()).is_err());
+
+            let path = std::env::temp_dir().join(format!("ambient_oversized_{index_len}.pack"));
+            std::fs::write(&path, &bytes).unwrap();
+            assert!(ContentPack::open(&path).is_err());
+            std::fs::remove_file(&path).unwrap();
+        }
+
+        // The index is intact but the data of the file is cut off
+        let pack = ContentPack::from_bytes(bytes[..bytes.len() - 2].to_vec()).unwrap();
+        assert!(pack.read("a.txt").is_err());
+    }
+
+    #[test]
+    fn urls_map_to_pack_paths() {
+        let base_url = AbsAssetUrl::parse("http://localhost:8999/content").unwrap().as_directory();
+        let url = |url: &str| AbsAssetUrl::parse(url).unwrap();
+
+        assert_eq!(pack_path(&base_url, &url("http://localhost:8999/content/assets/a.png")).as_deref(), Some("assets/a.png"));
+        assert_eq!(pack_path(&base_url, &url("http://localhost:8999/content/assets/a.png?v=2#top")).as_deref(), Some("assets/a.png"));
+        assert_eq!(pack_path(&base_url, &url("http://localhost:8999/content/assets/my%20tree.glb")).as_deref(), Some("assets/my tree.glb"));
+        assert_eq!(pack_path(&base_url, &url("http://localhost:8999/other/assets/a.png")), None);
+        assert_eq!(pack_path(&base_url, &url("http://example.com:8999/content/assets/a.png")), None);
+    }
+}
@@ -9,8 +9,9 @@ use thiserror::Error;
 use tokio::sync::Semaphore;
 
 use crate::{
-    asset_cache::{AssetCache, AssetKeepalive, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKey, SyncAssetKeyExt},
+    asset_cache::{AssetCache, AssetKeepalive, AssetSize, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKey, SyncAssetKeyExt},
     asset_url::AbsAssetUrl,
+    content_pack::read_from_content_packs,
     mesh::Mesh,
 };
 
@@ -129,6 +130,10 @@ impl BytesFromUrl {
 #[async_trait]
 impl AsyncAssetKey<AssetResult<Arc<Vec<u8>>>> for BytesFromUrl {
     async fn load(self, assets: AssetCache) -> AssetResult<Arc<Vec<u8>>> {
+        if let Some(data) = read_from_content_packs(&assets, &self.url).await? {
+            return Ok(Arc::new(data));
+        }
+
         if self.cache_on_disk && AssetsCacheOnDisk.get(&assets) {
             let path = BytesFromUrlCachedPath { url: self.url.clone() }.get(&assets).await?;
             let semaphore = FileReadSemaphore.get(&assets);
@@ -147,7 +152,7 @@ impl AsyncAssetKey<AssetResult<Arc<Vec<u8>>>> for BytesFromUrl {
 
     fn cpu_size(&self, value: &AssetResult<Arc<Vec<u8>>>) -> Option<u64> {
         // NOTE: on wasm bytes is limited to 4gb
-        value.as_ref().ok().map(|v| v.len() as u64)
+        value.size_bytes()
     }
 }
 
@@ -187,6 +192,12 @@ impl AsyncAssetKey<AssetResult<Arc<PathBuf>>> for BytesFromUrlCachedPath {
 
         let path = self.url.absolute_cache_path(&assets);
         if !path.exists() {
+            if let Some(data) = read_from_content_packs(&assets, &self.url).await? {
+                std::fs::create_dir_all(path.parent().unwrap()).context(format!("Failed to create asset dir: {path:?}"))?;
+                std::fs::write(&path, data).context(format!("Failed to write file: {path:?}"))?;
+                return Ok(Arc::new(path));
+            }
+
             use tokio::io::AsyncWriteExt;
             let mut dir = path.clone();
             dir.pop();
@@ -288,11 +299,15 @@ impl<T> BincodeFromUrl<T> {
     }
 }
 #[async_trait]
-impl<T: DeserializeOwned + std::fmt::Debug + Sync + Send + 'static> AsyncAssetKey<AssetResult<Arc<T>>> for BincodeFromUrl<T> {
+impl<T: DeserializeOwned + AssetSize + std::fmt::Debug + Sync + Send + 'static> AsyncAssetKey<AssetResult<Arc<T>>> for BincodeFromUrl<T> {
     async fn load(self, assets: AssetCache) -> AssetResult<Arc<T>> {
         let data = BytesFromUrl { url: self.url.clone(), cache_on_disk: self.cache_on_disk }.get(&assets).await?;
         Ok(Arc::new(bincode::deserialize(&data).context("Failed to deserialize")?))
     }
+
+    fn cpu_size(&self, value: &AssetResult<Arc<T>>) -> Option<u64> {
+        value.size_bytes()
+    }
 }
 
 pub type MeshFromUrl = BincodeFromUrl<Mesh>;
@@ -2,7 +2,7 @@ use glam::*;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
-use crate::shapes::AABB;
+use crate::{asset_cache::AssetSize, shapes::AABB};
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Mesh {
@@ -154,6 +154,10 @@ impl Mesh {
             .normals
             .as_mut()
             .map(|normals| normals.drain(..).enumerate().filter_map(|(i, v)| if used[i] { Some(v) } else { None }).collect());
+        self.tangents = self
+            .tangents
+            .as_mut()
+            .map(|tangents| tangents.drain(..).enumerate().filter_map(|(i, v)| if used[i] { Some(v) } else { None }).collect());
         self.joint_indices = self
             .joint_indices
             .as_mut()
@@ -226,3 +230,8 @@ impl Mesh {
             + self.texcoords.iter().map(|x| std::mem::size_of_val(&**x)).sum::<usize>()
     }
 }
+impl AssetSize for Mesh {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_in_bytes() as u64)
+    }
+}
@@ -0,0 +1,448 @@
+//! Mesh optimization and simplification, which run on the cpu when assets are built.
+//!
+//! - [optimize_vertex_cache] reorders triangles for the post-transform vertex cache (Tom Forsyth's algorithm)
+//! - [optimize_overdraw] reorders clusters of triangles so that the ones facing outwards are drawn first
+//! - [simplify] collapses edges, cheapest first by their quadric error, until a target triangle count is reached
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+};
+
+use glam::{DVec3, Vec3};
+use itertools::Itertools;
+
+use super::mesh::Mesh;
+
+const CACHE_SIZE: usize = 32;
+
+fn vertex_score(cache_position: Option<usize>, remaining_triangles: usize) -> f32 {
+    if remaining_triangles == 0 {
+        return -1.;
+    }
+    let cache_score = match cache_position {
+        // The vertices of the last triangle get a fixed score, so that its neighbours aren't preferred too much
+        Some(position) if position < 3 => 0.75,
+        Some(position) => (1. - (position - 3) as f32 / (CACHE_SIZE - 3) as f32).powf(1.5),
+        None => 0.,
+    };
+    cache_score + 2. * (remaining_triangles as f32).powf(-0.5)
+}
+
+/// Reorders the triangles of `indices` to improve the post-transform vertex cache hit rate
+pub fn optimize_vertex_cache(indices: &[u32], vertex_count: usize) -> Vec<u32> {
+    let triangle_count = indices.len() / 3;
+    let mut vertex_triangles: Vec<Vec<usize>> = vec![Vec::new(); vertex_count];
+    for (triangle, vertices) in indices.chunks_exact(3).enumerate() {
+        for &v in vertices {
+            vertex_triangles[v as usize].push(triangle);
+        }
+    }
+    let mut scores = vertex_triangles.iter().map(|triangles| vertex_score(None, triangles.len())).collect_vec();
+    let triangle_score =
+        |scores: &[f32], triangle: usize| indices[triangle * 3..triangle * 3 + 3].iter().map(|&v| scores[v as usize]).sum::<f32>();
+
+    let mut emitted = vec![false; triangle_count];
+    let mut cache: Vec<u32> = Vec::with_capacity(CACHE_SIZE + 3);
+    let mut result = Vec::with_capacity(triangle_count * 3);
+    let mut next_unemitted = 0;
+    let mut best = None;
+    for _ in 0..triangle_count {
+        let triangle = match best {
+            Some(triangle) => triangle,
+            None => {
+                while emitted[next_unemitted] {
+                    next_unemitted += 1;
+                }
+                next_unemitted
+            }
+        };
+        emitted[triangle] = true;
+        let vertices = &indices[triangle * 3..triangle * 3 + 3];
+        result.extend_from_slice(vertices);
+        for &v in vertices {
+            vertex_triangles[v as usize].retain(|&t| t != triangle);
+        }
+
+        let mut new_cache = vertices.to_vec();
+        new_cache.extend(cache.iter().copied().filter(|v| !vertices.contains(v)));
+        for (position, &v) in new_cache.iter().enumerate() {
+            let position = if position < CACHE_SIZE { Some(position) } else { None };
+            scores[v as usize] = vertex_score(position, vertex_triangles[v as usize].len());
+        }
+        new_cache.truncate(CACHE_SIZE);
+        cache = new_cache;
+
+        best = cache
+            .iter()
+            .flat_map(|&v| vertex_triangles[v as usize].iter().copied())
+            .map(|t| (t, triangle_score(&scores, t)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(t, _)| t);
+    }
+    result
+}
+
+const OVERDRAW_CACHE_SIZE: usize = 16;
+
+/// Reorders clusters of triangles so that the ones facing away from the center of the mesh are drawn first, which reduces
+/// overdraw. The clusters are split where the vertex cache misses entirely, so this should be run after [optimize_vertex_cache],
+/// and keeps most of its benefit
+pub fn optimize_overdraw(indices: &[u32], positions: &[Vec3]) -> Vec<u32> {
+    if indices.is_empty() {
+        return Vec::new();
+    }
+    let mut cache = VecDeque::with_capacity(OVERDRAW_CACHE_SIZE + 1);
+    let mut cluster_starts = vec![0];
+    for (triangle, vertices) in indices.chunks_exact(3).enumerate() {
+        if triangle > 0 && vertices.iter().all(|v| !cache.contains(v)) {
+            cluster_starts.push(triangle);
+        }
+        for &v in vertices {
+            if !cache.contains(&v) {
+                cache.push_back(v);
+                if cache.len() > OVERDRAW_CACHE_SIZE {
+                    cache.pop_front();
+                }
+            }
+        }
+    }
+    cluster_starts.push(indices.len() / 3);
+
+    let mesh_center = indices.iter().map(|&v| positions[v as usize]).sum::<Vec3>() / indices.len() as f32;
+    let mut clusters = cluster_starts
+        .iter()
+        .tuple_windows()
+        .map(|(&start, &end)| {
+            let mut center = Vec3::ZERO;
+            let mut normal = Vec3::ZERO;
+            let mut area = 0.;
+            for vertices in indices[start * 3..end * 3].chunks_exact(3) {
+                let [a, b, c] = [0, 1, 2].map(|k| positions[vertices[k] as usize]);
+                let triangle_normal = (b - a).cross(c - a);
+                let triangle_area = triangle_normal.length();
+                center += (a + b + c) / 3. * triangle_area;
+                normal += triangle_normal;
+                area += triangle_area;
+            }
+            let outwardness = if area > 0. { (center / area - mesh_center).dot(normal.normalize_or_zero()) } else { 0. };
+            (start, end, outwardness)
+        })
+        .collect_vec();
+    // Stable, so clusters facing the same way keep their vertex cache order
+    clusters.sort_by(|a, b| b.2.total_cmp(&a.2));
+    clusters.into_iter().flat_map(|(start, end, _)| indices[start * 3..end * 3].iter().copied()).collect()
+}
+
+/// An area weighted sum of squared distances to planes; the last element is the total weight
+#[derive(Debug, Clone, Copy, Default)]
+struct Quadric([f64; 11]);
+impl Quadric {
+    fn from_plane(normal: DVec3, d: f64, weight: f64) -> Self {
+        let DVec3 { x: a, y: b, z: c } = normal;
+        Self([a * a, a * b, a * c, a * d, b * b, b * c, b * d, c * c, c * d, d * d, 1.].map(|x| x * weight))
+    }
+    fn add(&mut self, other: &Quadric) {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a += b;
+        }
+    }
+    /// The weighted mean squared distance from `p` to the planes
+    fn error(&self, p: DVec3) -> f64 {
+        let q = &self.0;
+        let DVec3 { x, y, z } = p;
+        let error = q[0] * x * x
+            + 2. * q[1] * x * y
+            + 2. * q[2] * x * z
+            + 2. * q[3] * x
+            + q[4] * y * y
+            + 2. * q[5] * y * z
+            + 2. * q[6] * y
+            + q[7] * z * z
+            + 2. * q[8] * z
+            + q[9];
+        if q[10] > 0. {
+            error.max(0.) / q[10]
+        } else {
+            0.
+        }
+    }
+}
+
+/// Collapsing the vertex `from` into `to`; ordered so that the cheapest collapse is at the top of a [BinaryHeap]
+struct Collapse {
+    error: f64,
+    from: u32,
+    to: u32,
+    versions: (u32, u32),
+}
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Collapse {}
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Collapse {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.error.total_cmp(&self.error).then(other.from.cmp(&self.from)).then(other.to.cmp(&self.to))
+    }
+}
+
+/// Simplifies a triangle mesh by collapsing edges, cheapest first, until it has at most `target_index_count` indices or
+/// no edge can be collapsed with an error below `target_error`. Errors are relative to the size of the mesh, i.e. `0.01`
+/// is 1% of its largest extent.
+///
+/// Vertices on borders and on attribute seams (where a position is split into several vertices) are never moved, so
+/// silhouettes and uv layouts are kept. Returns the new indices, which refer to the same vertices, and the largest error
+/// that was introduced.
+pub fn simplify(indices: &[u32], positions: &[Vec3], target_index_count: usize, target_error: f32) -> (Vec<u32>, f32) {
+    let mut triangles = indices.chunks_exact(3).map(|t| [t[0], t[1], t[2]]).collect_vec();
+    if triangles.len() * 3 <= target_index_count {
+        return (indices.to_vec(), 0.);
+    }
+    let mut alive = vec![true; triangles.len()];
+    let mut alive_count = triangles.len();
+    let mut vertex_triangles: Vec<Vec<usize>> = vec![Vec::new(); positions.len()];
+    for (i, triangle) in triangles.iter().enumerate() {
+        for &v in triangle {
+            vertex_triangles[v as usize].push(i);
+        }
+    }
+
+    // Edges which are used by a single triangle are borders. Seams are borders too, as the vertices on each side are different
+    let mut edge_counts = HashMap::<(u32, u32), usize>::new();
+    for triangle in &triangles {
+        for k in 0..3 {
+            let (a, b) = (triangle[k], triangle[(k + 1) % 3]);
+            *edge_counts.entry((a.min(b), a.max(b))).or_default() += 1;
+        }
+    }
+    let mut locked = vec![false; positions.len()];
+    for (&(a, b), &count) in &edge_counts {
+        if count == 1 {
+            locked[a as usize] = true;
+            locked[b as usize] = true;
+        }
+    }
+
+    let positions = positions.iter().map(|p| p.as_dvec3()).collect_vec();
+    let (min, max) = positions.iter().fold((DVec3::splat(f64::MAX), DVec3::splat(f64::MIN)), |(min, max), &p| (min.min(p), max.max(p)));
+    let extent = (max - min).max_element().max(f64::EPSILON);
+    let max_error = (target_error as f64 * extent).powi(2);
+
+    let mut quadrics = vec![Quadric::default(); positions.len()];
+    for triangle in &triangles {
+        let [a, b, c] = triangle.map(|v| positions[v as usize]);
+        let normal = (b - a).cross(c - a);
+        let area = normal.length() * 0.5;
+        if area <= 0. {
+            continue;
+        }
+        let normal = normal / (area * 2.);
+        let quadric = Quadric::from_plane(normal, -normal.dot(a), area);
+        for &v in triangle {
+            quadrics[v as usize].add(&quadric);
+        }
+    }
+
+    // Collapses are queued with the versions of their vertices, and skipped when they're popped if either vertex has
+    // changed since, instead of removing them from the heap
+    let mut versions = vec![0u32; positions.len()];
+    let mut collapses = BinaryHeap::new();
+    let push_collapses = |collapses: &mut BinaryHeap<Collapse>, quadrics: &[Quadric], versions: &[u32], triangle: &[u32; 3]| {
+        for k in 0..3 {
+            let (a, b) = (triangle[k], triangle[(k + 1) % 3]);
+            for (from, to) in [(a, b), (b, a)] {
+                if !locked[from as usize] {
+                    let mut quadric = quadrics[from as usize];
+                    quadric.add(&quadrics[to as usize]);
+                    let error = quadric.error(positions[to as usize]);
+                    collapses.push(Collapse { error, from, to, versions: (versions[from as usize], versions[to as usize]) });
+                }
+            }
+        }
+    };
+    for triangle in &triangles {
+        push_collapses(&mut collapses, &quadrics, &versions, triangle);
+    }
+
+    let mut result_error = 0f64;
+    while alive_count * 3 > target_index_count {
+        let Collapse { error, from, to, versions: (from_version, to_version) } = match collapses.pop() {
+            Some(collapse) => collapse,
+            None => break,
+        };
+        if error > max_error {
+            break;
+        }
+        let (from, to) = (from as usize, to as usize);
+        if versions[from] != from_version || versions[to] != to_version {
+            continue;
+        }
+        if !vertex_triangles[from].iter().any(|&t| alive[t] && triangles[t].contains(&(to as u32))) {
+            continue;
+        }
+        if !can_collapse(&triangles, &alive, &vertex_triangles, &positions, from, to) {
+            continue;
+        }
+
+        let quadric = quadrics[from];
+        quadrics[to].add(&quadric);
+        for triangle in std::mem::take(&mut vertex_triangles[from]) {
+            if !alive[triangle] {
+                continue;
+            }
+            if triangles[triangle].contains(&(to as u32)) {
+                alive[triangle] = false;
+                alive_count -= 1;
+            } else {
+                for v in &mut triangles[triangle] {
+                    if *v == from as u32 {
+                        *v = to as u32;
+                    }
+                }
+                vertex_triangles[to].push(triangle);
+            }
+        }
+        vertex_triangles[to].retain(|&t| alive[t]);
+        versions[from] += 1;
+        versions[to] += 1;
+        // The costs of the edges around `to` have changed, and collapses that were rejected there may be possible now
+        for &triangle in &vertex_triangles[to] {
+            push_collapses(&mut collapses, &quadrics, &versions, &triangles[triangle]);
+        }
+        result_error = result_error.max(error);
+    }
+
+    let indices = triangles.into_iter().zip(alive).filter(|(_, alive)| *alive).flat_map(|(triangle, _)| triangle).collect();
+    (indices, (result_error.sqrt() / extent) as f32)
+}
+
+/// Checks that collapsing `from` into `to` keeps the mesh manifold, and doesn't flip any triangles
+fn can_collapse(
+    triangles: &[[u32; 3]],
+    alive: &[bool],
+    vertex_triangles: &[Vec<usize>],
+    positions: &[DVec3],
+    from: usize,
+    to: usize,
+) -> bool {
+    let neighbours = |v: usize| -> HashSet<u32> {
+        vertex_triangles[v].iter().filter(|&&t| alive[t]).flat_map(|&t| triangles[t]).filter(|&n| n as usize != v).collect()
+    };
+    // The vertices shared by both ends must be exactly the opposite corners of the triangles on the edge
+    let shared = neighbours(from).intersection(&neighbours(to)).count();
+    let edge_triangles = vertex_triangles[from].iter().filter(|&&t| alive[t] && triangles[t].contains(&(to as u32))).count();
+    if shared != edge_triangles {
+        return false;
+    }
+
+    vertex_triangles[from].iter().filter(|&&t| alive[t] && !triangles[t].contains(&(to as u32))).all(|&t| {
+        let [a, b, c] = triangles[t].map(|v| positions[v as usize]);
+        let [a2, b2, c2] = triangles[t].map(|v| if v as usize == from { positions[to] } else { positions[v as usize] });
+        let before = (b - a).cross(c - a);
+        let after = (b2 - a2).cross(c2 - a2);
+        before.normalize_or_zero().dot(after.normalize_or_zero()) > 0.
+    })
+}
+
+impl Mesh {
+    /// Reorders the triangles of this mesh for the vertex cache, and then for overdraw. The vertices are left as they are
+    pub fn optimize(&mut self) {
+        if let (Some(positions), Some(indices)) = (&self.positions, &mut self.indices) {
+            let optimized = optimize_vertex_cache(indices, positions.len());
+            *indices = optimize_overdraw(&optimized, positions);
+        }
+    }
+    /// Returns a simplified and optimized copy of this mesh, with about `triangle_ratio` of its triangles, along with the
+    /// error of the simplification; see [simplify]. Returns None if the mesh doesn't have positions and indices
+    pub fn simplified(&self, triangle_ratio: f32, target_error: f32) -> Option<(Mesh, f32)> {
+        let (positions, indices) = (self.positions.as_ref()?, self.indices.as_ref()?);
+        let target_index_count = ((indices.len() / 3) as f32 * triangle_ratio) as usize * 3;
+        let (indices, error) = simplify(indices, positions, target_index_count, target_error);
+        let mut mesh = Mesh { indices: Some(indices), ..self.clone() };
+        mesh.remove_unused_vertices();
+        mesh.optimize();
+        Some((mesh, error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::vec3;
+
+    use super::*;
+
+    /// A `size` by `size` grid of quads in the xy plane, with `z` given by `height`
+    fn grid(size: u32, height: impl Fn(f32, f32) -> f32) -> Mesh {
+        let positions =
+            (0..=size).flat_map(|y| (0..=size).map(move |x| (x as f32, y as f32))).map(|(x, y)| vec3(x, y, height(x, y))).collect_vec();
+        let indices = (0..size)
+            .flat_map(|y| (0..size).map(move |x| y * (size + 1) + x))
+            .flat_map(|i| [i, i + 1, i + size + 2, i, i + size + 2, i + size + 1])
+            .collect_vec();
+        Mesh { positions: Some(positions), indices: Some(indices), ..Default::default() }
+    }
+
+    fn sorted_triangles(indices: &[u32]) -> Vec<Vec<u32>> {
+        indices
+            .chunks_exact(3)
+            .map(|t| {
+                // Rotate so that the smallest index comes first, keeping the winding
+                let first = (0..3).min_by_key(|&k| t[k]).unwrap();
+                (0..3).map(|k| t[(first + k) % 3]).collect_vec()
+            })
+            .sorted()
+            .collect()
+    }
+
+    #[test]
+    fn optimizations_keep_triangles() {
+        let mesh = grid(16, |x, y| (x * 0.3).sin() + (y * 0.2).cos());
+        let indices = mesh.indices.as_ref().unwrap();
+        let positions = mesh.positions.as_ref().unwrap();
+
+        let cache_optimized = optimize_vertex_cache(indices, positions.len());
+        assert_eq!(sorted_triangles(&cache_optimized), sorted_triangles(indices));
+
+        let overdraw_optimized = optimize_overdraw(&cache_optimized, positions);
+        assert_eq!(sorted_triangles(&overdraw_optimized), sorted_triangles(indices));
+    }
+
+    #[test]
+    fn simplify_flat_grid() {
+        let mesh = grid(10, |_, _| 0.);
+        let (simplified, error) = mesh.simplified(0.25, 0.01).unwrap();
+        let indices = simplified.indices.as_ref().unwrap();
+        assert!(indices.len() <= 50 * 3, "{} triangles", indices.len() / 3);
+        assert!(indices.len() >= 3);
+        assert!(error < 1e-6);
+        // All vertices are on the plane and the border is kept
+        assert_eq!(simplified.aabb().unwrap().min, Vec3::ZERO);
+        assert_eq!(simplified.aabb().unwrap().max, vec3(10., 10., 0.));
+        assert!(indices.iter().all(|&i| (i as usize) < simplified.positions.as_ref().unwrap().len()));
+    }
+
+    #[test]
+    fn simplify_respects_max_error() {
+        let mesh = grid(10, |x, y| ((x * 12.9898 + y * 78.233).sin() * 43758.545).fract());
+        let indices = mesh.indices.as_ref().unwrap();
+        let (simplified, error) = simplify(indices, mesh.positions.as_ref().unwrap(), 0, 0.001);
+        assert_eq!(simplified.len(), indices.len());
+        assert_eq!(error, 0.);
+    }
+
+    #[test]
+    fn simplify_curved_grid() {
+        let mesh = grid(16, |x, y| (x * 0.3).sin() + (y * 0.2).cos());
+        let (simplified, error) = mesh.simplified(0.25, 0.05).unwrap();
+        let triangles = simplified.indices.as_ref().unwrap().len() / 3;
+        assert!(triangles < 512 / 2, "{triangles} triangles");
+        assert!(error > 0. && error <= 0.05, "{error}");
+    }
+}
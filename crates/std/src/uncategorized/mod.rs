@@ -2,12 +2,14 @@ pub use ambient_asset_cache as asset_cache;
 pub mod asset_url;
 pub mod barc;
 pub mod color;
+pub mod content_pack;
 pub mod disk_cache;
 pub mod download_asset;
 pub mod encode;
 pub mod fps_counter;
 pub mod math;
 pub mod mesh;
+pub mod mesh_optimization;
 pub mod ordered_glam;
 pub mod shapes;
 pub mod sparse_vec;
@@ -0,0 +1,136 @@
+use std::hash::{Hash, Hasher};
+
+use glyph_brush::{
+    ab_glyph::{Font, Rect, ScaleFont},
+    GlyphPositioner, Layout, SectionGeometry, SectionGlyph, ToSectionText,
+};
+
+/// Horizontal alignment of the lines of a text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextAlign {
+    #[default]
+    Begin,
+    Center,
+    End,
+}
+impl TextAlign {
+    fn factor(&self) -> f32 {
+        match self {
+            TextAlign::Begin => 0.,
+            TextAlign::Center => 0.5,
+            TextAlign::End => 1.,
+        }
+    }
+}
+
+/// Wraps text to the width of the section bounds like the default glyph_brush layout, then aligns its lines within
+/// that width (or the widest line, with unbounded text) and spaces them by `line_height` times their height
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextLayout {
+    pub align: TextAlign,
+    pub line_height: f32,
+}
+impl Default for TextLayout {
+    fn default() -> Self {
+        Self { align: TextAlign::Begin, line_height: 1. }
+    }
+}
+impl Hash for TextLayout {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.align.hash(state);
+        self.line_height.to_bits().hash(state);
+    }
+}
+impl GlyphPositioner for TextLayout {
+    fn calculate_glyphs<F, S>(&self, fonts: &[F], geometry: &SectionGeometry, sections: &[S]) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let mut glyphs = Layout::default_wrap().calculate_glyphs(fonts, geometry, sections);
+        if self.align == TextAlign::Begin && self.line_height == 1. {
+            return glyphs;
+        }
+
+        // glyph_brush lays out the lines in order, each below the previous one, and the glyphs of a line share its baseline,
+        // so a new line starts wherever the baseline moves down. Trailing whitespace doesn't count in the width of the lines
+        let left = geometry.screen_position.0;
+        let mut lines: Vec<(f32, f32)> = Vec::new();
+        let mut glyph_lines = Vec::with_capacity(glyphs.len());
+        for glyph in &glyphs {
+            let baseline = glyph.glyph.position.y;
+            if lines.last().map_or(true, |(line, _)| baseline > *line) {
+                lines.push((baseline, 0.));
+            }
+            let text = sections[glyph.section_index].to_section_text().text;
+            let is_whitespace = text[glyph.byte_index..].chars().next().map_or(true, char::is_whitespace);
+            let right = if is_whitespace {
+                left
+            } else {
+                glyph.glyph.position.x + fonts[glyph.font_id.0].as_scaled(glyph.glyph.scale).h_advance(glyph.glyph.id)
+            };
+            let (_, width) = lines.last_mut().unwrap();
+            *width = width.max(right - left);
+            glyph_lines.push(lines.len() - 1);
+        }
+        let width =
+            if geometry.bounds.0.is_finite() { geometry.bounds.0 } else { lines.iter().map(|(_, width)| *width).fold(0., f32::max) };
+        let first_baseline = lines.first().map(|(baseline, _)| *baseline).unwrap_or_default();
+
+        for (glyph, line) in glyphs.iter_mut().zip(glyph_lines) {
+            let (baseline, line_width) = lines[line];
+            let position = &mut glyph.glyph.position;
+            position.x += (width - line_width) * self.align.factor();
+            position.y = first_baseline + (baseline - first_baseline) * self.line_height;
+        }
+        glyphs
+    }
+
+    fn bounds_rect(&self, geometry: &SectionGeometry) -> Rect {
+        Layout::default_wrap().bounds_rect(geometry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glyph_brush::{ab_glyph::FontRef, SectionText};
+
+    use super::*;
+
+    #[test]
+    fn wrapped_centered_section() {
+        let font = FontRef::try_from_slice(include_bytes!("../../../assets/fonts/Ubuntu/Ubuntu Nerd Font Complete.ttf")).unwrap();
+        let geometry = SectionGeometry { screen_position: (10., 0.), bounds: (200., f32::INFINITY) };
+        let sections =
+            [SectionText { text: "Lorem ipsum dolor sit amet, consectetur adipiscing elit", scale: 20.0.into(), ..Default::default() }];
+        let wrapped = Layout::default_wrap().calculate_glyphs(&[&font], &geometry, &sections);
+        let layout = TextLayout { align: TextAlign::Center, line_height: 1.5 };
+        let glyphs = layout.calculate_glyphs(&[&font], &geometry, &sections);
+        assert_eq!(glyphs.len(), wrapped.len());
+
+        // The glyphs of each line, with their right edges
+        let mut lines: Vec<Vec<(f32, f32)>> = Vec::new();
+        let mut baselines: Vec<f32> = Vec::new();
+        for glyph in &glyphs {
+            let is_whitespace = sections[0].text[glyph.byte_index..].starts_with(' ');
+            if baselines.last() != Some(&glyph.glyph.position.y) {
+                baselines.push(glyph.glyph.position.y);
+                lines.push(Vec::new());
+            }
+            if !is_whitespace {
+                let right = glyph.glyph.position.x + font.as_scaled(glyph.glyph.scale).h_advance(glyph.glyph.id);
+                lines.last_mut().unwrap().push((glyph.glyph.position.x, right));
+            }
+        }
+        assert!(lines.len() >= 2, "The text should wrap");
+
+        let line_gap = wrapped.iter().map(|glyph| glyph.glyph.position.y).fold(f32::MIN, f32::max) - wrapped[0].glyph.position.y;
+        let line_gap = line_gap / (lines.len() - 1) as f32;
+        for (i, (line, baseline)) in lines.iter().zip(&baselines).enumerate() {
+            assert!((baseline - (baselines[0] + i as f32 * line_gap * 1.5)).abs() < 0.01);
+            let start = line.first().unwrap().0 - geometry.screen_position.0;
+            let end = geometry.screen_position.0 + geometry.bounds.0 - line.last().unwrap().1;
+            assert!((start - end).abs() < 0.01, "Line {i} isn't centered: {start} before and {end} after");
+        }
+    }
+}
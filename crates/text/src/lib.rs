@@ -1,7 +1,7 @@
 use std::{num::NonZeroU32, ops::Deref, sync::Arc};
 
 use ambient_core::{asset_cache, async_ecs::async_run, gpu, mesh, runtime, transform::*, window::window_scale_factor};
-use ambient_ecs::{components, query, Debuggable, Description, Entity, Name, Networked, Store, SystemGroup};
+use ambient_ecs::{components, query, Debuggable, Description, Entity, EntityId, Name, Networked, Store, SystemGroup, World};
 use ambient_gpu::{mesh_buffer::GpuMesh, texture::Texture};
 use ambient_layout::{height, min_height, min_width, width};
 use ambient_renderer::{gpu_primitives, material, primitives, renderer_shader, SharedMaterial};
@@ -12,15 +12,20 @@ use anyhow::Context;
 use async_trait::async_trait;
 use glam::*;
 use glyph_brush::{
-    ab_glyph::{Font, FontArc, PxScale, Rect}, BrushAction, BrushError, GlyphBrush, GlyphBrushBuilder, Section
+    ab_glyph::{Font, FontArc, PxScale, Rect}, BrushAction, BrushError, FontId, GlyphBrush, GlyphBrushBuilder, GlyphCruncher, Section
 };
 use log::info;
 use parking_lot::Mutex;
 
-use crate::text_material::{get_text_shader, TextMaterial};
+use crate::text_material::{get_text_shader, TextMaterial, TextPalette};
 
+mod layout;
+mod rich_text;
 mod text_material;
 
+pub use layout::{TextAlign, TextLayout};
+pub use rich_text::*;
+
 components!("ui", {
     @[Debuggable, Networked, Store, Name["Text"], Description["Create a text mesh on this entity."]]
     text: String,
@@ -33,9 +38,28 @@ components!("ui", {
     @[Debuggable]
     font_family: FontFamily,
     font_arc: Arc<FontArc>,
+    @[
+        Debuggable, Networked, Store,
+        Name["Rich text"],
+        Description["The `text` of this entity is rich text markup, with `[b]`, `[i]`, `[color=#rrggbb]` and `[size=N]` tags."]
+    ]
+    rich_text: (),
+    @[Debuggable, Networked, Store, Name["Text wrap width"], Description["Wrap the text of this entity to this width."]]
+    text_wrap_width: f32,
+    @[Debuggable, Networked, Store, Name["Text line height"], Description["Space the lines of the text by this times their height. Defaults to 1."]]
+    text_line_height: f32,
+    @[Debuggable, Networked, Store, Name["Text align begin"], Description["Align the lines of the text to the left. This is the default."]]
+    text_align_begin: (),
+    @[Debuggable, Networked, Store, Name["Text align center"], Description["Center the lines of the text, within the `text_wrap_width` or the widest line."]]
+    text_align_center: (),
+    @[Debuggable, Networked, Store, Name["Text align end"], Description["Align the lines of the text to the right, within the `text_wrap_width` or the widest line."]]
+    text_align_end: (),
+    /// The fonts of each [FontStyle::ALL] in the family of a [rich_text] entity
+    rich_text_fonts: Vec<Arc<FontArc>>,
 
     glyph_brush: Arc<Mutex<GlyphBrush<GlyphVertex>>>,
     text_texture: Arc<Texture>,
+    text_palette: Arc<TextPalette>,
 });
 
 #[derive(Debug, Clone, Copy)]
@@ -60,7 +84,7 @@ impl TextCase {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FontStyle {
     Bold,
     BoldItalic,
@@ -76,6 +100,24 @@ impl Default for FontStyle {
         Self::Regular
     }
 }
+impl FontStyle {
+    pub const ALL: [FontStyle; 8] =
+        [Self::Bold, Self::BoldItalic, Self::Medium, Self::MediumItalic, Self::Regular, Self::Italic, Self::Light, Self::LightItalic];
+    /// This style, made bold and/or italic
+    pub fn with_emphasis(self, bold: bool, italic: bool) -> Self {
+        let italic = italic || matches!(self, Self::BoldItalic | Self::MediumItalic | Self::Italic | Self::LightItalic);
+        match (if bold { Self::Bold } else { self }, italic) {
+            (Self::Bold | Self::BoldItalic, false) => Self::Bold,
+            (Self::Bold | Self::BoldItalic, true) => Self::BoldItalic,
+            (Self::Medium | Self::MediumItalic, false) => Self::Medium,
+            (Self::Medium | Self::MediumItalic, true) => Self::MediumItalic,
+            (Self::Regular | Self::Italic, false) => Self::Regular,
+            (Self::Regular | Self::Italic, true) => Self::Italic,
+            (Self::Light | Self::LightItalic, false) => Self::Light,
+            (Self::Light | Self::LightItalic, true) => Self::LightItalic,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub enum FontFamily {
@@ -188,27 +230,38 @@ pub fn systems(use_gpu: bool) -> SystemGroup {
                             label: Some("Text.texture"),
                         },
                     ));
-                    let texture_view = Arc::new(texture.create_view(&wgpu::TextureViewDescriptor::default()));
+                    let palette = Arc::new(TextPalette::new(gpu.clone(), Vec::new()));
                     world
                         .add_components(
                             id,
                             Entity::new()
+                                .with(material(), text_material(&assets, &texture, &palette))
                                 .with(text_texture(), texture)
+                                .with(text_palette(), palette)
                                 .with(renderer_shader(), cb(get_text_shader))
-                                .with(material(), SharedMaterial::new(TextMaterial::new(assets.clone(), texture_view)))
                                 .with(primitives(), vec![])
                                 .with_default(gpu_primitives()),
                         )
                         .unwrap();
                 }
             }),
-            query((font_family().changed(), font_style().changed())).to_system(|q, world, qs, _| {
+            query((font_family().changed(), font_style().changed())).optional_changed(rich_text()).to_system(|q, world, qs, _| {
                 for (id, (font_family, font_style)) in q.collect_cloned(world, qs) {
+                    let is_rich_text = world.has_component(id, rich_text());
                     let async_run = world.resource(async_run()).clone();
                     let assets = world.resource(asset_cache()).clone();
                     world.resource(runtime()).spawn(async move {
-                        let font = FontDef(font_family, font_style).get(&assets).await;
+                        let font = FontDef(font_family.clone(), font_style).get(&assets).await;
+                        let mut style_fonts = Vec::new();
+                        if is_rich_text {
+                            for style in FontStyle::ALL {
+                                style_fonts.push(FontDef(font_family.clone(), style).get(&assets).await);
+                            }
+                        }
                         async_run.run(move |world| {
+                            if is_rich_text {
+                                world.add_component(id, rich_text_fonts(), style_fonts).ok();
+                            }
                             world.add_component(id, font_arc(), font).ok();
                         });
                     });
@@ -216,7 +269,10 @@ pub fn systems(use_gpu: bool) -> SystemGroup {
             }),
             query(font_arc().changed()).to_system(|q, world, qs, _| {
                 for (id, font) in q.collect_cloned(world, qs) {
-                    let brush = Arc::new(Mutex::new(GlyphBrushBuilder::using_font(font.deref().clone()).build()));
+                    // The first font is the font of the entity, and the next ones are the rich text fonts of each style
+                    let style_fonts = world.get_ref(id, rich_text_fonts()).map(|fonts| fonts.as_slice()).unwrap_or_default();
+                    let fonts = std::iter::once(&font).chain(style_fonts).map(|font| font.deref().clone()).collect();
+                    let brush = Arc::new(Mutex::new(GlyphBrushBuilder::using_fonts(fonts).build()));
                     world.add_component(id, glyph_brush(), brush).unwrap();
                 }
             }),
@@ -236,22 +292,83 @@ pub fn systems(use_gpu: bool) -> SystemGroup {
             }
             .optional_changed(text_case())
             .optional_changed(min_width())
+            .optional_changed(rich_text())
+            .optional_changed(text_wrap_width())
+            .optional_changed(text_line_height())
+            .optional_changed(text_align_center())
+            .optional_changed(text_align_end())
             .to_system(move |q, world, qs, _| {
                 let scale_factor = world.resource_opt(window_scale_factor()).cloned().unwrap_or(1.) as f32;
-                for (id, (glyph_brush, text, font_size, font)) in q.collect_cloned(world, qs) {
+                for (id, (glyph_brush, text, font_size, _)) in q.collect_cloned(world, qs) {
                     let assets = world.resource(asset_cache()).clone();
-                    let text = world.get(id, text_case()).unwrap_or_default().format(text);
-                    let min_width = world.get(id, min_width()).unwrap_or(0.);
+                    let case = world.get(id, text_case()).unwrap_or_default();
+                    let mut spans = if world.has_component(id, rich_text()) { parse_markup(&text) } else { vec![TextSpan::new(text)] };
+                    for span in &mut spans {
+                        span.text = case.format(std::mem::take(&mut span.text));
+                    }
+                    let layout = get_text_layout(world, id);
+                    let wrap_width = world.get(id, text_wrap_width()).ok();
+                    let mut min_width = world.get(id, min_width()).unwrap_or(0.);
+                    if layout.align != TextAlign::Begin {
+                        min_width = min_width.max(wrap_width.unwrap_or(0.));
+                    }
                     let min_height = world.get(id, min_height()).unwrap_or(0.);
 
+                    // The colors of the spans go to a palette, and their index to the glyph vertices through `z`
+                    let mut colors = Vec::new();
+                    let sections: Vec<glyph_brush::Text> = {
+                        let base_style = world.get(id, font_style()).unwrap_or_default();
+                        let fonts = glyph_brush.lock().fonts().to_vec();
+                        spans
+                            .iter()
+                            .map(|span| {
+                                let font_id = if span.style.bold || span.style.italic {
+                                    let style = base_style.with_emphasis(span.style.bold, span.style.italic);
+                                    FontStyle::ALL
+                                        .iter()
+                                        .position(|s| *s == style)
+                                        .map(|index| index + 1)
+                                        .filter(|&index| index < fonts.len())
+                                } else {
+                                    None
+                                }
+                                .unwrap_or(0);
+                                let color_index = match span.style.color {
+                                    Some(color) => match colors.iter().position(|c| *c == color) {
+                                        Some(index) => index + 1,
+                                        None => {
+                                            colors.push(color);
+                                            colors.len()
+                                        }
+                                    },
+                                    None => 0,
+                                };
+                                let scale = pt_size_to_px_scale(&fonts[font_id], span.style.font_size.unwrap_or(font_size), scale_factor);
+                                glyph_brush::Text::new(&span.text)
+                                    .with_scale(scale)
+                                    .with_font_id(FontId(font_id))
+                                    .with_z(color_index as f32)
+                            })
+                            .collect()
+                    };
+                    let section = Section::default()
+                        .with_bounds((wrap_width.map_or(f32::INFINITY, |width| width * scale_factor), f32::INFINITY))
+                        .with_text(sections);
+                    if use_gpu && world.get_ref(id, text_palette()).map_or(true, |palette| palette.colors != colors) {
+                        let palette = Arc::new(TextPalette::new(world.resource(gpu()).clone(), colors));
+                        let texture = world.get_ref(id, text_texture()).unwrap().clone();
+                        world
+                            .add_components(
+                                id,
+                                Entity::new().with(material(), text_material(&assets, &texture, &palette)).with(text_palette(), palette),
+                            )
+                            .unwrap();
+                    }
+
                     loop {
                         let process_result = {
                             let mut brush = glyph_brush.lock();
-                            brush.queue(Section::default().add_text(glyph_brush::Text::new(&text).with_scale(pt_size_to_px_scale(
-                                &*font,
-                                font_size,
-                                scale_factor,
-                            ))));
+                            brush.queue_custom_layout(&section, &layout);
                             brush.process_queued(
                                 |rect, tex_data| {
                                     if !use_gpu {
@@ -275,7 +392,11 @@ pub fn systems(use_gpu: bool) -> SystemGroup {
                                         wgpu::Extent3d { width: rect.width(), height: rect.height(), depth_or_array_layers: 1 },
                                     );
                                 },
-                                |vertex_data| GlyphVertex { tex_coords: vertex_data.tex_coords, pixel_coords: vertex_data.pixel_coords },
+                                |vertex_data| GlyphVertex {
+                                    tex_coords: vertex_data.tex_coords,
+                                    pixel_coords: vertex_data.pixel_coords,
+                                    color_index: vertex_data.extra.z as u32,
+                                },
                             )
                         };
                         match process_result {
@@ -314,12 +435,12 @@ pub fn systems(use_gpu: bool) -> SystemGroup {
                                     },
                                 ));
                                 glyph_brush.lock().resize_texture(suggested.0, suggested.1);
-                                let view = Arc::new(texture.create_view(&wgpu::TextureViewDescriptor::default()));
+                                let palette = world.get_ref(id, text_palette()).unwrap().clone();
                                 world
                                     .add_components(
                                         id,
                                         Entity::new()
-                                            .with(material(), SharedMaterial::new(TextMaterial::new(assets.clone(), view.clone())))
+                                            .with(material(), text_material(&assets, &texture, &palette))
                                             .with(text_texture(), texture),
                                     )
                                     .unwrap();
@@ -332,6 +453,23 @@ pub fn systems(use_gpu: bool) -> SystemGroup {
     )
 }
 
+fn get_text_layout(world: &World, id: EntityId) -> TextLayout {
+    let align = if world.has_component(id, text_align_center()) {
+        TextAlign::Center
+    } else if world.has_component(id, text_align_end()) {
+        TextAlign::End
+    } else {
+        TextAlign::Begin
+    };
+    TextLayout { align, line_height: world.get(id, text_line_height()).unwrap_or(1.) }
+}
+
+fn text_material(assets: &AssetCache, font_atlas: &Arc<Texture>, palette: &TextPalette) -> SharedMaterial {
+    let font_atlas = Arc::new(font_atlas.create_view(&wgpu::TextureViewDescriptor::default()));
+    let palette = Arc::new(palette.texture.create_view(&wgpu::TextureViewDescriptor::default()));
+    SharedMaterial::new(TextMaterial::new(assets.clone(), font_atlas, palette))
+}
+
 // From: https://docs.rs/glyph_brush/latest/glyph_brush/ab_glyph/trait.Font.html#units
 fn pt_size_to_px_scale<F: Font>(font: &F, pt_size: f32, screen_scale_factor: f32) -> PxScale {
     let px_per_em = pt_size * screen_scale_factor; // * (96.0 / 72.0); // this part is used in the example but seems to make the scale wrong, hence disabled
@@ -344,6 +482,8 @@ fn pt_size_to_px_scale<F: Font>(font: &F, pt_size: f32, screen_scale_factor: f32
 pub struct GlyphVertex {
     pub tex_coords: Rect,
     pub pixel_coords: Rect,
+    /// The index of the color of the glyph in the [TextPalette], or 0 for the color of the entity
+    pub color_index: u32,
 }
 
 fn mesh_from_glyph_vertices(vertices: Vec<GlyphVertex>) -> Mesh {
@@ -358,10 +498,11 @@ fn mesh_from_glyph_vertices(vertices: Vec<GlyphVertex>) -> Mesh {
         positions.push(vec3(vert.pixel_coords.min.x, vert.pixel_coords.max.y, 0.));
         positions.push(vec3(vert.pixel_coords.max.x, vert.pixel_coords.max.y, 0.));
 
-        texcoords.push(vec2(vert.tex_coords.min.x, vert.tex_coords.min.y));
-        texcoords.push(vec2(vert.tex_coords.max.x, vert.tex_coords.min.y));
-        texcoords.push(vec2(vert.tex_coords.min.x, vert.tex_coords.max.y));
-        texcoords.push(vec2(vert.tex_coords.max.x, vert.tex_coords.max.y));
+        let color_offset = vert.color_index as f32 * 2.;
+        texcoords.push(vec2(vert.tex_coords.min.x + color_offset, vert.tex_coords.min.y));
+        texcoords.push(vec2(vert.tex_coords.max.x + color_offset, vert.tex_coords.min.y));
+        texcoords.push(vec2(vert.tex_coords.min.x + color_offset, vert.tex_coords.max.y));
+        texcoords.push(vec2(vert.tex_coords.max.x + color_offset, vert.tex_coords.max.y));
 
         normals.push(vec3(0., 0., 1.));
         normals.push(vec3(0., 0., 1.));
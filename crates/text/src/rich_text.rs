@@ -0,0 +1,202 @@
+use ambient_std::color::Color;
+use glam::Vec4;
+
+/// The style of a [TextSpan], overriding the style of its text entity
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpanStyle {
+    pub color: Option<Vec4>,
+    pub font_size: Option<f32>,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+/// A run of text with the same style
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    pub style: SpanStyle,
+}
+impl TextSpan {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into(), style: SpanStyle::default() }
+    }
+    pub fn color(mut self, color: impl Into<Vec4>) -> Self {
+        self.style.color = Some(color.into());
+        self
+    }
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.style.font_size = Some(font_size);
+        self
+    }
+    pub fn bold(mut self) -> Self {
+        self.style.bold = true;
+        self
+    }
+    pub fn italic(mut self) -> Self {
+        self.style.italic = true;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tag {
+    Bold,
+    Italic,
+    Color,
+    Size,
+}
+impl Tag {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "b" => Some(Self::Bold),
+            "i" => Some(Self::Italic),
+            "color" => Some(Self::Color),
+            "size" => Some(Self::Size),
+            _ => None,
+        }
+    }
+}
+
+/// Parses rich text markup into spans. The markup has tags like BBCode, which can be nested: `[b]bold[/b]`,
+/// `[i]italic[/i]`, `[color=#ff8000]colored[/color]` and `[size=20]bigger[/size]`. Closing a tag also closes the tags
+/// opened inside it, `[[` is a literal `[`, and invalid tags are kept as text
+pub fn parse_markup(markup: &str) -> Vec<TextSpan> {
+    let mut spans = Vec::new();
+    let mut style = SpanStyle::default();
+    // The open tags, with the style from before them
+    let mut open = Vec::new();
+    let mut rest = markup;
+    while let Some(index) = rest.find('[') {
+        push_text(&mut spans, &style, &rest[..index]);
+        rest = &rest[index..];
+        if let Some(escaped) = rest.strip_prefix("[[") {
+            push_text(&mut spans, &style, "[");
+            rest = escaped;
+            continue;
+        }
+        let tag_len = rest[1..].find(']').filter(|&end| apply_tag(&rest[1..end + 1], &mut style, &mut open)).map(|end| end + 2);
+        match tag_len {
+            Some(len) => rest = &rest[len..],
+            None => {
+                push_text(&mut spans, &style, "[");
+                rest = &rest[1..];
+            }
+        }
+    }
+    push_text(&mut spans, &style, rest);
+    spans
+}
+
+fn push_text(spans: &mut Vec<TextSpan>, style: &SpanStyle, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    match spans.last_mut() {
+        Some(span) if span.style == *style => span.text.push_str(text),
+        _ => spans.push(TextSpan { text: text.to_string(), style: style.clone() }),
+    }
+}
+
+fn apply_tag(tag: &str, style: &mut SpanStyle, open: &mut Vec<(Tag, SpanStyle)>) -> bool {
+    if let Some(name) = tag.strip_prefix('/') {
+        let Some(tag) = Tag::from_name(name) else { return false };
+        let Some(index) = open.iter().rposition(|(open, _)| *open == tag) else { return false };
+        *style = open[index].1.clone();
+        open.truncate(index);
+        return true;
+    }
+    let (name, value) = match tag.split_once('=') {
+        Some((name, value)) => (name, Some(value.trim())),
+        None => (tag, None),
+    };
+    let Some(tag) = Tag::from_name(name) else { return false };
+    let previous = style.clone();
+    match (tag, value) {
+        (Tag::Bold, None) => style.bold = true,
+        (Tag::Italic, None) => style.italic = true,
+        (Tag::Color, Some(value)) => match Color::hex(value.trim_start_matches('#')) {
+            Ok(color) => style.color = Some(color.into()),
+            Err(_) => return false,
+        },
+        (Tag::Size, Some(value)) => match value.parse::<f32>() {
+            Ok(size) if size > 0. => style.font_size = Some(size),
+            _ => return false,
+        },
+        _ => return false,
+    }
+    open.push((tag, previous));
+    true
+}
+
+/// Escapes `text` so that it's shown as it is in rich text
+pub fn escape_markup(text: &str) -> String {
+    text.replace('[', "[[")
+}
+
+/// The markup of `spans`, which [parse_markup] parses back into them
+pub fn to_markup(spans: &[TextSpan]) -> String {
+    let mut markup = String::new();
+    for TextSpan { text, style } in spans {
+        let mut close = Vec::new();
+        if let Some(size) = style.font_size {
+            markup.push_str(&format!("[size={size}]"));
+            close.push("[/size]");
+        }
+        if let Some(color) = style.color {
+            let [r, g, b, a] = color.to_array().map(|c| (c.clamp(0., 1.) * 255.).round() as u8);
+            markup.push_str(&format!("[color=#{r:02x}{g:02x}{b:02x}{a:02x}]"));
+            close.push("[/color]");
+        }
+        if style.bold {
+            markup.push_str("[b]");
+            close.push("[/b]");
+        }
+        if style.italic {
+            markup.push_str("[i]");
+            close.push("[/i]");
+        }
+        markup.push_str(&escape_markup(text));
+        for tag in close.into_iter().rev() {
+            markup.push_str(tag);
+        }
+    }
+    markup
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::vec4;
+
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_markup("Plain"), vec![TextSpan::new("Plain")]);
+        assert_eq!(
+            parse_markup("A [b]bold [i]move[/i][/b] for [size=20][color=#ff0000]12[/color][/size] damage"),
+            vec![
+                TextSpan::new("A "),
+                TextSpan::new("bold ").bold(),
+                TextSpan::new("move").bold().italic(),
+                TextSpan::new(" for "),
+                TextSpan::new("12").font_size(20.).color(vec4(1., 0., 0., 1.)),
+                TextSpan::new(" damage"),
+            ]
+        );
+        assert_eq!(
+            parse_markup("[b]a[i]b[/b]c[/i]"),
+            vec![TextSpan::new("a").bold(), TextSpan::new("b").bold().italic(), TextSpan::new("c[/i]")]
+        );
+        assert_eq!(parse_markup("[[b]] [x] [size=big]a[/size] [b"), vec![TextSpan::new("[b]] [x] [size=big]a[/size] [b")]);
+    }
+
+    #[test]
+    fn markup_round_trip() {
+        let spans = vec![
+            TextSpan::new("Player [1]: ").bold().color(vec4(0., 0., 1., 1.)),
+            TextSpan::new("hello").italic().font_size(14.),
+            TextSpan::new(" world"),
+        ];
+        assert_eq!(parse_markup(&to_markup(&spans)), spans);
+    }
+}
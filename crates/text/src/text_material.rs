@@ -4,13 +4,14 @@ use ambient_gpu::{
     gpu::{Gpu, GpuKey},
     shader_module::BindGroupDesc,
     std_assets::DefaultSamplerKey,
-    texture::TextureView,
+    texture::{Texture, TextureView},
 };
 use ambient_renderer::{Material, MaterialShader, RendererConfig, RendererShader, StandardShaderKey, MATERIAL_BIND_GROUP};
 use ambient_std::{
     asset_cache::{AssetCache, SyncAssetKey, SyncAssetKeyExt},
     friendly_id, include_file,
 };
+use glam::Vec4;
 use wgpu::BindGroup;
 
 #[derive(Debug, Clone)]
@@ -40,6 +41,16 @@ impl SyncAssetKey<Arc<MaterialShader>> for TextMaterialShaderKey {
                             ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                             count: None,
                         },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 2,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Texture {
+                                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                                view_dimension: wgpu::TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
                     ],
                     label: MATERIAL_BIND_GROUP.into(),
                 }
@@ -60,7 +71,7 @@ pub struct TextMaterial {
     bind_group: wgpu::BindGroup,
 }
 impl TextMaterial {
-    pub fn new(assets: AssetCache, font_atlas: Arc<TextureView>) -> Self {
+    pub fn new(assets: AssetCache, font_atlas: Arc<TextureView>, palette: Arc<TextureView>) -> Self {
         let gpu = GpuKey.get(&assets);
         let material = TextMaterialShaderKey.get(&assets);
         Self {
@@ -70,6 +81,7 @@ impl TextMaterial {
                 entries: &[
                     wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&font_atlas) },
                     wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&DefaultSamplerKey.get(&assets)) },
+                    wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::TextureView(&palette) },
                 ],
                 label: Some("TextMaterial.bind_group"),
             }),
@@ -93,3 +105,34 @@ impl Material for TextMaterial {
         Some(true)
     }
 }
+
+/// The colors of the spans of a rich text. The glyphs of a colored span have the index of its color in the palette added
+/// to their texture coordinates, times two
+pub struct TextPalette {
+    pub colors: Vec<Vec4>,
+    pub texture: Arc<Texture>,
+}
+impl TextPalette {
+    pub fn new(gpu: Arc<Gpu>, colors: Vec<Vec4>) -> Self {
+        // The first color is the color of the entity, which the shader doesn't read from the palette
+        let data = [Vec4::ONE]
+            .iter()
+            .chain(&colors)
+            .flat_map(|color| color.to_array().map(|c| (c.clamp(0., 1.) * 255.).round() as u8))
+            .collect::<Vec<_>>();
+        let texture = Texture::new_with_data(
+            gpu,
+            &wgpu::TextureDescriptor {
+                size: wgpu::Extent3d { width: colors.len() as u32 + 1, height: 1, depth_or_array_layers: 1 },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                label: Some("TextPalette.texture"),
+            },
+            &data,
+        );
+        Self { colors, texture: Arc::new(texture) }
+    }
+}
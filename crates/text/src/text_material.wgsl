@@ -8,12 +8,23 @@ var font_atlas: texture_2d<f32>;
 @binding(1)
 var font_sampler: sampler;
 
+@group(#MATERIAL_BIND_GROUP)
+@binding(2)
+var palette: texture_2d<f32>;
+
 fn get_material(in: MaterialInput) -> MaterialOutput {
     var out: MaterialOutput;
     out.roughness = 0.4;
     out.metallic = 0.5;
-    let color = get_entity_color_or(in.entity_loc, vec4<f32>(1., 1., 1., 1.));
-    out.opacity = textureSample(font_atlas, font_sampler, in.texcoord).x * color.a;
+    var color = get_entity_color_or(in.entity_loc, vec4<f32>(1., 1., 1., 1.));
+    // Colored spans add twice the index of their color in the palette to the x texture coordinate
+    let color_index = floor((in.texcoord.x + 0.5) / 2.);
+    let texcoord = vec2<f32>(in.texcoord.x - color_index * 2., in.texcoord.y);
+    if (color_index > 0.) {
+        let span_color = textureLoad(palette, vec2<i32>(i32(color_index), 0), 0);
+        color = vec4<f32>(span_color.rgb, span_color.a * color.a);
+    }
+    out.opacity = textureSample(font_atlas, font_sampler, texcoord).x * color.a;
     out.alpha_cutoff = 0.01;
     out.base_color = from_srgb_to_linear(color.rgb);
     out.emissive_factor = vec3<f32>(0., 0., 0.);
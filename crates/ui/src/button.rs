@@ -7,7 +7,7 @@ use std::{
 };
 
 use ambient_core::{runtime, window::window_ctl, window::WindowCtl};
-use ambient_ecs::World;
+use ambient_ecs::{EntityId, World};
 use ambient_element::{element_component, Element, ElementComponent, ElementComponentExt, Hooks};
 use ambient_input::{event_focus_change, event_keyboard_input, event_mouse_input, KeyboardEvent};
 use ambient_renderer::color;
@@ -24,8 +24,8 @@ use winit::{
 
 use super::{FlowColumn, FlowRow, Text, UIBase, UIElement};
 use crate::{
-    border_color, border_radius, border_thickness, cutout_color, font_style, layout::*, primary_color, secondary_color, Corners, FontStyle,
-    Tooltip,
+    border_color, border_radius, border_thickness, event_focus_activate, focus_disabled, focusable, font_style, layout::*, use_theme,
+    ButtonTheme, ButtonVariant, Corners, Focus, FontStyle, Theme, Tooltip,
 };
 use ambient_ui_components::UIExt;
 
@@ -64,79 +64,40 @@ pub enum ButtonStyle {
     Inline,
 }
 impl ButtonStyle {
+    /// The variant of the theme used for this style
+    pub fn variant<'a>(&self, theme: &'a ButtonTheme) -> &'a ButtonVariant {
+        match self {
+            ButtonStyle::Regular => &theme.regular,
+            ButtonStyle::Primary => &theme.primary,
+            ButtonStyle::Flat => &theme.flat,
+            ButtonStyle::Card => &theme.card,
+            ButtonStyle::Inline => &theme.inline,
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn create_container(
         &self,
+        theme: &Theme,
         is_pressed: bool,
         is_working: bool,
         disabled: bool,
         toggled: bool,
         hover: bool,
+        focused: bool,
         hotkey: Option<VirtualKeyCode>,
         hotkey_modifier: ModifiersState,
         tooltip: Option<Element>,
         content: Element,
     ) -> Element {
-        let background = match self {
-            ButtonStyle::Regular | ButtonStyle::Card => {
-                if is_pressed {
-                    cutout_color().lighten(0.1)
-                } else if disabled || is_working {
-                    cutout_color()
-                } else if toggled {
-                    primary_color()
-                } else if hover && *self == ButtonStyle::Card {
-                    cutout_color().lighten(0.05)
-                } else {
-                    cutout_color()
-                }
-            }
-            ButtonStyle::Primary => {
-                if is_pressed {
-                    secondary_color().lighten(0.2)
-                } else if disabled || is_working {
-                    secondary_color().desaturate(-1.)
-                } else if hover || disabled || is_working {
-                    secondary_color().lighten(0.1)
-                } else {
-                    secondary_color()
-                }
-            }
-            ButtonStyle::Flat | ButtonStyle::Inline => Color::rgba(1., 1., 1., 0.0),
-        };
-        let content = match self {
-            Self::Regular | Self::Flat | ButtonStyle::Inline => content.set(
-                color(),
-                if is_pressed {
-                    Color::rgba(1., 1., 1., 1.)
-                } else if disabled || is_working {
-                    Color::rgba(0.3, 0.3, 0.3, 1.)
-                } else if toggled {
-                    if *self == Self::Flat || *self == Self::Inline {
-                        primary_color()
-                    } else {
-                        Color::rgba(1., 1., 1., 1.)
-                    }
-                } else if hover {
-                    Color::rgba(0.8, 0.8, 0.8, 1.)
-                } else {
-                    Color::hex("B3B3B3").unwrap()
-                }
-                .into(),
-            ),
-            Self::Primary => content.set(
-                color(),
-                if is_pressed {
-                    Color::BLACK
-                } else if disabled || is_working {
-                    Color::BLACK.lighten(0.3)
-                } else {
-                    Color::BLACK
-                }
-                .into(),
-            ),
-            _ => content,
+        let variant = self.variant(&theme.button);
+        let disabled = disabled || is_working;
+        let background = variant.background.get(is_pressed, disabled, toggled, hover);
+        let content = match &variant.text {
+            Some(text) => content.set(color(), text.get(is_pressed, disabled, toggled, hover).into()),
+            None => content,
         };
+        let focus_thickness = if focused { theme.focus.thickness } else { 0. };
         if *self == ButtonStyle::Inline {
             FlowColumn::el([
                 content,
@@ -148,13 +109,15 @@ impl ButtonStyle {
                     .set(margin(), Borders::top(2.)),
             ])
             .with_background(background.into())
+            .set(border_thickness(), focus_thickness)
+            .set(border_color(), theme.focus.color.into())
         } else {
             let content = content.set(font_style(), FontStyle::Bold);
             let tooltip = if let Some(hotkey) = hotkey {
                 let modifier = if hotkey_modifier != ModifiersState::empty() { format!("{hotkey_modifier:?} + ") } else { String::new() };
                 let hotkey = Text::el(format!("[{modifier}{hotkey:?}]"));
                 if let Some(tooltip) = tooltip {
-                    Some(FlowColumn::el([tooltip, hotkey]).set(space_between_items(), 10.))
+                    Some(FlowColumn::el([tooltip, hotkey]).set(space_between_items(), theme.spacing.street))
                 } else {
                     Some(hotkey)
                 }
@@ -163,29 +126,14 @@ impl ButtonStyle {
             };
             let mut el = FlowRow(vec![content])
                 .el()
-                .set(
-                    padding(),
-                    match self {
-                        Self::Card => Borders::even(3.),
-                        Self::Flat => Borders::even(3.),
-                        _ => Borders::rect(3., 16.),
-                    },
-                )
+                .set(padding(), variant.padding())
                 .set(align_vertical(), Align::Center)
                 .with_background(background.into())
-                .set(
-                    border_radius(),
-                    match self {
-                        Self::Card => Corners::even(3.),
-                        Self::Flat => Corners::even(3.),
-                        _ => Corners::even(26. / 2.),
-                    }
-                    .into(),
-                )
-                .set(border_thickness(), 0.)
-                .set(border_color(), Color::WHITE.into());
-            if *self != Self::Flat {
-                el = el.set(min_height(), 26.);
+                .set(border_radius(), Corners::even(variant.corner_radius).into())
+                .set(border_thickness(), focus_thickness)
+                .set(border_color(), theme.focus.color.into());
+            if variant.min_height > 0. {
+                el = el.set(min_height(), variant.min_height);
             }
             if let Some(tooltip) = tooltip {
                 Tooltip { inner: el, tooltip }.el()
@@ -213,6 +161,9 @@ pub fn Button(
     let (hover, set_hover) = hooks.use_state(false);
     let (is_working, set_is_working) = hooks.use_state(false);
     let (is_pressed_immediate, _) = hooks.use_state_with(|_| Arc::new(AtomicBool::new(false)));
+    let (self_id, set_self_id) = hooks.use_state(EntityId::null());
+    let focused = hooks.consume_context::<Focus>().map_or(false, |(focus, _)| focus.is_visible(self_id));
+    let theme = use_theme(hooks);
 
     hooks.use_effect(is_pressed, move |world, _| {
         if let Some(on_is_pressed_changed) = on_is_pressed_changed {
@@ -225,7 +176,11 @@ pub fn Button(
         let on_invoked = on_invoked.clone();
         let set_is_working = set_is_working.clone();
         move |world, event| {
-            if let Some(pressed) = event.get(event_mouse_input()) {
+            if event.get(event_focus_activate()) == Some(self_id) {
+                if !disabled {
+                    on_invoked.invoke(world, set_is_working.clone());
+                }
+            } else if let Some(pressed) = event.get(event_mouse_input()) {
                 if pressed && hover {
                     set_is_pressed(true);
                     is_pressed_immediate.store(true, Ordering::SeqCst);
@@ -243,7 +198,7 @@ pub fn Button(
     });
 
     let content = style
-        .create_container(is_pressed, is_working, disabled, toggled, hover, hotkey, hotkey_modifier, tooltip, content)
+        .create_container(&theme, is_pressed, is_working, disabled, toggled, hover, focused, hotkey, hotkey_modifier, tooltip, content)
         .with_clickarea()
         .on_mouse_enter(
             closure!(clone set_hover, |world, _| { set_hover(true); world.resource(window_ctl()).send(WindowCtl::SetCursorIcon(CursorIcon::Hand)).ok(); }),
@@ -252,7 +207,10 @@ pub fn Button(
             set_hover(false);
             world.resource(window_ctl()).send(WindowCtl::SetCursorIcon(CursorIcon::Default)).ok();
         })
-        .el();
+        .el()
+        .init_default(focusable())
+        .set(focus_disabled(), disabled)
+        .on_spawned(move |_, id| set_self_id(id));
 
     if disabled {
         content
@@ -255,7 +255,7 @@ impl<T: std::fmt::Debug + Clone + Default + Sync + Send + 'static> ElementCompon
         let Self { value, on_change, on_delete, item_opts, item_editor } = *self;
         let (self_id, set_self_id) = hooks.use_state(EntityId::null());
         let (focus, set_focus) = hooks.consume_context::<Focus>().expect("No FocusRoot found");
-        let focused = focus == Focus(Some(self_id));
+        let focused = focus.is(self_id);
         hooks.use_world_event(move |_world, event| {
             if let Some(event) = event.get_ref(event_keyboard_input()) {
                 if !focused {
@@ -283,7 +283,7 @@ impl<T: std::fmt::Debug + Clone + Default + Sync + Send + 'static> ElementCompon
         .on_spawned(move |_, id| set_self_id(id))
         .with_clickarea()
         .on_mouse_down(move |_, id, _| {
-            set_focus(Focus(Some(id)));
+            set_focus(Focus::new(Some(id)));
         })
         .el()
         .set(padding(), Borders::vertical(STREET))
@@ -7,7 +7,7 @@ use super::{FlowColumn, UIBase};
 use crate::{
     border_radius,
     layout::{margin, Borders},
-    padding, tooltip_background_color, Corners, SMALL_ROUNDING, STREET,
+    padding, use_theme, Corners,
 };
 use ambient_ui_components::UIExt;
 
@@ -26,14 +26,15 @@ pub fn Dropdown(_: &mut Hooks, content: Element, dropdown: Element, show: bool)
 #[element_component]
 pub fn Tooltip(hooks: &mut Hooks, inner: Element, tooltip: Element) -> Element {
     let (hover, set_hover) = hooks.use_state(false);
+    let theme = use_theme(hooks).dropdown;
     Dropdown {
         content: inner,
         dropdown: FlowColumn(vec![tooltip])
             .el()
-            .set(padding(), Borders::even(STREET))
-            .with_background(tooltip_background_color().into())
-            .set(border_radius(), Corners::even(SMALL_ROUNDING).into())
-            .set(margin(), Borders::top(STREET)),
+            .set(padding(), Borders::even(theme.padding))
+            .with_background(theme.background.into())
+            .set(border_radius(), Corners::even(theme.corner_radius).into())
+            .set(margin(), Borders::top(theme.space_between_items)),
         show: hover,
     }
     .el()
@@ -0,0 +1,368 @@
+use std::collections::HashSet;
+
+use ambient_core::{hierarchy::parent, transform::get_world_position};
+use ambient_ecs::{components, query, world_events, Entity, EntityId, World};
+use ambient_element::{define_el_function_for_vec_element_newtype, element_component, Element, ElementComponent, Hooks};
+use ambient_input::{
+    event_keyboard_input, event_mouse_input,
+    gamepad::{gamepads, GamepadButton},
+    ElementState, KeyboardEvent, VirtualKeyCode,
+};
+use ambient_std::Cb;
+use glam::{vec2, Vec2};
+use itertools::Itertools;
+
+use crate::{
+    border_color, border_thickness,
+    layout::{height, width},
+    use_theme,
+};
+
+components!("ui", {
+    /// Can be focused with the keyboard or a gamepad, under a [FocusRoot]
+    focusable: (),
+    /// Is focused when it's spawned; used to set the initial focus of a screen
+    autofocus: (),
+    /// Focusable element which handles the arrow keys, Enter and Space itself, like a text input. Only Tab and gamepads
+    /// move the focus away from it
+    captures_keyboard: (),
+    /// The focusable element can't be focused while this is true, like a disabled `Button`
+    focus_disabled: bool,
+    /// The focused element was activated with Enter, Space or the South button of a gamepad. The value is the element
+    event_focus_activate: EntityId,
+});
+
+/// How far a gamepad stick has to be pushed to move the focus
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// The focused element of a [FocusRoot]
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Focus {
+    id: Option<EntityId>,
+    visible: bool,
+}
+impl Focus {
+    /// Focus set with the mouse, which isn't highlighted
+    pub fn new(id: Option<EntityId>) -> Self {
+        Self { id, visible: false }
+    }
+    /// Focus set with the keyboard or a gamepad, which the focused element highlights
+    pub fn visible(id: EntityId) -> Self {
+        Self { id: Some(id), visible: true }
+    }
+    pub fn id(&self) -> Option<EntityId> {
+        self.id
+    }
+    pub fn is(&self, id: EntityId) -> bool {
+        self.id == Some(id)
+    }
+    /// If `id` is focused and should be highlighted
+    pub fn is_visible(&self, id: EntityId) -> bool {
+        self.visible && self.is(id)
+    }
+}
+
+pub fn use_has_focus(_: &World, hooks: &mut Hooks) -> bool {
+    hooks.consume_context::<Focus>().is_some()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Navigation {
+    Next,
+    Previous,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+impl Navigation {
+    fn from_key(key: VirtualKeyCode, shift: bool) -> Option<Self> {
+        match key {
+            VirtualKeyCode::Tab if shift => Some(Self::Previous),
+            VirtualKeyCode::Tab => Some(Self::Next),
+            VirtualKeyCode::Up => Some(Self::Up),
+            VirtualKeyCode::Down => Some(Self::Down),
+            VirtualKeyCode::Left => Some(Self::Left),
+            VirtualKeyCode::Right => Some(Self::Right),
+            _ => None,
+        }
+    }
+    fn from_gamepad_button(button: GamepadButton) -> Option<Self> {
+        match button {
+            GamepadButton::DPadUp => Some(Self::Up),
+            GamepadButton::DPadDown => Some(Self::Down),
+            GamepadButton::DPadLeft => Some(Self::Left),
+            GamepadButton::DPadRight => Some(Self::Right),
+            GamepadButton::RightBumper => Some(Self::Next),
+            GamepadButton::LeftBumper => Some(Self::Previous),
+            _ => None,
+        }
+    }
+    fn from_stick(stick: Vec2) -> Option<Self> {
+        if stick.length() < STICK_THRESHOLD {
+            None
+        } else if stick.x.abs() > stick.y.abs() {
+            Some(if stick.x > 0. { Self::Right } else { Self::Left })
+        } else {
+            // Positive y is up on the sticks, and down in the UI
+            Some(if stick.y > 0. { Self::Up } else { Self::Down })
+        }
+    }
+    /// The direction on screen, for the directional navigations
+    fn direction(&self) -> Option<Vec2> {
+        match self {
+            Self::Up => Some(vec2(0., -1.)),
+            Self::Down => Some(vec2(0., 1.)),
+            Self::Left => Some(vec2(-1., 0.)),
+            Self::Right => Some(vec2(1., 0.)),
+            Self::Next | Self::Previous => None,
+        }
+    }
+}
+
+/// Keys which are only pressed together with other keys, and so aren't used by the game on their own
+fn is_modifier(key: VirtualKeyCode) -> bool {
+    use VirtualKeyCode::*;
+    matches!(key, LShift | RShift | LControl | RControl | LAlt | RAlt | LWin | RWin)
+}
+
+/// The focusable elements under `root` which aren't disabled, with their bounds on screen, in reading order
+fn focusables(world: &World, root: EntityId) -> Vec<(EntityId, Vec2, Vec2)> {
+    let is_under_root = |mut id: EntityId| loop {
+        match world.get(id, parent()) {
+            Ok(parent) if parent == root => return true,
+            Ok(parent) => id = parent,
+            Err(_) => return false,
+        }
+    };
+    query(focusable())
+        .iter(world, None)
+        .map(|(id, _)| id)
+        .filter(|&id| is_under_root(id) && !world.get(id, focus_disabled()).unwrap_or(false))
+        .filter_map(|id| {
+            let position = get_world_position(world, id).ok()?.truncate();
+            let size = vec2(world.get(id, width()).unwrap_or(0.), world.get(id, height()).unwrap_or(0.));
+            Some((id, position, size))
+        })
+        .sorted_by(|(_, a, _), (_, b, _)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+        .collect()
+}
+
+/// The element to focus when navigating from `current`
+fn find_next(world: &World, root: EntityId, current: Option<EntityId>, navigation: Navigation) -> Option<EntityId> {
+    let items = focusables(world, root);
+    let index = current.and_then(|current| items.iter().position(|(id, _, _)| *id == current));
+    let Some(index) = index else {
+        return items.first().map(|(id, _, _)| *id);
+    };
+    match navigation.direction() {
+        None => {
+            let len = items.len();
+            let next = if navigation == Navigation::Next { (index + 1) % len } else { (index + len - 1) % len };
+            Some(items[next].0)
+        }
+        Some(direction) => {
+            let (_, position, size) = items[index];
+            let center = position + size / 2.;
+            // Prefers the closest element in the direction, and the most in line with the current one
+            items
+                .iter()
+                .filter_map(|&(id, position, size)| {
+                    let offset = position + size / 2. - center;
+                    let along = offset.dot(direction);
+                    let across = offset.perp_dot(direction).abs();
+                    (along > 1.).then_some((id, along + across * 2.))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(id, _)| id)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Provides a context for focusable UI elements, and moves the focus between the [focusable] elements under it with
+/// Tab, the arrow keys, or the D-pad, bumpers and left stick of a gamepad. The focus is dropped when the mouse is clicked,
+/// or when the game takes the input, i.e. when another key or gamepad button is pressed
+pub struct FocusRoot(pub Vec<Element>);
+define_el_function_for_vec_element_newtype!(FocusRoot);
+impl ElementComponent for FocusRoot {
+    fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
+        let set_focus = hooks.provide_context(Focus::default);
+        let (focus, _) = hooks.consume_context::<Focus>().unwrap();
+        let root = hooks.use_ref_with(|_| None::<EntityId>);
+        let autofocused = hooks.use_ref_with(|_| HashSet::<EntityId>::new());
+        let prev_gamepad = hooks.use_ref_with(|_| (HashSet::<GamepadButton>::new(), None::<Navigation>));
+
+        let navigate = {
+            let root = root.clone();
+            let set_focus = set_focus.clone();
+            let current = focus.id();
+            move |world: &World, navigation: Navigation| {
+                let Some(root) = *root.lock() else { return };
+                // Directions only move an existing focus, so that they can still be used by the game otherwise
+                if current.is_none() && navigation.direction().is_some() {
+                    return;
+                }
+                if let Some(id) = find_next(world, root, current, navigation) {
+                    set_focus(Focus::visible(id));
+                }
+            }
+        };
+        let activate = {
+            let current = focus.id();
+            move |world: &mut World| {
+                if let Some(id) = current {
+                    world.resource_mut(world_events()).add_event(Entity::new().with(event_focus_activate(), id));
+                }
+            }
+        };
+
+        hooks.use_world_event({
+            let set_focus = set_focus.clone();
+            let focus = focus.clone();
+            let navigate = navigate.clone();
+            let activate = activate.clone();
+            move |world, event| {
+                if let Some(_event) = event.get_ref(event_mouse_input()) {
+                    set_focus(Focus::default());
+                } else if let Some(KeyboardEvent { keycode: Some(key), state: ElementState::Pressed, modifiers, .. }) =
+                    event.get_ref(event_keyboard_input())
+                {
+                    let captured = focus.id().map_or(false, |id| world.has_component(id, captures_keyboard()));
+                    if let Some(navigation) = Navigation::from_key(*key, modifiers.shift()) {
+                        if !captured || navigation.direction().is_none() {
+                            navigate(world, navigation);
+                        }
+                    } else if !captured && matches!(key, VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter | VirtualKeyCode::Space) {
+                        activate(world);
+                    } else if !captured && !is_modifier(*key) && focus.id().is_some() {
+                        set_focus(Focus::default());
+                    }
+                }
+            }
+        });
+        hooks.use_frame({
+            let root = root.clone();
+            move |world| {
+                let Some(root_id) = *root.lock() else { return };
+
+                if focus.id().is_none() {
+                    let mut autofocused = autofocused.lock();
+                    let new = query(autofocus()).iter(world, None).map(|(id, _)| id).find(|id| !autofocused.contains(id));
+                    if let Some(id) = new.filter(|id| focusables(world, root_id).iter().any(|(item, _, _)| item == id)) {
+                        autofocused.insert(id);
+                        set_focus(Focus::visible(id));
+                    }
+                }
+
+                let Some(gamepads) = world.resource_opt(gamepads()).cloned() else { return };
+                let buttons = gamepads.values().flat_map(|gamepad| gamepad.buttons.iter().copied()).collect::<HashSet<_>>();
+                let stick = gamepads.values().map(|gamepad| gamepad.left_stick).find(|stick| stick.length() >= STICK_THRESHOLD);
+                let stick = stick.and_then(Navigation::from_stick);
+                let (prev_buttons, prev_stick) = std::mem::replace(&mut *prev_gamepad.lock(), (buttons.clone(), stick));
+                for &button in buttons.difference(&prev_buttons) {
+                    if let Some(navigation) = Navigation::from_gamepad_button(button) {
+                        navigate(world, navigation);
+                    } else if button == GamepadButton::South {
+                        activate(world);
+                    } else if focus.id().is_some() {
+                        set_focus(Focus::default());
+                    }
+                }
+                if let Some(stick) = stick.filter(|stick| Some(*stick) != prev_stick) {
+                    navigate(world, stick);
+                }
+            }
+        });
+        Element::new().children(self.0).on_spawned(move |_, id| *root.lock() = Some(id))
+    }
+}
+
+/// Makes `inner` (e.g. a `ClickArea`) focusable with the keyboard and gamepads, outlines it when it has the visible
+/// focus, and calls `on_activate` when it's activated. `Button`s and `TextInput`s are already focusable
+#[element_component]
+pub fn Focusable(hooks: &mut Hooks, inner: Element, on_activate: Cb<dyn Fn(&mut World) + Sync + Send>) -> Element {
+    let (self_id, set_self_id) = hooks.use_state(EntityId::null());
+    let focused = hooks.consume_context::<Focus>().map_or(false, |(focus, _)| focus.is_visible(self_id));
+    let theme = use_theme(hooks);
+    hooks.use_world_event(move |world, event| {
+        if event.get(event_focus_activate()) == Some(self_id) {
+            on_activate(world);
+        }
+    });
+    let inner =
+        if focused { inner.set(border_thickness(), theme.focus.thickness).set(border_color(), theme.focus.color.into()) } else { inner };
+    inner.init_default(focusable()).on_spawned(move |_, id| set_self_id(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use ambient_core::transform::translation;
+    use glam::vec3;
+
+    use super::*;
+
+    /// A root with four focusable items in a 2x2 grid, in the order top left, top right, bottom left and bottom right
+    fn grid(world: &mut World) -> (EntityId, [EntityId; 4]) {
+        let root = world.spawn(Entity::new());
+        let items = [(0., 0.), (120., 0.), (0., 40.), (120., 40.)].map(|(x, y)| {
+            Entity::new()
+                .with_default(focusable())
+                .with(parent(), root)
+                .with(translation(), vec3(x, y, 0.))
+                .with(width(), 100.)
+                .with(height(), 20.)
+                .spawn(world)
+        });
+        (root, items)
+    }
+
+    fn init() -> World {
+        ambient_core::init_all_components();
+        crate::init_all_componets();
+        World::new("focus")
+    }
+
+    #[test]
+    fn tab_order() {
+        let mut world = init();
+        let (root, [a, b, c, d]) = grid(&mut world);
+        // Not under the root
+        Entity::new().with_default(focusable()).with(translation(), vec3(-10., -10., 0.)).spawn(&mut world);
+
+        assert_eq!(find_next(&world, root, None, Navigation::Next), Some(a));
+        assert_eq!(find_next(&world, root, Some(a), Navigation::Next), Some(b));
+        assert_eq!(find_next(&world, root, Some(b), Navigation::Next), Some(c));
+        assert_eq!(find_next(&world, root, Some(d), Navigation::Next), Some(a));
+        assert_eq!(find_next(&world, root, Some(a), Navigation::Previous), Some(d));
+        assert_eq!(find_next(&world, root, Some(c), Navigation::Previous), Some(b));
+    }
+
+    #[test]
+    fn directional_navigation() {
+        let mut world = init();
+        let (root, [a, b, c, d]) = grid(&mut world);
+
+        assert_eq!(find_next(&world, root, Some(a), Navigation::Right), Some(b));
+        assert_eq!(find_next(&world, root, Some(a), Navigation::Down), Some(c));
+        assert_eq!(find_next(&world, root, Some(b), Navigation::Down), Some(d));
+        assert_eq!(find_next(&world, root, Some(d), Navigation::Up), Some(b));
+        assert_eq!(find_next(&world, root, Some(d), Navigation::Left), Some(c));
+        assert_eq!(find_next(&world, root, Some(a), Navigation::Left), None);
+        assert_eq!(find_next(&world, root, Some(a), Navigation::Up), None);
+    }
+
+    #[test]
+    fn disabled_items_are_skipped() {
+        let mut world = init();
+        let (root, [a, b, c, d]) = grid(&mut world);
+        world.add_component(b, focus_disabled(), true).unwrap();
+
+        assert_eq!(find_next(&world, root, Some(a), Navigation::Next), Some(c));
+        assert_eq!(find_next(&world, root, Some(a), Navigation::Right), Some(d));
+        assert_eq!(find_next(&world, root, Some(d), Navigation::Up), Some(a));
+
+        world.set(b, focus_disabled(), false).unwrap();
+        assert_eq!(find_next(&world, root, Some(a), Navigation::Next), Some(b));
+    }
+}
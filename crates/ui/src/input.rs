@@ -23,8 +23,8 @@ use winit::window::CursorIcon;
 
 use super::{Editor, EditorOpts, FlowColumn, FlowRow, Focus, Text, UIBase};
 use crate::{
-    background_color, border_radius, layout::*, primary_color, text_input::TextInput, Button, ButtonStyle, ChangeCb, Corners,
-    FontAwesomeIcon, Rectangle, STREET,
+    background_color, border_radius, layout::*, text_input::TextInput, use_theme, Button, ButtonStyle, ChangeCb, Corners, FontAwesomeIcon,
+    Rectangle, SliderTheme, STREET,
 };
 
 #[derive(Debug, Clone)]
@@ -42,7 +42,7 @@ impl<T: FromStr + Debug + std::fmt::Display + Clone + Sync + Send + 'static> Ele
         let Self { value, on_change } = *self;
         let (self_id, set_self_id) = hooks.use_state(EntityId::null());
         let (focus, _) = hooks.consume_context::<Focus>().expect("No FocusRoot found");
-        let focused = focus == Focus(Some(self_id));
+        let focused = focus.is(self_id);
         let (text, set_text) = hooks.use_state(None);
         if focused && text.is_none() {
             set_text(Some(value.to_string()));
@@ -77,7 +77,7 @@ impl<T: Debug + ComponentValue> ElementComponent for CustomParseInput<T> {
 
         let (self_id, set_self_id) = hooks.use_state(EntityId::null());
         let (focus, _) = hooks.consume_context::<Focus>().expect("No FocusRoot found");
-        let focused = focus == Focus(Some(self_id));
+        let focused = focus.is(self_id);
         let (text, set_text) = hooks.use_state(None);
         if focused && text.is_none() {
             set_text(Some(to_string(&value)));
@@ -217,8 +217,8 @@ pub struct Slider {
 impl ElementComponent for Slider {
     fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
         let Slider { value, min, max, width: slider_width, logarithmic, round, suffix, .. } = *self;
-        const THUMB_WIDTH: f32 = 12.;
-        const SLIDER_HEIGHT: f32 = 12.;
+        let theme = use_theme(hooks);
+        let SliderTheme { track, thumb, track_height, thumb_width, thumb_height } = theme.slider;
 
         fn cleanup_value(value: f32, min: f32, max: f32, round: Option<u32>) -> f32 {
             let mut processed = value.clamp(min, max);
@@ -259,7 +259,7 @@ impl ElementComponent for Slider {
         // f'(y) = y ^ (1/e)
         // (p ^ e) ^ (1/e) = p ^ (e / e) = p ^ 1 = p
         let p = interpolate(value, min, max, 0., 1.);
-        let block_left_offset = if logarithmic { p.powf(1. / E) } else { p } * (slider_width - THUMB_WIDTH);
+        let block_left_offset = if logarithmic { p.powf(1. / E) } else { p } * (slider_width - thumb_width);
         let block_left_offset = if block_left_offset.is_nan() || block_left_offset.is_infinite() { 0. } else { block_left_offset };
 
         let dragging = hooks.use_ref_with(|_| false);
@@ -287,18 +287,18 @@ impl ElementComponent for Slider {
         let rectangle = Rectangle
             .el()
             .set(width(), slider_width)
-            .set(height(), 2.)
-            .set(translation(), vec3(0., (SLIDER_HEIGHT - 2.) / 2., 0.))
-            .set(background_color(), primary_color().into())
+            .set(height(), track_height)
+            .set(translation(), vec3(0., (thumb_height - track_height) / 2., 0.))
+            .set(background_color(), track.into())
             .on_spawned(move |_, id| *block_id.lock() = id);
 
         let thumb = {
             let thumb = UIBase
                 .el()
-                .set(width(), THUMB_WIDTH)
-                .set(height(), SLIDER_HEIGHT)
-                .with_background(primary_color().into())
-                .set(border_radius(), Corners::even(THUMB_WIDTH / 2.).into())
+                .set(width(), thumb_width)
+                .set(height(), thumb_height)
+                .with_background(thumb.into())
+                .set(border_radius(), Corners::even(thumb_width / 2.).into())
                 .set(translation(), vec3(block_left_offset, 0., -0.01))
                 .with_clickarea()
                 .on_mouse_enter(|world, _| {
@@ -320,10 +320,10 @@ impl ElementComponent for Slider {
         };
 
         FlowRow::el([
-            UIBase.el().set(width(), slider_width).set(height(), SLIDER_HEIGHT).children(vec![rectangle, thumb]),
+            UIBase.el().set(width(), slider_width).set(height(), thumb_height).children(vec![rectangle, thumb]),
             FlowRow::el([f32::edit_or_view(value, on_change_raw, EditorOpts::default()), suffix.map(Text::el).unwrap_or_default()]),
         ])
-        .set(space_between_items(), STREET)
+        .set(space_between_items(), theme.spacing.street)
     }
 }
 
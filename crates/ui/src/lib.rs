@@ -10,10 +10,8 @@ use ambient_core::{hierarchy::children, transform::*, window::window_ctl, window
 pub use ambient_ecs::{EntityId, SystemGroup, World};
 pub use ambient_editor_derive::ElementEditor;
 pub use ambient_element as element;
-use ambient_element::{
-    define_el_function_for_vec_element_newtype, element_component, Element, ElementComponent, ElementComponentExt, Hooks,
-};
-use ambient_input::{event_focus_change, event_mouse_input, event_mouse_motion, event_mouse_wheel, event_mouse_wheel_pixels};
+use ambient_element::{element_component, Element, ElementComponent, ElementComponentExt, Hooks};
+use ambient_input::{event_focus_change, event_mouse_motion, event_mouse_wheel, event_mouse_wheel_pixels};
 use ambient_std::color::Color;
 pub use ambient_std::{cb, Cb};
 use glam::*;
@@ -25,6 +23,7 @@ mod button;
 mod collections;
 mod dropdown;
 mod editor;
+mod focus;
 pub mod graph;
 mod hooks;
 mod image;
@@ -37,9 +36,11 @@ mod select;
 mod style_constants;
 mod tabs;
 mod text_input;
+mod theme;
 mod throbber;
 
 pub use ambient_layout as layout;
+pub use ambient_localization as localization;
 pub use ambient_rect as rect;
 pub use ambient_rect::{background_color, border_color, border_radius, border_thickness, Corners};
 use ambient_text as text;
@@ -53,6 +54,7 @@ pub use button::*;
 pub use collections::*;
 pub use dropdown::*;
 pub use editor::*;
+pub use focus::*;
 pub use hooks::*;
 pub use input::*;
 pub use layout::*;
@@ -63,6 +65,7 @@ pub use select::*;
 pub use style_constants::*;
 pub use tabs::*;
 pub use text_input::*;
+pub use theme::*;
 pub use throbber::*;
 
 pub use self::image::*;
@@ -72,13 +75,21 @@ pub fn init_all_componets() {
     layout::init_gpu_components();
     rect::init_components();
     text::init_components();
+    localization::init_components();
     screens::init_components();
+    focus::init_components();
 }
 
 pub fn systems() -> SystemGroup {
     SystemGroup::new(
         "ui",
-        vec![Box::new(rect::systems()), Box::new(text::systems(true)), Box::new(layout::layout_systems()), Box::new(screens::systems())],
+        vec![
+            Box::new(rect::systems()),
+            Box::new(localization::systems()),
+            Box::new(text::systems(true)),
+            Box::new(layout::layout_systems()),
+            Box::new(screens::systems()),
+        ],
     )
 }
 
@@ -108,29 +119,6 @@ impl ScrollArea {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Focus(Option<EntityId>);
-
-pub fn use_has_focus(_: &World, hooks: &mut Hooks) -> bool {
-    hooks.consume_context::<Focus>().is_some()
-}
-
-#[derive(Debug, Clone)]
-/// Provides a context for focusable UI elements
-pub struct FocusRoot(pub Vec<Element>);
-define_el_function_for_vec_element_newtype!(FocusRoot);
-impl ElementComponent for FocusRoot {
-    fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
-        let set_focus = hooks.provide_context(|| Focus(None));
-        hooks.use_world_event(move |_world, event| {
-            if let Some(_event) = event.get_ref(event_mouse_input()) {
-                set_focus(Focus(None));
-            }
-        });
-        Element::new().children(self.0)
-    }
-}
-
 impl Default for HighjackMouse {
     fn default() -> Self {
         Self { on_mouse_move: cb(|_, _, _| {}), on_click: cb(|_| {}), hide_mouse: false }
@@ -6,7 +6,7 @@ use super::{Button, ButtonStyle, FlowColumn, FlowRow, Text};
 use crate::{
     border_radius,
     layout::{margin, Borders},
-    padding, tooltip_background_color, Corners, Dropdown, SMALL_ROUNDING, STREET,
+    padding, use_theme, Corners, Dropdown,
 };
 use ambient_input::event_mouse_input;
 use ambient_ui_components::UIExt;
@@ -22,6 +22,7 @@ impl ElementComponent for DropdownSelect {
     fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
         let Self { content, on_select, items, inline } = *self;
         let (show, set_show) = hooks.use_state(false);
+        let theme = use_theme(hooks).dropdown;
         hooks.use_world_event({
             let set_show = set_show.clone();
             move |_world, event| {
@@ -47,7 +48,7 @@ impl ElementComponent for DropdownSelect {
                         Button::new(item, closure!(clone on_select, |_| { on_select.0(i); }))
                             .style(ButtonStyle::Card)
                             .el()
-                            .set(margin(), Borders::top(if i != 0 { STREET } else { 0. }))
+                            .set(margin(), Borders::top(if i != 0 { theme.space_between_items } else { 0. }))
                     })
                     .collect(), //     vec![Bookcase(
                                 //     items
@@ -66,9 +67,9 @@ impl ElementComponent for DropdownSelect {
                                 // .set(orientation(), Orientation::Vertical)]
             )
             .el()
-            .set(padding(), Borders::even(STREET))
-            .set(border_radius(), Corners::even(SMALL_ROUNDING).into())
-            .with_background(tooltip_background_color().into()),
+            .set(padding(), Borders::even(theme.padding))
+            .set(border_radius(), Corners::even(theme.corner_radius).into())
+            .with_background(theme.background.into()),
             show,
         }
         .el()
@@ -1,9 +1,9 @@
-use ambient_element::Element;
+use ambient_element::{element_component, Element, ElementComponentExt, Hooks};
 use ambient_renderer::color;
 use ambient_std::color::Color;
 use glam::vec4;
 
-use crate::{align_vertical, border_radius, font_size, padding, space_between_items, Align, Borders, Corners};
+use crate::{align_vertical, border_radius, font_size, padding, space_between_items, use_theme, Align, Borders, Corners};
 use ambient_ui_components::UIExt;
 
 pub fn primary_color() -> Color {
@@ -31,6 +31,25 @@ pub const STREET: f32 = 10.;
 /// Default rounding of corners
 pub const SMALL_ROUNDING: f32 = 3.;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextStyle {
+    Heading,
+    Section,
+    Small,
+}
+
+/// Sizes `text` with the [crate::Typography] of the theme
+#[element_component]
+fn StyledText(hooks: &mut Hooks, text: Element, style: TextStyle) -> Element {
+    let typography = use_theme(hooks).typography;
+    let size = match style {
+        TextStyle::Heading => typography.heading,
+        TextStyle::Section => typography.section,
+        TextStyle::Small => typography.small,
+    };
+    text.set(font_size(), size)
+}
+
 pub trait StylesExt {
     fn section_style(self) -> Self;
     fn header_style(self) -> Self;
@@ -43,13 +62,13 @@ pub trait StylesExt {
 }
 impl StylesExt for Element {
     fn section_style(self) -> Self {
-        self.set(font_size(), 16.).set(color(), vec4(0.9, 0.9, 0.9, 1.))
+        StyledText { text: self.set(color(), vec4(0.9, 0.9, 0.9, 1.)), style: TextStyle::Section }.el()
     }
     fn header_style(self) -> Self {
-        self.set(font_size(), 25.).set(color(), vec4(0.9, 0.9, 0.9, 1.))
+        StyledText { text: self.set(color(), vec4(0.9, 0.9, 0.9, 1.)), style: TextStyle::Heading }.el()
     }
     fn small_style(self) -> Self {
-        self.set(font_size(), 10.).set(color(), vec4(0.5, 0.5, 0.5, 1.))
+        StyledText { text: self.set(color(), vec4(0.5, 0.5, 0.5, 1.)), style: TextStyle::Small }.el()
     }
     fn error_text_style(self) -> Self {
         self.set(color(), vec4(1., 0.5, 0.5, 1.))
@@ -4,7 +4,7 @@ use ambient_element::{Element, ElementComponent, ElementComponentExt, Hooks};
 use ambient_std::{cb, Cb};
 use closure::closure;
 
-use crate::{space_between_items, Button, FlowColumn, FlowRow, STREET};
+use crate::{space_between_items, use_theme, Button, FlowColumn, FlowRow};
 
 #[derive(Clone, Debug)]
 pub struct TabBar<T: ToString + PartialEq + Clone + Debug + Sync + Send + 'static> {
@@ -13,8 +13,9 @@ pub struct TabBar<T: ToString + PartialEq + Clone + Debug + Sync + Send + 'stati
     pub on_change: Cb<dyn Fn(T) + Sync + Send>,
 }
 impl<T: ToString + PartialEq + Clone + Debug + Sync + Send + 'static> ElementComponent for TabBar<T> {
-    fn render(self: Box<Self>, _: &mut Hooks) -> Element {
+    fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
         let Self { tabs, value, on_change } = *self;
+        let theme = use_theme(hooks);
         FlowRow(
             tabs.into_iter()
                 .map(|tab| {
@@ -25,7 +26,7 @@ impl<T: ToString + PartialEq + Clone + Debug + Sync + Send + 'static> ElementCom
                 .collect(),
         )
         .el()
-        .set(space_between_items(), STREET)
+        .set(space_between_items(), theme.spacing.street)
     }
 }
 
@@ -46,6 +47,7 @@ impl<T: ToString + PartialEq + Default + Clone + Debug + Sync + Send + 'static>
 impl<T: ToString + PartialEq + Default + Clone + Debug + Sync + Send + 'static> ElementComponent for Tabs<T> {
     fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
         let (value, set_value) = hooks.use_state(T::default());
+        let theme = use_theme(hooks);
         let selected_tab = self.tabs.iter().find(|it| it.0 == value).map(|it| it.1.clone()).unwrap_or(cb(Element::new));
         let key = value.to_string();
 
@@ -53,6 +55,6 @@ impl<T: ToString + PartialEq + Default + Clone + Debug + Sync + Send + 'static>
             TabBar { tabs: self.tabs.iter().map(|it| it.0.clone()).collect(), value, on_change: cb(move |value| set_value(value)) }.el(),
             selected_tab().key(key),
         ])
-        .set(space_between_items(), STREET)
+        .set(space_between_items(), theme.spacing.street)
     }
 }
@@ -13,8 +13,8 @@ use winit::{
     window::CursorIcon,
 };
 
-use super::{Editor, EditorOpts, Focus, Text, UIExt};
-use crate::{layout::*, text, use_interval_deps, Rectangle, UIBase};
+use super::{captures_keyboard, focusable, Editor, EditorOpts, Focus, Text, UIExt};
+use crate::{background_color, font_size, layout::*, text, use_interval_deps, use_theme, Rectangle, UIBase};
 
 #[element_component]
 pub fn TextInput(
@@ -27,12 +27,13 @@ pub fn TextInput(
 ) -> Element {
     let (self_id, set_self_id) = hooks.use_state(EntityId::null());
     let (focus, set_focus) = hooks.consume_context::<Focus>().expect("No FocusRoot available");
-    let focused = focus == Focus(Some(self_id));
+    let focused = focus.is(self_id);
     let (command, set_command) = hooks.use_state(false);
+    let theme = use_theme(hooks);
     hooks.use_spawn(closure!(clone set_focus, |_| {
         Box::new(move |_| {
             if focused {
-                set_focus(Focus(None));
+                set_focus(Focus::new(None));
             }
         })
     }));
@@ -84,19 +85,21 @@ pub fn TextInput(
         }
     });
     let el = if value.is_empty() && !focused && placeholder.is_some() {
-        Text.el().set(text(), placeholder.unwrap()).set(color(), vec4(1., 1., 1., 0.2))
+        Text.el().set(text(), placeholder.unwrap()).set(color(), theme.palette.text_muted.into())
     } else {
-        Text.el().set(text(), if password { value.chars().map(|_| '*').collect() } else { value }).set(color(), vec4(0.9, 0.9, 0.9, 1.))
+        Text.el()
+            .set(text(), if password { value.chars().map(|_| '*').collect() } else { value })
+            .set(color(), theme.text_input.text.into())
     }
+    .set(font_size(), theme.typography.body)
     .init(layout(), Layout::Flow)
     .set(fit_horizontal(), Fit::None)
     .set(fit_vertical(), Fit::None)
-    .set(min_width(), 3.)
-    .set(min_height(), 13.)
-    .on_spawned(move |_, id| set_self_id(id))
+    .set(min_width(), theme.text_input.min_width)
+    .set(min_height(), theme.text_input.min_height)
     .with_clickarea()
     .on_mouse_up(move |_, id, _| {
-        set_focus(Focus(Some(id)));
+        set_focus(Focus::new(Some(id)));
     })
     .on_mouse_enter(|world, _| {
         world.resource(window_ctl()).send(WindowCtl::SetCursorIcon(CursorIcon::Text)).ok();
@@ -104,7 +107,10 @@ pub fn TextInput(
     .on_mouse_leave(|world, _| {
         world.resource(window_ctl()).send(WindowCtl::SetCursorIcon(CursorIcon::Default)).ok();
     })
-    .el();
+    .el()
+    .init_default(focusable())
+    .init_default(captures_keyboard())
+    .on_spawned(move |_, id| set_self_id(id));
 
     if focused {
         el.set(align_horizontal(), Align::End).children(vec![Cursor.el()])
@@ -144,9 +150,15 @@ impl Editor for String {
 #[element_component]
 pub fn Cursor(hooks: &mut Hooks) -> Element {
     let (show, set_show) = hooks.use_state(true);
+    let theme = use_theme(hooks);
     use_interval_deps(hooks, Duration::from_millis(500), false, show, move |show| set_show(!show));
     if show {
-        UIBase.el().children(vec![Rectangle.el().set(width(), 2.).set(height(), 13.).set(translation(), vec3(1., 0., 0.))])
+        UIBase.el().children(vec![Rectangle
+            .el()
+            .set(width(), 2.)
+            .set(height(), theme.text_input.min_height)
+            .set(translation(), vec3(1., 0., 0.))
+            .set(background_color(), theme.text_input.cursor.into())])
     } else {
         Element::new()
     }
@@ -0,0 +1,516 @@
+use ambient_core::{asset_cache, runtime};
+use ambient_element::{element_component, Element, ElementComponentExt, Hooks, Setter};
+use ambient_std::{asset_cache::AssetCache, asset_url::AbsAssetUrl, color::Color};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::{
+    app_background_color, cutout_color, error_color, layout::Borders, primary_color, secondary_color, tooltip_background_color,
+    SMALL_ROUNDING, STREET,
+};
+
+/// The look of the `ambient_ui` widgets: colors, font sizes, spacing, corner radii and the variants of each widget.
+///
+/// Widgets read it with [use_theme]; it's provided by a [ThemeProvider] or [ThemeFromUrl], and without one the widgets use
+/// [Theme::default]. Themes are usually loaded from JSON, where colors are hex strings like `"#DE0B5D"`. Any missing field
+/// of the widget variants is derived from the palette, spacing and radii of the same theme, and the others keep their default value.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Theme {
+    pub palette: Palette,
+    pub typography: Typography,
+    pub spacing: Spacing,
+    pub radii: Radii,
+    pub button: ButtonTheme,
+    pub text_input: TextInputTheme,
+    pub slider: SliderTheme,
+    pub dropdown: DropdownTheme,
+    pub focus: FocusTheme,
+}
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new(Palette::default(), Spacing::default(), Radii::default())
+    }
+}
+impl<'de> Deserialize<'de> for Theme {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Default, Deserialize)]
+        #[serde(default)]
+        struct Base {
+            palette: Palette,
+            typography: Typography,
+            spacing: Spacing,
+            radii: Radii,
+        }
+        #[derive(Deserialize)]
+        struct Widgets {
+            button: ButtonTheme,
+            text_input: TextInputTheme,
+            slider: SliderTheme,
+            dropdown: DropdownTheme,
+            focus: FocusTheme,
+        }
+        fn merge(base: &mut Value, overrides: Value) {
+            match (base, overrides) {
+                (Value::Object(base), Value::Object(overrides)) => {
+                    for (key, value) in overrides {
+                        match base.get_mut(&key) {
+                            Some(base) => merge(base, value),
+                            None => {
+                                base.insert(key, value);
+                            }
+                        }
+                    }
+                }
+                (base, overrides) => *base = overrides,
+            }
+        }
+
+        // The given widget variants are laid over the ones derived from the given palette, spacing and radii
+        let overrides = Value::deserialize(deserializer)?;
+        let Base { palette, typography, spacing, radii } = Base::deserialize(&overrides).map_err(D::Error::custom)?;
+        let mut widgets = serde_json::to_value(Theme::new(palette.clone(), spacing.clone(), radii.clone())).map_err(D::Error::custom)?;
+        merge(&mut widgets, overrides);
+        let Widgets { button, text_input, slider, dropdown, focus } = Widgets::deserialize(widgets).map_err(D::Error::custom)?;
+        Ok(Self { palette, typography, spacing, radii, button, text_input, slider, dropdown, focus })
+    }
+}
+impl Theme {
+    /// A theme with the variants of the widgets derived from `palette`, `spacing` and `radii`
+    pub fn new(palette: Palette, spacing: Spacing, radii: Radii) -> Self {
+        Self {
+            button: ButtonTheme::new(&palette, &radii),
+            text_input: TextInputTheme::new(&palette),
+            slider: SliderTheme::new(&palette),
+            dropdown: DropdownTheme::new(&palette, &spacing, &radii),
+            focus: FocusTheme::new(&palette),
+            palette,
+            typography: Typography::default(),
+            spacing,
+            radii,
+        }
+    }
+    /// Loads a theme from a JSON asset
+    pub async fn load(url: &AbsAssetUrl, assets: &AssetCache) -> anyhow::Result<Self> {
+        url.download_json(assets).await
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Palette {
+    #[serde(with = "hex_color")]
+    pub primary: Color,
+    #[serde(with = "hex_color")]
+    pub secondary: Color,
+    #[serde(with = "hex_color")]
+    pub app_background: Color,
+    #[serde(with = "hex_color")]
+    pub error: Color,
+    /// A color slightly darker than the app_background
+    #[serde(with = "hex_color")]
+    pub cutout: Color,
+    #[serde(with = "hex_color")]
+    pub tooltip_background: Color,
+    /// The text of the `TextInput`
+    #[serde(with = "hex_color")]
+    pub text: Color,
+    /// Text which is less important, such as placeholders
+    #[serde(with = "hex_color")]
+    pub text_muted: Color,
+}
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            primary: primary_color(),
+            secondary: secondary_color(),
+            app_background: app_background_color(),
+            error: error_color(),
+            cutout: cutout_color(),
+            tooltip_background: tooltip_background_color(),
+            text: Color::rgba(0.9, 0.9, 0.9, 1.),
+            text_muted: Color::rgba(1., 1., 1., 0.2),
+        }
+    }
+}
+
+/// Font sizes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Typography {
+    /// Titles, see `StylesExt::header_style`
+    pub heading: f32,
+    /// See `StylesExt::section_style`
+    pub section: f32,
+    /// The text of widgets such as the `TextInput`
+    pub body: f32,
+    /// See `StylesExt::small_style`
+    pub small: f32,
+}
+impl Default for Typography {
+    fn default() -> Self {
+        Self { heading: 25., section: 16., body: 12., small: 10. }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Spacing {
+    /// The default margin and padding, and space between items
+    pub street: f32,
+    pub small: f32,
+}
+impl Default for Spacing {
+    fn default() -> Self {
+        Self { street: STREET, small: 5. }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Radii {
+    /// The flat, card and inline buttons
+    pub small: f32,
+    /// The dropdowns and tooltips
+    pub panel: f32,
+}
+impl Default for Radii {
+    fn default() -> Self {
+        Self { small: SMALL_ROUNDING, panel: SMALL_ROUNDING }
+    }
+}
+
+/// A color for each state of a widget. When several states apply, the first one in the order of the fields wins
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StateColors {
+    #[serde(with = "hex_color")]
+    pub pressed: Color,
+    #[serde(with = "hex_color")]
+    pub disabled: Color,
+    #[serde(with = "hex_color")]
+    pub toggled: Color,
+    #[serde(with = "hex_color")]
+    pub hover: Color,
+    #[serde(with = "hex_color")]
+    pub normal: Color,
+}
+impl StateColors {
+    pub fn even(color: Color) -> Self {
+        Self { pressed: color, disabled: color, toggled: color, hover: color, normal: color }
+    }
+    pub fn get(&self, pressed: bool, disabled: bool, toggled: bool, hover: bool) -> Color {
+        if pressed {
+            self.pressed
+        } else if disabled {
+            self.disabled
+        } else if toggled {
+            self.toggled
+        } else if hover {
+            self.hover
+        } else {
+            self.normal
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ButtonVariant {
+    pub background: StateColors,
+    /// The color of the content; the content keeps its own color if this is None
+    pub text: Option<StateColors>,
+    pub padding_vertical: f32,
+    pub padding_horizontal: f32,
+    pub corner_radius: f32,
+    pub min_height: f32,
+}
+impl Default for ButtonVariant {
+    fn default() -> Self {
+        ButtonTheme::default().regular
+    }
+}
+impl ButtonVariant {
+    pub fn padding(&self) -> Borders {
+        Borders::rect(self.padding_vertical, self.padding_horizontal)
+    }
+}
+
+/// The variants of the `Button`, one for each `ButtonStyle`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ButtonTheme {
+    pub regular: ButtonVariant,
+    pub primary: ButtonVariant,
+    pub flat: ButtonVariant,
+    pub card: ButtonVariant,
+    pub inline: ButtonVariant,
+}
+impl ButtonTheme {
+    fn new(palette: &Palette, radii: &Radii) -> Self {
+        let cutout = palette.cutout;
+        let secondary = palette.secondary;
+        let text = |toggled| StateColors {
+            pressed: Color::WHITE,
+            disabled: Color::rgba(0.3, 0.3, 0.3, 1.),
+            toggled,
+            hover: Color::rgba(0.8, 0.8, 0.8, 1.),
+            normal: Color::hex("B3B3B3").unwrap(),
+        };
+        let regular = ButtonVariant {
+            background: StateColors {
+                pressed: cutout.lighten(0.1),
+                disabled: cutout,
+                toggled: palette.primary,
+                hover: cutout,
+                normal: cutout,
+            },
+            text: Some(text(Color::WHITE)),
+            padding_vertical: 3.,
+            padding_horizontal: 16.,
+            corner_radius: 26. / 2.,
+            min_height: 26.,
+        };
+        let flat = ButtonVariant {
+            background: StateColors::even(Color::TRANSPARENT),
+            text: Some(text(palette.primary)),
+            padding_vertical: 3.,
+            padding_horizontal: 3.,
+            corner_radius: radii.small,
+            min_height: 0.,
+        };
+        Self {
+            primary: ButtonVariant {
+                background: StateColors {
+                    pressed: secondary.lighten(0.2),
+                    disabled: secondary.desaturate(-1.),
+                    toggled: secondary,
+                    hover: secondary.lighten(0.1),
+                    normal: secondary,
+                },
+                text: Some(StateColors { disabled: Color::BLACK.lighten(0.3), ..StateColors::even(Color::BLACK) }),
+                ..regular.clone()
+            },
+            card: ButtonVariant {
+                background: StateColors { hover: cutout.lighten(0.05), ..regular.background },
+                text: None,
+                padding_vertical: 3.,
+                padding_horizontal: 3.,
+                corner_radius: radii.small,
+                ..regular.clone()
+            },
+            inline: flat.clone(),
+            regular,
+            flat,
+        }
+    }
+}
+impl Default for ButtonTheme {
+    fn default() -> Self {
+        Self::new(&Palette::default(), &Radii::default())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TextInputTheme {
+    /// The color of the text; the placeholder uses [Palette::text_muted]
+    #[serde(with = "hex_color")]
+    pub text: Color,
+    #[serde(with = "hex_color")]
+    pub cursor: Color,
+    pub min_width: f32,
+    pub min_height: f32,
+}
+impl TextInputTheme {
+    fn new(palette: &Palette) -> Self {
+        Self { text: palette.text, cursor: Color::WHITE, min_width: 3., min_height: 13. }
+    }
+}
+impl Default for TextInputTheme {
+    fn default() -> Self {
+        Self::new(&Palette::default())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SliderTheme {
+    #[serde(with = "hex_color")]
+    pub track: Color,
+    #[serde(with = "hex_color")]
+    pub thumb: Color,
+    pub track_height: f32,
+    pub thumb_width: f32,
+    pub thumb_height: f32,
+}
+impl SliderTheme {
+    fn new(palette: &Palette) -> Self {
+        Self { track: palette.primary, thumb: palette.primary, track_height: 2., thumb_width: 12., thumb_height: 12. }
+    }
+}
+impl Default for SliderTheme {
+    fn default() -> Self {
+        Self::new(&Palette::default())
+    }
+}
+
+/// Used by the `DropdownSelect` and the `Tooltip`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DropdownTheme {
+    #[serde(with = "hex_color")]
+    pub background: Color,
+    pub padding: f32,
+    pub corner_radius: f32,
+    /// The space between the items of a `DropdownSelect`
+    pub space_between_items: f32,
+}
+impl DropdownTheme {
+    fn new(palette: &Palette, spacing: &Spacing, radii: &Radii) -> Self {
+        Self {
+            background: palette.tooltip_background,
+            padding: spacing.street,
+            corner_radius: radii.panel,
+            space_between_items: spacing.street,
+        }
+    }
+}
+impl Default for DropdownTheme {
+    fn default() -> Self {
+        Self::new(&Palette::default(), &Spacing::default(), &Radii::default())
+    }
+}
+
+/// The outline of a widget focused with the keyboard or a gamepad
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FocusTheme {
+    #[serde(with = "hex_color")]
+    pub color: Color,
+    pub thickness: f32,
+}
+impl FocusTheme {
+    fn new(palette: &Palette) -> Self {
+        Self { color: palette.secondary, thickness: 2. }
+    }
+}
+impl Default for FocusTheme {
+    fn default() -> Self {
+        Self::new(&Palette::default())
+    }
+}
+
+/// The theme provided by the closest [ThemeProvider], or the default theme if there's none
+pub fn use_theme(hooks: &mut Hooks) -> Theme {
+    hooks.consume_context::<Theme>().map(|(theme, _)| theme).unwrap_or_default()
+}
+
+/// Changes the theme of the closest [ThemeProvider] at runtime, e.g. for a settings page. None if there's no provider
+pub fn use_set_theme(hooks: &mut Hooks) -> Option<Setter<Theme>> {
+    hooks.consume_context::<Theme>().map(|(_, set_theme)| set_theme)
+}
+
+/// Provides `theme` to the widgets in `items`. The theme can be swapped at runtime by changing `theme`, or with [use_set_theme]
+#[element_component]
+pub fn ThemeProvider(hooks: &mut Hooks, theme: Theme, items: Vec<Element>) -> Element {
+    let set_theme = hooks.provide_context({
+        let theme = theme.clone();
+        || theme
+    });
+    hooks.use_effect(theme.clone(), move |_, theme| {
+        set_theme(theme.clone());
+        Box::new(|_| {})
+    });
+    Element::new().children(items)
+}
+
+/// A [ThemeProvider] with the theme loaded from a JSON asset. The default theme is used until it's loaded, or if it fails to load
+#[element_component]
+pub fn ThemeFromUrl(hooks: &mut Hooks, url: String, items: Vec<Element>) -> Element {
+    let (theme, set_theme) = hooks.use_state(None);
+    hooks.use_effect(url.clone(), move |world, url| {
+        let assets = world.resource(asset_cache()).clone();
+        let url = url.clone();
+        let task = world.resource(runtime()).spawn(async move {
+            let theme = match AbsAssetUrl::parse(&url) {
+                Ok(abs_url) => Theme::load(&abs_url, &assets).await,
+                Err(err) => Err(err),
+            };
+            match theme {
+                Ok(theme) => set_theme(Some(theme)),
+                Err(err) => log::error!("Failed to load the theme {url}: {err:?}"),
+            }
+        });
+        Box::new(move |_| task.abort())
+    });
+    ThemeProvider { theme: theme.unwrap_or_default(), items }.el()
+}
+
+/// Serializes colors as hex strings, like `"#DE0B5D"`, or `"#DE0B5D80"` when they aren't opaque
+mod hex_color {
+    use ambient_std::color::Color;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        let [r, g, b, a] = color.as_rgba_f32().map(|c| (c.clamp(0., 1.) * 255.).round() as u8);
+        if a == 255 {
+            serializer.serialize_str(&format!("#{r:02X}{g:02X}{b:02X}"))
+        } else {
+            serializer.serialize_str(&format!("#{r:02X}{g:02X}{b:02X}{a:02X}"))
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        Color::hex(hex.trim_start_matches('#')).map_err(|err| D::Error::custom(format!("Invalid color {hex:?}: {err}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_colors() {
+        let palette: Palette = serde_json::from_str(r##"{ "primary": "#FF8000", "secondary": "00FF0080" }"##).unwrap();
+        assert_eq!(palette.primary.as_rgba_f32(), [1., 128. / 255., 0., 1.]);
+        assert_eq!(palette.secondary.as_rgba_f32(), [0., 1., 0., 128. / 255.]);
+
+        let json = serde_json::to_value(&palette).unwrap();
+        assert_eq!(json["primary"], "#FF8000");
+        assert_eq!(json["secondary"], "#00FF0080");
+
+        assert!(serde_json::from_str::<Palette>(r##"{ "primary": "#FF80" }"##).is_ok());
+        assert!(serde_json::from_str::<Palette>(r##"{ "primary": "#FF800" }"##).is_err());
+        assert!(serde_json::from_str::<Palette>(r##"{ "primary": "#GG8000" }"##).is_err());
+    }
+
+    #[test]
+    fn partial_json() {
+        let red = Color::hex("FF0000").unwrap();
+        let theme: Theme = serde_json::from_str(
+            r##"{
+                "palette": { "primary": "#FF0000" },
+                "radii": { "small": 6 },
+                "typography": { "heading": 30 },
+                "button": { "flat": { "min_height": 10 } }
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(theme.palette, Palette { primary: red, ..Palette::default() });
+        assert_eq!(theme.typography, Typography { heading: 30., ..Typography::default() });
+        assert_eq!(theme.spacing, Spacing::default());
+        assert_eq!(theme.radii, Radii { small: 6., ..Radii::default() });
+
+        // The variants which aren't given are derived from the palette and radii
+        assert_eq!(theme.button.regular.background.toggled, red);
+        assert_eq!(theme.slider.track, red);
+        assert_eq!(theme.button.card.corner_radius, 6.);
+        assert_eq!(theme.button.flat.min_height, 10.);
+        assert_eq!(theme.button.flat.corner_radius, 6.);
+        assert_eq!(theme.button.flat.text.unwrap().toggled, red);
+
+        let default = serde_json::to_value(Theme::default()).unwrap();
+        let empty = serde_json::to_value(serde_json::from_str::<Theme>("{}").unwrap()).unwrap();
+        assert_eq!(empty, default);
+    }
+}
@@ -7,8 +7,9 @@ use ambient_guest_bridge::components::{
     transform::{local_to_parent, translation},
     ui::{
         align_horizontal_begin, align_horizontal_center, align_vertical_begin, align_vertical_center, fit_horizontal_children,
-        fit_horizontal_none, fit_vertical_children, fit_vertical_none, height, is_book_file, layout_bookcase, layout_dock, layout_flow,
-        orientation_horizontal, orientation_vertical, width,
+        fit_horizontal_none, fit_vertical_children, fit_vertical_none, grid_column_gap, grid_row_gap, grid_template_columns,
+        grid_template_rows, height, is_book_file, layout_bookcase, layout_dock, layout_flow, layout_grid, layout_wrap,
+        orientation_horizontal, orientation_vertical, space_between_items, width,
     },
 };
 use cb::Cb;
@@ -146,6 +147,31 @@ pub fn FixedGrid(_: &mut Hooks, items: Vec<Element>, item_stride: Vec2, items_ho
     )
 }
 
+/// A grid with the given `columns` and `rows`, e.g. `"100 1fr auto"`; see `grid_template_columns`.
+/// The items are placed in the free cells in order, unless they have a `grid_column` and `grid_row`
+#[element_component]
+pub fn Grid(_: &mut Hooks, items: Vec<Element>, columns: String, rows: String, gap: Vec2) -> Element {
+    Element::from(UIBase)
+        .init_default(layout_grid())
+        .set(grid_template_columns(), columns)
+        .set(grid_template_rows(), rows)
+        .set(grid_column_gap(), gap.x)
+        .set(grid_row_gap(), gap.y)
+        .init_default(children())
+        .children(items)
+}
+
+/// A row which wraps onto new lines when it's full. Items with a `flex_grow` or `flex_shrink` fill each line
+#[element_component]
+pub fn Wrap(_: &mut Hooks, items: Vec<Element>, space_between: f32) -> Element {
+    Element::from(UIBase)
+        .init_default(layout_wrap())
+        .set_default(orientation_horizontal())
+        .set(space_between_items(), space_between)
+        .init_default(children())
+        .children(items)
+}
+
 #[element_component]
 pub fn MeasureSize(hooks: &mut Hooks, inner: Element, on_change: Cb<dyn Fn(Vec2) + Sync + Send + 'static>) -> Element {
     let (id, set_id) = hooks.use_state(None);
@@ -4,7 +4,7 @@ use ambient_guest_bridge::{
     components::{
         app::{name, ui_scene},
         rendering::color,
-        ui::{font_size, text},
+        ui::{font_size, rich_text, text, text_args, text_key},
     },
     components::{
         transform::mesh_to_local,
@@ -33,6 +33,15 @@ impl Text {
     pub fn el(value: impl Into<String>) -> Element {
         Text.el().set(text(), value.into())
     }
+    /// A text with rich text `markup`, e.g. `Text::rich("[b]Critical hit![/b] [color=#ff4040]120[/color] damage")`
+    pub fn rich(markup: impl Into<String>) -> Element {
+        Text.el().set(text(), markup.into()).set(rich_text(), ())
+    }
+    /// A text showing the localized string `key` in the active locale, formatted with `args`, e.g.
+    /// `Text::localized("inventory.items", &[("count", &3)])`
+    pub fn localized(key: impl Into<String>, args: &[(&str, &dyn std::fmt::Display)]) -> Element {
+        Text.el().set(text_key(), key.into()).set(text_args(), args.iter().map(|(name, value)| format!("{name}={value}")).collect())
+    }
 }
 impl From<&str> for UIElement {
     fn from(value: &str) -> Self {
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use ambient_ecs::{with_component_registry, ComponentSet, QueryEvent, World};
+use ambient_network::debug_draw::DebugShape;
 use ambient_physics::{helpers::PhysicsObjectCollection, physx::character_controller};
 use itertools::Itertools;
 use parking_lot::RwLock;
@@ -194,6 +195,10 @@ impl host::Host for Bindings {
         shared_impl::entity::get_all(&mut self.world_mut(), index).into_bindgen()
     }
 
+    fn entity_get_related(&mut self, target: host::EntityId, index: u32) -> Vec<host::EntityId> {
+        shared_impl::entity::get_related(&mut self.world_mut(), target.from_bindgen(), index).into_bindgen()
+    }
+
     fn entity_query(&mut self, query: host::Query, query_event: host::QueryEvent) -> u64 {
         shared_impl::entity::query(
             &mut self.shared_state.write().base_mut().query_states,
@@ -201,6 +206,9 @@ impl host::Host for Bindings {
             query.include.iter().map(|v| v.get()),
             query.exclude.iter().map(|v| v.get()),
             query.changed.iter().map(|v| v.get()),
+            query
+                .related_to
+                .map(|(index, target)| (index, target.from_bindgen())),
             match query_event {
                 host::QueryEvent::Frame => QueryEvent::Frame,
                 host::QueryEvent::Spawn => QueryEvent::Spawned,
@@ -219,7 +227,7 @@ impl host::Host for Bindings {
         let (result, query_state) = {
             let lock = shared_state.read();
             let base = lock.base();
-            let (query, query_state, primitive_components) =
+            let (query, query_state, primitive_components, related_to) =
                 base.query_states.get(key).expect("no query state for key");
 
             let mut query_state = query_state.clone();
@@ -228,6 +236,10 @@ impl host::Host for Bindings {
             (
                 query
                     .iter(world, Some(&mut query_state))
+                    .filter(|ea| match related_to {
+                        Some((relationship, target)) => ea.get(world, *relationship) == target,
+                        None => true,
+                    })
                     .map(|ea| {
                         (
                             ea.id().into_bindgen(),
@@ -280,6 +292,22 @@ impl host::Host for Bindings {
         server_impl::player::get_prev_raw_input(&self.world(), player.from_bindgen()).into_bindgen()
     }
 
+    fn player_get_actions(&mut self, player: host::EntityId) -> Vec<(String, host::Vec2)> {
+        server_impl::player::get_actions(&self.world(), player.from_bindgen())
+            .0
+            .into_iter()
+            .map(|(name, value)| (name, value.into_bindgen()))
+            .collect()
+    }
+
+    fn player_get_prev_actions(&mut self, player: host::EntityId) -> Vec<(String, host::Vec2)> {
+        server_impl::player::get_prev_actions(&self.world(), player.from_bindgen())
+            .0
+            .into_iter()
+            .map(|(name, value)| (name, value.into_bindgen()))
+            .collect()
+    }
+
     fn physics_apply_force(&mut self, entities: &[Le<host::EntityId>], force: host::Vec3) {
         let collection = PhysicsObjectCollection::from_entities(
             &self.world(),
@@ -401,6 +429,22 @@ impl host::Host for Bindings {
         )
     }
 
+    fn message_send(
+        &mut self,
+        target: Option<host::EntityId>,
+        name: &str,
+        data: ComponentsParam<'_>,
+        reliable: bool,
+    ) {
+        server_impl::message::send(
+            &self.world(),
+            target.map(|id| id.from_bindgen()),
+            name,
+            convert_components_to_entity_data(data),
+            reliable,
+        )
+    }
+
     fn asset_url(&mut self, path: &str) -> Option<String> {
         let base_url = ServerBaseUrlKey.get(self.world().resource(asset_cache()));
         AssetUrl::parse(path)
@@ -409,4 +453,169 @@ impl host::Host for Bindings {
             .ok()
             .map(|x| x.to_string())
     }
+
+    fn instance_current(&mut self) -> String {
+        server_impl::instance::current(&self.world())
+    }
+
+    fn instance_list(&mut self) -> Vec<(String, u32)> {
+        server_impl::instance::list(&self.world())
+    }
+
+    fn instance_create(&mut self, empty: bool) -> String {
+        server_impl::instance::create(&mut self.world_mut(), empty)
+    }
+
+    fn instance_move_player(&mut self, player: host::EntityId, instance: &str) -> bool {
+        server_impl::instance::move_player(&mut self.world_mut(), player.from_bindgen(), instance)
+    }
+
+    fn instance_remove_if_empty(&mut self, instance: &str) {
+        server_impl::instance::remove_if_empty(&mut self.world_mut(), instance)
+    }
+
+    fn storage_get(&mut self, key: &str) -> Option<Vec<u8>> {
+        server_impl::storage::get(&self.world(), key)
+    }
+
+    fn storage_set(&mut self, key: &str, value: &[u8]) -> Option<String> {
+        server_impl::storage::set(&self.world(), key, value.to_vec())
+    }
+
+    fn storage_set_entity(&mut self, key: &str, data: ComponentsParam<'_>) -> Option<String> {
+        server_impl::storage::set_entity(
+            &self.world(),
+            key,
+            convert_components_to_entity_data(data),
+        )
+    }
+
+    fn storage_load_entity(&mut self, key: &str, entity: host::EntityId) -> bool {
+        server_impl::storage::load_entity(&mut self.world_mut(), key, entity.from_bindgen())
+    }
+
+    fn storage_delete(&mut self, key: &str) -> bool {
+        server_impl::storage::delete(&self.world(), key)
+    }
+
+    fn storage_list(&mut self, prefix: &str) -> Vec<String> {
+        server_impl::storage::list(&self.world(), prefix)
+    }
+
+    fn storage_is_flushed(&mut self) -> bool {
+        server_impl::storage::is_flushed(&self.world())
+    }
+
+    fn storage_write_error(&mut self) -> Option<String> {
+        server_impl::storage::write_error(&self.world())
+    }
+
+    fn persistence_save(&mut self) {
+        ambient_network::persistence::request_world_save(&mut self.world_mut())
+    }
+
+    fn debug_draw_line(
+        &mut self,
+        category: &str,
+        start: host::Vec3,
+        end: host::Vec3,
+        color: host::Vec3,
+        lifetime: f32,
+    ) {
+        let shape = DebugShape::Line {
+            start: start.from_bindgen(),
+            end: end.from_bindgen(),
+        };
+        server_impl::debug::draw(
+            &mut self.world_mut(),
+            category,
+            shape,
+            color.from_bindgen(),
+            lifetime,
+        )
+    }
+
+    fn debug_draw_arrow(
+        &mut self,
+        category: &str,
+        start: host::Vec3,
+        end: host::Vec3,
+        color: host::Vec3,
+        lifetime: f32,
+    ) {
+        let shape = DebugShape::Arrow {
+            start: start.from_bindgen(),
+            end: end.from_bindgen(),
+        };
+        server_impl::debug::draw(
+            &mut self.world_mut(),
+            category,
+            shape,
+            color.from_bindgen(),
+            lifetime,
+        )
+    }
+
+    fn debug_draw_sphere(
+        &mut self,
+        category: &str,
+        center: host::Vec3,
+        radius: f32,
+        color: host::Vec3,
+        lifetime: f32,
+    ) {
+        let shape = DebugShape::Sphere {
+            center: center.from_bindgen(),
+            radius,
+        };
+        server_impl::debug::draw(
+            &mut self.world_mut(),
+            category,
+            shape,
+            color.from_bindgen(),
+            lifetime,
+        )
+    }
+
+    fn debug_draw_box(
+        &mut self,
+        category: &str,
+        center: host::Vec3,
+        half_extents: host::Vec3,
+        color: host::Vec3,
+        lifetime: f32,
+    ) {
+        let shape = DebugShape::Box {
+            center: center.from_bindgen(),
+            half_extents: half_extents.from_bindgen(),
+        };
+        server_impl::debug::draw(
+            &mut self.world_mut(),
+            category,
+            shape,
+            color.from_bindgen(),
+            lifetime,
+        )
+    }
+
+    fn debug_draw_text(
+        &mut self,
+        category: &str,
+        position: host::Vec3,
+        text: &str,
+        color: host::Vec3,
+        lifetime: f32,
+    ) {
+        let shape = DebugShape::Text {
+            position: position.from_bindgen(),
+            text: text.to_string(),
+        };
+        server_impl::debug::draw(
+            &mut self.world_mut(),
+            category,
+            shape,
+            color.from_bindgen(),
+            lifetime,
+        )
+    }
 }
@@ -0,0 +1,15 @@
+use ambient_ecs::World;
+use ambient_network::debug_draw::{debug_draw, DebugDraw, DebugShape};
+use glam::Vec3;
+
+pub fn draw(world: &mut World, category: &str, shape: DebugShape, color: Vec3, lifetime: f32) {
+    debug_draw(
+        world,
+        DebugDraw {
+            category: category.to_string(),
+            shape,
+            color,
+            lifetime,
+        },
+    )
+}
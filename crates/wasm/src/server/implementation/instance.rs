@@ -0,0 +1,148 @@
+use ambient_core::player::user_id;
+use ambient_ecs::{EntityId, World};
+use ambient_network::server::{
+    request_world_instance_change, world_instance_id, world_instance_requests, world_instances,
+    WorldInstanceRequest, MAIN_INSTANCE_ID,
+};
+use ambient_std::friendly_id;
+
+pub fn current(world: &World) -> String {
+    world
+        .resource_opt(world_instance_id())
+        .cloned()
+        .unwrap_or_else(|| MAIN_INSTANCE_ID.to_string())
+}
+
+pub fn list(world: &World) -> Vec<(String, u32)> {
+    world
+        .resource_opt(world_instances())
+        .map(|info| {
+            info.instances
+                .iter()
+                .map(|(id, instance)| (id.clone(), instance.n_players))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn create(world: &mut World, empty: bool) -> String {
+    let id = friendly_id();
+    let from = current(world);
+    request_world_instance_change(
+        world,
+        WorldInstanceRequest::Create {
+            from,
+            id: id.clone(),
+            empty,
+        },
+    );
+    id
+}
+
+/// Whether the instance `instance_id` exists, or will be created at the end of this frame
+fn exists(world: &World, instance_id: &str) -> bool {
+    current(world) == instance_id
+        || world
+            .resource_opt(world_instances())
+            .map_or(false, |info| info.instances.contains_key(instance_id))
+        || world
+            .resource_opt(world_instance_requests())
+            .map_or(false, |requests| {
+                requests.iter().any(|request| {
+                    matches!(request, WorldInstanceRequest::Create { id, .. } if id == instance_id)
+                })
+            })
+}
+
+pub fn move_player(world: &mut World, player: EntityId, instance_id: &str) -> bool {
+    let user_id = match world.get_cloned(player, user_id()) {
+        Ok(user_id) => user_id,
+        Err(_) => return false,
+    };
+    if !exists(world, instance_id) {
+        return false;
+    }
+    request_world_instance_change(
+        world,
+        WorldInstanceRequest::MovePlayer {
+            user_id,
+            instance_id: instance_id.to_string(),
+        },
+    );
+    true
+}
+
+pub fn remove_if_empty(world: &mut World, instance_id: &str) {
+    request_world_instance_change(
+        world,
+        WorldInstanceRequest::RemoveIfEmpty {
+            instance_id: instance_id.to_string(),
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use ambient_ecs::Entity;
+    use ambient_network::rpc::{InstanceInfo, InstancesInfo};
+
+    use super::*;
+
+    fn world_with_player() -> (World, EntityId) {
+        ambient_core::init_all_components();
+        ambient_network::init_all_components();
+        let mut world = World::new("instance_test");
+        world.add_resource(
+            world_instances(),
+            InstancesInfo {
+                instances: [(MAIN_INSTANCE_ID.to_string(), InstanceInfo { n_players: 1 })]
+                    .into_iter()
+                    .collect(),
+            },
+        );
+        let player = Entity::new()
+            .with(user_id(), "user".to_string())
+            .spawn(&mut world);
+        (world, player)
+    }
+
+    fn requests(world: &World) -> Vec<WorldInstanceRequest> {
+        world
+            .resource_opt(world_instance_requests())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn create_instance_and_move_player() {
+        let (mut world, player) = world_with_player();
+        assert_eq!(current(&world), MAIN_INSTANCE_ID);
+        assert_eq!(list(&world), vec![(MAIN_INSTANCE_ID.to_string(), 1)]);
+
+        let id = create(&mut world, true);
+        assert!(matches!(
+            &requests(&world)[..],
+            [WorldInstanceRequest::Create { from, id: created, empty: true }]
+                if from == MAIN_INSTANCE_ID && created == &id
+        ));
+
+        // The instance is created at the end of the frame, but players can be moved into it already
+        assert!(move_player(&mut world, player, &id));
+        assert!(matches!(
+            &requests(&world)[1..],
+            [WorldInstanceRequest::MovePlayer { user_id, instance_id }]
+                if user_id == "user" && instance_id == &id
+        ));
+        assert!(move_player(&mut world, player, MAIN_INSTANCE_ID));
+        assert_eq!(requests(&world).len(), 3);
+    }
+
+    #[test]
+    fn move_player_to_missing_instance() {
+        let (mut world, player) = world_with_player();
+        assert!(!move_player(&mut world, player, "missing"));
+        let not_a_player = Entity::new().spawn(&mut world);
+        assert!(!move_player(&mut world, not_a_player, MAIN_INSTANCE_ID));
+        assert!(requests(&world).is_empty());
+    }
+}
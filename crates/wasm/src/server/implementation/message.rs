@@ -0,0 +1,18 @@
+use ambient_ecs::{Entity, EntityId, World};
+use ambient_network::messages::{broadcast_message, send_message, Message, Reliability};
+
+pub fn send(world: &World, target: Option<EntityId>, name: &str, data: Entity, reliable: bool) {
+    let message = Message::new(name, data);
+    if !message.is_allowed() {
+        return;
+    }
+    let reliability = if reliable {
+        Reliability::Reliable
+    } else {
+        Reliability::Unreliable
+    };
+    match target {
+        Some(player_id) => send_message(world, player_id, message, reliability),
+        None => broadcast_message(world, message, reliability),
+    }
+}
@@ -1,2 +1,6 @@
+pub mod debug;
+pub mod instance;
+pub mod message;
 pub mod physics;
 pub mod player;
+pub mod storage;
@@ -1,5 +1,8 @@
 use ambient_ecs::{EntityId, World};
-use ambient_input::{player_prev_raw_input, player_raw_input, PlayerRawInput};
+use ambient_input::{
+    actions::{player_actions, player_prev_actions, PlayerActions},
+    player_prev_raw_input, player_raw_input, PlayerRawInput,
+};
 
 pub fn get_raw_input(world: &World, player_id: EntityId) -> Option<PlayerRawInput> {
     world.get_cloned(player_id, player_raw_input()).ok()
@@ -8,3 +11,15 @@ pub fn get_raw_input(world: &World, player_id: EntityId) -> Option<PlayerRawInpu
 pub fn get_prev_raw_input(world: &World, player_id: EntityId) -> Option<PlayerRawInput> {
     world.get_cloned(player_id, player_prev_raw_input()).ok()
 }
+
+pub fn get_actions(world: &World, player_id: EntityId) -> PlayerActions {
+    world
+        .get_cloned(player_id, player_actions())
+        .unwrap_or_default()
+}
+
+pub fn get_prev_actions(world: &World, player_id: EntityId) -> PlayerActions {
+    world
+        .get_cloned(player_id, player_prev_actions())
+        .unwrap_or_default()
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use ambient_ecs::{Entity, EntityId, World};
+
+use crate::server::storage::{storage, Storage};
+
+fn get_storage(world: &World) -> Option<Arc<Storage>> {
+    world.resource_opt(storage()).cloned()
+}
+
+const NO_STORAGE: &str = "Storage is not available on this server";
+
+pub fn get(world: &World, key: &str) -> Option<Vec<u8>> {
+    get_storage(world)?.get(key).map(|value| value.to_vec())
+}
+
+/// Returns an error message if the value couldn't be stored
+pub fn set(world: &World, key: &str, value: Vec<u8>) -> Option<String> {
+    match get_storage(world) {
+        Some(storage) => storage.set(key, value).err().map(|err| err.to_string()),
+        None => Some(NO_STORAGE.to_string()),
+    }
+}
+
+/// Entities are stored as JSON, with their components identified by path so that they survive
+/// changes to the component registry. Components which aren't serializable are skipped.
+pub fn set_entity(world: &World, key: &str, data: Entity) -> Option<String> {
+    match serde_json::to_vec(&data) {
+        Ok(value) => set(world, key, value),
+        Err(err) => Some(format!("Failed to serialize entity: {err}")),
+    }
+}
+
+/// Adds the components of the entity stored at `key` to `entity`. Returns false if there is no
+/// valid entity at `key`
+pub fn load_entity(world: &mut World, key: &str, entity: EntityId) -> bool {
+    let data = match get(world, key).map(|value| serde_json::from_slice::<Entity>(&value)) {
+        Some(Ok(data)) => data,
+        Some(Err(err)) => {
+            log::warn!("Failed to deserialize the entity stored at {key:?}: {err}");
+            return false;
+        }
+        None => return false,
+    };
+    world.add_components(entity, data).is_ok()
+}
+
+pub fn delete(world: &World, key: &str) -> bool {
+    get_storage(world)
+        .and_then(|storage| storage.delete(key))
+        .is_some()
+}
+
+pub fn list(world: &World, prefix: &str) -> Vec<String> {
+    get_storage(world)
+        .map(|storage| storage.list(prefix))
+        .unwrap_or_default()
+}
+
+pub fn is_flushed(world: &World) -> bool {
+    get_storage(world)
+        .map(|storage| storage.is_flushed())
+        .unwrap_or(true)
+}
+
+/// The error of the first write which failed, after which [is_flushed] stays false
+pub fn write_error(world: &World) -> Option<String> {
+    get_storage(world)?.write_error().map(|err| err.to_string())
+}
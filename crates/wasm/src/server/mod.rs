@@ -3,7 +3,10 @@ use std::sync::Arc;
 use ambient_ecs::{
     query, Component, ComponentEntry, Entity, EntityId, FnSystem, SystemGroup, World,
 };
-use ambient_network::server::{ForkingEvent, ShutdownEvent};
+use ambient_network::{
+    persistence::world_loaded,
+    server::{ForkingEvent, ShutdownEvent},
+};
 use ambient_physics::{collider_loads, collisions, PxShapeUserData};
 use itertools::Itertools;
 use parking_lot::RwLock;
@@ -20,6 +23,7 @@ use ambient_ecs::{world_events, WorldEventReader};
 
 pub mod bindings;
 pub(crate) mod implementation;
+pub mod storage;
 
 pub const MAXIMUM_ERROR_COUNT: usize = 5;
 
@@ -82,6 +86,21 @@ pub fn systems<
                     );
                 }
             })),
+            Box::new(FnSystem::new(move |world, _| {
+                profiling::scope!("WASM module world load event");
+                // the modules are loaded on the first frame, so they are told about a restored world here
+                if world.resource_opt(world_loaded()).is_none() {
+                    return;
+                }
+                world
+                    .remove_component(world.resource_entity(), world_loaded())
+                    .unwrap();
+                run_all(
+                    world,
+                    state_component,
+                    &RunContext::new(world, "core/world_load", Entity::new()),
+                );
+            })),
             Box::new(FnSystem::new(move |world, _| {
                 profiling::scope!("WASM module frame event");
                 // trigger frame event
@@ -0,0 +1,371 @@
+use std::{
+    collections::BTreeMap,
+    io::Write as _,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use ambient_ecs::{components, Debuggable, Resource};
+use data_encoding::HEXLOWER;
+use parking_lot::Mutex;
+
+components!("wasm::storage", {
+    /// The key-value store of the guest modules of the project
+    @[Debuggable, Resource]
+    storage: Arc<Storage>,
+});
+
+/// The limits of a [Storage]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageQuota {
+    /// The maximum length of a key, in bytes
+    pub max_key_len: usize,
+    /// The maximum size of a single value, in bytes
+    pub max_value_size: usize,
+    /// The maximum size of all keys and values together, in bytes
+    pub max_total_size: usize,
+}
+impl Default for StorageQuota {
+    fn default() -> Self {
+        Self {
+            max_key_len: 100,
+            max_value_size: 1024 * 1024,
+            max_total_size: 64 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum StorageError {
+    #[error("The key is empty")]
+    EmptyKey,
+    #[error("The key is {0} bytes long, but keys can be at most {1} bytes long")]
+    KeyTooLong(usize, usize),
+    #[error("The value is {0} bytes, but values can be at most {1} bytes")]
+    ValueTooLarge(usize, usize),
+    #[error("Storing the value would use {0} bytes, but the storage quota is {1} bytes")]
+    QuotaExceeded(usize, usize),
+    #[error("Failed to write to disk: {0}")]
+    WriteFailed(String),
+}
+
+/// Sequence number of a write, which can be compared with [Storage::written] to know when it's on
+/// disk
+pub type WriteTicket = u64;
+
+enum Write {
+    Set(WriteTicket, String, Arc<[u8]>),
+    Delete(WriteTicket, String),
+}
+impl std::fmt::Debug for Write {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Write::Set(ticket, key, value) => {
+                write!(f, "Set({ticket}, {key:?}, {} bytes)", value.len())
+            }
+            Write::Delete(ticket, key) => write!(f, "Delete({ticket}, {key:?})"),
+        }
+    }
+}
+
+/// A persistent key-value store. All the values are kept in memory so that reads never wait for the
+/// disk, and every key is stored in its own file in `dir`, which is written in the background. Each
+/// file is replaced atomically, so a crash leaves every key with either its old or its new value.
+///
+/// If a write fails, the files no longer match the values in memory: [Storage::written] stops
+/// advancing and [Storage::write_error] returns the error from then on.
+#[derive(Debug)]
+pub struct Storage {
+    quota: StorageQuota,
+    state: Mutex<StorageState>,
+    writes: flume::Sender<Write>,
+    written: Arc<AtomicU64>,
+    write_error: Arc<Mutex<Option<String>>>,
+}
+#[derive(Debug, Default)]
+struct StorageState {
+    values: BTreeMap<String, Arc<[u8]>>,
+    total_size: usize,
+    last_ticket: WriteTicket,
+}
+impl Storage {
+    /// Opens the store in `dir`, creating the directory if needed
+    pub fn open(dir: impl Into<PathBuf>, quota: StorageQuota) -> anyhow::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+
+        let mut state = StorageState::default();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let key = match key_from_path(&path) {
+                Some(key) => key,
+                // Leftovers of interrupted writes, or unrelated files
+                None => continue,
+            };
+            let value: Arc<[u8]> = std::fs::read(&path)?.into();
+            state.total_size += key.len() + value.len();
+            state.values.insert(key, value);
+        }
+
+        let (writes, rx) = flume::unbounded();
+        let written = Arc::new(AtomicU64::new(0));
+        let write_error = Arc::new(Mutex::new(None));
+        std::thread::Builder::new()
+            .name("storage".to_string())
+            .spawn({
+                let written = written.clone();
+                let write_error = write_error.clone();
+                move || write_loop(dir, rx, written, write_error)
+            })?;
+
+        Ok(Self {
+            quota,
+            state: Mutex::new(state),
+            writes,
+            written,
+            write_error,
+        })
+    }
+
+    pub fn quota(&self) -> StorageQuota {
+        self.quota
+    }
+    /// The size of all the keys and values, in bytes
+    pub fn total_size(&self) -> usize {
+        self.state.lock().total_size
+    }
+
+    pub fn get(&self, key: &str) -> Option<Arc<[u8]>> {
+        self.state.lock().values.get(key).cloned()
+    }
+
+    /// Sets `key` to `value`. The new value is visible immediately, and is on disk once
+    /// [Storage::written] reaches the returned ticket
+    pub fn set(&self, key: &str, value: Vec<u8>) -> Result<WriteTicket, StorageError> {
+        self.check_key(key)?;
+        if value.len() > self.quota.max_value_size {
+            return Err(StorageError::ValueTooLarge(
+                value.len(),
+                self.quota.max_value_size,
+            ));
+        }
+
+        let mut state = self.state.lock();
+        let old_size = state
+            .values
+            .get(key)
+            .map(|old| key.len() + old.len())
+            .unwrap_or_default();
+        let total_size = state.total_size - old_size + key.len() + value.len();
+        if total_size > self.quota.max_total_size {
+            return Err(StorageError::QuotaExceeded(
+                total_size,
+                self.quota.max_total_size,
+            ));
+        }
+
+        let value: Arc<[u8]> = value.into();
+        state.total_size = total_size;
+        state.values.insert(key.to_string(), value.clone());
+        state.last_ticket += 1;
+        let ticket = state.last_ticket;
+        // The write loop only stops when the storage is dropped
+        self.writes
+            .send(Write::Set(ticket, key.to_string(), value))
+            .ok();
+        Ok(ticket)
+    }
+
+    /// Removes `key`. Returns None if it didn't exist
+    pub fn delete(&self, key: &str) -> Option<WriteTicket> {
+        let mut state = self.state.lock();
+        let old = state.values.remove(key)?;
+        state.total_size -= key.len() + old.len();
+        state.last_ticket += 1;
+        let ticket = state.last_ticket;
+        self.writes
+            .send(Write::Delete(ticket, key.to_string()))
+            .ok();
+        Some(ticket)
+    }
+
+    /// All the keys starting with `prefix`, in order
+    pub fn list(&self, prefix: &str) -> Vec<String> {
+        self.state
+            .lock()
+            .values
+            .range(prefix.to_string()..)
+            .map(|(key, _)| key)
+            .take_while(|key| key.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+
+    /// The ticket of the last write which is on disk
+    pub fn written(&self) -> WriteTicket {
+        self.written.load(Ordering::Acquire)
+    }
+    /// Whether all the writes so far are on disk
+    pub fn is_flushed(&self) -> bool {
+        self.written() >= self.state.lock().last_ticket
+    }
+    /// The error of the first write which failed, if any
+    pub fn write_error(&self) -> Option<StorageError> {
+        self.write_error
+            .lock()
+            .clone()
+            .map(StorageError::WriteFailed)
+    }
+
+    fn check_key(&self, key: &str) -> Result<(), StorageError> {
+        if key.is_empty() {
+            Err(StorageError::EmptyKey)
+        } else if key.len() > self.quota.max_key_len {
+            Err(StorageError::KeyTooLong(key.len(), self.quota.max_key_len))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Keys are hex encoded, so that they can contain any character and still be valid file names on
+/// case insensitive file systems
+fn key_path(dir: &Path, key: &str) -> PathBuf {
+    dir.join(format!("{}.bin", HEXLOWER.encode(key.as_bytes())))
+}
+fn key_from_path(path: &Path) -> Option<String> {
+    if path.extension()? != "bin" {
+        return None;
+    }
+    let name = path.file_stem()?.to_str()?;
+    String::from_utf8(HEXLOWER.decode(name.as_bytes()).ok()?).ok()
+}
+
+/// Writes a value to a temporary file which replaces the file of the key once it's synced, so that
+/// the rename can't be persisted before the data
+fn write_value(dir: &Path, key: &str, value: &[u8]) -> std::io::Result<()> {
+    let path = key_path(dir, key);
+    let tmp_path = path.with_extension("tmp");
+    let mut file = std::fs::File::create(&tmp_path)?;
+    file.write_all(value)?;
+    file.sync_all()?;
+    std::fs::rename(&tmp_path, &path)?;
+    sync_dir(dir)
+}
+
+fn delete_value(dir: &Path, key: &str) -> std::io::Result<()> {
+    match std::fs::remove_file(key_path(dir, key)) {
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        result => result.and_then(|_| sync_dir(dir)),
+    }
+}
+
+/// Persists the renames and removals in `dir`. Directories can't be opened as files on Windows,
+/// where renames are persisted with the file instead
+fn sync_dir(dir: &Path) -> std::io::Result<()> {
+    if cfg!(unix) {
+        std::fs::File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+fn write_loop(
+    dir: PathBuf,
+    rx: flume::Receiver<Write>,
+    written: Arc<AtomicU64>,
+    write_error: Arc<Mutex<Option<String>>>,
+) {
+    for write in rx.iter() {
+        let (ticket, result) = match &write {
+            Write::Set(ticket, key, value) => (*ticket, write_value(&dir, key, value)),
+            Write::Delete(ticket, key) => (*ticket, delete_value(&dir, key)),
+        };
+        let mut write_error = write_error.lock();
+        match result {
+            // Later writes are still made, but the failed one is never on disk
+            Ok(()) if write_error.is_none() => written.store(ticket, Ordering::Release),
+            Ok(()) => {}
+            Err(err) => {
+                log::error!("Failed to persist {write:?} to {dir:?}: {err}");
+                write_error.get_or_insert_with(|| err.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wait_until_written(storage: &Storage, ticket: WriteTicket) {
+        while storage.written() < ticket {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn persists_and_enforces_quota() {
+        let dir = std::env::temp_dir().join(format!("ambient_storage_{}", std::process::id()));
+        let quota = StorageQuota {
+            max_key_len: 16,
+            max_value_size: 8,
+            max_total_size: 36,
+        };
+
+        let storage = Storage::open(&dir, quota).unwrap();
+        storage.set("player/a", vec![1, 2, 3]).unwrap();
+        storage.set("player/b", vec![4]).unwrap();
+        storage.set("world", vec![5; 8]).unwrap();
+        let ticket = storage.delete("player/b").unwrap();
+        assert_eq!(storage.list("player/"), vec!["player/a".to_string()]);
+        assert_eq!(storage.total_size(), 8 + 3 + 5 + 8);
+
+        assert_eq!(storage.set("", vec![]), Err(StorageError::EmptyKey));
+        assert_eq!(
+            storage.set("a", vec![0; 9]),
+            Err(StorageError::ValueTooLarge(9, 8))
+        );
+        assert_eq!(
+            storage.set("player/c", vec![0; 8]),
+            Err(StorageError::QuotaExceeded(40, 36))
+        );
+        wait_until_written(&storage, ticket);
+        drop(storage);
+
+        let storage = Storage::open(&dir, quota).unwrap();
+        assert_eq!(storage.get("player/a").as_deref(), Some(&[1, 2, 3][..]));
+        assert_eq!(storage.get("player/b"), None);
+        assert_eq!(
+            storage.list(""),
+            vec!["player/a".to_string(), "world".to_string()]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reports_failed_writes() {
+        let dir =
+            std::env::temp_dir().join(format!("ambient_storage_failed_{}", std::process::id()));
+        let storage = Storage::open(&dir, StorageQuota::default()).unwrap();
+        let ticket = storage.set("a", vec![1]).unwrap();
+        wait_until_written(&storage, ticket);
+        assert_eq!(storage.write_error(), None);
+
+        // Writes fail once the directory is gone
+        std::fs::remove_dir_all(&dir).unwrap();
+        storage.set("b", vec![2]).unwrap();
+        while storage.write_error().is_none() {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert!(matches!(
+            storage.write_error(),
+            Some(StorageError::WriteFailed(_))
+        ));
+        assert_eq!(storage.written(), ticket);
+        assert!(!storage.is_flushed());
+    }
+}
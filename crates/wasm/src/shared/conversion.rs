@@ -313,6 +313,48 @@ impl IntoBindgen for ambient_input::PlayerRawInput {
                 .into_iter()
                 .map(|b| b.into_bindgen())
                 .collect(),
+            gamepads: self
+                .gamepads
+                .into_iter()
+                .map(|(id, gamepad)| host::Gamepad {
+                    id,
+                    buttons: gamepad
+                        .buttons
+                        .into_iter()
+                        .map(|b| b.into_bindgen())
+                        .collect(),
+                    left_stick: gamepad.left_stick.into_bindgen(),
+                    right_stick: gamepad.right_stick.into_bindgen(),
+                    left_trigger: gamepad.left_trigger,
+                    right_trigger: gamepad.right_trigger,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl IntoBindgen for ambient_input::gamepad::GamepadButton {
+    type Item = host::GamepadButton;
+
+    fn into_bindgen(self) -> Self::Item {
+        match self {
+            Self::South => Self::Item::South,
+            Self::East => Self::Item::East,
+            Self::North => Self::Item::North,
+            Self::West => Self::Item::West,
+            Self::LeftBumper => Self::Item::LeftBumper,
+            Self::RightBumper => Self::Item::RightBumper,
+            Self::LeftTrigger => Self::Item::LeftTrigger,
+            Self::RightTrigger => Self::Item::RightTrigger,
+            Self::Select => Self::Item::Select,
+            Self::Start => Self::Item::Start,
+            Self::Mode => Self::Item::Mode,
+            Self::LeftStick => Self::Item::LeftStick,
+            Self::RightStick => Self::Item::RightStick,
+            Self::DPadUp => Self::Item::DPadUp,
+            Self::DPadDown => Self::Item::DPadDown,
+            Self::DPadLeft => Self::Item::DPadLeft,
+            Self::DPadRight => Self::Item::DPadRight,
         }
     }
 }
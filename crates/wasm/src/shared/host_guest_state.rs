@@ -1,9 +1,16 @@
 use std::collections::HashSet;
 
-use ambient_ecs::{EntityId, PrimitiveComponent, Query, QueryState, World};
+use ambient_ecs::{Component, EntityId, PrimitiveComponent, Query, QueryState, World};
 
-pub type QueryStateMap =
-    slotmap::SlotMap<slotmap::DefaultKey, (Query, QueryState, Vec<PrimitiveComponent>)>;
+pub type QueryStateMap = slotmap::SlotMap<
+    slotmap::DefaultKey,
+    (
+        Query,
+        QueryState,
+        Vec<PrimitiveComponent>,
+        Option<(Component<EntityId>, EntityId)>,
+    ),
+>;
 
 #[derive(Default, Clone)]
 pub struct BaseHostGuestState {
@@ -1,6 +1,6 @@
 use crate::shared::host_guest_state::QueryStateMap;
 use ambient_animation::{animation_controller, AnimationController};
-use ambient_core::transform::translation;
+use ambient_core::{relationship, transform::translation};
 use ambient_ecs::{
     query as ecs_query, with_component_registry, Component, ComponentValue, Entity, EntityId,
     QueryEvent, QueryState, World,
@@ -57,12 +57,24 @@ pub fn get_all(world: &mut World, index: u32) -> Vec<EntityId> {
         .map(|ea| ea.id())
         .collect()
 }
+pub fn get_related(world: &mut World, target: EntityId, index: u32) -> Vec<EntityId> {
+    let desc = match with_component_registry(|r| r.get_by_index(index)) {
+        Some(c) => c,
+        None => return vec![],
+    };
+    if !desc.has_attribute::<relationship::Relationship>() {
+        return vec![];
+    }
+
+    relationship::related_to(world, Component::new(desc), target)
+}
 pub fn query(
     query_states: &mut QueryStateMap,
     components: impl Iterator<Item = u32> + Sync + Send,
     include: impl Iterator<Item = u32> + Sync + Send,
     exclude: impl Iterator<Item = u32> + Sync + Send,
     changed: impl Iterator<Item = u32> + Sync + Send,
+    related_to: Option<(u32, EntityId)>,
     query_event: QueryEvent,
 ) -> anyhow::Result<u64> {
     fn get_components(
@@ -87,6 +99,19 @@ pub fn query(
         ))
     })?;
 
+    let related_to = related_to
+        .map(|(index, target)| {
+            let desc = with_component_registry(|r| r.get_by_index(index))
+                .context("no component for index")?;
+            anyhow::ensure!(
+                desc.has_attribute::<relationship::Relationship>(),
+                "{} is not a relationship component",
+                desc.path()
+            );
+            anyhow::Ok((Component::<EntityId>::new(desc), target))
+        })
+        .transpose()?;
+
     let mut query = ambient_ecs::Query::new(ambient_ecs::ArchetypeFilter::new());
     query.event = query_event;
     for component in &components {
@@ -101,9 +126,12 @@ pub fn query(
     for component in changed {
         query = query.optional_changed_ref(component.as_component());
     }
+    if let Some((relationship, _)) = related_to {
+        query = query.incl(relationship);
+    }
 
     Ok(query_states
-        .insert((query, QueryState::new(), components))
+        .insert((query, QueryState::new(), components, related_to))
         .data()
         .as_ffi())
 }
@@ -29,6 +29,10 @@ components!("wasm::shared", {
     module_enabled: bool,
     @[Networked, Store, Debuggable]
     module_errors: ModuleErrors,
+    /// Spawned by a module while it was loading. The module spawns it again the next time it loads,
+    /// so it's not saved with the world
+    @[Debuggable]
+    spawned_on_module_load: (),
 
     /// used to signal messages from the WASM host/runtime
     @[Resource]
@@ -191,6 +195,17 @@ pub fn load<
                 sms.clone(),
                 &RunContext::new(world, "core/module_load", Entity::new()),
             ));
+            let spawned = sms
+                .shared_state()
+                .read()
+                .base()
+                .spawned_entities
+                .iter()
+                .copied()
+                .collect_vec();
+            for id in spawned {
+                world.add_component(id, spawned_on_module_load(), ()).ok();
+            }
 
             world
                 .add_component(module_id, state_component, sms)
@@ -343,7 +358,8 @@ pub fn spawn_module(
         .with_default(module())
         .with(module_enabled(), enabled)
         .with_default(module_errors())
-        .with(ambient_project::description(), description);
+        .with(ambient_project::description(), description)
+        .with_default(ambient_network::server::keep_in_empty_instances());
 
     Ok(ed.spawn(world))
 }
@@ -0,0 +1,72 @@
+use crate::{
+    global::Vec3,
+    internal::{conversion::IntoBindgen, host},
+};
+
+/// Draws a line from `start` to `end` for `lifetime` seconds.
+pub fn line(category: impl AsRef<str>, start: Vec3, end: Vec3, color: Vec3, lifetime: f32) {
+    host::debug_draw_line(
+        category.as_ref(),
+        start.into_bindgen(),
+        end.into_bindgen(),
+        color.into_bindgen(),
+        lifetime,
+    )
+}
+
+/// Draws an arrow from `start` pointing at `end` for `lifetime` seconds.
+pub fn arrow(category: impl AsRef<str>, start: Vec3, end: Vec3, color: Vec3, lifetime: f32) {
+    host::debug_draw_arrow(
+        category.as_ref(),
+        start.into_bindgen(),
+        end.into_bindgen(),
+        color.into_bindgen(),
+        lifetime,
+    )
+}
+
+/// Draws the outline of a sphere for `lifetime` seconds.
+pub fn sphere(category: impl AsRef<str>, center: Vec3, radius: f32, color: Vec3, lifetime: f32) {
+    host::debug_draw_sphere(
+        category.as_ref(),
+        center.into_bindgen(),
+        radius,
+        color.into_bindgen(),
+        lifetime,
+    )
+}
+
+/// Draws the outline of an axis-aligned box, which extends `half_extents` from `center` in each
+/// direction, for `lifetime` seconds.
+pub fn cuboid(
+    category: impl AsRef<str>,
+    center: Vec3,
+    half_extents: Vec3,
+    color: Vec3,
+    lifetime: f32,
+) {
+    host::debug_draw_box(
+        category.as_ref(),
+        center.into_bindgen(),
+        half_extents.into_bindgen(),
+        color.into_bindgen(),
+        lifetime,
+    )
+}
+
+/// Draws `text` at `position`, facing the camera, for `lifetime` seconds.
+pub fn text(
+    category: impl AsRef<str>,
+    position: Vec3,
+    text: impl AsRef<str>,
+    color: Vec3,
+    lifetime: f32,
+) {
+    host::debug_draw_text(
+        category.as_ref(),
+        position.into_bindgen(),
+        text.as_ref(),
+        color.into_bindgen(),
+        lifetime,
+    )
+}
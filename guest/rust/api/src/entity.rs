@@ -55,6 +55,11 @@ pub fn get_all<T>(component: Component<T>) -> Vec<EntityId> {
     host::entity_get_all(component.index()).from_bindgen()
 }
 
+/// Gets all of the entities whose relationship `component` (i.e. `attached_to` or `owned_by`) points at `target`.
+pub fn get_related(target: EntityId, component: Component<EntityId>) -> Vec<EntityId> {
+    host::entity_get_related(target.into_bindgen(), component.index()).from_bindgen()
+}
+
 /// Gets all of the entities within `radius` of `position`.
 pub fn in_area(position: Vec3, radius: f32) -> Vec<EntityId> {
     host::entity_in_area(position.into_bindgen(), radius).from_bindgen()
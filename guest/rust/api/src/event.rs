@@ -12,6 +12,9 @@ pub const MODULE_LOAD: &str = "core/module_load";
 pub const MODULE_UNLOAD: &str = "core/module_unload";
 /// A world event was fired.
 pub const WORLD_EVENT: &str = "core/world_event";
+/// Fired once after the modules are loaded, when the world was restored from a save.
+/// See [persistence](crate::persistence).
+pub const WORLD_LOAD: &str = "core/world_load";
 
 /// Sends a (non-core) event to all other modules. This can be used for inter-module communication.
 pub fn send(name: impl AsRef<str>, data: Entity) {
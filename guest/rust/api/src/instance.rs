@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+use crate::{
+    global::EntityId,
+    internal::{conversion::IntoBindgen, host},
+};
+
+/// The id of the main world instance, which players join when they connect.
+pub const MAIN: &str = "main";
+
+/// Gets the id of the world instance this module is running in.
+pub fn current() -> String {
+    host::instance_current()
+}
+
+/// Gets all of the world instances on the server and how many players are in each of them,
+/// as of the start of this frame.
+pub fn list() -> HashMap<String, u32> {
+    host::instance_list().into_iter().collect()
+}
+
+/// Creates a new world instance from this one, and returns its id.
+///
+/// If `empty` is true, the new instance only has this instance's resources and modules, otherwise
+/// it also has all of its entities except for the players. The modules are restarted in the new
+/// instance, and can use [current] to tell that they are not in the [MAIN] instance.
+///
+/// The instance is created at the end of this frame, so players can be moved into it with
+/// [move_player] right away.
+pub fn create(empty: bool) -> String {
+    host::instance_create(empty)
+}
+
+/// Moves `player_id` into the world instance `instance_id` at the end of this frame. If the
+/// instance the player leaves is empty afterwards, it is removed, unless it is the [MAIN] instance.
+///
+/// Returns false if `player_id` is not a player, or if `instance_id` doesn't exist and isn't being
+/// created this frame.
+pub fn move_player(player_id: EntityId, instance_id: &str) -> bool {
+    host::instance_move_player(player_id.into_bindgen(), instance_id)
+}
+
+/// Removes the world instance `instance_id` at the end of this frame, if it has no players and is
+/// not the [MAIN] instance.
+pub fn remove_if_empty(instance_id: &str) {
+    host::instance_remove_if_empty(instance_id)
+}
@@ -1312,6 +1312,97 @@
       }
     }
   }
+  #[repr(u8)]
+  #[derive(Clone, Copy, PartialEq, Eq)]
+  pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    Mode,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+  }
+  impl core::fmt::Debug for GamepadButton {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+      match self {
+        GamepadButton::South => {
+          f.debug_tuple("GamepadButton::South").finish()
+        }
+        GamepadButton::East => {
+          f.debug_tuple("GamepadButton::East").finish()
+        }
+        GamepadButton::North => {
+          f.debug_tuple("GamepadButton::North").finish()
+        }
+        GamepadButton::West => {
+          f.debug_tuple("GamepadButton::West").finish()
+        }
+        GamepadButton::LeftBumper => {
+          f.debug_tuple("GamepadButton::LeftBumper").finish()
+        }
+        GamepadButton::RightBumper => {
+          f.debug_tuple("GamepadButton::RightBumper").finish()
+        }
+        GamepadButton::LeftTrigger => {
+          f.debug_tuple("GamepadButton::LeftTrigger").finish()
+        }
+        GamepadButton::RightTrigger => {
+          f.debug_tuple("GamepadButton::RightTrigger").finish()
+        }
+        GamepadButton::Select => {
+          f.debug_tuple("GamepadButton::Select").finish()
+        }
+        GamepadButton::Start => {
+          f.debug_tuple("GamepadButton::Start").finish()
+        }
+        GamepadButton::Mode => {
+          f.debug_tuple("GamepadButton::Mode").finish()
+        }
+        GamepadButton::LeftStick => {
+          f.debug_tuple("GamepadButton::LeftStick").finish()
+        }
+        GamepadButton::RightStick => {
+          f.debug_tuple("GamepadButton::RightStick").finish()
+        }
+        GamepadButton::DPadUp => {
+          f.debug_tuple("GamepadButton::DPadUp").finish()
+        }
+        GamepadButton::DPadDown => {
+          f.debug_tuple("GamepadButton::DPadDown").finish()
+        }
+        GamepadButton::DPadLeft => {
+          f.debug_tuple("GamepadButton::DPadLeft").finish()
+        }
+        GamepadButton::DPadRight => {
+          f.debug_tuple("GamepadButton::DPadRight").finish()
+        }
+      }
+    }
+  }
+  #[derive(Clone)]
+  pub struct Gamepad {
+    pub id: u32,
+    pub buttons: Vec<GamepadButton>,
+    pub left_stick: Vec2,
+    pub right_stick: Vec2,
+    pub left_trigger: f32,
+    pub right_trigger: f32,
+  }
+  impl core::fmt::Debug for Gamepad {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+      f.debug_struct("Gamepad").field("id", &self.id).field("buttons", &self.buttons).field("left-stick", &self.left_stick).field("right-stick", &self.right_stick).field("left-trigger", &self.left_trigger).field("right-trigger", &self.right_trigger).finish()}
+  }
   #[derive(Clone)]
   pub struct PlayerRawInput {
     pub keys: Vec<VirtualKeyCode>,
@@ -1319,10 +1410,11 @@
     pub cursor_position: Vec2,
     pub mouse_wheel: f32,
     pub mouse_buttons: Vec<MouseButton>,
+    pub gamepads: Vec<Gamepad>,
   }
   impl core::fmt::Debug for PlayerRawInput {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-      f.debug_struct("PlayerRawInput").field("keys", &self.keys).field("mouse-position", &self.mouse_position).field("cursor-position", &self.cursor_position).field("mouse-wheel", &self.mouse_wheel).field("mouse-buttons", &self.mouse_buttons).finish()}
+      f.debug_struct("PlayerRawInput").field("keys", &self.keys).field("mouse-position", &self.mouse_position).field("cursor-position", &self.cursor_position).field("mouse-wheel", &self.mouse_wheel).field("mouse-buttons", &self.mouse_buttons).field("gamepads", &self.gamepads).finish()}
   }
   #[derive(Clone)]
   pub struct Query<'a,> {
@@ -1330,10 +1422,11 @@
     pub include: &'a [u32],
     pub exclude: &'a [u32],
     pub changed: &'a [u32],
+    pub related_to: Option<(u32,EntityId,)>,
   }
   impl<'a,> core::fmt::Debug for Query<'a,> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-      f.debug_struct("Query").field("components", &self.components).field("include", &self.include).field("exclude", &self.exclude).field("changed", &self.changed).finish()}
+      f.debug_struct("Query").field("components", &self.components).field("include", &self.include).field("exclude", &self.exclude).field("changed", &self.changed).field("related-to", &self.related_to).finish()}
   }
   #[repr(u8)]
   #[derive(Clone, Copy, PartialEq, Eq)]
@@ -5481,6 +5574,21 @@
                                   Vec::from_raw_parts(*((ptr0 + 0) as *const i32) as *mut _, len1, len1)
                                 }
                               }
+                              pub fn entity_get_related(target: EntityId,index: u32,) -> Vec<EntityId>{
+                                unsafe {
+                                  let EntityId{ id0:id00, id1:id10, } = target;
+                                  let ptr1 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
+                                  #[link(wasm_import_module = "host")]
+                                  extern "C" {
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "entity-get-related: func(target: record { id0: u64, id1: u64 }, index: u32) -> list<record { id0: u64, id1: u64 }>")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_entity-get-related: func(target: record { id0: u64, id1: u64 }, index: u32) -> list<record { id0: u64, id1: u64 }>")]
+                                    fn wit_import(_: i64, _: i64, _: i32, _: i32, );
+                                  }
+                                  wit_import(wit_bindgen_guest_rust::rt::as_i64(id00), wit_bindgen_guest_rust::rt::as_i64(id10), wit_bindgen_guest_rust::rt::as_i32(index), ptr1);
+                                  let len2 = *((ptr1 + 4) as *const i32) as usize;
+                                  Vec::from_raw_parts(*((ptr1 + 0) as *const i32) as *mut _, len2, len2)
+                                }
+                              }
                               pub fn entity_resources() -> EntityId{
                                 unsafe {
                                   let ptr0 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
@@ -5496,7 +5604,7 @@
                               }
                               pub fn entity_query(q: Query<'_,>,t: QueryEvent,) -> u64{
                                 unsafe {
-                                  let Query{ components:components0, include:include0, exclude:exclude0, changed:changed0, } = q;
+                                  let Query{ components:components0, include:include0, exclude:exclude0, changed:changed0, related_to:related_to0, } = q;
                                   let vec1 = components0;
                                   let ptr1 = vec1.as_ptr() as i32;
                                   let len1 = vec1.len() as i32;
@@ -5509,13 +5617,28 @@
                                   let vec4 = changed0;
                                   let ptr4 = vec4.as_ptr() as i32;
                                   let len4 = vec4.len() as i32;
+                                  let (result7_0,result7_1,result7_2,result7_3,) = match related_to0 {
+                                    Some(e) => {
+                                      let (t5_0, t5_1, ) = e;
+                                      let EntityId{ id0:id06, id1:id16, } = t5_1;
+                                      (1i32, wit_bindgen_guest_rust::rt::as_i32(t5_0), wit_bindgen_guest_rust::rt::as_i64(id06), wit_bindgen_guest_rust::rt::as_i64(id16))
+                                    },
+                                    None => {
+                                      let e = ();
+                                      {
+                                        let () = e;
+                                        
+                                        (0i32, 0i32, 0i64, 0i64)
+                                      }
+                                    },
+                                  };
                                   #[link(wasm_import_module = "host")]
                                   extern "C" {
-                                    #[cfg_attr(target_arch = "wasm32", link_name = "entity-query: func(q: record { components: list<u32>, include: list<u32>, exclude: list<u32>, changed: list<u32> }, t: enum { frame, spawn, despawn }) -> u64")]
-                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_entity-query: func(q: record { components: list<u32>, include: list<u32>, exclude: list<u32>, changed: list<u32> }, t: enum { frame, spawn, despawn }) -> u64")]
-                                    fn wit_import(_: i32, _: i32, _: i32, _: i32, _: i32, _: i32, _: i32, _: i32, _: i32, ) -> i64;
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "entity-query: func(q: record { components: list<u32>, include: list<u32>, exclude: list<u32>, changed: list<u32>, related-to: option<tuple<u32, record { id0: u64, id1: u64 }>> }, t: enum { frame, spawn, despawn }) -> u64")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_entity-query: func(q: record { components: list<u32>, include: list<u32>, exclude: list<u32>, changed: list<u32>, related-to: option<tuple<u32, record { id0: u64, id1: u64 }>> }, t: enum { frame, spawn, despawn }) -> u64")]
+                                    fn wit_import(_: i32, _: i32, _: i32, _: i32, _: i32, _: i32, _: i32, _: i32, _: i32, _: i32, _: i64, _: i64, _: i32, ) -> i64;
                                   }
-                                  let ret = wit_import(ptr1, len1, ptr2, len2, ptr3, len3, ptr4, len4, match t {
+                                  let ret = wit_import(ptr1, len1, ptr2, len2, ptr3, len3, ptr4, len4, result7_0, result7_1, result7_2, result7_3, match t {
                                     QueryEvent::Frame => 0,
                                     QueryEvent::Spawn => 1,
                                     QueryEvent::Despawn => 2,
@@ -5802,8 +5925,8 @@
                                   let ptr1 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
                                   #[link(wasm_import_module = "host")]
                                   extern "C" {
-                                    #[cfg_attr(target_arch = "wasm32", link_name = "player-get-raw-input: func(player: record { id0: u64, id1: u64 }) -> option<record { keys: list<enum { key1, key2, key3, key4, key5, key6, key7, key8, key9, key0, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, escape, f1, f2, f3, f4, f5, f6, f7, f8, f9, f10, f11, f12, f13, f14, f15, f16, f17, f18, f19, f20, f21, f22, f23, f24, snapshot, scroll, pause, insert, home, delete, end, page-down, page-up, left, up, right, down, back, return, space, compose, caret, numlock, numpad0, numpad1, numpad2, numpad3, numpad4, numpad5, numpad6, numpad7, numpad8, numpad9, numpad-add, numpad-divide, numpad-decimal, numpad-comma, numpad-enter, numpad-equals, numpad-multiply, numpad-subtract, abnt-c1, abnt-c2, apostrophe, apps, asterisk, at, ax, backslash, calculator, capital, colon, comma, convert, equals, grave, kana, kanji, l-alt, l-bracket, l-control, l-shift, l-win, mail, media-select, media-stop, minus, mute, my-computer, navigate-forward, navigate-backward, next-track, no-convert, oem102, period, play-pause, plus, power, prev-track, r-alt, r-bracket, r-control, r-shift, r-win, semicolon, slash, sleep, stop, sysrq, tab, underline, unlabeled, volume-down, volume-up, wake, web-back, web-favorites, web-forward, web-home, web-refresh, web-search, web-stop, yen, copy, paste, cut }>, mouse-position: record { x: float32, y: float32 }, cursor-position: record { x: float32, y: float32 }, mouse-wheel: float32, mouse-buttons: list<variant { left(unit), right(unit), middle(unit), other(u16) }> }>")]
-                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_player-get-raw-input: func(player: record { id0: u64, id1: u64 }) -> option<record { keys: list<enum { key1, key2, key3, key4, key5, key6, key7, key8, key9, key0, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, escape, f1, f2, f3, f4, f5, f6, f7, f8, f9, f10, f11, f12, f13, f14, f15, f16, f17, f18, f19, f20, f21, f22, f23, f24, snapshot, scroll, pause, insert, home, delete, end, page-down, page-up, left, up, right, down, back, return, space, compose, caret, numlock, numpad0, numpad1, numpad2, numpad3, numpad4, numpad5, numpad6, numpad7, numpad8, numpad9, numpad-add, numpad-divide, numpad-decimal, numpad-comma, numpad-enter, numpad-equals, numpad-multiply, numpad-subtract, abnt-c1, abnt-c2, apostrophe, apps, asterisk, at, ax, backslash, calculator, capital, colon, comma, convert, equals, grave, kana, kanji, l-alt, l-bracket, l-control, l-shift, l-win, mail, media-select, media-stop, minus, mute, my-computer, navigate-forward, navigate-backward, next-track, no-convert, oem102, period, play-pause, plus, power, prev-track, r-alt, r-bracket, r-control, r-shift, r-win, semicolon, slash, sleep, stop, sysrq, tab, underline, unlabeled, volume-down, volume-up, wake, web-back, web-favorites, web-forward, web-home, web-refresh, web-search, web-stop, yen, copy, paste, cut }>, mouse-position: record { x: float32, y: float32 }, cursor-position: record { x: float32, y: float32 }, mouse-wheel: float32, mouse-buttons: list<variant { left(unit), right(unit), middle(unit), other(u16) }> }>")]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "player-get-raw-input: func(player: record { id0: u64, id1: u64 }) -> option<record { keys: list<enum { key1, key2, key3, key4, key5, key6, key7, key8, key9, key0, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, escape, f1, f2, f3, f4, f5, f6, f7, f8, f9, f10, f11, f12, f13, f14, f15, f16, f17, f18, f19, f20, f21, f22, f23, f24, snapshot, scroll, pause, insert, home, delete, end, page-down, page-up, left, up, right, down, back, return, space, compose, caret, numlock, numpad0, numpad1, numpad2, numpad3, numpad4, numpad5, numpad6, numpad7, numpad8, numpad9, numpad-add, numpad-divide, numpad-decimal, numpad-comma, numpad-enter, numpad-equals, numpad-multiply, numpad-subtract, abnt-c1, abnt-c2, apostrophe, apps, asterisk, at, ax, backslash, calculator, capital, colon, comma, convert, equals, grave, kana, kanji, l-alt, l-bracket, l-control, l-shift, l-win, mail, media-select, media-stop, minus, mute, my-computer, navigate-forward, navigate-backward, next-track, no-convert, oem102, period, play-pause, plus, power, prev-track, r-alt, r-bracket, r-control, r-shift, r-win, semicolon, slash, sleep, stop, sysrq, tab, underline, unlabeled, volume-down, volume-up, wake, web-back, web-favorites, web-forward, web-home, web-refresh, web-search, web-stop, yen, copy, paste, cut }>, mouse-position: record { x: float32, y: float32 }, cursor-position: record { x: float32, y: float32 }, mouse-wheel: float32, mouse-buttons: list<variant { left(unit), right(unit), middle(unit), other(u16) }>, gamepads: list<record { id: u32, buttons: list<enum { south, east, north, west, left-bumper, right-bumper, left-trigger, right-trigger, select, start, mode, left-stick, right-stick, d-pad-up, d-pad-down, d-pad-left, d-pad-right }>, left-stick: record { x: float32, y: float32 }, right-stick: record { x: float32, y: float32 }, left-trigger: float32, right-trigger: float32 }> }>")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_player-get-raw-input: func(player: record { id0: u64, id1: u64 }) -> option<record { keys: list<enum { key1, key2, key3, key4, key5, key6, key7, key8, key9, key0, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, escape, f1, f2, f3, f4, f5, f6, f7, f8, f9, f10, f11, f12, f13, f14, f15, f16, f17, f18, f19, f20, f21, f22, f23, f24, snapshot, scroll, pause, insert, home, delete, end, page-down, page-up, left, up, right, down, back, return, space, compose, caret, numlock, numpad0, numpad1, numpad2, numpad3, numpad4, numpad5, numpad6, numpad7, numpad8, numpad9, numpad-add, numpad-divide, numpad-decimal, numpad-comma, numpad-enter, numpad-equals, numpad-multiply, numpad-subtract, abnt-c1, abnt-c2, apostrophe, apps, asterisk, at, ax, backslash, calculator, capital, colon, comma, convert, equals, grave, kana, kanji, l-alt, l-bracket, l-control, l-shift, l-win, mail, media-select, media-stop, minus, mute, my-computer, navigate-forward, navigate-backward, next-track, no-convert, oem102, period, play-pause, plus, power, prev-track, r-alt, r-bracket, r-control, r-shift, r-win, semicolon, slash, sleep, stop, sysrq, tab, underline, unlabeled, volume-down, volume-up, wake, web-back, web-favorites, web-forward, web-home, web-refresh, web-search, web-stop, yen, copy, paste, cut }>, mouse-position: record { x: float32, y: float32 }, cursor-position: record { x: float32, y: float32 }, mouse-wheel: float32, mouse-buttons: list<variant { left(unit), right(unit), middle(unit), other(u16) }>, gamepads: list<record { id: u32, buttons: list<enum { south, east, north, west, left-bumper, right-bumper, left-trigger, right-trigger, select, start, mode, left-stick, right-stick, d-pad-up, d-pad-down, d-pad-left, d-pad-right }>, left-stick: record { x: float32, y: float32 }, right-stick: record { x: float32, y: float32 }, left-trigger: float32, right-trigger: float32 }> }>")]
                                     fn wit_import(_: i64, _: i64, _: i32, );
                                   }
                                   wit_import(wit_bindgen_guest_rust::rt::as_i64(id00), wit_bindgen_guest_rust::rt::as_i64(id10), ptr1);
@@ -6001,8 +6124,50 @@
                                       if len3 != 0 {
                                         std::alloc::dealloc(base3 as *mut _, std::alloc::Layout::from_size_align_unchecked((len3 as usize) * 4, 2));
                                       }
+                                      let base4 = *((ptr1 + 40) as *const i32);
+                                      let len4 = *((ptr1 + 44) as *const i32);
+                                      let mut result4 = Vec::with_capacity(len4 as usize);
+                                      for i in 0..len4 {
+                                        let base = base4 + i *36;
+                                        result4.push({
+                                          let base5 = *((base + 4) as *const i32);
+                                          let len5 = *((base + 8) as *const i32);
+                                          let mut result5 = Vec::with_capacity(len5 as usize);
+                                          for i in 0..len5 {
+                                            let base = base5 + i *1;
+                                            result5.push(match i32::from(*((base + 0) as *const u8)) {
+                                              0 => GamepadButton::South,
+                                              1 => GamepadButton::East,
+                                              2 => GamepadButton::North,
+                                              3 => GamepadButton::West,
+                                              4 => GamepadButton::LeftBumper,
+                                              5 => GamepadButton::RightBumper,
+                                              6 => GamepadButton::LeftTrigger,
+                                              7 => GamepadButton::RightTrigger,
+                                              8 => GamepadButton::Select,
+                                              9 => GamepadButton::Start,
+                                              10 => GamepadButton::Mode,
+                                              11 => GamepadButton::LeftStick,
+                                              12 => GamepadButton::RightStick,
+                                              13 => GamepadButton::DPadUp,
+                                              14 => GamepadButton::DPadDown,
+                                              15 => GamepadButton::DPadLeft,
+                                              16 => GamepadButton::DPadRight,
+                                              _ => panic!("invalid enum discriminant"),
+                                            });
+                                          }
+                                          if len5 != 0 {
+                                            std::alloc::dealloc(base5 as *mut _, std::alloc::Layout::from_size_align_unchecked((len5 as usize) * 1, 1));
+                                          }
+                                          
+                                          Gamepad{id:*((base + 0) as *const i32) as u32, buttons:result5, left_stick:Vec2{x:*((base + 12) as *const f32), y:*((base + 16) as *const f32), }, right_stick:Vec2{x:*((base + 20) as *const f32), y:*((base + 24) as *const f32), }, left_trigger:*((base + 28) as *const f32), right_trigger:*((base + 32) as *const f32), }
+                                        });
+                                      }
+                                      if len4 != 0 {
+                                        std::alloc::dealloc(base4 as *mut _, std::alloc::Layout::from_size_align_unchecked((len4 as usize) * 36, 4));
+                                      }
                                       
-                                      PlayerRawInput{keys:result2, mouse_position:Vec2{x:*((ptr1 + 12) as *const f32), y:*((ptr1 + 16) as *const f32), }, cursor_position:Vec2{x:*((ptr1 + 20) as *const f32), y:*((ptr1 + 24) as *const f32), }, mouse_wheel:*((ptr1 + 28) as *const f32), mouse_buttons:result3, }
+                                      PlayerRawInput{keys:result2, mouse_position:Vec2{x:*((ptr1 + 12) as *const f32), y:*((ptr1 + 16) as *const f32), }, cursor_position:Vec2{x:*((ptr1 + 20) as *const f32), y:*((ptr1 + 24) as *const f32), }, mouse_wheel:*((ptr1 + 28) as *const f32), mouse_buttons:result3, gamepads:result4, }
                                     }),
                                     _ => panic!("invalid enum discriminant"),
                                   }
@@ -6014,8 +6179,8 @@
                                   let ptr1 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
                                   #[link(wasm_import_module = "host")]
                                   extern "C" {
-                                    #[cfg_attr(target_arch = "wasm32", link_name = "player-get-prev-raw-input: func(player: record { id0: u64, id1: u64 }) -> option<record { keys: list<enum { key1, key2, key3, key4, key5, key6, key7, key8, key9, key0, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, escape, f1, f2, f3, f4, f5, f6, f7, f8, f9, f10, f11, f12, f13, f14, f15, f16, f17, f18, f19, f20, f21, f22, f23, f24, snapshot, scroll, pause, insert, home, delete, end, page-down, page-up, left, up, right, down, back, return, space, compose, caret, numlock, numpad0, numpad1, numpad2, numpad3, numpad4, numpad5, numpad6, numpad7, numpad8, numpad9, numpad-add, numpad-divide, numpad-decimal, numpad-comma, numpad-enter, numpad-equals, numpad-multiply, numpad-subtract, abnt-c1, abnt-c2, apostrophe, apps, asterisk, at, ax, backslash, calculator, capital, colon, comma, convert, equals, grave, kana, kanji, l-alt, l-bracket, l-control, l-shift, l-win, mail, media-select, media-stop, minus, mute, my-computer, navigate-forward, navigate-backward, next-track, no-convert, oem102, period, play-pause, plus, power, prev-track, r-alt, r-bracket, r-control, r-shift, r-win, semicolon, slash, sleep, stop, sysrq, tab, underline, unlabeled, volume-down, volume-up, wake, web-back, web-favorites, web-forward, web-home, web-refresh, web-search, web-stop, yen, copy, paste, cut }>, mouse-position: record { x: float32, y: float32 }, cursor-position: record { x: float32, y: float32 }, mouse-wheel: float32, mouse-buttons: list<variant { left(unit), right(unit), middle(unit), other(u16) }> }>")]
-                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_player-get-prev-raw-input: func(player: record { id0: u64, id1: u64 }) -> option<record { keys: list<enum { key1, key2, key3, key4, key5, key6, key7, key8, key9, key0, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, escape, f1, f2, f3, f4, f5, f6, f7, f8, f9, f10, f11, f12, f13, f14, f15, f16, f17, f18, f19, f20, f21, f22, f23, f24, snapshot, scroll, pause, insert, home, delete, end, page-down, page-up, left, up, right, down, back, return, space, compose, caret, numlock, numpad0, numpad1, numpad2, numpad3, numpad4, numpad5, numpad6, numpad7, numpad8, numpad9, numpad-add, numpad-divide, numpad-decimal, numpad-comma, numpad-enter, numpad-equals, numpad-multiply, numpad-subtract, abnt-c1, abnt-c2, apostrophe, apps, asterisk, at, ax, backslash, calculator, capital, colon, comma, convert, equals, grave, kana, kanji, l-alt, l-bracket, l-control, l-shift, l-win, mail, media-select, media-stop, minus, mute, my-computer, navigate-forward, navigate-backward, next-track, no-convert, oem102, period, play-pause, plus, power, prev-track, r-alt, r-bracket, r-control, r-shift, r-win, semicolon, slash, sleep, stop, sysrq, tab, underline, unlabeled, volume-down, volume-up, wake, web-back, web-favorites, web-forward, web-home, web-refresh, web-search, web-stop, yen, copy, paste, cut }>, mouse-position: record { x: float32, y: float32 }, cursor-position: record { x: float32, y: float32 }, mouse-wheel: float32, mouse-buttons: list<variant { left(unit), right(unit), middle(unit), other(u16) }> }>")]
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "player-get-prev-raw-input: func(player: record { id0: u64, id1: u64 }) -> option<record { keys: list<enum { key1, key2, key3, key4, key5, key6, key7, key8, key9, key0, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, escape, f1, f2, f3, f4, f5, f6, f7, f8, f9, f10, f11, f12, f13, f14, f15, f16, f17, f18, f19, f20, f21, f22, f23, f24, snapshot, scroll, pause, insert, home, delete, end, page-down, page-up, left, up, right, down, back, return, space, compose, caret, numlock, numpad0, numpad1, numpad2, numpad3, numpad4, numpad5, numpad6, numpad7, numpad8, numpad9, numpad-add, numpad-divide, numpad-decimal, numpad-comma, numpad-enter, numpad-equals, numpad-multiply, numpad-subtract, abnt-c1, abnt-c2, apostrophe, apps, asterisk, at, ax, backslash, calculator, capital, colon, comma, convert, equals, grave, kana, kanji, l-alt, l-bracket, l-control, l-shift, l-win, mail, media-select, media-stop, minus, mute, my-computer, navigate-forward, navigate-backward, next-track, no-convert, oem102, period, play-pause, plus, power, prev-track, r-alt, r-bracket, r-control, r-shift, r-win, semicolon, slash, sleep, stop, sysrq, tab, underline, unlabeled, volume-down, volume-up, wake, web-back, web-favorites, web-forward, web-home, web-refresh, web-search, web-stop, yen, copy, paste, cut }>, mouse-position: record { x: float32, y: float32 }, cursor-position: record { x: float32, y: float32 }, mouse-wheel: float32, mouse-buttons: list<variant { left(unit), right(unit), middle(unit), other(u16) }>, gamepads: list<record { id: u32, buttons: list<enum { south, east, north, west, left-bumper, right-bumper, left-trigger, right-trigger, select, start, mode, left-stick, right-stick, d-pad-up, d-pad-down, d-pad-left, d-pad-right }>, left-stick: record { x: float32, y: float32 }, right-stick: record { x: float32, y: float32 }, left-trigger: float32, right-trigger: float32 }> }>")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_player-get-prev-raw-input: func(player: record { id0: u64, id1: u64 }) -> option<record { keys: list<enum { key1, key2, key3, key4, key5, key6, key7, key8, key9, key0, a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, escape, f1, f2, f3, f4, f5, f6, f7, f8, f9, f10, f11, f12, f13, f14, f15, f16, f17, f18, f19, f20, f21, f22, f23, f24, snapshot, scroll, pause, insert, home, delete, end, page-down, page-up, left, up, right, down, back, return, space, compose, caret, numlock, numpad0, numpad1, numpad2, numpad3, numpad4, numpad5, numpad6, numpad7, numpad8, numpad9, numpad-add, numpad-divide, numpad-decimal, numpad-comma, numpad-enter, numpad-equals, numpad-multiply, numpad-subtract, abnt-c1, abnt-c2, apostrophe, apps, asterisk, at, ax, backslash, calculator, capital, colon, comma, convert, equals, grave, kana, kanji, l-alt, l-bracket, l-control, l-shift, l-win, mail, media-select, media-stop, minus, mute, my-computer, navigate-forward, navigate-backward, next-track, no-convert, oem102, period, play-pause, plus, power, prev-track, r-alt, r-bracket, r-control, r-shift, r-win, semicolon, slash, sleep, stop, sysrq, tab, underline, unlabeled, volume-down, volume-up, wake, web-back, web-favorites, web-forward, web-home, web-refresh, web-search, web-stop, yen, copy, paste, cut }>, mouse-position: record { x: float32, y: float32 }, cursor-position: record { x: float32, y: float32 }, mouse-wheel: float32, mouse-buttons: list<variant { left(unit), right(unit), middle(unit), other(u16) }>, gamepads: list<record { id: u32, buttons: list<enum { south, east, north, west, left-bumper, right-bumper, left-trigger, right-trigger, select, start, mode, left-stick, right-stick, d-pad-up, d-pad-down, d-pad-left, d-pad-right }>, left-stick: record { x: float32, y: float32 }, right-stick: record { x: float32, y: float32 }, left-trigger: float32, right-trigger: float32 }> }>")]
                                     fn wit_import(_: i64, _: i64, _: i32, );
                                   }
                                   wit_import(wit_bindgen_guest_rust::rt::as_i64(id00), wit_bindgen_guest_rust::rt::as_i64(id10), ptr1);
@@ -6213,13 +6378,111 @@
                                       if len3 != 0 {
                                         std::alloc::dealloc(base3 as *mut _, std::alloc::Layout::from_size_align_unchecked((len3 as usize) * 4, 2));
                                       }
+                                      let base4 = *((ptr1 + 40) as *const i32);
+                                      let len4 = *((ptr1 + 44) as *const i32);
+                                      let mut result4 = Vec::with_capacity(len4 as usize);
+                                      for i in 0..len4 {
+                                        let base = base4 + i *36;
+                                        result4.push({
+                                          let base5 = *((base + 4) as *const i32);
+                                          let len5 = *((base + 8) as *const i32);
+                                          let mut result5 = Vec::with_capacity(len5 as usize);
+                                          for i in 0..len5 {
+                                            let base = base5 + i *1;
+                                            result5.push(match i32::from(*((base + 0) as *const u8)) {
+                                              0 => GamepadButton::South,
+                                              1 => GamepadButton::East,
+                                              2 => GamepadButton::North,
+                                              3 => GamepadButton::West,
+                                              4 => GamepadButton::LeftBumper,
+                                              5 => GamepadButton::RightBumper,
+                                              6 => GamepadButton::LeftTrigger,
+                                              7 => GamepadButton::RightTrigger,
+                                              8 => GamepadButton::Select,
+                                              9 => GamepadButton::Start,
+                                              10 => GamepadButton::Mode,
+                                              11 => GamepadButton::LeftStick,
+                                              12 => GamepadButton::RightStick,
+                                              13 => GamepadButton::DPadUp,
+                                              14 => GamepadButton::DPadDown,
+                                              15 => GamepadButton::DPadLeft,
+                                              16 => GamepadButton::DPadRight,
+                                              _ => panic!("invalid enum discriminant"),
+                                            });
+                                          }
+                                          if len5 != 0 {
+                                            std::alloc::dealloc(base5 as *mut _, std::alloc::Layout::from_size_align_unchecked((len5 as usize) * 1, 1));
+                                          }
+                                          
+                                          Gamepad{id:*((base + 0) as *const i32) as u32, buttons:result5, left_stick:Vec2{x:*((base + 12) as *const f32), y:*((base + 16) as *const f32), }, right_stick:Vec2{x:*((base + 20) as *const f32), y:*((base + 24) as *const f32), }, left_trigger:*((base + 28) as *const f32), right_trigger:*((base + 32) as *const f32), }
+                                        });
+                                      }
+                                      if len4 != 0 {
+                                        std::alloc::dealloc(base4 as *mut _, std::alloc::Layout::from_size_align_unchecked((len4 as usize) * 36, 4));
+                                      }
                                       
-                                      PlayerRawInput{keys:result2, mouse_position:Vec2{x:*((ptr1 + 12) as *const f32), y:*((ptr1 + 16) as *const f32), }, cursor_position:Vec2{x:*((ptr1 + 20) as *const f32), y:*((ptr1 + 24) as *const f32), }, mouse_wheel:*((ptr1 + 28) as *const f32), mouse_buttons:result3, }
+                                      PlayerRawInput{keys:result2, mouse_position:Vec2{x:*((ptr1 + 12) as *const f32), y:*((ptr1 + 16) as *const f32), }, cursor_position:Vec2{x:*((ptr1 + 20) as *const f32), y:*((ptr1 + 24) as *const f32), }, mouse_wheel:*((ptr1 + 28) as *const f32), mouse_buttons:result3, gamepads:result4, }
                                     }),
                                     _ => panic!("invalid enum discriminant"),
                                   }
                                 }
                               }
+                              pub fn player_get_actions(player: EntityId,) -> Vec<(String,Vec2,)>{
+                                unsafe {
+                                  let EntityId{ id0:id00, id1:id10, } = player;
+                                  let ptr1 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
+                                  #[link(wasm_import_module = "host")]
+                                  extern "C" {
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "player-get-actions: func(player: record { id0: u64, id1: u64 }) -> list<tuple<string, record { x: float32, y: float32 }>>")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_player-get-actions: func(player: record { id0: u64, id1: u64 }) -> list<tuple<string, record { x: float32, y: float32 }>>")]
+                                    fn wit_import(_: i64, _: i64, _: i32, );
+                                  }
+                                  wit_import(wit_bindgen_guest_rust::rt::as_i64(id00), wit_bindgen_guest_rust::rt::as_i64(id10), ptr1);
+                                  let base3 = *((ptr1 + 0) as *const i32);
+                                  let len3 = *((ptr1 + 4) as *const i32);
+                                  let mut result3 = Vec::with_capacity(len3 as usize);
+                                  for i in 0..len3 {
+                                    let base = base3 + i *16;
+                                    result3.push({
+                                      let len2 = *((base + 4) as *const i32) as usize;
+                                      
+                                      (String::from_utf8(Vec::from_raw_parts(*((base + 0) as *const i32) as *mut _, len2, len2)).unwrap(), Vec2{x:*((base + 8) as *const f32), y:*((base + 12) as *const f32), })
+                                    });
+                                  }
+                                  if len3 != 0 {
+                                    std::alloc::dealloc(base3 as *mut _, std::alloc::Layout::from_size_align_unchecked((len3 as usize) * 16, 4));
+                                  }
+                                  result3
+                                }
+                              }
+                              pub fn player_get_prev_actions(player: EntityId,) -> Vec<(String,Vec2,)>{
+                                unsafe {
+                                  let EntityId{ id0:id00, id1:id10, } = player;
+                                  let ptr1 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
+                                  #[link(wasm_import_module = "host")]
+                                  extern "C" {
+                                    #[cfg_attr(target_arch = "wasm32", link_name = "player-get-prev-actions: func(player: record { id0: u64, id1: u64 }) -> list<tuple<string, record { x: float32, y: float32 }>>")]
+                                    #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_player-get-prev-actions: func(player: record { id0: u64, id1: u64 }) -> list<tuple<string, record { x: float32, y: float32 }>>")]
+                                    fn wit_import(_: i64, _: i64, _: i32, );
+                                  }
+                                  wit_import(wit_bindgen_guest_rust::rt::as_i64(id00), wit_bindgen_guest_rust::rt::as_i64(id10), ptr1);
+                                  let base3 = *((ptr1 + 0) as *const i32);
+                                  let len3 = *((ptr1 + 4) as *const i32);
+                                  let mut result3 = Vec::with_capacity(len3 as usize);
+                                  for i in 0..len3 {
+                                    let base = base3 + i *16;
+                                    result3.push({
+                                      let len2 = *((base + 4) as *const i32) as usize;
+                                      
+                                      (String::from_utf8(Vec::from_raw_parts(*((base + 0) as *const i32) as *mut _, len2, len2)).unwrap(), Vec2{x:*((base + 8) as *const f32), y:*((base + 12) as *const f32), })
+                                    });
+                                  }
+                                  if len3 != 0 {
+                                    std::alloc::dealloc(base3 as *mut _, std::alloc::Layout::from_size_align_unchecked((len3 as usize) * 16, 4));
+                                  }
+                                  result3
+                                }
+                              }
                               pub fn physics_apply_force(entities: &[EntityId],force: Vec3,) -> (){
                                 unsafe {
                                   let vec0 = entities;
@@ -7144,28 +7407,1887 @@
                                         ()
                                       }
                                     }
-                                    pub fn asset_url(path: & str,) -> Option<String>{
-                                      unsafe {
-                                        let vec0 = path;
-                                        let ptr0 = vec0.as_ptr() as i32;
-                                        let len0 = vec0.len() as i32;
-                                        let ptr1 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
-                                        #[link(wasm_import_module = "host")]
-                                        extern "C" {
-                                          #[cfg_attr(target_arch = "wasm32", link_name = "asset-url: func(path: string) -> option<string>")]
-                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_asset-url: func(path: string) -> option<string>")]
-                                          fn wit_import(_: i32, _: i32, _: i32, );
-                                        }
-                                        wit_import(ptr0, len0, ptr1);
-                                        match i32::from(*((ptr1 + 0) as *const u8)) {
-                                          0 => None,
-                                          1 => Some({
-                                            let len2 = *((ptr1 + 8) as *const i32) as usize;
-                                            
-                                            String::from_utf8(Vec::from_raw_parts(*((ptr1 + 4) as *const i32) as *mut _, len2, len2)).unwrap()
-                                          }),
-                                          _ => panic!("invalid enum discriminant"),
-                                        }
+                              pub fn message_send(target: Option<EntityId>,name: & str,data: Entity<'_,>,reliable: bool,) -> (){
+                                unsafe {
+                                  let mut cleanup_list = Vec::new();
+                                  let (result1_0,result1_1,result1_2,) = match target {
+                                    Some(e) => {
+                                      let EntityId{ id0:id00, id1:id10, } = e;
+                                      (1i32, wit_bindgen_guest_rust::rt::as_i64(id00), wit_bindgen_guest_rust::rt::as_i64(id10))
+                                    },
+                                    None => {
+                                      let e = ();
+                                      {
+                                        let () = e;
+                                        
+                                        (0i32, 0i64, 0i64)
+                                      }
+                                    },
+                                  };
+                                  let vec0 = name;
+                                  let ptr0 = vec0.as_ptr() as i32;
+                                  let len0 = vec0.len() as i32;
+                                  let vec50 = data;
+                                  let len50 = vec50.len() as i32;
+                                  let layout50 = core::alloc::Layout::from_size_align_unchecked(vec50.len() * 88, 8);
+                                  let result50 = if layout50.size() != 0
+                                  {
+                                    let ptr = std::alloc::alloc(layout50);
+                                    if ptr.is_null()
+                                    {
+                                      std::alloc::handle_alloc_error(layout50);
+                                    }
+                                    ptr
+                                  }else {
+                                    std::ptr::null_mut()
+                                  };
+                                  for (i, e) in vec50.into_iter().enumerate() {
+                                    let base = result50 as i32 + (i as i32) * 88;
+                                    {
+                                      let (t1_0, t1_1, ) = e;
+                                      *((base + 0) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(t1_0);
+                                      match t1_1 {
+                                        ComponentTypeParam::TypeEmpty(e) => {
+                                          *((base + 8) as *mut u8) = (0i32) as u8;
+                                          let () = e;
+                                          
+                                        },
+                                        ComponentTypeParam::TypeBool(e) => {
+                                          *((base + 8) as *mut u8) = (1i32) as u8;
+                                          *((base + 16) as *mut u8) = (match e { true => 1, false => 0 }) as u8;
+                                          
+                                        },
+                                        ComponentTypeParam::TypeEntityId(e) => {
+                                          *((base + 8) as *mut u8) = (2i32) as u8;
+                                          let EntityId{ id0:id03, id1:id13, } = e;
+                                          *((base + 16) as *mut i64) = wit_bindgen_guest_rust::rt::as_i64(id03);
+                                          *((base + 24) as *mut i64) = wit_bindgen_guest_rust::rt::as_i64(id13);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeF32(e) => {
+                                          *((base + 8) as *mut u8) = (3i32) as u8;
+                                          *((base + 16) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(e);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeF64(e) => {
+                                          *((base + 8) as *mut u8) = (4i32) as u8;
+                                          *((base + 16) as *mut f64) = wit_bindgen_guest_rust::rt::as_f64(e);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeMat4(e) => {
+                                          *((base + 8) as *mut u8) = (5i32) as u8;
+                                          let Mat4{ x:x4, y:y4, z:z4, w:w4, } = e;
+                                          let Vec4{ x:x5, y:y5, z:z5, w:w5, } = x4;
+                                          *((base + 16) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x5);
+                                          *((base + 20) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y5);
+                                          *((base + 24) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z5);
+                                          *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w5);
+                                          let Vec4{ x:x6, y:y6, z:z6, w:w6, } = y4;
+                                          *((base + 32) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x6);
+                                          *((base + 36) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y6);
+                                          *((base + 40) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z6);
+                                          *((base + 44) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w6);
+                                          let Vec4{ x:x7, y:y7, z:z7, w:w7, } = z4;
+                                          *((base + 48) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x7);
+                                          *((base + 52) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y7);
+                                          *((base + 56) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z7);
+                                          *((base + 60) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w7);
+                                          let Vec4{ x:x8, y:y8, z:z8, w:w8, } = w4;
+                                          *((base + 64) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x8);
+                                          *((base + 68) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y8);
+                                          *((base + 72) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z8);
+                                          *((base + 76) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w8);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeI32(e) => {
+                                          *((base + 8) as *mut u8) = (6i32) as u8;
+                                          *((base + 16) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(e);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeQuat(e) => {
+                                          *((base + 8) as *mut u8) = (7i32) as u8;
+                                          let Quat{ x:x9, y:y9, z:z9, w:w9, } = e;
+                                          *((base + 16) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x9);
+                                          *((base + 20) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y9);
+                                          *((base + 24) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z9);
+                                          *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w9);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeString(e) => {
+                                          *((base + 8) as *mut u8) = (8i32) as u8;
+                                          let vec10 = e;
+                                          let ptr10 = vec10.as_ptr() as i32;
+                                          let len10 = vec10.len() as i32;
+                                          *((base + 20) as *mut i32) = len10;
+                                          *((base + 16) as *mut i32) = ptr10;
+                                          
+                                        },
+                                        ComponentTypeParam::TypeU32(e) => {
+                                          *((base + 8) as *mut u8) = (9i32) as u8;
+                                          *((base + 16) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(e);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeU64(e) => {
+                                          *((base + 8) as *mut u8) = (10i32) as u8;
+                                          *((base + 16) as *mut i64) = wit_bindgen_guest_rust::rt::as_i64(e);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeVec2(e) => {
+                                          *((base + 8) as *mut u8) = (11i32) as u8;
+                                          let Vec2{ x:x11, y:y11, } = e;
+                                          *((base + 16) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x11);
+                                          *((base + 20) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y11);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeVec3(e) => {
+                                          *((base + 8) as *mut u8) = (12i32) as u8;
+                                          let Vec3{ x:x12, y:y12, z:z12, } = e;
+                                          *((base + 16) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x12);
+                                          *((base + 20) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y12);
+                                          *((base + 24) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z12);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeVec4(e) => {
+                                          *((base + 8) as *mut u8) = (13i32) as u8;
+                                          let Vec4{ x:x13, y:y13, z:z13, w:w13, } = e;
+                                          *((base + 16) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x13);
+                                          *((base + 20) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y13);
+                                          *((base + 24) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z13);
+                                          *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w13);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeUvec2(e) => {
+                                          *((base + 8) as *mut u8) = (14i32) as u8;
+                                          let Uvec2{ x:x14, y:y14, } = e;
+                                          *((base + 16) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(x14);
+                                          *((base + 20) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(y14);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeUvec3(e) => {
+                                          *((base + 8) as *mut u8) = (15i32) as u8;
+                                          let Uvec3{ x:x15, y:y15, z:z15, } = e;
+                                          *((base + 16) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(x15);
+                                          *((base + 20) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(y15);
+                                          *((base + 24) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(z15);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeUvec4(e) => {
+                                          *((base + 8) as *mut u8) = (16i32) as u8;
+                                          let Uvec4{ x:x16, y:y16, z:z16, w:w16, } = e;
+                                          *((base + 16) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(x16);
+                                          *((base + 20) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(y16);
+                                          *((base + 24) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(z16);
+                                          *((base + 28) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(w16);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeList(e) => {
+                                          *((base + 8) as *mut u8) = (17i32) as u8;
+                                          match e {
+                                            ComponentListTypeParam::TypeEmpty(e) => {
+                                              *((base + 16) as *mut u8) = (0i32) as u8;
+                                              let vec17 = e;
+                                              let ptr17 = vec17.as_ptr() as i32;
+                                              let len17 = vec17.len() as i32;
+                                              *((base + 24) as *mut i32) = len17;
+                                              *((base + 20) as *mut i32) = ptr17;
+                                              
+                                            },
+                                            ComponentListTypeParam::TypeBool(e) => {
+                                              *((base + 16) as *mut u8) = (1i32) as u8;
+                                              let vec18 = e;
+                                              let len18 = vec18.len() as i32;
+                                              let layout18 = core::alloc::Layout::from_size_align_unchecked(vec18.len() * 1, 1);
+                                              let result18 = if layout18.size() != 0
+                                              {
+                                                let ptr = std::alloc::alloc(layout18);
+                                                if ptr.is_null()
+                                                {
+                                                  std::alloc::handle_alloc_error(layout18);
+                                                }
+                                                ptr
+                                              }else {
+                                                std::ptr::null_mut()
+                                              };
+                                              for (i, e) in vec18.into_iter().enumerate() {
+                                                let base = result18 as i32 + (i as i32) * 1;
+                                                {
+                                                  *((base + 0) as *mut u8) = (match e { true => 1, false => 0 }) as u8;
+                                                  
+                                                }}
+                                                *((base + 24) as *mut i32) = len18;
+                                                *((base + 20) as *mut i32) = result18 as i32;
+                                                cleanup_list.extend_from_slice(&[(result18, layout18),]);
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeEntityId(e) => {
+                                                *((base + 16) as *mut u8) = (2i32) as u8;
+                                                let vec19 = e;
+                                                let ptr19 = vec19.as_ptr() as i32;
+                                                let len19 = vec19.len() as i32;
+                                                *((base + 24) as *mut i32) = len19;
+                                                *((base + 20) as *mut i32) = ptr19;
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeF32(e) => {
+                                                *((base + 16) as *mut u8) = (3i32) as u8;
+                                                let vec20 = e;
+                                                let ptr20 = vec20.as_ptr() as i32;
+                                                let len20 = vec20.len() as i32;
+                                                *((base + 24) as *mut i32) = len20;
+                                                *((base + 20) as *mut i32) = ptr20;
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeF64(e) => {
+                                                *((base + 16) as *mut u8) = (4i32) as u8;
+                                                let vec21 = e;
+                                                let ptr21 = vec21.as_ptr() as i32;
+                                                let len21 = vec21.len() as i32;
+                                                *((base + 24) as *mut i32) = len21;
+                                                *((base + 20) as *mut i32) = ptr21;
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeMat4(e) => {
+                                                *((base + 16) as *mut u8) = (5i32) as u8;
+                                                let vec22 = e;
+                                                let ptr22 = vec22.as_ptr() as i32;
+                                                let len22 = vec22.len() as i32;
+                                                *((base + 24) as *mut i32) = len22;
+                                                *((base + 20) as *mut i32) = ptr22;
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeI32(e) => {
+                                                *((base + 16) as *mut u8) = (6i32) as u8;
+                                                let vec23 = e;
+                                                let ptr23 = vec23.as_ptr() as i32;
+                                                let len23 = vec23.len() as i32;
+                                                *((base + 24) as *mut i32) = len23;
+                                                *((base + 20) as *mut i32) = ptr23;
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeQuat(e) => {
+                                                *((base + 16) as *mut u8) = (7i32) as u8;
+                                                let vec24 = e;
+                                                let ptr24 = vec24.as_ptr() as i32;
+                                                let len24 = vec24.len() as i32;
+                                                *((base + 24) as *mut i32) = len24;
+                                                *((base + 20) as *mut i32) = ptr24;
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeString(e) => {
+                                                *((base + 16) as *mut u8) = (8i32) as u8;
+                                                let vec26 = e;
+                                                let len26 = vec26.len() as i32;
+                                                let layout26 = core::alloc::Layout::from_size_align_unchecked(vec26.len() * 8, 4);
+                                                let result26 = if layout26.size() != 0
+                                                {
+                                                  let ptr = std::alloc::alloc(layout26);
+                                                  if ptr.is_null()
+                                                  {
+                                                    std::alloc::handle_alloc_error(layout26);
+                                                  }
+                                                  ptr
+                                                }else {
+                                                  std::ptr::null_mut()
+                                                };
+                                                for (i, e) in vec26.into_iter().enumerate() {
+                                                  let base = result26 as i32 + (i as i32) * 8;
+                                                  {
+                                                    let vec25 = e;
+                                                    let ptr25 = vec25.as_ptr() as i32;
+                                                    let len25 = vec25.len() as i32;
+                                                    *((base + 4) as *mut i32) = len25;
+                                                    *((base + 0) as *mut i32) = ptr25;
+                                                    
+                                                  }}
+                                                  *((base + 24) as *mut i32) = len26;
+                                                  *((base + 20) as *mut i32) = result26 as i32;
+                                                  cleanup_list.extend_from_slice(&[(result26, layout26),]);
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeU32(e) => {
+                                                  *((base + 16) as *mut u8) = (9i32) as u8;
+                                                  let vec27 = e;
+                                                  let ptr27 = vec27.as_ptr() as i32;
+                                                  let len27 = vec27.len() as i32;
+                                                  *((base + 24) as *mut i32) = len27;
+                                                  *((base + 20) as *mut i32) = ptr27;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeU64(e) => {
+                                                  *((base + 16) as *mut u8) = (10i32) as u8;
+                                                  let vec28 = e;
+                                                  let ptr28 = vec28.as_ptr() as i32;
+                                                  let len28 = vec28.len() as i32;
+                                                  *((base + 24) as *mut i32) = len28;
+                                                  *((base + 20) as *mut i32) = ptr28;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeVec2(e) => {
+                                                  *((base + 16) as *mut u8) = (11i32) as u8;
+                                                  let vec29 = e;
+                                                  let ptr29 = vec29.as_ptr() as i32;
+                                                  let len29 = vec29.len() as i32;
+                                                  *((base + 24) as *mut i32) = len29;
+                                                  *((base + 20) as *mut i32) = ptr29;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeVec3(e) => {
+                                                  *((base + 16) as *mut u8) = (12i32) as u8;
+                                                  let vec30 = e;
+                                                  let ptr30 = vec30.as_ptr() as i32;
+                                                  let len30 = vec30.len() as i32;
+                                                  *((base + 24) as *mut i32) = len30;
+                                                  *((base + 20) as *mut i32) = ptr30;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeVec4(e) => {
+                                                  *((base + 16) as *mut u8) = (13i32) as u8;
+                                                  let vec31 = e;
+                                                  let ptr31 = vec31.as_ptr() as i32;
+                                                  let len31 = vec31.len() as i32;
+                                                  *((base + 24) as *mut i32) = len31;
+                                                  *((base + 20) as *mut i32) = ptr31;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeUvec2(e) => {
+                                                  *((base + 16) as *mut u8) = (14i32) as u8;
+                                                  let vec32 = e;
+                                                  let ptr32 = vec32.as_ptr() as i32;
+                                                  let len32 = vec32.len() as i32;
+                                                  *((base + 24) as *mut i32) = len32;
+                                                  *((base + 20) as *mut i32) = ptr32;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeUvec3(e) => {
+                                                  *((base + 16) as *mut u8) = (15i32) as u8;
+                                                  let vec33 = e;
+                                                  let ptr33 = vec33.as_ptr() as i32;
+                                                  let len33 = vec33.len() as i32;
+                                                  *((base + 24) as *mut i32) = len33;
+                                                  *((base + 20) as *mut i32) = ptr33;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeUvec4(e) => {
+                                                  *((base + 16) as *mut u8) = (16i32) as u8;
+                                                  let vec34 = e;
+                                                  let ptr34 = vec34.as_ptr() as i32;
+                                                  let len34 = vec34.len() as i32;
+                                                  *((base + 24) as *mut i32) = len34;
+                                                  *((base + 20) as *mut i32) = ptr34;
+                                                  
+                                                },
+                                              };
+                                              
+                                            },
+                                            ComponentTypeParam::TypeOption(e) => {
+                                              *((base + 8) as *mut u8) = (18i32) as u8;
+                                              match e {
+                                                ComponentOptionTypeParam::TypeEmpty(e) => {
+                                                  *((base + 16) as *mut u8) = (0i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let () = e;
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeBool(e) => {
+                                                  *((base + 16) as *mut u8) = (1i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      *((base + 25) as *mut u8) = (match e { true => 1, false => 0 }) as u8;
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeEntityId(e) => {
+                                                  *((base + 16) as *mut u8) = (2i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let EntityId{ id0:id036, id1:id136, } = e;
+                                                      *((base + 32) as *mut i64) = wit_bindgen_guest_rust::rt::as_i64(id036);
+                                                      *((base + 40) as *mut i64) = wit_bindgen_guest_rust::rt::as_i64(id136);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeF32(e) => {
+                                                  *((base + 16) as *mut u8) = (3i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(e);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeF64(e) => {
+                                                  *((base + 16) as *mut u8) = (4i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      *((base + 32) as *mut f64) = wit_bindgen_guest_rust::rt::as_f64(e);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeMat4(e) => {
+                                                  *((base + 16) as *mut u8) = (5i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Mat4{ x:x37, y:y37, z:z37, w:w37, } = e;
+                                                      let Vec4{ x:x38, y:y38, z:z38, w:w38, } = x37;
+                                                      *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x38);
+                                                      *((base + 32) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y38);
+                                                      *((base + 36) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z38);
+                                                      *((base + 40) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w38);
+                                                      let Vec4{ x:x39, y:y39, z:z39, w:w39, } = y37;
+                                                      *((base + 44) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x39);
+                                                      *((base + 48) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y39);
+                                                      *((base + 52) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z39);
+                                                      *((base + 56) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w39);
+                                                      let Vec4{ x:x40, y:y40, z:z40, w:w40, } = z37;
+                                                      *((base + 60) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x40);
+                                                      *((base + 64) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y40);
+                                                      *((base + 68) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z40);
+                                                      *((base + 72) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w40);
+                                                      let Vec4{ x:x41, y:y41, z:z41, w:w41, } = w37;
+                                                      *((base + 76) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x41);
+                                                      *((base + 80) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y41);
+                                                      *((base + 84) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z41);
+                                                      *((base + 88) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w41);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeI32(e) => {
+                                                  *((base + 16) as *mut u8) = (6i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      *((base + 28) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(e);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeQuat(e) => {
+                                                  *((base + 16) as *mut u8) = (7i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Quat{ x:x42, y:y42, z:z42, w:w42, } = e;
+                                                      *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x42);
+                                                      *((base + 32) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y42);
+                                                      *((base + 36) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z42);
+                                                      *((base + 40) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w42);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeString(e) => {
+                                                  *((base + 16) as *mut u8) = (8i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let vec43 = e;
+                                                      let ptr43 = vec43.as_ptr() as i32;
+                                                      let len43 = vec43.len() as i32;
+                                                      *((base + 32) as *mut i32) = len43;
+                                                      *((base + 28) as *mut i32) = ptr43;
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeU32(e) => {
+                                                  *((base + 16) as *mut u8) = (9i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      *((base + 28) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(e);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeU64(e) => {
+                                                  *((base + 16) as *mut u8) = (10i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      *((base + 32) as *mut i64) = wit_bindgen_guest_rust::rt::as_i64(e);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeVec2(e) => {
+                                                  *((base + 16) as *mut u8) = (11i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Vec2{ x:x44, y:y44, } = e;
+                                                      *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x44);
+                                                      *((base + 32) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y44);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeVec3(e) => {
+                                                  *((base + 16) as *mut u8) = (12i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Vec3{ x:x45, y:y45, z:z45, } = e;
+                                                      *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x45);
+                                                      *((base + 32) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y45);
+                                                      *((base + 36) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z45);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeVec4(e) => {
+                                                  *((base + 16) as *mut u8) = (13i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Vec4{ x:x46, y:y46, z:z46, w:w46, } = e;
+                                                      *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x46);
+                                                      *((base + 32) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y46);
+                                                      *((base + 36) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z46);
+                                                      *((base + 40) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w46);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeUvec2(e) => {
+                                                  *((base + 16) as *mut u8) = (14i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Uvec2{ x:x47, y:y47, } = e;
+                                                      *((base + 28) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(x47);
+                                                      *((base + 32) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(y47);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeUvec3(e) => {
+                                                  *((base + 16) as *mut u8) = (15i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Uvec3{ x:x48, y:y48, z:z48, } = e;
+                                                      *((base + 28) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(x48);
+                                                      *((base + 32) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(y48);
+                                                      *((base + 36) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(z48);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeUvec4(e) => {
+                                                  *((base + 16) as *mut u8) = (16i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Uvec4{ x:x49, y:y49, z:z49, w:w49, } = e;
+                                                      *((base + 28) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(x49);
+                                                      *((base + 32) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(y49);
+                                                      *((base + 36) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(z49);
+                                                      *((base + 40) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(w49);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                              };
+                                              
+                                            },
+                                          };
+                                          
+                                        }}
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "message-send: func(target: option<record { id0: u64, id1: u64 }>, name: string, data: list<tuple<u32, variant { type-empty(tuple<>), type-bool(bool), type-entity-id(record { id0: u64, id1: u64 }), type-f32(float32), type-f64(float64), type-mat4(record { x: record { x: float32, y: float32, z: float32, w: float32 }, y: record { x: float32, y: float32, z: float32, w: float32 }, z: record { x: float32, y: float32, z: float32, w: float32 }, w: record { x: float32, y: float32, z: float32, w: float32 } }), type-i32(s32), type-quat(record { x: float32, y: float32, z: float32, w: float32 }), type-string(string), type-u32(u32), type-u64(u64), type-vec2(record { x: float32, y: float32 }), type-vec3(record { x: float32, y: float32, z: float32 }), type-vec4(record { x: float32, y: float32, z: float32, w: float32 }), type-uvec2(record { x: u32, y: u32 }), type-uvec3(record { x: u32, y: u32, z: u32 }), type-uvec4(record { x: u32, y: u32, z: u32, w: u32 }), type-list(variant { type-empty(list<tuple<>>), type-bool(list<bool>), type-entity-id(list<record { id0: u64, id1: u64 }>), type-f32(list<float32>), type-f64(list<float64>), type-mat4(list<record { x: record { x: float32, y: float32, z: float32, w: float32 }, y: record { x: float32, y: float32, z: float32, w: float32 }, z: record { x: float32, y: float32, z: float32, w: float32 }, w: record { x: float32, y: float32, z: float32, w: float32 } }>), type-i32(list<s32>), type-quat(list<record { x: float32, y: float32, z: float32, w: float32 }>), type-string(list<string>), type-u32(list<u32>), type-u64(list<u64>), type-vec2(list<record { x: float32, y: float32 }>), type-vec3(list<record { x: float32, y: float32, z: float32 }>), type-vec4(list<record { x: float32, y: float32, z: float32, w: float32 }>), type-uvec2(list<record { x: u32, y: u32 }>), type-uvec3(list<record { x: u32, y: u32, z: u32 }>), type-uvec4(list<record { x: u32, y: u32, z: u32, w: u32 }>) }), type-option(variant { type-empty(option<tuple<>>), type-bool(option<bool>), type-entity-id(option<record { id0: u64, id1: u64 }>), type-f32(option<float32>), type-f64(option<float64>), type-mat4(option<record { x: record { x: float32, y: float32, z: float32, w: float32 }, y: record { x: float32, y: float32, z: float32, w: float32 }, z: record { x: float32, y: float32, z: float32, w: float32 }, w: record { x: float32, y: float32, z: float32, w: float32 } }>), type-i32(option<s32>), type-quat(option<record { x: float32, y: float32, z: float32, w: float32 }>), type-string(option<string>), type-u32(option<u32>), type-u64(option<u64>), type-vec2(option<record { x: float32, y: float32 }>), type-vec3(option<record { x: float32, y: float32, z: float32 }>), type-vec4(option<record { x: float32, y: float32, z: float32, w: float32 }>), type-uvec2(option<record { x: u32, y: u32 }>), type-uvec3(option<record { x: u32, y: u32, z: u32 }>), type-uvec4(option<record { x: u32, y: u32, z: u32, w: u32 }>) }) }>>, reliable: bool) -> unit")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_message-send: func(target: option<record { id0: u64, id1: u64 }>, name: string, data: list<tuple<u32, variant { type-empty(tuple<>), type-bool(bool), type-entity-id(record { id0: u64, id1: u64 }), type-f32(float32), type-f64(float64), type-mat4(record { x: record { x: float32, y: float32, z: float32, w: float32 }, y: record { x: float32, y: float32, z: float32, w: float32 }, z: record { x: float32, y: float32, z: float32, w: float32 }, w: record { x: float32, y: float32, z: float32, w: float32 } }), type-i32(s32), type-quat(record { x: float32, y: float32, z: float32, w: float32 }), type-string(string), type-u32(u32), type-u64(u64), type-vec2(record { x: float32, y: float32 }), type-vec3(record { x: float32, y: float32, z: float32 }), type-vec4(record { x: float32, y: float32, z: float32, w: float32 }), type-uvec2(record { x: u32, y: u32 }), type-uvec3(record { x: u32, y: u32, z: u32 }), type-uvec4(record { x: u32, y: u32, z: u32, w: u32 }), type-list(variant { type-empty(list<tuple<>>), type-bool(list<bool>), type-entity-id(list<record { id0: u64, id1: u64 }>), type-f32(list<float32>), type-f64(list<float64>), type-mat4(list<record { x: record { x: float32, y: float32, z: float32, w: float32 }, y: record { x: float32, y: float32, z: float32, w: float32 }, z: record { x: float32, y: float32, z: float32, w: float32 }, w: record { x: float32, y: float32, z: float32, w: float32 } }>), type-i32(list<s32>), type-quat(list<record { x: float32, y: float32, z: float32, w: float32 }>), type-string(list<string>), type-u32(list<u32>), type-u64(list<u64>), type-vec2(list<record { x: float32, y: float32 }>), type-vec3(list<record { x: float32, y: float32, z: float32 }>), type-vec4(list<record { x: float32, y: float32, z: float32, w: float32 }>), type-uvec2(list<record { x: u32, y: u32 }>), type-uvec3(list<record { x: u32, y: u32, z: u32 }>), type-uvec4(list<record { x: u32, y: u32, z: u32, w: u32 }>) }), type-option(variant { type-empty(option<tuple<>>), type-bool(option<bool>), type-entity-id(option<record { id0: u64, id1: u64 }>), type-f32(option<float32>), type-f64(option<float64>), type-mat4(option<record { x: record { x: float32, y: float32, z: float32, w: float32 }, y: record { x: float32, y: float32, z: float32, w: float32 }, z: record { x: float32, y: float32, z: float32, w: float32 }, w: record { x: float32, y: float32, z: float32, w: float32 } }>), type-i32(option<s32>), type-quat(option<record { x: float32, y: float32, z: float32, w: float32 }>), type-string(option<string>), type-u32(option<u32>), type-u64(option<u64>), type-vec2(option<record { x: float32, y: float32 }>), type-vec3(option<record { x: float32, y: float32, z: float32 }>), type-vec4(option<record { x: float32, y: float32, z: float32, w: float32 }>), type-uvec2(option<record { x: u32, y: u32 }>), type-uvec3(option<record { x: u32, y: u32, z: u32 }>), type-uvec4(option<record { x: u32, y: u32, z: u32, w: u32 }>) }) }>>, reliable: bool) -> unit")]
+                                          fn wit_import(_: i32, _: i64, _: i64, _: i32, _: i32, _: i32, _: i32, _: i32, );
+                                        }
+                                        wit_import(result1_0, result1_1, result1_2, ptr0, len0, result50 as i32, len50, match reliable { true => 1, false => 0 });
+                                        if layout50.size() != 0 {
+                                          std::alloc::dealloc(result50, layout50);
+                                        }
+                                        for (ptr, layout) in cleanup_list {
+                                          
+                                          if layout.size() != 0 {
+                                            
+                                            std::alloc::dealloc(ptr, layout);
+                                            
+                                          }
+                                          
+                                        }
+                                        ()
+                                      }
+                                    }
+                                    pub fn asset_url(path: & str,) -> Option<String>{
+                                      unsafe {
+                                        let vec0 = path;
+                                        let ptr0 = vec0.as_ptr() as i32;
+                                        let len0 = vec0.len() as i32;
+                                        let ptr1 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "asset-url: func(path: string) -> option<string>")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_asset-url: func(path: string) -> option<string>")]
+                                          fn wit_import(_: i32, _: i32, _: i32, );
+                                        }
+                                        wit_import(ptr0, len0, ptr1);
+                                        match i32::from(*((ptr1 + 0) as *const u8)) {
+                                          0 => None,
+                                          1 => Some({
+                                            let len2 = *((ptr1 + 8) as *const i32) as usize;
+                                            
+                                            String::from_utf8(Vec::from_raw_parts(*((ptr1 + 4) as *const i32) as *mut _, len2, len2)).unwrap()
+                                          }),
+                                          _ => panic!("invalid enum discriminant"),
+                                        }
+                                      }
+                                    }
+                                    pub fn instance_current() -> String{
+                                      unsafe {
+                                        let ptr0 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "instance-current: func() -> string")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_instance-current: func() -> string")]
+                                          fn wit_import(_: i32, );
+                                        }
+                                        wit_import(ptr0);
+                                        let len1 = *((ptr0 + 4) as *const i32) as usize;
+                                        String::from_utf8(Vec::from_raw_parts(*((ptr0 + 0) as *const i32) as *mut _, len1, len1)).unwrap()
+                                      }
+                                    }
+                                    pub fn instance_list() -> Vec<(String,u32,)>{
+                                      unsafe {
+                                        let ptr0 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "instance-list: func() -> list<tuple<string, u32>>")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_instance-list: func() -> list<tuple<string, u32>>")]
+                                          fn wit_import(_: i32, );
+                                        }
+                                        wit_import(ptr0);
+                                        let base2 = *((ptr0 + 0) as *const i32);
+                                        let len2 = *((ptr0 + 4) as *const i32);
+                                        let mut result2 = Vec::with_capacity(len2 as usize);
+                                        for i in 0..len2 {
+                                          let base = base2 + i *12;
+                                          result2.push({
+                                            let len1 = *((base + 4) as *const i32) as usize;
+                                            
+                                            (String::from_utf8(Vec::from_raw_parts(*((base + 0) as *const i32) as *mut _, len1, len1)).unwrap(), *((base + 8) as *const i32) as u32)
+                                          });
+                                        }
+                                        if len2 != 0 {
+                                          std::alloc::dealloc(base2 as *mut _, std::alloc::Layout::from_size_align_unchecked((len2 as usize) * 12, 4));
+                                        }
+                                        result2
+                                      }
+                                    }
+                                    pub fn instance_create(empty: bool,) -> String{
+                                      unsafe {
+                                        let ptr0 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "instance-create: func(empty: bool) -> string")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_instance-create: func(empty: bool) -> string")]
+                                          fn wit_import(_: i32, _: i32, );
+                                        }
+                                        wit_import(match empty { true => 1, false => 0 }, ptr0);
+                                        let len1 = *((ptr0 + 4) as *const i32) as usize;
+                                        String::from_utf8(Vec::from_raw_parts(*((ptr0 + 0) as *const i32) as *mut _, len1, len1)).unwrap()
+                                      }
+                                    }
+                                    pub fn instance_move_player(player: EntityId,instance: & str,) -> bool{
+                                      unsafe {
+                                        let EntityId{ id0:id00, id1:id10, } = player;
+                                        let vec1 = instance;
+                                        let ptr1 = vec1.as_ptr() as i32;
+                                        let len1 = vec1.len() as i32;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "instance-move-player: func(player: record { id0: u64, id1: u64 }, instance: string) -> bool")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_instance-move-player: func(player: record { id0: u64, id1: u64 }, instance: string) -> bool")]
+                                          fn wit_import(_: i64, _: i64, _: i32, _: i32, ) -> i32;
+                                        }
+                                        let ret = wit_import(wit_bindgen_guest_rust::rt::as_i64(id00), wit_bindgen_guest_rust::rt::as_i64(id10), ptr1, len1);
+                                        match ret {
+                                          0 => false,
+                                          1 => true,
+                                          _ => panic!("invalid bool discriminant"),
+                                        }
+                                      }
+                                    }
+                                    pub fn instance_remove_if_empty(instance: & str,) -> (){
+                                      unsafe {
+                                        let vec0 = instance;
+                                        let ptr0 = vec0.as_ptr() as i32;
+                                        let len0 = vec0.len() as i32;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "instance-remove-if-empty: func(instance: string) -> unit")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_instance-remove-if-empty: func(instance: string) -> unit")]
+                                          fn wit_import(_: i32, _: i32, );
+                                        }
+                                        wit_import(ptr0, len0);
+                                        ()
+                                      }
+                                    }
+                                    pub fn storage_get(key: & str,) -> Option<Vec<u8>>{
+                                      unsafe {
+                                        let vec0 = key;
+                                        let ptr0 = vec0.as_ptr() as i32;
+                                        let len0 = vec0.len() as i32;
+                                        let ptr1 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "storage-get: func(key: string) -> option<list<u8>>")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_storage-get: func(key: string) -> option<list<u8>>")]
+                                          fn wit_import(_: i32, _: i32, _: i32, );
+                                        }
+                                        wit_import(ptr0, len0, ptr1);
+                                        match i32::from(*((ptr1 + 0) as *const u8)) {
+                                          0 => None,
+                                          1 => Some({
+                                            let len2 = *((ptr1 + 8) as *const i32) as usize;
+                                            
+                                            Vec::from_raw_parts(*((ptr1 + 4) as *const i32) as *mut _, len2, len2)
+                                          }),
+                                          _ => panic!("invalid enum discriminant"),
+                                        }
+                                      }
+                                    }
+                                    pub fn storage_set(key: & str,value: &[u8],) -> Option<String>{
+                                      unsafe {
+                                        let vec0 = key;
+                                        let ptr0 = vec0.as_ptr() as i32;
+                                        let len0 = vec0.len() as i32;
+                                        let vec1 = value;
+                                        let ptr1 = vec1.as_ptr() as i32;
+                                        let len1 = vec1.len() as i32;
+                                        let ptr2 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "storage-set: func(key: string, value: list<u8>) -> option<string>")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_storage-set: func(key: string, value: list<u8>) -> option<string>")]
+                                          fn wit_import(_: i32, _: i32, _: i32, _: i32, _: i32, );
+                                        }
+                                        wit_import(ptr0, len0, ptr1, len1, ptr2);
+                                        match i32::from(*((ptr2 + 0) as *const u8)) {
+                                          0 => None,
+                                          1 => Some({
+                                            let len3 = *((ptr2 + 8) as *const i32) as usize;
+                                            
+                                            String::from_utf8(Vec::from_raw_parts(*((ptr2 + 4) as *const i32) as *mut _, len3, len3)).unwrap()
+                                          }),
+                                          _ => panic!("invalid enum discriminant"),
+                                        }
+                                      }
+                                    }
+                                    pub fn storage_set_entity(key: & str,data: Entity<'_,>,) -> Option<String>{
+                                unsafe {
+                                  let mut cleanup_list = Vec::new();
+                                  let vec0 = key;
+                                  let ptr0 = vec0.as_ptr() as i32;
+                                  let len0 = vec0.len() as i32;
+                                  let vec50 = data;
+                                  let len50 = vec50.len() as i32;
+                                  let layout50 = core::alloc::Layout::from_size_align_unchecked(vec50.len() * 88, 8);
+                                  let result50 = if layout50.size() != 0
+                                  {
+                                    let ptr = std::alloc::alloc(layout50);
+                                    if ptr.is_null()
+                                    {
+                                      std::alloc::handle_alloc_error(layout50);
+                                    }
+                                    ptr
+                                  }else {
+                                    std::ptr::null_mut()
+                                  };
+                                  for (i, e) in vec50.into_iter().enumerate() {
+                                    let base = result50 as i32 + (i as i32) * 88;
+                                    {
+                                      let (t1_0, t1_1, ) = e;
+                                      *((base + 0) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(t1_0);
+                                      match t1_1 {
+                                        ComponentTypeParam::TypeEmpty(e) => {
+                                          *((base + 8) as *mut u8) = (0i32) as u8;
+                                          let () = e;
+                                          
+                                        },
+                                        ComponentTypeParam::TypeBool(e) => {
+                                          *((base + 8) as *mut u8) = (1i32) as u8;
+                                          *((base + 16) as *mut u8) = (match e { true => 1, false => 0 }) as u8;
+                                          
+                                        },
+                                        ComponentTypeParam::TypeEntityId(e) => {
+                                          *((base + 8) as *mut u8) = (2i32) as u8;
+                                          let EntityId{ id0:id03, id1:id13, } = e;
+                                          *((base + 16) as *mut i64) = wit_bindgen_guest_rust::rt::as_i64(id03);
+                                          *((base + 24) as *mut i64) = wit_bindgen_guest_rust::rt::as_i64(id13);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeF32(e) => {
+                                          *((base + 8) as *mut u8) = (3i32) as u8;
+                                          *((base + 16) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(e);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeF64(e) => {
+                                          *((base + 8) as *mut u8) = (4i32) as u8;
+                                          *((base + 16) as *mut f64) = wit_bindgen_guest_rust::rt::as_f64(e);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeMat4(e) => {
+                                          *((base + 8) as *mut u8) = (5i32) as u8;
+                                          let Mat4{ x:x4, y:y4, z:z4, w:w4, } = e;
+                                          let Vec4{ x:x5, y:y5, z:z5, w:w5, } = x4;
+                                          *((base + 16) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x5);
+                                          *((base + 20) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y5);
+                                          *((base + 24) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z5);
+                                          *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w5);
+                                          let Vec4{ x:x6, y:y6, z:z6, w:w6, } = y4;
+                                          *((base + 32) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x6);
+                                          *((base + 36) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y6);
+                                          *((base + 40) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z6);
+                                          *((base + 44) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w6);
+                                          let Vec4{ x:x7, y:y7, z:z7, w:w7, } = z4;
+                                          *((base + 48) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x7);
+                                          *((base + 52) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y7);
+                                          *((base + 56) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z7);
+                                          *((base + 60) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w7);
+                                          let Vec4{ x:x8, y:y8, z:z8, w:w8, } = w4;
+                                          *((base + 64) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x8);
+                                          *((base + 68) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y8);
+                                          *((base + 72) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z8);
+                                          *((base + 76) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w8);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeI32(e) => {
+                                          *((base + 8) as *mut u8) = (6i32) as u8;
+                                          *((base + 16) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(e);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeQuat(e) => {
+                                          *((base + 8) as *mut u8) = (7i32) as u8;
+                                          let Quat{ x:x9, y:y9, z:z9, w:w9, } = e;
+                                          *((base + 16) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x9);
+                                          *((base + 20) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y9);
+                                          *((base + 24) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z9);
+                                          *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w9);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeString(e) => {
+                                          *((base + 8) as *mut u8) = (8i32) as u8;
+                                          let vec10 = e;
+                                          let ptr10 = vec10.as_ptr() as i32;
+                                          let len10 = vec10.len() as i32;
+                                          *((base + 20) as *mut i32) = len10;
+                                          *((base + 16) as *mut i32) = ptr10;
+                                          
+                                        },
+                                        ComponentTypeParam::TypeU32(e) => {
+                                          *((base + 8) as *mut u8) = (9i32) as u8;
+                                          *((base + 16) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(e);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeU64(e) => {
+                                          *((base + 8) as *mut u8) = (10i32) as u8;
+                                          *((base + 16) as *mut i64) = wit_bindgen_guest_rust::rt::as_i64(e);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeVec2(e) => {
+                                          *((base + 8) as *mut u8) = (11i32) as u8;
+                                          let Vec2{ x:x11, y:y11, } = e;
+                                          *((base + 16) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x11);
+                                          *((base + 20) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y11);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeVec3(e) => {
+                                          *((base + 8) as *mut u8) = (12i32) as u8;
+                                          let Vec3{ x:x12, y:y12, z:z12, } = e;
+                                          *((base + 16) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x12);
+                                          *((base + 20) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y12);
+                                          *((base + 24) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z12);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeVec4(e) => {
+                                          *((base + 8) as *mut u8) = (13i32) as u8;
+                                          let Vec4{ x:x13, y:y13, z:z13, w:w13, } = e;
+                                          *((base + 16) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x13);
+                                          *((base + 20) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y13);
+                                          *((base + 24) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z13);
+                                          *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w13);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeUvec2(e) => {
+                                          *((base + 8) as *mut u8) = (14i32) as u8;
+                                          let Uvec2{ x:x14, y:y14, } = e;
+                                          *((base + 16) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(x14);
+                                          *((base + 20) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(y14);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeUvec3(e) => {
+                                          *((base + 8) as *mut u8) = (15i32) as u8;
+                                          let Uvec3{ x:x15, y:y15, z:z15, } = e;
+                                          *((base + 16) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(x15);
+                                          *((base + 20) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(y15);
+                                          *((base + 24) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(z15);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeUvec4(e) => {
+                                          *((base + 8) as *mut u8) = (16i32) as u8;
+                                          let Uvec4{ x:x16, y:y16, z:z16, w:w16, } = e;
+                                          *((base + 16) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(x16);
+                                          *((base + 20) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(y16);
+                                          *((base + 24) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(z16);
+                                          *((base + 28) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(w16);
+                                          
+                                        },
+                                        ComponentTypeParam::TypeList(e) => {
+                                          *((base + 8) as *mut u8) = (17i32) as u8;
+                                          match e {
+                                            ComponentListTypeParam::TypeEmpty(e) => {
+                                              *((base + 16) as *mut u8) = (0i32) as u8;
+                                              let vec17 = e;
+                                              let ptr17 = vec17.as_ptr() as i32;
+                                              let len17 = vec17.len() as i32;
+                                              *((base + 24) as *mut i32) = len17;
+                                              *((base + 20) as *mut i32) = ptr17;
+                                              
+                                            },
+                                            ComponentListTypeParam::TypeBool(e) => {
+                                              *((base + 16) as *mut u8) = (1i32) as u8;
+                                              let vec18 = e;
+                                              let len18 = vec18.len() as i32;
+                                              let layout18 = core::alloc::Layout::from_size_align_unchecked(vec18.len() * 1, 1);
+                                              let result18 = if layout18.size() != 0
+                                              {
+                                                let ptr = std::alloc::alloc(layout18);
+                                                if ptr.is_null()
+                                                {
+                                                  std::alloc::handle_alloc_error(layout18);
+                                                }
+                                                ptr
+                                              }else {
+                                                std::ptr::null_mut()
+                                              };
+                                              for (i, e) in vec18.into_iter().enumerate() {
+                                                let base = result18 as i32 + (i as i32) * 1;
+                                                {
+                                                  *((base + 0) as *mut u8) = (match e { true => 1, false => 0 }) as u8;
+                                                  
+                                                }}
+                                                *((base + 24) as *mut i32) = len18;
+                                                *((base + 20) as *mut i32) = result18 as i32;
+                                                cleanup_list.extend_from_slice(&[(result18, layout18),]);
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeEntityId(e) => {
+                                                *((base + 16) as *mut u8) = (2i32) as u8;
+                                                let vec19 = e;
+                                                let ptr19 = vec19.as_ptr() as i32;
+                                                let len19 = vec19.len() as i32;
+                                                *((base + 24) as *mut i32) = len19;
+                                                *((base + 20) as *mut i32) = ptr19;
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeF32(e) => {
+                                                *((base + 16) as *mut u8) = (3i32) as u8;
+                                                let vec20 = e;
+                                                let ptr20 = vec20.as_ptr() as i32;
+                                                let len20 = vec20.len() as i32;
+                                                *((base + 24) as *mut i32) = len20;
+                                                *((base + 20) as *mut i32) = ptr20;
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeF64(e) => {
+                                                *((base + 16) as *mut u8) = (4i32) as u8;
+                                                let vec21 = e;
+                                                let ptr21 = vec21.as_ptr() as i32;
+                                                let len21 = vec21.len() as i32;
+                                                *((base + 24) as *mut i32) = len21;
+                                                *((base + 20) as *mut i32) = ptr21;
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeMat4(e) => {
+                                                *((base + 16) as *mut u8) = (5i32) as u8;
+                                                let vec22 = e;
+                                                let ptr22 = vec22.as_ptr() as i32;
+                                                let len22 = vec22.len() as i32;
+                                                *((base + 24) as *mut i32) = len22;
+                                                *((base + 20) as *mut i32) = ptr22;
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeI32(e) => {
+                                                *((base + 16) as *mut u8) = (6i32) as u8;
+                                                let vec23 = e;
+                                                let ptr23 = vec23.as_ptr() as i32;
+                                                let len23 = vec23.len() as i32;
+                                                *((base + 24) as *mut i32) = len23;
+                                                *((base + 20) as *mut i32) = ptr23;
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeQuat(e) => {
+                                                *((base + 16) as *mut u8) = (7i32) as u8;
+                                                let vec24 = e;
+                                                let ptr24 = vec24.as_ptr() as i32;
+                                                let len24 = vec24.len() as i32;
+                                                *((base + 24) as *mut i32) = len24;
+                                                *((base + 20) as *mut i32) = ptr24;
+                                                
+                                              },
+                                              ComponentListTypeParam::TypeString(e) => {
+                                                *((base + 16) as *mut u8) = (8i32) as u8;
+                                                let vec26 = e;
+                                                let len26 = vec26.len() as i32;
+                                                let layout26 = core::alloc::Layout::from_size_align_unchecked(vec26.len() * 8, 4);
+                                                let result26 = if layout26.size() != 0
+                                                {
+                                                  let ptr = std::alloc::alloc(layout26);
+                                                  if ptr.is_null()
+                                                  {
+                                                    std::alloc::handle_alloc_error(layout26);
+                                                  }
+                                                  ptr
+                                                }else {
+                                                  std::ptr::null_mut()
+                                                };
+                                                for (i, e) in vec26.into_iter().enumerate() {
+                                                  let base = result26 as i32 + (i as i32) * 8;
+                                                  {
+                                                    let vec25 = e;
+                                                    let ptr25 = vec25.as_ptr() as i32;
+                                                    let len25 = vec25.len() as i32;
+                                                    *((base + 4) as *mut i32) = len25;
+                                                    *((base + 0) as *mut i32) = ptr25;
+                                                    
+                                                  }}
+                                                  *((base + 24) as *mut i32) = len26;
+                                                  *((base + 20) as *mut i32) = result26 as i32;
+                                                  cleanup_list.extend_from_slice(&[(result26, layout26),]);
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeU32(e) => {
+                                                  *((base + 16) as *mut u8) = (9i32) as u8;
+                                                  let vec27 = e;
+                                                  let ptr27 = vec27.as_ptr() as i32;
+                                                  let len27 = vec27.len() as i32;
+                                                  *((base + 24) as *mut i32) = len27;
+                                                  *((base + 20) as *mut i32) = ptr27;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeU64(e) => {
+                                                  *((base + 16) as *mut u8) = (10i32) as u8;
+                                                  let vec28 = e;
+                                                  let ptr28 = vec28.as_ptr() as i32;
+                                                  let len28 = vec28.len() as i32;
+                                                  *((base + 24) as *mut i32) = len28;
+                                                  *((base + 20) as *mut i32) = ptr28;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeVec2(e) => {
+                                                  *((base + 16) as *mut u8) = (11i32) as u8;
+                                                  let vec29 = e;
+                                                  let ptr29 = vec29.as_ptr() as i32;
+                                                  let len29 = vec29.len() as i32;
+                                                  *((base + 24) as *mut i32) = len29;
+                                                  *((base + 20) as *mut i32) = ptr29;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeVec3(e) => {
+                                                  *((base + 16) as *mut u8) = (12i32) as u8;
+                                                  let vec30 = e;
+                                                  let ptr30 = vec30.as_ptr() as i32;
+                                                  let len30 = vec30.len() as i32;
+                                                  *((base + 24) as *mut i32) = len30;
+                                                  *((base + 20) as *mut i32) = ptr30;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeVec4(e) => {
+                                                  *((base + 16) as *mut u8) = (13i32) as u8;
+                                                  let vec31 = e;
+                                                  let ptr31 = vec31.as_ptr() as i32;
+                                                  let len31 = vec31.len() as i32;
+                                                  *((base + 24) as *mut i32) = len31;
+                                                  *((base + 20) as *mut i32) = ptr31;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeUvec2(e) => {
+                                                  *((base + 16) as *mut u8) = (14i32) as u8;
+                                                  let vec32 = e;
+                                                  let ptr32 = vec32.as_ptr() as i32;
+                                                  let len32 = vec32.len() as i32;
+                                                  *((base + 24) as *mut i32) = len32;
+                                                  *((base + 20) as *mut i32) = ptr32;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeUvec3(e) => {
+                                                  *((base + 16) as *mut u8) = (15i32) as u8;
+                                                  let vec33 = e;
+                                                  let ptr33 = vec33.as_ptr() as i32;
+                                                  let len33 = vec33.len() as i32;
+                                                  *((base + 24) as *mut i32) = len33;
+                                                  *((base + 20) as *mut i32) = ptr33;
+                                                  
+                                                },
+                                                ComponentListTypeParam::TypeUvec4(e) => {
+                                                  *((base + 16) as *mut u8) = (16i32) as u8;
+                                                  let vec34 = e;
+                                                  let ptr34 = vec34.as_ptr() as i32;
+                                                  let len34 = vec34.len() as i32;
+                                                  *((base + 24) as *mut i32) = len34;
+                                                  *((base + 20) as *mut i32) = ptr34;
+                                                  
+                                                },
+                                              };
+                                              
+                                            },
+                                            ComponentTypeParam::TypeOption(e) => {
+                                              *((base + 8) as *mut u8) = (18i32) as u8;
+                                              match e {
+                                                ComponentOptionTypeParam::TypeEmpty(e) => {
+                                                  *((base + 16) as *mut u8) = (0i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let () = e;
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeBool(e) => {
+                                                  *((base + 16) as *mut u8) = (1i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      *((base + 25) as *mut u8) = (match e { true => 1, false => 0 }) as u8;
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeEntityId(e) => {
+                                                  *((base + 16) as *mut u8) = (2i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let EntityId{ id0:id036, id1:id136, } = e;
+                                                      *((base + 32) as *mut i64) = wit_bindgen_guest_rust::rt::as_i64(id036);
+                                                      *((base + 40) as *mut i64) = wit_bindgen_guest_rust::rt::as_i64(id136);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeF32(e) => {
+                                                  *((base + 16) as *mut u8) = (3i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(e);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeF64(e) => {
+                                                  *((base + 16) as *mut u8) = (4i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      *((base + 32) as *mut f64) = wit_bindgen_guest_rust::rt::as_f64(e);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeMat4(e) => {
+                                                  *((base + 16) as *mut u8) = (5i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Mat4{ x:x37, y:y37, z:z37, w:w37, } = e;
+                                                      let Vec4{ x:x38, y:y38, z:z38, w:w38, } = x37;
+                                                      *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x38);
+                                                      *((base + 32) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y38);
+                                                      *((base + 36) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z38);
+                                                      *((base + 40) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w38);
+                                                      let Vec4{ x:x39, y:y39, z:z39, w:w39, } = y37;
+                                                      *((base + 44) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x39);
+                                                      *((base + 48) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y39);
+                                                      *((base + 52) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z39);
+                                                      *((base + 56) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w39);
+                                                      let Vec4{ x:x40, y:y40, z:z40, w:w40, } = z37;
+                                                      *((base + 60) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x40);
+                                                      *((base + 64) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y40);
+                                                      *((base + 68) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z40);
+                                                      *((base + 72) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w40);
+                                                      let Vec4{ x:x41, y:y41, z:z41, w:w41, } = w37;
+                                                      *((base + 76) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x41);
+                                                      *((base + 80) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y41);
+                                                      *((base + 84) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z41);
+                                                      *((base + 88) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w41);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeI32(e) => {
+                                                  *((base + 16) as *mut u8) = (6i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      *((base + 28) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(e);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeQuat(e) => {
+                                                  *((base + 16) as *mut u8) = (7i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Quat{ x:x42, y:y42, z:z42, w:w42, } = e;
+                                                      *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x42);
+                                                      *((base + 32) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y42);
+                                                      *((base + 36) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z42);
+                                                      *((base + 40) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w42);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeString(e) => {
+                                                  *((base + 16) as *mut u8) = (8i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let vec43 = e;
+                                                      let ptr43 = vec43.as_ptr() as i32;
+                                                      let len43 = vec43.len() as i32;
+                                                      *((base + 32) as *mut i32) = len43;
+                                                      *((base + 28) as *mut i32) = ptr43;
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeU32(e) => {
+                                                  *((base + 16) as *mut u8) = (9i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      *((base + 28) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(e);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeU64(e) => {
+                                                  *((base + 16) as *mut u8) = (10i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      *((base + 32) as *mut i64) = wit_bindgen_guest_rust::rt::as_i64(e);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeVec2(e) => {
+                                                  *((base + 16) as *mut u8) = (11i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Vec2{ x:x44, y:y44, } = e;
+                                                      *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x44);
+                                                      *((base + 32) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y44);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeVec3(e) => {
+                                                  *((base + 16) as *mut u8) = (12i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Vec3{ x:x45, y:y45, z:z45, } = e;
+                                                      *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x45);
+                                                      *((base + 32) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y45);
+                                                      *((base + 36) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z45);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeVec4(e) => {
+                                                  *((base + 16) as *mut u8) = (13i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Vec4{ x:x46, y:y46, z:z46, w:w46, } = e;
+                                                      *((base + 28) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(x46);
+                                                      *((base + 32) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(y46);
+                                                      *((base + 36) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(z46);
+                                                      *((base + 40) as *mut f32) = wit_bindgen_guest_rust::rt::as_f32(w46);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeUvec2(e) => {
+                                                  *((base + 16) as *mut u8) = (14i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Uvec2{ x:x47, y:y47, } = e;
+                                                      *((base + 28) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(x47);
+                                                      *((base + 32) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(y47);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeUvec3(e) => {
+                                                  *((base + 16) as *mut u8) = (15i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Uvec3{ x:x48, y:y48, z:z48, } = e;
+                                                      *((base + 28) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(x48);
+                                                      *((base + 32) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(y48);
+                                                      *((base + 36) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(z48);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                                ComponentOptionTypeParam::TypeUvec4(e) => {
+                                                  *((base + 16) as *mut u8) = (16i32) as u8;
+                                                  match e {
+                                                    Some(e) => {
+                                                      *((base + 24) as *mut u8) = (1i32) as u8;
+                                                      let Uvec4{ x:x49, y:y49, z:z49, w:w49, } = e;
+                                                      *((base + 28) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(x49);
+                                                      *((base + 32) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(y49);
+                                                      *((base + 36) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(z49);
+                                                      *((base + 40) as *mut i32) = wit_bindgen_guest_rust::rt::as_i32(w49);
+                                                      
+                                                    },
+                                                    None => {
+                                                      let e = ();
+                                                      {
+                                                        *((base + 24) as *mut u8) = (0i32) as u8;
+                                                        let () = e;
+                                                        
+                                                      }
+                                                    },
+                                                  };
+                                                },
+                                              };
+                                              
+                                            },
+                                          };
+                                          
+                                        }}
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "storage-set-entity: func(key: string, data: list<tuple<u32, variant { type-empty(tuple<>), type-bool(bool), type-entity-id(record { id0: u64, id1: u64 }), type-f32(float32), type-f64(float64), type-mat4(record { x: record { x: float32, y: float32, z: float32, w: float32 }, y: record { x: float32, y: float32, z: float32, w: float32 }, z: record { x: float32, y: float32, z: float32, w: float32 }, w: record { x: float32, y: float32, z: float32, w: float32 } }), type-i32(s32), type-quat(record { x: float32, y: float32, z: float32, w: float32 }), type-string(string), type-u32(u32), type-u64(u64), type-vec2(record { x: float32, y: float32 }), type-vec3(record { x: float32, y: float32, z: float32 }), type-vec4(record { x: float32, y: float32, z: float32, w: float32 }), type-uvec2(record { x: u32, y: u32 }), type-uvec3(record { x: u32, y: u32, z: u32 }), type-uvec4(record { x: u32, y: u32, z: u32, w: u32 }), type-list(variant { type-empty(list<tuple<>>), type-bool(list<bool>), type-entity-id(list<record { id0: u64, id1: u64 }>), type-f32(list<float32>), type-f64(list<float64>), type-mat4(list<record { x: record { x: float32, y: float32, z: float32, w: float32 }, y: record { x: float32, y: float32, z: float32, w: float32 }, z: record { x: float32, y: float32, z: float32, w: float32 }, w: record { x: float32, y: float32, z: float32, w: float32 } }>), type-i32(list<s32>), type-quat(list<record { x: float32, y: float32, z: float32, w: float32 }>), type-string(list<string>), type-u32(list<u32>), type-u64(list<u64>), type-vec2(list<record { x: float32, y: float32 }>), type-vec3(list<record { x: float32, y: float32, z: float32 }>), type-vec4(list<record { x: float32, y: float32, z: float32, w: float32 }>), type-uvec2(list<record { x: u32, y: u32 }>), type-uvec3(list<record { x: u32, y: u32, z: u32 }>), type-uvec4(list<record { x: u32, y: u32, z: u32, w: u32 }>) }), type-option(variant { type-empty(option<tuple<>>), type-bool(option<bool>), type-entity-id(option<record { id0: u64, id1: u64 }>), type-f32(option<float32>), type-f64(option<float64>), type-mat4(option<record { x: record { x: float32, y: float32, z: float32, w: float32 }, y: record { x: float32, y: float32, z: float32, w: float32 }, z: record { x: float32, y: float32, z: float32, w: float32 }, w: record { x: float32, y: float32, z: float32, w: float32 } }>), type-i32(option<s32>), type-quat(option<record { x: float32, y: float32, z: float32, w: float32 }>), type-string(option<string>), type-u32(option<u32>), type-u64(option<u64>), type-vec2(option<record { x: float32, y: float32 }>), type-vec3(option<record { x: float32, y: float32, z: float32 }>), type-vec4(option<record { x: float32, y: float32, z: float32, w: float32 }>), type-uvec2(option<record { x: u32, y: u32 }>), type-uvec3(option<record { x: u32, y: u32, z: u32 }>), type-uvec4(option<record { x: u32, y: u32, z: u32, w: u32 }>) }) }>>) -> option<string>")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_storage-set-entity: func(key: string, data: list<tuple<u32, variant { type-empty(tuple<>), type-bool(bool), type-entity-id(record { id0: u64, id1: u64 }), type-f32(float32), type-f64(float64), type-mat4(record { x: record { x: float32, y: float32, z: float32, w: float32 }, y: record { x: float32, y: float32, z: float32, w: float32 }, z: record { x: float32, y: float32, z: float32, w: float32 }, w: record { x: float32, y: float32, z: float32, w: float32 } }), type-i32(s32), type-quat(record { x: float32, y: float32, z: float32, w: float32 }), type-string(string), type-u32(u32), type-u64(u64), type-vec2(record { x: float32, y: float32 }), type-vec3(record { x: float32, y: float32, z: float32 }), type-vec4(record { x: float32, y: float32, z: float32, w: float32 }), type-uvec2(record { x: u32, y: u32 }), type-uvec3(record { x: u32, y: u32, z: u32 }), type-uvec4(record { x: u32, y: u32, z: u32, w: u32 }), type-list(variant { type-empty(list<tuple<>>), type-bool(list<bool>), type-entity-id(list<record { id0: u64, id1: u64 }>), type-f32(list<float32>), type-f64(list<float64>), type-mat4(list<record { x: record { x: float32, y: float32, z: float32, w: float32 }, y: record { x: float32, y: float32, z: float32, w: float32 }, z: record { x: float32, y: float32, z: float32, w: float32 }, w: record { x: float32, y: float32, z: float32, w: float32 } }>), type-i32(list<s32>), type-quat(list<record { x: float32, y: float32, z: float32, w: float32 }>), type-string(list<string>), type-u32(list<u32>), type-u64(list<u64>), type-vec2(list<record { x: float32, y: float32 }>), type-vec3(list<record { x: float32, y: float32, z: float32 }>), type-vec4(list<record { x: float32, y: float32, z: float32, w: float32 }>), type-uvec2(list<record { x: u32, y: u32 }>), type-uvec3(list<record { x: u32, y: u32, z: u32 }>), type-uvec4(list<record { x: u32, y: u32, z: u32, w: u32 }>) }), type-option(variant { type-empty(option<tuple<>>), type-bool(option<bool>), type-entity-id(option<record { id0: u64, id1: u64 }>), type-f32(option<float32>), type-f64(option<float64>), type-mat4(option<record { x: record { x: float32, y: float32, z: float32, w: float32 }, y: record { x: float32, y: float32, z: float32, w: float32 }, z: record { x: float32, y: float32, z: float32, w: float32 }, w: record { x: float32, y: float32, z: float32, w: float32 } }>), type-i32(option<s32>), type-quat(option<record { x: float32, y: float32, z: float32, w: float32 }>), type-string(option<string>), type-u32(option<u32>), type-u64(option<u64>), type-vec2(option<record { x: float32, y: float32 }>), type-vec3(option<record { x: float32, y: float32, z: float32 }>), type-vec4(option<record { x: float32, y: float32, z: float32, w: float32 }>), type-uvec2(option<record { x: u32, y: u32 }>), type-uvec3(option<record { x: u32, y: u32, z: u32 }>), type-uvec4(option<record { x: u32, y: u32, z: u32, w: u32 }>) }) }>>) -> option<string>")]
+                                          fn wit_import(_: i32, _: i32, _: i32, _: i32, _: i32, );
+                                        }
+                                        let ptr51 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
+                                        wit_import(ptr0, len0, result50 as i32, len50, ptr51);
+                                        if layout50.size() != 0 {
+                                          std::alloc::dealloc(result50, layout50);
+                                        }
+                                        for (ptr, layout) in cleanup_list {
+                                          
+                                          if layout.size() != 0 {
+                                            
+                                            std::alloc::dealloc(ptr, layout);
+                                            
+                                          }
+                                          
+                                        }
+                                        match i32::from(*((ptr51 + 0) as *const u8)) {
+                                          0 => None,
+                                          1 => Some({
+                                            let len52 = *((ptr51 + 8) as *const i32) as usize;
+                                            
+                                            String::from_utf8(Vec::from_raw_parts(*((ptr51 + 4) as *const i32) as *mut _, len52, len52)).unwrap()
+                                          }),
+                                          _ => panic!("invalid enum discriminant"),
+                                        }
+                                      }
+                                    }
+                                    pub fn storage_load_entity(key: & str,entity: EntityId,) -> bool{
+                                      unsafe {
+                                        let vec0 = key;
+                                        let ptr0 = vec0.as_ptr() as i32;
+                                        let len0 = vec0.len() as i32;
+                                        let EntityId{ id0:id01, id1:id11, } = entity;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "storage-load-entity: func(key: string, entity: record { id0: u64, id1: u64 }) -> bool")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_storage-load-entity: func(key: string, entity: record { id0: u64, id1: u64 }) -> bool")]
+                                          fn wit_import(_: i32, _: i32, _: i64, _: i64, ) -> i32;
+                                        }
+                                        let ret = wit_import(ptr0, len0, wit_bindgen_guest_rust::rt::as_i64(id01), wit_bindgen_guest_rust::rt::as_i64(id11));
+                                        match ret {
+                                          0 => false,
+                                          1 => true,
+                                          _ => panic!("invalid bool discriminant"),
+                                        }
+                                      }
+                                    }
+                                    pub fn storage_delete(key: & str,) -> bool{
+                                      unsafe {
+                                        let vec0 = key;
+                                        let ptr0 = vec0.as_ptr() as i32;
+                                        let len0 = vec0.len() as i32;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "storage-delete: func(key: string) -> bool")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_storage-delete: func(key: string) -> bool")]
+                                          fn wit_import(_: i32, _: i32, ) -> i32;
+                                        }
+                                        let ret = wit_import(ptr0, len0);
+                                        match ret {
+                                          0 => false,
+                                          1 => true,
+                                          _ => panic!("invalid bool discriminant"),
+                                        }
+                                      }
+                                    }
+                                    pub fn storage_list(prefix: & str,) -> Vec<String>{
+                                      unsafe {
+                                        let vec0 = prefix;
+                                        let ptr0 = vec0.as_ptr() as i32;
+                                        let len0 = vec0.len() as i32;
+                                        let ptr1 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "storage-list: func(prefix: string) -> list<string>")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_storage-list: func(prefix: string) -> list<string>")]
+                                          fn wit_import(_: i32, _: i32, _: i32, );
+                                        }
+                                        wit_import(ptr0, len0, ptr1);
+                                        let base3 = *((ptr1 + 0) as *const i32);
+                                        let len3 = *((ptr1 + 4) as *const i32);
+                                        let mut result3 = Vec::with_capacity(len3 as usize);
+                                        for i in 0..len3 {
+                                          let base = base3 + i *8;
+                                          result3.push({
+                                            let len2 = *((base + 4) as *const i32) as usize;
+                                            
+                                            String::from_utf8(Vec::from_raw_parts(*((base + 0) as *const i32) as *mut _, len2, len2)).unwrap()
+                                          });
+                                        }
+                                        if len3 != 0 {
+                                          std::alloc::dealloc(base3 as *mut _, std::alloc::Layout::from_size_align_unchecked((len3 as usize) * 8, 4));
+                                        }
+                                        result3
+                                      }
+                                    }
+                                    pub fn storage_is_flushed() -> bool{
+                                      unsafe {
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "storage-is-flushed: func() -> bool")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_storage-is-flushed: func() -> bool")]
+                                          fn wit_import() -> i32;
+                                        }
+                                        let ret = wit_import();
+                                        match ret {
+                                          0 => false,
+                                          1 => true,
+                                          _ => panic!("invalid bool discriminant"),
+                                        }
+                                      }
+                                    }
+                                    pub fn storage_write_error() -> Option<String>{
+                                      unsafe {
+                                        let ptr0 = __HOST_RET_AREA.0.as_mut_ptr() as i32;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "storage-write-error: func() -> option<string>")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_storage-write-error: func() -> option<string>")]
+                                          fn wit_import(_: i32, );
+                                        }
+                                        wit_import(ptr0);
+                                        match i32::from(*((ptr0 + 0) as *const u8)) {
+                                          0 => None,
+                                          1 => Some({
+                                            let len1 = *((ptr0 + 8) as *const i32) as usize;
+                                            
+                                            String::from_utf8(Vec::from_raw_parts(*((ptr0 + 4) as *const i32) as *mut _, len1, len1)).unwrap()
+                                          }),
+                                          _ => panic!("invalid enum discriminant"),
+                                        }
+                                      }
+                                    }
+                                    pub fn persistence_save() -> (){
+                                      unsafe {
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "persistence-save: func() -> unit")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_persistence-save: func() -> unit")]
+                                          fn wit_import();
+                                        }
+                                        wit_import();
+                                        ()
+                                      }
+                                    }
+                                    pub fn debug_draw_line(category: & str,start: Vec3,end: Vec3,color: Vec3,lifetime: f32,) -> (){
+                                      unsafe {
+                                        let vec0 = category;
+                                        let ptr0 = vec0.as_ptr() as i32;
+                                        let len0 = vec0.len() as i32;
+                                        let Vec3{ x:x1, y:y1, z:z1, } = start;
+                                        let Vec3{ x:x2, y:y2, z:z2, } = end;
+                                        let Vec3{ x:x3, y:y3, z:z3, } = color;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "debug-draw-line: func(category: string, start: record { x: float32, y: float32, z: float32 }, end: record { x: float32, y: float32, z: float32 }, color: record { x: float32, y: float32, z: float32 }, lifetime: float32) -> unit")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_debug-draw-line: func(category: string, start: record { x: float32, y: float32, z: float32 }, end: record { x: float32, y: float32, z: float32 }, color: record { x: float32, y: float32, z: float32 }, lifetime: float32) -> unit")]
+                                          fn wit_import(_: i32, _: i32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, );
+                                        }
+                                        wit_import(ptr0, len0, wit_bindgen_guest_rust::rt::as_f32(x1), wit_bindgen_guest_rust::rt::as_f32(y1), wit_bindgen_guest_rust::rt::as_f32(z1), wit_bindgen_guest_rust::rt::as_f32(x2), wit_bindgen_guest_rust::rt::as_f32(y2), wit_bindgen_guest_rust::rt::as_f32(z2), wit_bindgen_guest_rust::rt::as_f32(x3), wit_bindgen_guest_rust::rt::as_f32(y3), wit_bindgen_guest_rust::rt::as_f32(z3), wit_bindgen_guest_rust::rt::as_f32(lifetime));
+                                        ()
+                                      }
+                                    }
+                                    pub fn debug_draw_arrow(category: & str,start: Vec3,end: Vec3,color: Vec3,lifetime: f32,) -> (){
+                                      unsafe {
+                                        let vec0 = category;
+                                        let ptr0 = vec0.as_ptr() as i32;
+                                        let len0 = vec0.len() as i32;
+                                        let Vec3{ x:x1, y:y1, z:z1, } = start;
+                                        let Vec3{ x:x2, y:y2, z:z2, } = end;
+                                        let Vec3{ x:x3, y:y3, z:z3, } = color;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "debug-draw-arrow: func(category: string, start: record { x: float32, y: float32, z: float32 }, end: record { x: float32, y: float32, z: float32 }, color: record { x: float32, y: float32, z: float32 }, lifetime: float32) -> unit")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_debug-draw-arrow: func(category: string, start: record { x: float32, y: float32, z: float32 }, end: record { x: float32, y: float32, z: float32 }, color: record { x: float32, y: float32, z: float32 }, lifetime: float32) -> unit")]
+                                          fn wit_import(_: i32, _: i32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, );
+                                        }
+                                        wit_import(ptr0, len0, wit_bindgen_guest_rust::rt::as_f32(x1), wit_bindgen_guest_rust::rt::as_f32(y1), wit_bindgen_guest_rust::rt::as_f32(z1), wit_bindgen_guest_rust::rt::as_f32(x2), wit_bindgen_guest_rust::rt::as_f32(y2), wit_bindgen_guest_rust::rt::as_f32(z2), wit_bindgen_guest_rust::rt::as_f32(x3), wit_bindgen_guest_rust::rt::as_f32(y3), wit_bindgen_guest_rust::rt::as_f32(z3), wit_bindgen_guest_rust::rt::as_f32(lifetime));
+                                        ()
+                                      }
+                                    }
+                                    pub fn debug_draw_sphere(category: & str,center: Vec3,radius: f32,color: Vec3,lifetime: f32,) -> (){
+                                      unsafe {
+                                        let vec0 = category;
+                                        let ptr0 = vec0.as_ptr() as i32;
+                                        let len0 = vec0.len() as i32;
+                                        let Vec3{ x:x1, y:y1, z:z1, } = center;
+                                        let Vec3{ x:x3, y:y3, z:z3, } = color;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "debug-draw-sphere: func(category: string, center: record { x: float32, y: float32, z: float32 }, radius: float32, color: record { x: float32, y: float32, z: float32 }, lifetime: float32) -> unit")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_debug-draw-sphere: func(category: string, center: record { x: float32, y: float32, z: float32 }, radius: float32, color: record { x: float32, y: float32, z: float32 }, lifetime: float32) -> unit")]
+                                          fn wit_import(_: i32, _: i32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, );
+                                        }
+                                        wit_import(ptr0, len0, wit_bindgen_guest_rust::rt::as_f32(x1), wit_bindgen_guest_rust::rt::as_f32(y1), wit_bindgen_guest_rust::rt::as_f32(z1), wit_bindgen_guest_rust::rt::as_f32(radius), wit_bindgen_guest_rust::rt::as_f32(x3), wit_bindgen_guest_rust::rt::as_f32(y3), wit_bindgen_guest_rust::rt::as_f32(z3), wit_bindgen_guest_rust::rt::as_f32(lifetime));
+                                        ()
+                                      }
+                                    }
+                                    pub fn debug_draw_box(category: & str,center: Vec3,half_extents: Vec3,color: Vec3,lifetime: f32,) -> (){
+                                      unsafe {
+                                        let vec0 = category;
+                                        let ptr0 = vec0.as_ptr() as i32;
+                                        let len0 = vec0.len() as i32;
+                                        let Vec3{ x:x1, y:y1, z:z1, } = center;
+                                        let Vec3{ x:x2, y:y2, z:z2, } = half_extents;
+                                        let Vec3{ x:x3, y:y3, z:z3, } = color;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "debug-draw-box: func(category: string, center: record { x: float32, y: float32, z: float32 }, half-extents: record { x: float32, y: float32, z: float32 }, color: record { x: float32, y: float32, z: float32 }, lifetime: float32) -> unit")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_debug-draw-box: func(category: string, center: record { x: float32, y: float32, z: float32 }, half-extents: record { x: float32, y: float32, z: float32 }, color: record { x: float32, y: float32, z: float32 }, lifetime: float32) -> unit")]
+                                          fn wit_import(_: i32, _: i32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, );
+                                        }
+                                        wit_import(ptr0, len0, wit_bindgen_guest_rust::rt::as_f32(x1), wit_bindgen_guest_rust::rt::as_f32(y1), wit_bindgen_guest_rust::rt::as_f32(z1), wit_bindgen_guest_rust::rt::as_f32(x2), wit_bindgen_guest_rust::rt::as_f32(y2), wit_bindgen_guest_rust::rt::as_f32(z2), wit_bindgen_guest_rust::rt::as_f32(x3), wit_bindgen_guest_rust::rt::as_f32(y3), wit_bindgen_guest_rust::rt::as_f32(z3), wit_bindgen_guest_rust::rt::as_f32(lifetime));
+                                        ()
+                                      }
+                                    }
+                                    pub fn debug_draw_text(category: & str,position: Vec3,text: & str,color: Vec3,lifetime: f32,) -> (){
+                                      unsafe {
+                                        let vec0 = category;
+                                        let ptr0 = vec0.as_ptr() as i32;
+                                        let len0 = vec0.len() as i32;
+                                        let Vec3{ x:x1, y:y1, z:z1, } = position;
+                                        let vec2 = text;
+                                        let ptr2 = vec2.as_ptr() as i32;
+                                        let len2 = vec2.len() as i32;
+                                        let Vec3{ x:x3, y:y3, z:z3, } = color;
+                                        #[link(wasm_import_module = "host")]
+                                        extern "C" {
+                                          #[cfg_attr(target_arch = "wasm32", link_name = "debug-draw-text: func(category: string, position: record { x: float32, y: float32, z: float32 }, text: string, color: record { x: float32, y: float32, z: float32 }, lifetime: float32) -> unit")]
+                                          #[cfg_attr(not(target_arch = "wasm32"), link_name = "host_debug-draw-text: func(category: string, position: record { x: float32, y: float32, z: float32 }, text: string, color: record { x: float32, y: float32, z: float32 }, lifetime: float32) -> unit")]
+                                          fn wit_import(_: i32, _: i32, _: f32, _: f32, _: f32, _: i32, _: i32, _: f32, _: f32, _: f32, _: f32, );
+                                        }
+                                        wit_import(ptr0, len0, wit_bindgen_guest_rust::rt::as_f32(x1), wit_bindgen_guest_rust::rt::as_f32(y1), wit_bindgen_guest_rust::rt::as_f32(z1), ptr2, len2, wit_bindgen_guest_rust::rt::as_f32(x3), wit_bindgen_guest_rust::rt::as_f32(y3), wit_bindgen_guest_rust::rt::as_f32(z3), wit_bindgen_guest_rust::rt::as_f32(lifetime));
+                                        ()
                                       }
                                     }
                                     
@@ -7790,4 +9912,4 @@
     fn exec(ctx: RunContext,event_name: String,event_data: Vec<(u32,ComponentType,)>,) -> ();
   }
 }
-#[allow(missing_docs)] pub const INTERFACE_VERSION: u32 = 10;
\ No newline at end of file
+#[allow(missing_docs)] pub const INTERFACE_VERSION: u32 = 20;
\ No newline at end of file
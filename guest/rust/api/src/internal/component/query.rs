@@ -3,7 +3,11 @@ use std::{future::Future, marker::PhantomData};
 use crate::{
     event,
     global::{on, on_async, EntityId, EventOk},
-    internal::{component::ComponentsTuple, conversion::FromBindgen, host},
+    internal::{
+        component::{Component, ComponentsTuple, UntypedComponent},
+        conversion::{FromBindgen, IntoBindgen},
+        host,
+    },
 };
 
 /// Creates a new [GeneralQueryBuilder] that will find entities that have the specified `components`
@@ -102,6 +106,14 @@ impl<Components: ComponentsTuple + Copy + Clone + 'static> GeneralQueryBuilder<C
         self
     }
 
+    /// The entities must point at `target` with the relationship `component` (i.e. `attached_to` or `owned_by`).
+    /// See [crate::entity::get_related] to find the related entities without a query.
+    pub fn related_to(mut self, component: Component<EntityId>, target: EntityId) -> Self {
+        self.0.include.push(component.index());
+        self.0.related_to = Some((component, target));
+        self
+    }
+
     /// Builds a [GeneralQuery].
     pub fn build(self) -> GeneralQuery<Components> {
         GeneralQuery(QueryImpl::new(
@@ -269,6 +281,7 @@ struct QueryBuilderImpl<Components: ComponentsTuple + Copy + Clone + 'static> {
     components: Vec<u32>,
     include: Vec<u32>,
     exclude: Vec<u32>,
+    related_to: Option<(Component<EntityId>, EntityId)>,
     _data: PhantomData<Components>,
 }
 impl<Components: ComponentsTuple + Copy + Clone + 'static> QueryBuilderImpl<Components> {
@@ -277,6 +290,7 @@ impl<Components: ComponentsTuple + Copy + Clone + 'static> QueryBuilderImpl<Comp
             components,
             include: vec![],
             exclude: vec![],
+            related_to: None,
             _data: PhantomData,
         }
     }
@@ -293,6 +307,9 @@ impl<Components: ComponentsTuple + Copy + Clone + 'static> QueryBuilderImpl<Comp
                 include: &self.include,
                 exclude: &self.exclude,
                 changed,
+                related_to: self
+                    .related_to
+                    .map(|(component, target)| (component.index(), target.into_bindgen())),
             },
             event,
         )
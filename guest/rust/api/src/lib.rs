@@ -7,6 +7,13 @@
 //! Ambient has first-class support for Rust. Please report any issues you encounter to the repository.
 #![deny(missing_docs)]
 
+/// Debug drawing of lines, arrows, spheres, boxes and text in the world, for visualizing what a
+/// module is doing.
+///
+/// Every drawing has a category. Drawings are only sent to the players which enabled their
+/// category in the debugger, and are shown with a color for a lifetime in seconds; a lifetime of
+/// zero shows the drawing for one frame, so that it can be redrawn every frame.
+pub mod debug;
 /// ECS-related functionality not directly related to entities.
 pub mod ecs;
 /// Entity-related functionality, including manipulation, creation, removal, and search.
@@ -15,10 +22,36 @@ pub mod entity;
 pub mod event;
 /// Global functions and types for your convenience.
 pub mod global;
+/// World instance functionality, including creating instances, listing them and moving players between them.
+pub mod instance;
+/// Localization of the text of the UI.
+///
+/// Text entities with a `text_key` (and optionally `text_args`) show the string of that key in the
+/// active locale, from the string tables built by the `Localization` asset pipeline. This module
+/// switches the locale at runtime, for all players or for one player.
+pub mod localization;
+/// Messaging between the server and its players, including sending messages to one or all players.
+///
+/// Messages from players arrive as events with the name they were sent with, which can be
+/// received with [on](crate::global::on). Their `message_source` component is the player that
+/// sent them.
+pub mod message;
+/// Saving and restoring of the world, which is enabled by starting the server with `--save`.
+///
+/// Only entities spawned at runtime are restored, and only their components with the `Store`
+/// attribute. When the world is restored, modules receive the [WORLD_LOAD](crate::event::WORLD_LOAD)
+/// event after they are loaded.
+pub mod persistence;
 /// Physics-related functionality, including applying forces, changing physical properties, and more.
 pub mod physics;
 /// Player-related functionality.
 pub mod player;
+/// Persistent key-value storage for the project, which is kept across server restarts.
+///
+/// Keys are strings, and values are either bytes or entities. All functions are fast, as values
+/// are kept in memory and written to disk in the background; use [storage::flush] to wait for the
+/// writes.
+pub mod storage;
 
 /// Helpful imports that almost all Ambient projects will use.
 pub mod prelude;
@@ -0,0 +1,33 @@
+use crate::{
+    components::core::{network::synced_resources, ui::locale},
+    entity,
+    global::EntityId,
+};
+
+fn synced_resources_entity() -> Option<EntityId> {
+    entity::get_all(synced_resources()).into_iter().next()
+}
+
+/// Sets the locale of all players, like `en` or `fr-CA`. Players with their own locale (see
+/// [set_player_locale]) keep it.
+pub fn set_locale(value: &str) {
+    if let Some(resources) = synced_resources_entity() {
+        entity::add_component(resources, locale(), value.to_string());
+    }
+}
+
+/// The locale of all players, if it was set with [set_locale]. Otherwise, the default locale of
+/// the project is used.
+pub fn get_locale() -> Option<String> {
+    entity::get_component(synced_resources_entity()?, locale())
+}
+
+/// Sets the locale of `player`, which overrides the locale of all players for them.
+pub fn set_player_locale(player: EntityId, value: &str) {
+    entity::add_component(player, locale(), value.to_string());
+}
+
+/// Removes the locale of `player`, who then uses the locale of all players.
+pub fn reset_player_locale(player: EntityId) {
+    entity::remove_component(player, locale());
+}
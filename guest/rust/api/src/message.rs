@@ -0,0 +1,40 @@
+use crate::{
+    ecs::Entity,
+    global::EntityId,
+    internal::{conversion::IntoBindgen, host},
+};
+
+/// How a message is delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reliability {
+    /// The message will arrive, but not necessarily in order with other messages.
+    Reliable,
+    /// The message may be dropped, e.g. when the connection is congested or the message is too
+    /// large for a single datagram. Use this for frequent updates where only the latest one matters.
+    Unreliable,
+}
+
+/// Sends a (non-core) message to the player `player_id`.
+///
+/// The client receives the message as a world event with `name`. Only the serializable components
+/// of `data` are sent.
+pub fn send_to_player(
+    player_id: EntityId,
+    name: impl AsRef<str>,
+    data: Entity,
+    reliability: Reliability,
+) {
+    send(Some(player_id), name.as_ref(), data, reliability)
+}
+
+/// Sends a (non-core) message to all connected players.
+///
+/// See [send_to_player] for how the message is received.
+pub fn broadcast(name: impl AsRef<str>, data: Entity, reliability: Reliability) {
+    send(None, name.as_ref(), data, reliability)
+}
+
+fn send(target: Option<EntityId>, name: &str, data: Entity, reliability: Reliability) {
+    let reliable = reliability == Reliability::Reliable;
+    data.call_with(|data| host::message_send(target.into_bindgen(), name, data, reliable))
+}
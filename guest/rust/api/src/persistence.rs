@@ -0,0 +1,12 @@
+use crate::internal::host;
+
+/// Saves the world at the end of the frame, if the server was started with `--save`.
+///
+/// The server also saves the world periodically and when it shuts down, so this is only needed
+/// to save at important moments, e.g. at the end of a round.
+///
+/// Entities spawned by a module while it loads are not saved, as the module spawns them again
+/// when the server restarts.
+pub fn save() {
+    host::persistence_save()
+}
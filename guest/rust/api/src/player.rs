@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     global::{EntityId, Vec2},
@@ -406,6 +406,93 @@ impl FromBindgen for host::MouseButton {
     }
 }
 
+/// A button on a gamepad, named after its position on a standard controller layout.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GamepadButton {
+    /// The bottom face button (A on Xbox controllers, Cross on PlayStation controllers).
+    South,
+    /// The right face button (B on Xbox controllers, Circle on PlayStation controllers).
+    East,
+    /// The top face button (Y on Xbox controllers, Triangle on PlayStation controllers).
+    North,
+    /// The left face button (X on Xbox controllers, Square on PlayStation controllers).
+    West,
+    LeftBumper,
+    RightBumper,
+    /// The left trigger, pressed past its threshold; see [Gamepad::left_trigger] for its analog value.
+    LeftTrigger,
+    /// The right trigger, pressed past its threshold; see [Gamepad::right_trigger] for its analog value.
+    RightTrigger,
+    Select,
+    Start,
+    /// The guide button, e.g. the Xbox or PS button.
+    Mode,
+    /// Pressing in the left stick.
+    LeftStick,
+    /// Pressing in the right stick.
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+impl FromBindgen for host::GamepadButton {
+    type Item = GamepadButton;
+
+    fn from_bindgen(self) -> Self::Item {
+        match self {
+            Self::South => Self::Item::South,
+            Self::East => Self::Item::East,
+            Self::North => Self::Item::North,
+            Self::West => Self::Item::West,
+            Self::LeftBumper => Self::Item::LeftBumper,
+            Self::RightBumper => Self::Item::RightBumper,
+            Self::LeftTrigger => Self::Item::LeftTrigger,
+            Self::RightTrigger => Self::Item::RightTrigger,
+            Self::Select => Self::Item::Select,
+            Self::Start => Self::Item::Start,
+            Self::Mode => Self::Item::Mode,
+            Self::LeftStick => Self::Item::LeftStick,
+            Self::RightStick => Self::Item::RightStick,
+            Self::DPadUp => Self::Item::DPadUp,
+            Self::DPadDown => Self::Item::DPadDown,
+            Self::DPadLeft => Self::Item::DPadLeft,
+            Self::DPadRight => Self::Item::DPadRight,
+        }
+    }
+}
+
+/// The state of one of a player's gamepads.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gamepad {
+    /// All of the buttons being pressed this frame.
+    pub buttons: HashSet<GamepadButton>,
+    /// The position of the left stick, with the dead zone applied.
+    pub left_stick: Vec2,
+    /// The position of the right stick, with the dead zone applied.
+    pub right_stick: Vec2,
+    /// How far the left trigger is pressed, from 0 to 1.
+    pub left_trigger: f32,
+    /// How far the right trigger is pressed, from 0 to 1.
+    pub right_trigger: f32,
+}
+impl FromBindgen for host::Gamepad {
+    type Item = (u32, Gamepad);
+    fn from_bindgen(self) -> Self::Item {
+        (
+            self.id,
+            Gamepad {
+                buttons: self.buttons.into_iter().map(|b| b.from_bindgen()).collect(),
+                left_stick: self.left_stick.from_bindgen(),
+                right_stick: self.right_stick.from_bindgen(),
+                left_trigger: self.left_trigger,
+                right_trigger: self.right_trigger,
+            },
+        )
+    }
+}
+
 /// The state of a player's raw input. Get these with [get_raw_input] or [get_prev_raw_input].
 #[derive(Clone, Debug, PartialEq)]
 pub struct RawInput {
@@ -417,6 +504,8 @@ pub struct RawInput {
     pub mouse_wheel: f32,
     /// All of the mouse buttons being pressed this frame.
     pub mouse_buttons: HashSet<MouseButton>,
+    /// The connected gamepads, by their id. Ids stay the same while a gamepad is connected.
+    pub gamepads: HashMap<u32, Gamepad>,
 }
 impl FromBindgen for host::PlayerRawInput {
     type Item = RawInput;
@@ -430,6 +519,11 @@ impl FromBindgen for host::PlayerRawInput {
                 .into_iter()
                 .map(|b| b.from_bindgen())
                 .collect(),
+            gamepads: self
+                .gamepads
+                .into_iter()
+                .map(|g| g.from_bindgen())
+                .collect(),
         }
     }
 }
@@ -450,6 +544,10 @@ pub struct RawInputDelta {
     pub mouse_buttons: HashSet<MouseButton>,
     /// All of the mouse buttons that were released this frame.
     pub mouse_buttons_released: HashSet<MouseButton>,
+    /// All of the gamepad buttons that were pressed this frame, but not last frame, by gamepad id.
+    pub gamepad_buttons: HashMap<u32, HashSet<GamepadButton>>,
+    /// All of the gamepad buttons that were released this frame, by gamepad id.
+    pub gamepad_buttons_released: HashMap<u32, HashSet<GamepadButton>>,
 }
 impl RawInput {
     /// Returns whether or not each input has changed from `previous` to this [RawInput].
@@ -463,10 +561,29 @@ impl RawInput {
             mouse_wheel: c.mouse_wheel - p.mouse_wheel,
             mouse_buttons: &c.mouse_buttons - &p.mouse_buttons,
             mouse_buttons_released: &p.mouse_buttons - &c.mouse_buttons,
+            gamepad_buttons: gamepad_buttons_difference(&c.gamepads, &p.gamepads),
+            gamepad_buttons_released: gamepad_buttons_difference(&p.gamepads, &c.gamepads),
         }
     }
 }
 
+/// The buttons held on each gamepad of `a` which aren't held on the same gamepad of `b`.
+fn gamepad_buttons_difference(
+    a: &HashMap<u32, Gamepad>,
+    b: &HashMap<u32, Gamepad>,
+) -> HashMap<u32, HashSet<GamepadButton>> {
+    a.iter()
+        .map(|(id, gamepad)| {
+            let buttons = match b.get(id) {
+                Some(other) => &gamepad.buttons - &other.buttons,
+                None => gamepad.buttons.clone(),
+            };
+            (*id, buttons)
+        })
+        .filter(|(_, buttons)| !buttons.is_empty())
+        .collect()
+}
+
 /// Gets `player_id`'s most recent raw input state.
 ///
 /// To determine if the player just supplied an input, compare it to [get_prev_raw_input] or use [get_raw_input_delta].
@@ -492,3 +609,47 @@ pub fn get_raw_input_delta(player_id: EntityId) -> Option<(RawInputDelta, RawInp
     let (p, c) = get_prev_and_current_raw_input(player_id)?;
     Some((c.delta(&p), c))
 }
+
+/// The values of a player's actions, as declared in the `[actions]` of `ambient.toml` and
+/// evaluated by their client. Get these with [get_actions] or [get_prev_actions].
+///
+/// Button actions are `(1, 0)` while pressed, axis actions only use `x`, and 2D axis actions use
+/// both `x` and `y`. Actions which aren't active are zero.
+///
+/// The server only checks that the actions and their kinds match the action map: players can
+/// rebind their actions and run modified clients, so like the raw input, these are not a
+/// security boundary.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Actions(pub HashMap<String, Vec2>);
+impl Actions {
+    /// The value of `action`, or zero if it isn't active or doesn't exist.
+    pub fn get(&self, action: &str) -> Vec2 {
+        self.0.get(action).copied().unwrap_or_default()
+    }
+    /// Whether or not `action` is active.
+    pub fn is_pressed(&self, action: &str) -> bool {
+        self.get(action) != Vec2::ZERO
+    }
+    /// Whether or not `action` is active now, but wasn't in `previous`.
+    pub fn just_pressed(&self, previous: &Actions, action: &str) -> bool {
+        self.is_pressed(action) && !previous.is_pressed(action)
+    }
+}
+fn actions_from_bindgen(actions: Vec<(String, host::Vec2)>) -> Actions {
+    Actions(
+        actions
+            .into_iter()
+            .map(|(name, value)| (name, value.from_bindgen()))
+            .collect(),
+    )
+}
+
+/// Gets the most recent values of `player_id`'s actions.
+pub fn get_actions(player_id: EntityId) -> Actions {
+    actions_from_bindgen(host::player_get_actions(player_id.into_bindgen()))
+}
+
+/// Gets the values of `player_id`'s actions prior to the most recent update.
+pub fn get_prev_actions(player_id: EntityId) -> Actions {
+    actions_from_bindgen(host::player_get_prev_actions(player_id.into_bindgen()))
+}
@@ -1,8 +1,9 @@
 pub use crate::{
+    debug,
     ecs::{change_query, despawn_query, query, spawn_query, Component, Entity, QueryEvent},
     entity, event,
     global::*,
-    main, physics, player,
+    instance, main, message, persistence, physics, player, storage,
 };
 pub use anyhow::{anyhow, Context as AnyhowContext};
 pub use rand::prelude::*;
@@ -0,0 +1,68 @@
+use crate::{
+    ecs::Entity,
+    global::{block_until, EntityId},
+    internal::{conversion::IntoBindgen, host},
+};
+
+/// Gets the value stored at `key`, if there is one.
+pub fn get(key: impl AsRef<str>) -> Option<Vec<u8>> {
+    host::storage_get(key.as_ref())
+}
+
+/// Stores `value` at `key`, replacing the previous value.
+///
+/// The new value can be read immediately, and is written to disk in the background; use [flush]
+/// to wait until it has been. Fails if the key or value is too large, or if storing the value
+/// would exceed the storage quota of the project.
+pub fn set(key: impl AsRef<str>, value: impl AsRef<[u8]>) -> anyhow::Result<()> {
+    match host::storage_set(key.as_ref(), value.as_ref()) {
+        None => Ok(()),
+        Some(err) => Err(anyhow::anyhow!(err)),
+    }
+}
+
+/// Stores the serializable components of `data` at `key`, replacing the previous value.
+///
+/// Components are stored by name, so the entity can be loaded with [load_entity] after a restart
+/// or a change to the project's components. See [set] for when the value is written.
+pub fn set_entity(key: impl AsRef<str>, data: Entity) -> anyhow::Result<()> {
+    match data.call_with(|data| host::storage_set_entity(key.as_ref(), data)) {
+        None => Ok(()),
+        Some(err) => Err(anyhow::anyhow!(err)),
+    }
+}
+
+/// Adds the components of the entity stored at `key` with [set_entity] to `entity`.
+///
+/// Returns `false` if there is no entity stored at `key`.
+pub fn load_entity(key: impl AsRef<str>, entity: EntityId) -> bool {
+    host::storage_load_entity(key.as_ref(), entity.into_bindgen())
+}
+
+/// Removes the value stored at `key`. Returns `false` if there was none.
+pub fn delete(key: impl AsRef<str>) -> bool {
+    host::storage_delete(key.as_ref())
+}
+
+/// Lists the keys starting with `prefix`, in order. Use an empty prefix to list all keys.
+pub fn list(prefix: impl AsRef<str>) -> Vec<String> {
+    host::storage_list(prefix.as_ref())
+}
+
+/// Whether all the changes made so far have been written to disk.
+///
+/// Fails once a write has failed, as the changes since then may never be written.
+pub fn is_flushed() -> anyhow::Result<bool> {
+    match host::storage_write_error() {
+        None => Ok(host::storage_is_flushed()),
+        Some(err) => Err(anyhow::anyhow!(err)),
+    }
+}
+
+/// Waits until all the changes made so far have been written to disk, or fails if a write failed.
+///
+/// This must be used with `.await` in either an `async fn` or an `async` block.
+pub async fn flush() -> anyhow::Result<()> {
+    block_until(|| !matches!(is_flushed(), Ok(false))).await;
+    is_flushed().map(|_| ())
+}